@@ -1,7 +1,10 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 use curie_util::TrieCurieUtil;
-use ontolius::io::{OntologyLoaderBuilder, obographs::ObographsParser};
+use ontolius::io::{
+    obographs::{MetaFields, ObographsParser},
+    OntologyLoaderBuilder,
+};
 use ontolius::ontology::csr::CsrOntology;
 
 fn load_csr_ontology(c: &mut Criterion) {
@@ -18,6 +21,34 @@ fn load_csr_ontology(c: &mut Criterion) {
             black_box(ontology);
         })
     });
+
+    // A partial set skips over the unselected metadata parts without deserializing them.
+    let loader = OntologyLoaderBuilder::new()
+        .parser(
+            ObographsParser::new(TrieCurieUtil::default()).meta_fields(MetaFields {
+                definitions: true,
+                ..MetaFields::NONE
+            }),
+        )
+        .build();
+    group.bench_function("CsrOntologyLoader::load definitions only", |b| {
+        b.iter(|| {
+            let ontology: CsrOntology<usize, _> = loader.load_from_path(path).unwrap();
+            black_box(ontology);
+        })
+    });
+
+    // The minimal terms skip the synonyms, definitions, comments, and xrefs by default.
+    // Parse all metadata to measure the wasted work.
+    let loader = OntologyLoaderBuilder::new()
+        .parser(ObographsParser::new(TrieCurieUtil::default()).meta_fields(MetaFields::ALL))
+        .build();
+    group.bench_function("CsrOntologyLoader::load all meta fields", |b| {
+        b.iter(|| {
+            let ontology: CsrOntology<usize, _> = loader.load_from_path(path).unwrap();
+            black_box(ontology);
+        })
+    });
     group.finish();
 }
 
@@ -81,6 +81,99 @@ fn hierarchy_traversals(c: &mut Criterion) {
         );
     }
     group.finish();
+
+    // The bitset-backed counts and the baseline of counting the traversal.
+    macro_rules! bench_count {
+        ($group: expr, $func: expr, $name: expr, $curie: expr) => {
+            $group.bench_function(BenchmarkId::from_parameter($name), |b| {
+                let term_id = TermId::from_str($curie).expect("Curie should be parsable");
+                let term_idx = ontology.id_to_idx(&term_id).expect("Should be there!");
+                b.iter(|| black_box($func(black_box(term_idx))));
+            });
+        };
+    }
+
+    let mut group = c.benchmark_group("CsrOntologyHierarchy::count_descendants");
+    group.throughput(criterion::Throughput::Elements(1));
+    for &(label, curie) in &payload {
+        bench_count!(
+            group,
+            |term_id| hierarchy.count_descendants(term_id),
+            label,
+            curie
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("CsrOntologyHierarchy::descendants_of.count");
+    group.throughput(criterion::Throughput::Elements(1));
+    for &(label, curie) in &payload {
+        bench_count!(
+            group,
+            |term_id| hierarchy.descendants_of(term_id).count(),
+            label,
+            curie
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("CsrOntologyHierarchy::count_ancestors");
+    group.throughput(criterion::Throughput::Elements(1));
+    for &(label, curie) in &payload {
+        bench_count!(
+            group,
+            |term_id| hierarchy.count_ancestors(term_id),
+            label,
+            curie
+        );
+    }
+    group.finish();
+
+    // Pairs of a child and its parent with many children.
+    let edges = vec![
+        ("Abnormality of the upper limb", "HP:0002817", "HP:0040064"),
+        ("Abnormality of the nervous system", "HP:0000707", "HP:0000118"),
+        ("Seizure", "HP:0001250", "HP:0012638"),
+    ];
+
+    macro_rules! bench_edge_test {
+        ($group: expr, $func: expr, $name: expr, $sub: expr, $obj: expr) => {
+            $group.bench_function(BenchmarkId::from_parameter($name), |b| {
+                let sub = TermId::from_str($sub).expect("Curie should be parsable");
+                let sub = ontology.id_to_idx(&sub).expect("Should be there!");
+                let obj = TermId::from_str($obj).expect("Curie should be parsable");
+                let obj = ontology.id_to_idx(&obj).expect("Should be there!");
+                b.iter(|| black_box($func(black_box(sub), black_box(obj))));
+            });
+        };
+    }
+
+    let mut group = c.benchmark_group("CsrOntologyHierarchy::is_child_of");
+    group.throughput(criterion::Throughput::Elements(1));
+    for &(label, sub, obj) in &edges {
+        bench_edge_test!(
+            group,
+            |sub, obj| hierarchy.is_child_of(sub, obj),
+            label,
+            sub,
+            obj
+        );
+    }
+    group.finish();
+
+    // The baseline: scanning the children of `obj`.
+    let mut group = c.benchmark_group("CsrOntologyHierarchy::children_of.any");
+    group.throughput(criterion::Throughput::Elements(1));
+    for &(label, sub, obj) in &edges {
+        bench_edge_test!(
+            group,
+            |sub, obj| hierarchy.children_of(obj).any(|&child| child == sub),
+            label,
+            sub,
+            obj
+        );
+    }
+    group.finish();
 }
 
 criterion_group!(benches, hierarchy_traversals);
@@ -0,0 +1,69 @@
+use std::str::FromStr;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use curie_util::TrieCurieUtil;
+use ontolius::io::obographs::ObographsParser;
+use ontolius::ontology::csr::CsrOntology;
+use ontolius::prelude::*;
+use ontolius::search::{Searcher, WeightedRanking};
+
+fn search(c: &mut Criterion) {
+    let path = "resources/hp.2023-10-09.json.gz";
+    let loader = OntologyLoaderBuilder::new()
+        .parser(ObographsParser::new(TrieCurieUtil::default()))
+        .build();
+    let ontology: CsrOntology<usize, _> = loader.load_from_path(path).unwrap();
+    let searcher = Searcher::new(&ontology);
+
+    let queries = vec![
+        ("exact", "Arachnodactyly"),
+        ("prefix", "seiz"),
+        ("common word", "abnormality"),
+        ("no match", "xylophone"),
+    ];
+
+    let mut group = c.benchmark_group("Searcher::search");
+    group.throughput(criterion::Throughput::Elements(1));
+    for &(label, query) in &queries {
+        group.bench_function(BenchmarkId::from_parameter(label), |b| {
+            b.iter(|| black_box(searcher.search(black_box(query), 10)));
+        });
+    }
+    group.finish();
+
+    let policy = WeightedRanking::default().within(TermId::from(("HP", "0000118")));
+    let mut group = c.benchmark_group("Searcher::search_with");
+    group.throughput(criterion::Throughput::Elements(1));
+    for &(label, query) in &queries {
+        group.bench_function(BenchmarkId::from_parameter(label), |b| {
+            b.iter(|| black_box(searcher.search_with(black_box(query), 10, &policy)));
+        });
+    }
+    group.finish();
+
+    // The lookups of the IDs that are mostly not in the ontology,
+    // with and without the Bloom filter.
+    let term_ids: Vec<_> = ["HP:0001166", "MONDO:0007947", "GO:0005634", "HP:9999999"]
+        .into_iter()
+        .map(|curie| TermId::from_str(curie).expect("Curie should be parsable"))
+        .collect();
+    let mut filtered = ontology.clone();
+    filtered.enable_bloom_filter();
+
+    let mut group = c.benchmark_group("CsrOntology::id_to_idx");
+    group.throughput(criterion::Throughput::Elements(term_ids.len() as u64));
+    for (label, ontology) in [("map", &ontology), ("bloom filter", &filtered)] {
+        group.bench_function(BenchmarkId::from_parameter(label), |b| {
+            b.iter(|| {
+                for term_id in &term_ids {
+                    black_box(ontology.id_to_idx(black_box(term_id)));
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, search);
+criterion_main!(benches);
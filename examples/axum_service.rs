@@ -0,0 +1,141 @@
+//! Serve HPO terms and hierarchy over HTTP with `axum`.
+//!
+//! Run the example by:
+//!
+//! ```shell
+//! cargo run --example axum_service --features service
+//! ```
+//!
+//! and query the endpoints, e.g.:
+//!
+//! ```shell
+//! curl localhost:3000/terms/HP:0001166
+//! curl "localhost:3000/terms/HP:0001166/ancestors?limit=5"
+//! curl "localhost:3000/terms/HP:0040064/descendants?limit=10&cursor=100"
+//! curl "localhost:3000/search?query=arachno&limit=5"
+//! curl "localhost:3000/similarity?left=HP:0001166&right=HP:0001238"
+//! ```
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use curie_util::TrieCurieUtil;
+use serde::Deserialize;
+
+use ontolius::base::term::simple::SimpleMinimalTerm;
+use ontolius::io::obographs::ObographsParser;
+use ontolius::io::OntologyLoaderBuilder;
+use ontolius::ontology::csr::CsrOntology;
+use ontolius::ontology::IcOntology;
+use ontolius::search::Searcher;
+use ontolius::service::{
+    self, ErrorResponse, HierarchyRequest, SearchRequest, ServiceError, SimilarityRequest,
+    TermRequest, DEFAULT_PAGE_LIMIT,
+};
+
+type Ontology = CsrOntology<usize, SimpleMinimalTerm>;
+
+/// The ontology with the search index and the information content,
+/// computed once when the server starts.
+struct Hpo {
+    ontology: &'static Ontology,
+    searcher: Searcher<'static, Ontology>,
+    ic: IcOntology<'static, Ontology>,
+}
+
+type AppState = Arc<Hpo>;
+
+#[derive(Deserialize)]
+struct PageParams {
+    cursor: Option<usize>,
+    limit: Option<usize>,
+}
+
+impl PageParams {
+    fn into_request(self, id: String) -> HierarchyRequest {
+        HierarchyRequest {
+            id,
+            cursor: self.cursor,
+            limit: self.limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+        }
+    }
+}
+
+fn into_response<T: serde::Serialize>(result: Result<T, ServiceError>) -> Response {
+    match result {
+        Ok(body) => Json(body).into_response(),
+        Err(e) => {
+            let status = match e {
+                ServiceError::InvalidCurie(_)
+                | ServiceError::InvalidCursor(_)
+                | ServiceError::InvalidLimit => StatusCode::BAD_REQUEST,
+                ServiceError::UnknownTerm(_) => StatusCode::NOT_FOUND,
+            };
+            (status, Json(ErrorResponse::from(&e))).into_response()
+        }
+    }
+}
+
+async fn term(State(hpo): State<AppState>, Path(id): Path<String>) -> Response {
+    into_response(service::lookup_term(hpo.ontology, &TermRequest { id }))
+}
+
+async fn ancestors(
+    State(hpo): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<PageParams>,
+) -> Response {
+    into_response(service::ancestors(hpo.ontology, &params.into_request(id)))
+}
+
+async fn descendants(
+    State(hpo): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<PageParams>,
+) -> Response {
+    into_response(service::descendants(hpo.ontology, &params.into_request(id)))
+}
+
+async fn search(State(hpo): State<AppState>, Query(request): Query<SearchRequest>) -> Response {
+    Json(service::search(&hpo.searcher, &request)).into_response()
+}
+
+async fn similarity(
+    State(hpo): State<AppState>,
+    Query(request): Query<SimilarityRequest>,
+) -> Response {
+    into_response(service::similarity(&hpo.ic, &request))
+}
+
+#[tokio::main]
+async fn main() {
+    let loader = OntologyLoaderBuilder::new()
+        .parser(ObographsParser::new(TrieCurieUtil::default()))
+        .build();
+    let ontology: Ontology = loader
+        .load_from_path("resources/hp.small.json.gz")
+        .expect("HPO should be loaded");
+    // The ontology is served until the process exits.
+    let ontology: &'static Ontology = Box::leak(Box::new(ontology));
+    let hpo = Hpo {
+        ontology,
+        searcher: Searcher::new(ontology),
+        ic: IcOntology::new(ontology),
+    };
+
+    let app = Router::new()
+        .route("/terms/:id", get(term))
+        .route("/terms/:id/ancestors", get(ancestors))
+        .route("/terms/:id/descendants", get(descendants))
+        .route("/search", get(search))
+        .route("/similarity", get(similarity))
+        .with_state(Arc::new(hpo));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .expect("Should be able to bind the address");
+    axum::serve(listener, app).await.expect("Server should run");
+}
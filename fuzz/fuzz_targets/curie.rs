@@ -0,0 +1,18 @@
+//! Parse arbitrary strings as CURIEs.
+//!
+//! The parsed term IDs must round-trip through their string representation.
+#![no_main]
+
+use std::str::FromStr;
+
+use libfuzzer_sys::fuzz_target;
+use ontolius::prelude::*;
+
+fuzz_target!(|curie: &str| {
+    if let Ok(term_id) = TermId::from_str(curie) {
+        let written = term_id.to_string();
+        let parsed = TermId::from_str(&written).expect("Written CURIE should be parsable");
+        assert_eq!(term_id, parsed);
+        assert!(written.starts_with(term_id.prefix()));
+    }
+});
@@ -0,0 +1,17 @@
+//! Load arbitrary bytes as an Obographs JSON document.
+//!
+//! The loading must fail with an error rather than panic, and the `paranoid` feature
+//! checks the invariants of every ontology that is built.
+#![no_main]
+
+use curie_util::TrieCurieUtil;
+use libfuzzer_sys::fuzz_target;
+use ontolius::io::obographs::ObographsParser;
+use ontolius::prelude::*;
+
+fuzz_target!(|data: &[u8]| {
+    let loader = OntologyLoaderBuilder::new()
+        .parser(ObographsParser::new(TrieCurieUtil::default()))
+        .build();
+    let _: Result<MinimalCsrOntology, _> = loader.load_from_read(&mut &data[..]);
+});
@@ -0,0 +1,356 @@
+//! Align the datasets with the current ontology release.
+//!
+//! The datasets, such as phenopackets or legacy tables, record the term IDs
+//! along with the term labels of the ontology release used at the time of curation.
+//! [`find_label_drift`] reports the labels that differ from the current term names,
+//! and [`plan_migration`] tells how to update the term IDs that are no longer current.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! use ontolius::align::find_label_drift;
+//! use ontolius::base::term::{simple::SimpleTerm, SynonymCategory};
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::with_term_type(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, SimpleTerm> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                             .expect("HPO should be loaded");
+//! #
+//! let arachnodactyly = TermId::from(("HP", "0001166"));
+//! let pairs = [
+//!     (&arachnodactyly, "Arachnodactyly"),
+//!     (&arachnodactyly, "Spider fingers"),
+//!     (&arachnodactyly, "Long fingers"),
+//! ];
+//!
+//! let drift = find_label_drift(&hpo, pairs);
+//!
+//! assert_eq!(drift.len(), 2);
+//! assert_eq!(drift[0].label(), "Spider fingers");
+//! assert_eq!(drift[0].current_label(), "Arachnodactyly");
+//! assert_eq!(drift[0].synonym(), Some(SynonymCategory::Exact));
+//! assert_eq!(drift[1].synonym(), None);
+//! ```
+use std::collections::HashSet;
+
+use crate::base::term::{MinimalTerm, ReplacementAware, SynonymAware, SynonymCategory};
+use crate::base::{Identified, TermId};
+use crate::ontology::Ontology;
+
+/// A supplied term label that differs from the current name of the term.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LabelDrift {
+    term_id: TermId,
+    label: String,
+    current_label: String,
+    synonym: Option<SynonymCategory>,
+}
+
+impl LabelDrift {
+    /// Get the supplied term ID.
+    pub fn term_id(&self) -> &TermId {
+        &self.term_id
+    }
+
+    /// Get the supplied label.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the current name of the term.
+    pub fn current_label(&self) -> &str {
+        &self.current_label
+    }
+
+    /// Get the category of the term synonym that matches the supplied label,
+    /// or `None` if the label is not a synonym of the term.
+    pub fn synonym(&self) -> Option<SynonymCategory> {
+        self.synonym
+    }
+}
+
+/// Find the supplied `(term ID, label)` pairs whose label differs from the current term name.
+///
+/// The labels are compared verbatim, after trimming the surrounding whitespace.
+/// The alternate term IDs are compared with the names of their primary terms.
+/// The term IDs unknown to the `ontology` are skipped (see [`plan_migration`]).
+///
+/// Returns the drifts in the order of the `pairs`.
+pub fn find_label_drift<'a, O, I, L>(ontology: &O, pairs: I) -> Vec<LabelDrift>
+where
+    O: Ontology,
+    O::T: SynonymAware,
+    I: IntoIterator<Item = (&'a TermId, L)>,
+    L: AsRef<str>,
+{
+    pairs
+        .into_iter()
+        .filter_map(|(term_id, label)| {
+            let label = label.as_ref().trim();
+            let term = ontology.id_to_term(term_id)?;
+            if term.name() == label {
+                return None;
+            }
+            let synonym = term
+                .iter_synonyms()
+                .find(|synonym| synonym.name() == label)
+                .map(|synonym| synonym.category());
+            Some(LabelDrift {
+                term_id: term_id.clone(),
+                label: label.to_string(),
+                current_label: term.name().to_string(),
+                synonym,
+            })
+        })
+        .collect()
+}
+
+/// The action to take for a term ID to align it with the current ontology release.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Migration {
+    /// The term ID is the primary ID of a current term.
+    Keep,
+    /// Replace the term ID with the primary ID of a current term.
+    ///
+    /// The term ID is an alternate ID of the current term,
+    /// or an ID of an obsolete term replaced by the current term.
+    Replace(TermId),
+    /// Review the term ID of an obsolete term, with the current terms to consider instead.
+    Review(Vec<TermId>),
+    /// Drop the term ID, as it leads to no current term.
+    ///
+    /// The term ID is unknown to the ontology, or it is an ID of an obsolete term
+    /// with no current replacement and no current term to consider.
+    Drop,
+}
+
+/// The [`Migration`] of a term ID.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MigrationStep {
+    term_id: TermId,
+    migration: Migration,
+}
+
+impl MigrationStep {
+    /// Get the term ID to migrate.
+    pub fn term_id(&self) -> &TermId {
+        &self.term_id
+    }
+
+    /// Get the migration of the term ID.
+    pub fn migration(&self) -> &Migration {
+        &self.migration
+    }
+}
+
+/// A plan for migrating a set of term IDs to the current ontology release.
+///
+/// Use [`plan_migration`] to create the plan.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationPlan {
+    steps: Vec<MigrationStep>,
+}
+
+impl MigrationPlan {
+    /// Get the migration steps, in the order of the term IDs.
+    pub fn steps(&self) -> &[MigrationStep] {
+        &self.steps
+    }
+
+    /// Iterate over the pairs of the term IDs to replace and their replacements.
+    pub fn replacements(&self) -> impl Iterator<Item = (&TermId, &TermId)> {
+        self.steps.iter().filter_map(|step| match &step.migration {
+            Migration::Replace(replacement) => Some((&step.term_id, replacement)),
+            _ => None,
+        })
+    }
+
+    /// Test if all term IDs are kept.
+    pub fn is_noop(&self) -> bool {
+        self.steps
+            .iter()
+            .all(|step| step.migration == Migration::Keep)
+    }
+}
+
+/// Plan the migration of the `term_ids` to the current terms of the `ontology`.
+///
+/// The replacements of the obsolete terms (*term replaced by*) are followed
+/// to the current terms, and the alternate IDs are resolved to the primary IDs.
+/// The terms to consider are reviewed if the obsolete term has no current replacement.
+/// Each term ID is planned once, in the order of the first occurrence.
+pub fn plan_migration<'a, O, I>(ontology: &O, term_ids: I) -> MigrationPlan
+where
+    O: Ontology,
+    O::T: ReplacementAware,
+    I: IntoIterator<Item = &'a TermId>,
+{
+    let mut seen = HashSet::new();
+    let steps = term_ids
+        .into_iter()
+        .filter(|&term_id| seen.insert(term_id))
+        .map(|term_id| MigrationStep {
+            term_id: term_id.clone(),
+            migration: migrate(ontology, term_id),
+        })
+        .collect();
+
+    MigrationPlan { steps }
+}
+
+fn migrate<O>(ontology: &O, term_id: &TermId) -> Migration
+where
+    O: Ontology,
+    O::T: ReplacementAware,
+{
+    let Some(term) = ontology.id_to_term(term_id) else {
+        return Migration::Drop;
+    };
+    if term.is_current() {
+        return if term.identifier() == term_id {
+            Migration::Keep
+        } else {
+            Migration::Replace(term.identifier().clone())
+        };
+    }
+    if let Some(replacement) = current_replacement(ontology, term) {
+        return Migration::Replace(replacement.clone());
+    }
+
+    let mut candidates = vec![];
+    for candidate in term.consider() {
+        let candidate = ontology
+            .id_to_term(candidate)
+            .and_then(|candidate| current_replacement(ontology, candidate));
+        if let Some(candidate) = candidate {
+            if !candidates.contains(candidate) {
+                candidates.push(candidate.clone());
+            }
+        }
+    }
+    if candidates.is_empty() {
+        Migration::Drop
+    } else {
+        Migration::Review(candidates)
+    }
+}
+
+/// Follow the chain of the replacements of the `term` to a current term.
+fn current_replacement<'o, O>(ontology: &'o O, term: &'o O::T) -> Option<&'o TermId>
+where
+    O: Ontology,
+    O::T: ReplacementAware,
+{
+    let mut visited = HashSet::new();
+    let mut term = term;
+    while term.is_obsolete() && visited.insert(term.identifier()) {
+        term = ontology.id_to_term(term.replaced_by()?)?;
+    }
+    term.is_current().then(|| term.identifier())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::{simple::SimpleTerm, Synonym};
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_find_label_drift() {
+        let terms = vec![
+            SimpleTerm::new(TermId::from(("HP", "1")), "Root", vec![], false),
+            SimpleTerm::new(
+                TermId::from(("HP", "2")),
+                "Child",
+                vec![TermId::from(("HP", "3"))],
+                false,
+            )
+            .with_synonyms(vec![Synonym::new("Kid", SynonymCategory::Related, vec![])]),
+        ];
+        let ontology = toy_ontology(terms, &[(1, 0)]);
+
+        let child = TermId::from(("HP", "2"));
+        let alt = TermId::from(("HP", "3"));
+        let unknown = TermId::from(("HP", "4"));
+        let pairs = vec![
+            (&child, String::from(" Child ")),
+            (&alt, String::from("Kid")),
+            (&child, String::from("Offspring")),
+            (&unknown, String::from("Unknown")),
+        ];
+
+        let drift = find_label_drift(&ontology, pairs);
+
+        assert_eq!(
+            drift,
+            [
+                LabelDrift {
+                    term_id: alt.clone(),
+                    label: "Kid".into(),
+                    current_label: "Child".into(),
+                    synonym: Some(SynonymCategory::Related),
+                },
+                LabelDrift {
+                    term_id: child.clone(),
+                    label: "Offspring".into(),
+                    current_label: "Child".into(),
+                    synonym: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_migration() {
+        let obsolete =
+            |id: &str| SimpleTerm::new(TermId::from(("HP", id)), "obsolete", vec![], true);
+        let terms = vec![
+            SimpleTerm::new(TermId::from(("HP", "1")), "Root", vec![], false),
+            SimpleTerm::new(
+                TermId::from(("HP", "2")),
+                "Child",
+                vec![TermId::from(("HP", "3"))],
+                false,
+            ),
+            obsolete("4").with_replaced_by(TermId::from(("HP", "5"))),
+            obsolete("5").with_replaced_by(TermId::from(("HP", "3"))),
+            obsolete("6").with_consider(vec![
+                TermId::from(("HP", "4")),
+                TermId::from(("HP", "1")),
+                TermId::from(("HP", "2")),
+                TermId::from(("HP", "99")),
+            ]),
+            obsolete("7"),
+            obsolete("8").with_replaced_by(TermId::from(("HP", "8"))),
+        ];
+        let ontology = toy_ontology(terms, &[(1, 0)]);
+
+        let term_ids: Vec<_> = ["2", "3", "4", "6", "7", "8", "99", "2"]
+            .into_iter()
+            .map(|id| TermId::from(("HP", id)))
+            .collect();
+        let plan = plan_migration(&ontology, &term_ids);
+
+        let migrations: Vec<_> = plan.steps().iter().map(MigrationStep::migration).collect();
+        let child = TermId::from(("HP", "2"));
+        assert_eq!(
+            migrations,
+            [
+                &Migration::Keep,
+                &Migration::Replace(child.clone()),
+                &Migration::Replace(child.clone()),
+                &Migration::Review(vec![child.clone(), TermId::from(("HP", "1"))]),
+                &Migration::Drop,
+                &Migration::Drop,
+                &Migration::Drop,
+            ]
+        );
+        assert_eq!(plan.replacements().count(), 2);
+        assert!(!plan.is_noop());
+    }
+}
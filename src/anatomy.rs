@@ -0,0 +1,241 @@
+//! Project the phenotype terms onto the anatomical entities, e.g. for a "body map".
+//!
+//! [`AnatomicalProjection`] maps the phenotype terms (e.g. HPO) to the anatomical entities
+//! (e.g. UBERON) affected by the phenotype, using a mapping table such as the one derived
+//! from the HPO logical definitions. The phenotype terms with no mapping inherit the entities
+//! of their closest mapped ancestors.
+//!
+//! The entities are grouped by the organs of interest along the `is_a` hierarchy
+//! of the anatomy ontology (see [`AnatomicalProjection::group_by_organ`]).
+//! Note that the `part_of` relationships are not considered.
+use std::collections::HashMap;
+
+use crate::base::{Identified, TermId};
+use crate::hierarchy::{bfs_levels, AncestorNodes, ParentNodes};
+use crate::ontology::Ontology;
+
+/// A mapping from the phenotype terms to the affected anatomical entities.
+#[derive(Debug, Clone, Default)]
+pub struct AnatomicalProjection {
+    entities: HashMap<TermId, Vec<TermId>>,
+}
+
+impl AnatomicalProjection {
+    /// Create the projection from the `(phenotype, anatomical entity)` pairs of the mapping table.
+    pub fn new<I>(mapping: I) -> Self
+    where
+        I: IntoIterator<Item = (TermId, TermId)>,
+    {
+        let mut entities: HashMap<_, Vec<_>> = HashMap::new();
+        for (phenotype, entity) in mapping {
+            let mapped = entities.entry(phenotype).or_default();
+            if !mapped.contains(&entity) {
+                mapped.push(entity);
+            }
+        }
+        Self { entities }
+    }
+
+    /// Get the anatomical entities mapped to the `phenotype` term by the mapping table.
+    pub fn mapped_entities(&self, phenotype: &TermId) -> &[TermId] {
+        self.entities
+            .get(phenotype)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Get the anatomical entities affected by the `phenotype` term.
+    ///
+    /// The entities are mapped to the primary ID of the term. If the term has no mapping,
+    /// the entities of its closest mapped ancestors in the `phenotypes` ontology are returned.
+    /// Returns no entities if the term is not in the `phenotypes` ontology.
+    pub fn project<'a, O>(&'a self, phenotypes: &O, phenotype: &TermId) -> Vec<&'a TermId>
+    where
+        O: Ontology,
+    {
+        let Some(idx) = phenotypes.id_to_idx(phenotype) else {
+            return vec![];
+        };
+        let hierarchy = phenotypes.hierarchy();
+        let mut projected = vec![];
+        for level in bfs_levels(idx, None, |node| hierarchy.parents_of(node)) {
+            for node in level {
+                let entities = phenotypes
+                    .idx_to_term(node)
+                    .map(|term| self.mapped_entities(term.identifier()))
+                    .unwrap_or_default();
+                for entity in entities {
+                    if !projected.contains(&entity) {
+                        projected.push(entity);
+                    }
+                }
+            }
+            if !projected.is_empty() {
+                break;
+            }
+        }
+        projected
+    }
+
+    /// Group the `phenotype_ids` by the `organs` affected by the phenotypes.
+    ///
+    /// A phenotype affects an organ if any of its projected entities (see [`Self::project`])
+    /// is the organ or a descendant of the organ in the `anatomy` ontology.
+    /// A phenotype can affect several organs.
+    pub fn group_by_organ<'p, P, A, I>(
+        &self,
+        phenotypes: &P,
+        anatomy: &A,
+        organs: &[TermId],
+        phenotype_ids: I,
+    ) -> BodyMap
+    where
+        P: Ontology,
+        A: Ontology,
+        I: IntoIterator<Item = &'p TermId>,
+    {
+        let hierarchy = anatomy.hierarchy();
+        let organ_indices: Vec<_> = organs
+            .iter()
+            .map(|organ| anatomy.id_to_idx(organ))
+            .collect();
+
+        let mut body_map = BodyMap {
+            organs: organs.iter().map(|organ| (organ.clone(), vec![])).collect(),
+            unassigned: vec![],
+        };
+        for phenotype in phenotype_ids {
+            let entities: Vec<_> = self
+                .project(phenotypes, phenotype)
+                .into_iter()
+                .flat_map(|entity| anatomy.id_to_idx(entity))
+                .collect();
+            let mut assigned = false;
+            for ((_, members), organ) in body_map.organs.iter_mut().zip(&organ_indices) {
+                let Some(organ) = *organ else {
+                    continue;
+                };
+                if entities
+                    .iter()
+                    .any(|&entity| entity == organ || hierarchy.is_ancestor_of(organ, entity))
+                {
+                    members.push(phenotype.clone());
+                    assigned = true;
+                }
+            }
+            if !assigned {
+                body_map.unassigned.push(phenotype.clone());
+            }
+        }
+
+        body_map
+    }
+}
+
+/// The phenotypes grouped by the affected organs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BodyMap {
+    organs: Vec<(TermId, Vec<TermId>)>,
+    unassigned: Vec<TermId>,
+}
+
+impl BodyMap {
+    /// Get the phenotypes that affect the `organ`,
+    /// or `None` if the organ was not among the organs of interest.
+    pub fn phenotypes_of(&self, organ: &TermId) -> Option<&[TermId]> {
+        self.organs
+            .iter()
+            .find(|(candidate, _)| candidate == organ)
+            .map(|(_, phenotypes)| phenotypes.as_slice())
+    }
+
+    /// Iterate over the organs of interest and the phenotypes that affect them,
+    /// in the order of the organs.
+    pub fn iter(&self) -> impl Iterator<Item = (&TermId, &[TermId])> {
+        self.organs
+            .iter()
+            .map(|(organ, phenotypes)| (organ, phenotypes.as_slice()))
+    }
+
+    /// Get the phenotypes that affect none of the organs of interest.
+    pub fn unassigned(&self) -> &[TermId] {
+        &self.unassigned
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::ontology::csr::CsrOntology;
+    use crate::test_util::toy_ontology;
+
+    fn ontology(
+        prefix: &str,
+        names: &[&str],
+        edges: &[(usize, usize)],
+    ) -> CsrOntology<usize, SimpleMinimalTerm> {
+        let terms: Vec<_> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                SimpleMinimalTerm::new(
+                    TermId::from((prefix, i.to_string().as_str())),
+                    *name,
+                    vec![],
+                    false,
+                )
+            })
+            .collect();
+        toy_ontology(terms, edges)
+    }
+
+    #[test]
+    fn test_group_by_organ() {
+        let hpo = ontology(
+            "HP",
+            &[
+                "All",
+                "Abnormal limb",
+                "Arachnodactyly",
+                "Abnormal heart",
+                "Tall stature",
+            ],
+            &[(1, 0), (2, 1), (3, 0), (4, 0)],
+        );
+        let uberon = ontology(
+            "UBERON",
+            &["Anatomical entity", "Limb", "Finger", "Heart"],
+            &[(1, 0), (2, 1), (3, 0)],
+        );
+        let hp = |id: &str| TermId::from(("HP", id));
+        let uberon_id = |id: &str| TermId::from(("UBERON", id));
+        let projection = AnatomicalProjection::new([
+            (hp("1"), uberon_id("1")),
+            (hp("3"), uberon_id("3")),
+            (hp("3"), uberon_id("3")),
+        ]);
+
+        assert_eq!(projection.mapped_entities(&hp("3")), [uberon_id("3")]);
+        assert_eq!(projection.project(&hpo, &hp("2")), [&uberon_id("1")]);
+        assert!(projection.project(&hpo, &hp("4")).is_empty());
+
+        let organs = [uberon_id("1"), uberon_id("2"), uberon_id("3")];
+        let body_map = projection.group_by_organ(
+            &hpo,
+            &uberon,
+            &organs,
+            &[hp("1"), hp("2"), hp("3"), hp("4")],
+        );
+
+        assert_eq!(
+            body_map.phenotypes_of(&uberon_id("1")).unwrap(),
+            [hp("1"), hp("2")]
+        );
+        assert!(body_map.phenotypes_of(&uberon_id("2")).unwrap().is_empty());
+        assert_eq!(body_map.phenotypes_of(&uberon_id("3")).unwrap(), [hp("3")]);
+        assert!(body_map.phenotypes_of(&uberon_id("0")).is_none());
+        assert_eq!(body_map.unassigned(), [hp("4")]);
+        assert_eq!(body_map.iter().count(), 3);
+    }
+}
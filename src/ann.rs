@@ -0,0 +1,432 @@
+//! Find the most similar profiles (e.g. the diseases with phenotype annotations)
+//! to a query profile with an approximate nearest neighbor (ANN) index.
+//!
+//! A profile is a set of terms, represented by a sparse vector over the terms
+//! and their ancestors, weighted by the intrinsic information content
+//! (see [`IcOntology`]). The similarity of two profiles is the cosine of their vectors,
+//! hence the profiles that share the specific terms (or the specific ancestors)
+//! are more similar than the profiles that share the general terms only.
+//!
+//! [`HnswIndex`] is a Hierarchical Navigable Small World graph of the profiles,
+//! which finds the nearest neighbors of a query without comparing it to all profiles.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! use ontolius::ann::HnswBuilder;
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                    .expect("HPO should be loaded");
+//! let hpo = hpo.with_intrinsic_ic();
+//!
+//! let profiles = vec![
+//!     // Arachnodactyly and Ectopia lentis.
+//!     (TermId::from(("OMIM", "154700")), vec![TermId::from(("HP", "0001166")), TermId::from(("HP", "0001083"))]),
+//!     // Abnormality of limbs.
+//!     (TermId::from(("OMIM", "000001")), vec![TermId::from(("HP", "0040064"))]),
+//! ];
+//! let index = HnswBuilder::new().build(&hpo, profiles);
+//!
+//! let hits = index.search(&[TermId::from(("HP", "0001166"))], 2);
+//! assert_eq!(hits[0].term_id(), &TermId::from(("OMIM", "154700")));
+//! assert!(hits[0].score() > hits[1].score());
+//! ```
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeSet, BinaryHeap, HashSet};
+
+use crate::base::TermId;
+use crate::hierarchy::AncestorNodes;
+use crate::ontology::{HierarchyAware, IcOntology, Ontology, TermAware, TermIdx};
+use crate::ranking::{rank_all, Ranked};
+
+/// A unit vector with the weights of the term indices, sorted by the index.
+type SparseVector = Vec<(usize, f64)>;
+
+/// Get the IC-weighted vector of the `terms` and their ancestors.
+///
+/// The obsolete and the unknown terms, and the terms with IC of `0` (the root), are skipped.
+fn closure_vector<O>(ontology: &IcOntology<'_, O>, terms: &[TermId]) -> SparseVector
+where
+    O: Ontology,
+{
+    let hierarchy = ontology.hierarchy();
+    let mut closure = BTreeSet::new();
+    for idx in terms.iter().flat_map(|term_id| ontology.id_to_idx(term_id)) {
+        if closure.insert(idx) {
+            closure.extend(hierarchy.ancestors_of(idx).copied());
+        }
+    }
+
+    let mut vector: SparseVector = closure
+        .into_iter()
+        .flat_map(|idx| {
+            ontology
+                .intrinsic_ic(idx)
+                .filter(|&ic| ic > 0.)
+                .map(|ic| (TermIdx::index(idx), ic))
+        })
+        .collect();
+    let norm = vector.iter().map(|(_, w)| w * w).sum::<f64>().sqrt();
+    if norm > 0. {
+        vector.iter_mut().for_each(|(_, w)| *w /= norm);
+    }
+    vector.sort_unstable_by_key(|&(idx, _)| idx);
+    vector
+}
+
+/// Get the cosine distance of the unit vectors.
+fn distance(left: &SparseVector, right: &SparseVector) -> f64 {
+    let (mut i, mut j, mut dot) = (0, 0, 0.);
+    while i < left.len() && j < right.len() {
+        match left[i].0.cmp(&right[j].0) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                dot += left[i].1 * right[j].1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    1. - dot
+}
+
+/// A node of the graph with its distance from the query, ordered by the distance and the node.
+#[derive(Clone, Copy)]
+struct Near(f64, usize);
+
+impl PartialEq for Near {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Near {}
+
+impl PartialOrd for Near {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Near {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+/// Builder of [`HnswIndex`].
+///
+/// By default, each node links to `16` neighbors (`32` in the bottom layer),
+/// the construction explores `100` candidates, and the search explores `64` candidates.
+/// Exploring more candidates improves the recall, at the expense of the speed.
+pub struct HnswBuilder {
+    m: usize,
+    ef_construction: usize,
+    ef_search: usize,
+}
+
+impl Default for HnswBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HnswBuilder {
+    pub fn new() -> Self {
+        Self {
+            m: 16,
+            ef_construction: 100,
+            ef_search: 64,
+        }
+    }
+
+    /// Set the number of the neighbors of each node.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `m` is less than `2`.
+    #[must_use]
+    pub fn m(mut self, m: usize) -> Self {
+        assert!(m >= 2, "The number of neighbors must be at least 2");
+        self.m = m;
+        self
+    }
+
+    /// Set the number of the candidates explored when inserting a profile.
+    #[must_use]
+    pub fn ef_construction(mut self, ef_construction: usize) -> Self {
+        self.ef_construction = ef_construction.max(1);
+        self
+    }
+
+    /// Set the number of the candidates explored by [`HnswIndex::search`].
+    #[must_use]
+    pub fn ef_search(mut self, ef_search: usize) -> Self {
+        self.ef_search = ef_search.max(1);
+        self
+    }
+
+    /// Build the index of the `profiles`, the pairs of a profile ID (e.g. a disease ID)
+    /// and the profile terms, with the term weights of the `ontology`.
+    ///
+    /// The index is the same for the same profiles, since the layers of the nodes
+    /// are derived from the order of the profiles rather than chosen at random.
+    pub fn build<'a, 'o, O, I>(
+        &self,
+        ontology: &'a IcOntology<'o, O>,
+        profiles: I,
+    ) -> HnswIndex<'a, 'o, O>
+    where
+        O: Ontology,
+        I: IntoIterator<Item = (TermId, Vec<TermId>)>,
+    {
+        let mut index = HnswIndex {
+            ontology,
+            ids: vec![],
+            vectors: vec![],
+            links: vec![],
+            entry: None,
+            m: self.m,
+            ef_construction: self.ef_construction,
+            ef_search: self.ef_search,
+        };
+        for (id, terms) in profiles {
+            let vector = closure_vector(ontology, &terms);
+            index.insert(id, vector);
+        }
+        index
+    }
+}
+
+/// An approximate nearest neighbor index of the IC-weighted profiles
+/// (see the [module](self) documentation).
+pub struct HnswIndex<'a, 'o, O>
+where
+    O: Ontology,
+{
+    ontology: &'a IcOntology<'o, O>,
+    ids: Vec<TermId>,
+    vectors: Vec<SparseVector>,
+    /// The neighbors of each node, in each layer of the node, starting from the bottom layer.
+    links: Vec<Vec<Vec<usize>>>,
+    /// The node in the top layer.
+    entry: Option<usize>,
+    m: usize,
+    ef_construction: usize,
+    ef_search: usize,
+}
+
+impl<O> HnswIndex<'_, '_, O>
+where
+    O: Ontology,
+{
+    /// Get the number of the profiles.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Test if there are no profiles.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Find the `k` profiles most similar to the `query` terms, with the cosine similarity
+    /// as the score, from the most to the least similar.
+    ///
+    /// The result is approximate: a similar profile can be missed.
+    pub fn search(&self, query: &[TermId], k: usize) -> Vec<Ranked<f64>> {
+        let Some(entry) = self.entry else {
+            return vec![];
+        };
+        let query = closure_vector(self.ontology, query);
+        let mut nearest = vec![Near(distance(&query, &self.vectors[entry]), entry)];
+        for layer in (1..self.links[entry].len()).rev() {
+            nearest = self.search_layer(&query, &nearest, 1, layer);
+        }
+        nearest = self.search_layer(&query, &nearest, self.ef_search.max(k), 0);
+        self.to_ranked(nearest, k)
+    }
+
+    /// Find the `k` profiles most similar to the `query` terms by comparing the query
+    /// to all profiles, e.g. to measure the recall of [`HnswIndex::search`].
+    pub fn exact_search(&self, query: &[TermId], k: usize) -> Vec<Ranked<f64>> {
+        let query = closure_vector(self.ontology, query);
+        let mut nearest: Vec<_> = self
+            .vectors
+            .iter()
+            .enumerate()
+            .map(|(node, vector)| Near(distance(&query, vector), node))
+            .collect();
+        nearest.sort_unstable();
+        self.to_ranked(nearest, k)
+    }
+
+    fn to_ranked(&self, nearest: Vec<Near>, k: usize) -> Vec<Ranked<f64>> {
+        let mut ranked = rank_all(
+            nearest
+                .into_iter()
+                .map(|Near(distance, node)| (self.ids[node].clone(), 1. - distance)),
+        );
+        ranked.truncate(k);
+        ranked
+    }
+
+    fn insert(&mut self, id: TermId, vector: SparseVector) {
+        let node = self.ids.len();
+        let level = self.level(node);
+        self.ids.push(id);
+        self.vectors.push(vector);
+        self.links.push(vec![vec![]; level + 1]);
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(node);
+            return;
+        };
+        let top = self.links[entry].len() - 1;
+        let query = &self.vectors[node];
+        let mut nearest = vec![Near(distance(query, &self.vectors[entry]), entry)];
+        for layer in (level + 1..=top).rev() {
+            nearest = self.search_layer(query, &nearest, 1, layer);
+        }
+
+        for layer in (0..=level.min(top)).rev() {
+            nearest = self.search_layer(&self.vectors[node], &nearest, self.ef_construction, layer);
+            let neighbors: Vec<_> = nearest.iter().take(self.m).map(|near| near.1).collect();
+            let max_links = if layer == 0 { 2 * self.m } else { self.m };
+            for &neighbor in &neighbors {
+                self.links[neighbor][layer].push(node);
+                if self.links[neighbor][layer].len() > max_links {
+                    self.prune(neighbor, layer, max_links);
+                }
+            }
+            self.links[node][layer] = neighbors;
+        }
+
+        if level > top {
+            self.entry = Some(node);
+        }
+    }
+
+    /// Keep the `max_links` nearest neighbors of the `node` in the `layer`.
+    fn prune(&mut self, node: usize, layer: usize, max_links: usize) {
+        let vector = &self.vectors[node];
+        let mut neighbors: Vec<_> = self.links[node][layer]
+            .iter()
+            .map(|&neighbor| Near(distance(vector, &self.vectors[neighbor]), neighbor))
+            .collect();
+        neighbors.sort_unstable();
+        self.links[node][layer] = neighbors
+            .into_iter()
+            .take(max_links)
+            .map(|near| near.1)
+            .collect();
+    }
+
+    /// Get the `ef` nodes nearest to the `query` in the `layer`,
+    /// starting from the `entries`, sorted by the distance.
+    fn search_layer(
+        &self,
+        query: &SparseVector,
+        entries: &[Near],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Near> {
+        let mut visited: HashSet<_> = entries.iter().map(|near| near.1).collect();
+        let mut candidates: BinaryHeap<_> = entries.iter().copied().map(Reverse).collect();
+        let mut nearest: BinaryHeap<_> = entries.iter().copied().collect();
+        while nearest.len() > ef {
+            nearest.pop();
+        }
+
+        while let Some(Reverse(candidate)) = candidates.pop() {
+            if nearest.len() >= ef && nearest.peek().is_some_and(|&furthest| candidate > furthest) {
+                break;
+            }
+            for &neighbor in &self.links[candidate.1][layer] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let near = Near(distance(query, &self.vectors[neighbor]), neighbor);
+                if nearest.len() < ef || nearest.peek().is_some_and(|&furthest| near < furthest) {
+                    candidates.push(Reverse(near));
+                    nearest.push(near);
+                    if nearest.len() > ef {
+                        nearest.pop();
+                    }
+                }
+            }
+        }
+        nearest.into_sorted_vec()
+    }
+
+    /// Draw the top layer of the `node` from the geometric distribution of the HNSW,
+    /// with a hash of the node instead of a random number.
+    fn level(&self, node: usize) -> usize {
+        // SplitMix64.
+        let mut x = (node as u64).wrapping_add(0x9e3779b97f4a7c15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+        x ^= x >> 31;
+        // A uniform number in `(0, 1]`.
+        let uniform = ((x >> 11) + 1) as f64 / (1u64 << 53) as f64;
+        (-uniform.ln() / (self.m as f64).ln()) as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curie_util::TrieCurieUtil;
+
+    use super::*;
+    use crate::io::obographs::ObographsParser;
+    use crate::io::OntologyLoaderBuilder;
+    use crate::ontology::csr::MinimalCsrOntology;
+
+    #[test]
+    fn test_search_recall() {
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .build();
+        let hpo: MinimalCsrOntology = loader.load_from_path("resources/hp.small.json.gz").unwrap();
+        let hpo = hpo.with_intrinsic_ic();
+
+        // The profiles of two or three terms, spread over the ontology.
+        let term_ids: Vec<_> = hpo.iter_term_ids().cloned().collect();
+        let profiles: Vec<_> = (0..term_ids.len())
+            .map(|i| {
+                let terms = (0..2 + i % 2)
+                    .map(|j| term_ids[(i * 7 + j * 131) % term_ids.len()].clone())
+                    .collect();
+                (TermId::from(("P", i.to_string().as_str())), terms)
+            })
+            .collect();
+        let index = HnswBuilder::new().m(8).build(&hpo, profiles.clone());
+        assert_eq!(index.len(), profiles.len());
+
+        let k = 10;
+        let mut found = 0;
+        for (id, terms) in profiles.iter().step_by(10) {
+            let exact = index.exact_search(terms, k);
+            let approximate = index.search(terms, k);
+            assert_eq!(approximate.len(), k);
+            // The profile is the most similar to itself, unless other profiles are identical.
+            assert!(approximate
+                .iter()
+                .any(|hit| hit.term_id() == id || *hit.score() > 1. - 1e-9));
+            found += approximate
+                .iter()
+                .filter(|hit| exact.iter().any(|e| e.term_id() == hit.term_id()))
+                .count();
+        }
+        let recall = found as f64 / (profiles.len().div_ceil(10) * k) as f64;
+        assert!(recall > 0.9, "Recall {recall} should be above 0.9");
+    }
+}
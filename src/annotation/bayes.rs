@@ -0,0 +1,297 @@
+//! Rank the diseases by the posterior probability given the phenotypes of a patient,
+//! with a naive Bayes classifier over the [`DiseaseProfile`]s, in the spirit of BOQA.
+//!
+//! Each phenotype term of the ontology is a binary feature. A patient with a disease
+//! has the phenotype with the frequency of the disease profile, and the features
+//! are observed with noise: an absent phenotype is reported with the false positive rate,
+//! and a present phenotype is missed with the false negative rate.
+//! The query phenotypes and their ancestors are observed as present,
+//! and all other terms as absent.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! use ontolius::annotation::bayes::NaiveBayesMatcher;
+//! use ontolius::annotation::hpoa::{read_hpoa, DiseaseProfileBuilder};
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                    .expect("HPO should be loaded");
+//! #
+//! let table = "\
+//! database_id\tdisease_name\tqualifier\thpo_id\tevidence\tfrequency\taspect
+//! OMIM:154700\tMarfan syndrome\t\tHP:0001166\tTAS\tHP:0040281\tP
+//! OMIM:000001\tOther syndrome\t\tHP:0040064\tTAS\tHP:0040281\tP
+//! ";
+//! let records = read_hpoa(table.as_bytes()).expect("Annotations should be readable");
+//! let profiles = DiseaseProfileBuilder::new().build(&records, &hpo);
+//!
+//! let matcher = NaiveBayesMatcher::new(&hpo, &profiles);
+//! let ranked = matcher.rank(&[TermId::from(("HP", "0001166"))]);
+//!
+//! // Arachnodactyly points to Marfan syndrome.
+//! assert_eq!(ranked[0].disease_id(), &TermId::from(("OMIM", "154700")));
+//! assert!(ranked[0].posterior() > ranked[1].posterior());
+//! ```
+use std::collections::HashMap;
+
+use crate::base::TermId;
+use crate::hierarchy::AncestorNodes;
+use crate::ontology::{Ontology, TermIdx};
+use crate::ranking::cmp_ranked;
+
+use super::hpoa::DiseaseProfile;
+
+/// A disease profile with the phenotypes as the term indices.
+struct Model {
+    disease_id: TermId,
+    weight: f64,
+    frequencies: Vec<(usize, f64)>,
+}
+
+/// A disease ranked by [`NaiveBayesMatcher::rank`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiseaseMatch {
+    disease_id: TermId,
+    log_likelihood: f64,
+    posterior: f64,
+}
+
+impl DiseaseMatch {
+    /// Get the ID of the disease.
+    pub fn disease_id(&self) -> &TermId {
+        &self.disease_id
+    }
+
+    /// Get the natural logarithm of the likelihood of the query given the disease.
+    pub fn log_likelihood(&self) -> f64 {
+        self.log_likelihood
+    }
+
+    /// Get the posterior probability of the disease given the query.
+    pub fn posterior(&self) -> f64 {
+        self.posterior
+    }
+}
+
+/// A naive Bayes classifier of the diseases by the phenotypes.
+///
+/// By default, all diseases have the same prior probability, the false positive rate is `0.01`,
+/// and the false negative rate is `0.1`.
+pub struct NaiveBayesMatcher<'o, O> {
+    ontology: &'o O,
+    models: Vec<Model>,
+    false_positive_rate: f64,
+    false_negative_rate: f64,
+}
+
+impl<'o, O> NaiveBayesMatcher<'o, O>
+where
+    O: Ontology,
+{
+    /// Create the matcher of the diseases with the `profiles`,
+    /// where the phenotypes are the terms of the `ontology`.
+    ///
+    /// The phenotypes missing from the `ontology` are ignored.
+    pub fn new(ontology: &'o O, profiles: &[DiseaseProfile]) -> Self {
+        let models = profiles
+            .iter()
+            .map(|profile| {
+                let mut frequencies: Vec<_> = profile
+                    .iter()
+                    .flat_map(|(term_id, frequency)| {
+                        ontology
+                            .id_to_idx(term_id)
+                            .map(|idx| (TermIdx::index(idx), frequency))
+                    })
+                    .collect();
+                frequencies.sort_unstable_by_key(|&(idx, _)| idx);
+                Model {
+                    disease_id: profile.disease_id().clone(),
+                    weight: 1.,
+                    frequencies,
+                }
+            })
+            .collect();
+        Self {
+            ontology,
+            models,
+            false_positive_rate: 0.01,
+            false_negative_rate: 0.1,
+        }
+    }
+
+    /// Set the relative prior weights of the diseases, e.g. the disease prevalences.
+    ///
+    /// The weights are normalized over the diseases of the matcher,
+    /// and the diseases with no weight keep the weight of `1`.
+    #[must_use]
+    pub fn priors<I>(mut self, weights: I) -> Self
+    where
+        I: IntoIterator<Item = (TermId, f64)>,
+    {
+        let weights: HashMap<_, _> = weights.into_iter().collect();
+        for model in self.models.iter_mut() {
+            if let Some(&weight) = weights.get(&model.disease_id) {
+                model.weight = weight;
+            }
+        }
+        self
+    }
+
+    /// Set the probability of observing a phenotype that the patient does not have
+    /// (`false_positive_rate`), and of missing a phenotype that the patient has
+    /// (`false_negative_rate`).
+    ///
+    /// ## Panics
+    ///
+    /// Panics if a rate is not in the open interval `(0, 1)`.
+    #[must_use]
+    pub fn noise(mut self, false_positive_rate: f64, false_negative_rate: f64) -> Self {
+        assert!(
+            0. < false_positive_rate && false_positive_rate < 1.,
+            "The false positive rate must be in (0, 1)"
+        );
+        assert!(
+            0. < false_negative_rate && false_negative_rate < 1.,
+            "The false negative rate must be in (0, 1)"
+        );
+        self.false_positive_rate = false_positive_rate;
+        self.false_negative_rate = false_negative_rate;
+        self
+    }
+
+    /// Rank the diseases by the posterior probability given the `query` phenotypes,
+    /// from the most to the least probable. The diseases with the same posterior
+    /// are ordered by the disease ID (see [`cmp_ranked`]).
+    ///
+    /// The query terms missing from the ontology are ignored.
+    pub fn rank(&self, query: &[TermId]) -> Vec<DiseaseMatch> {
+        let observed = self.observed(query);
+        let observed_count: usize = observed.iter().map(|word| word.count_ones() as usize).sum();
+        let (alpha, beta) = (self.false_positive_rate, self.false_negative_rate);
+        let ln_on = |frequency: f64| (frequency * (1. - beta) + (1. - frequency) * alpha).ln();
+        let ln_off = |frequency: f64| (frequency * beta + (1. - frequency) * (1. - alpha)).ln();
+        let term_count = self.ontology.len();
+
+        let mut matches: Vec<_> = self
+            .models
+            .iter()
+            .map(|model| {
+                // The terms missing from the profile have the frequency of `0`.
+                let mut observed_in_profile = 0;
+                let mut log_likelihood = 0.;
+                for &(idx, frequency) in &model.frequencies {
+                    if observed[idx / 64] & (1 << (idx % 64)) != 0 {
+                        observed_in_profile += 1;
+                        log_likelihood += ln_on(frequency);
+                    } else {
+                        log_likelihood += ln_off(frequency);
+                    }
+                }
+                let observed_out = observed_count - observed_in_profile;
+                let unobserved_out = term_count - model.frequencies.len() - observed_out;
+                log_likelihood += observed_out as f64 * ln_on(0.);
+                log_likelihood += unobserved_out as f64 * ln_off(0.);
+
+                DiseaseMatch {
+                    disease_id: model.disease_id.clone(),
+                    log_likelihood,
+                    posterior: log_likelihood + model.weight.ln(),
+                }
+            })
+            .collect();
+
+        // Normalize the joint log probabilities with the log-sum-exp trick.
+        let max = matches
+            .iter()
+            .map(|m| m.posterior)
+            .fold(f64::NEG_INFINITY, f64::max);
+        if max.is_finite() {
+            let total: f64 = matches.iter().map(|m| (m.posterior - max).exp()).sum();
+            for m in matches.iter_mut() {
+                m.posterior = (m.posterior - max).exp() / total;
+            }
+        } else {
+            matches.iter_mut().for_each(|m| m.posterior = 0.);
+        }
+        matches.sort_by(|a, b| {
+            cmp_ranked((&a.disease_id, &a.posterior), (&b.disease_id, &b.posterior))
+        });
+        matches
+    }
+
+    /// Get the bitset of the indices of the `query` terms and their ancestors.
+    fn observed(&self, query: &[TermId]) -> Vec<u64> {
+        let hierarchy = self.ontology.hierarchy();
+        let mut observed = vec![0u64; self.ontology.len().div_ceil(64)];
+        let mut mark = |idx: usize| {
+            let (word, bit) = (idx / 64, 1 << (idx % 64));
+            let unseen = observed[word] & bit == 0;
+            observed[word] |= bit;
+            unseen
+        };
+        for idx in query
+            .iter()
+            .flat_map(|term_id| self.ontology.id_to_idx(term_id))
+        {
+            if mark(TermIdx::index(idx)) {
+                for &ancestor in hierarchy.ancestors_of(idx) {
+                    mark(TermIdx::index(ancestor));
+                }
+            }
+        }
+        observed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::annotation::hpoa::{read_hpoa, DiseaseProfileBuilder};
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_rank() {
+        let terms: Vec<_> = (1..=5)
+            .map(|i| {
+                let id = i.to_string();
+                SimpleMinimalTerm::new(TermId::from(("HP", id.as_str())), "Term", vec![], false)
+            })
+            .collect();
+        // HP:1 is the root of HP:2 and HP:4, HP:2 is the parent of HP:3, and HP:4 of HP:5.
+        let hpo = toy_ontology(terms, &[(1, 0), (2, 1), (3, 0), (4, 3)]);
+
+        let table = "database_id\tdisease_name\tqualifier\thpo_id\tevidence\tfrequency\taspect\n\
+            OMIM:1\tFirst\t\tHP:3\tPCS\t\tP\n\
+            OMIM:2\tSecond\t\tHP:5\tPCS\t1/2\tP\n\
+            OMIM:3\tThird\t\tHP:5\tPCS\t\tP\n";
+        let records = read_hpoa(table.as_bytes()).unwrap();
+        let profiles = DiseaseProfileBuilder::new().build(&records, &hpo);
+        let matcher = NaiveBayesMatcher::new(&hpo, &profiles);
+
+        let ranked = matcher.rank(&[TermId::from(("HP", "5"))]);
+        let ids: Vec<_> = ranked.iter().map(|m| m.disease_id().to_string()).collect();
+        assert_eq!(ids, ["OMIM:3", "OMIM:2", "OMIM:1"]);
+        let total: f64 = ranked.iter().map(DiseaseMatch::posterior).sum();
+        assert!((total - 1.).abs() < 1e-9);
+
+        // The prior of the second disease outweighs the likelihood.
+        let ranked = NaiveBayesMatcher::new(&hpo, &profiles)
+            .priors([(TermId::from(("OMIM", "2")), 100.)])
+            .rank(&[TermId::from(("HP", "5"))]);
+        assert_eq!(ranked[0].disease_id(), &TermId::from(("OMIM", "2")));
+
+        // An empty query favors the diseases with the rare phenotypes.
+        let ranked = matcher.noise(0.05, 0.2).rank(&[]);
+        assert_eq!(ranked[0].disease_id(), &TermId::from(("OMIM", "2")));
+        assert!(ranked[1].log_likelihood() < ranked[0].log_likelihood());
+    }
+}
@@ -0,0 +1,411 @@
+//! Benchmark the disease matchers with the rank-based metrics, such as the top-k accuracy
+//! and the mean reciprocal rank (MRR).
+//!
+//! A matcher implements [`ProfileMatcher`] to rank the diseases by the phenotypes of a patient,
+//! and [`evaluate`] ranks the diseases for each [`Patient`] and reports the rank
+//! of the true diagnosis in a [`BenchmarkReport`]. With the `rand` feature,
+//! `PatientSimulator` samples the patients from the [`DiseaseProfile`](super::hpoa::DiseaseProfile)s.
+//! The simulated cohorts are reproducible with a seeded random number generator,
+//! hence the matchers can be compared on the same patients.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! use ontolius::annotation::bayes::NaiveBayesMatcher;
+//! use ontolius::annotation::benchmark::{evaluate, Patient};
+//! use ontolius::annotation::hpoa::{read_hpoa, DiseaseProfileBuilder};
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                    .expect("HPO should be loaded");
+//! #
+//! let table = "\
+//! database_id\tdisease_name\tqualifier\thpo_id\tevidence\tfrequency\taspect
+//! OMIM:154700\tMarfan syndrome\t\tHP:0001166\tTAS\tHP:0040281\tP
+//! OMIM:000001\tOther syndrome\t\tHP:0040064\tTAS\tHP:0040281\tP
+//! ";
+//! let records = read_hpoa(table.as_bytes()).expect("Annotations should be readable");
+//! let profiles = DiseaseProfileBuilder::new().build(&records, &hpo);
+//! let matcher = NaiveBayesMatcher::new(&hpo, &profiles);
+//!
+//! let patients = [
+//!     Patient::new(TermId::from(("OMIM", "154700")), vec![TermId::from(("HP", "0001166"))]),
+//!     Patient::new(TermId::from(("OMIM", "000001")), vec![TermId::from(("HP", "0001166"))]),
+//! ];
+//! let report = evaluate(&matcher, &patients);
+//!
+//! // The second patient has a phenotype that points to Marfan syndrome.
+//! assert_eq!(report.ranks(), [Some(1), Some(2)]);
+//! assert_eq!(report.top_k(1), 0.5);
+//! assert_eq!(report.mean_reciprocal_rank(), 0.75);
+//! ```
+use std::fmt::Display;
+
+use crate::base::TermId;
+use crate::ontology::Ontology;
+
+use super::bayes::NaiveBayesMatcher;
+
+/// A matcher that ranks the diseases by the phenotypes of a patient.
+pub trait ProfileMatcher {
+    /// Rank the IDs of the diseases by the `query` phenotypes, from the best to the worst match.
+    fn rank_diseases(&self, query: &[TermId]) -> Vec<TermId>;
+}
+
+impl<O> ProfileMatcher for NaiveBayesMatcher<'_, O>
+where
+    O: Ontology,
+{
+    fn rank_diseases(&self, query: &[TermId]) -> Vec<TermId> {
+        self.rank(query)
+            .into_iter()
+            .map(|m| m.disease_id().clone())
+            .collect()
+    }
+}
+
+/// A patient with the phenotypes and the true diagnosis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patient {
+    disease_id: TermId,
+    phenotypes: Vec<TermId>,
+}
+
+impl Patient {
+    pub fn new(disease_id: TermId, phenotypes: Vec<TermId>) -> Self {
+        Self {
+            disease_id,
+            phenotypes,
+        }
+    }
+
+    /// Get the ID of the disease of the patient.
+    pub fn disease_id(&self) -> &TermId {
+        &self.disease_id
+    }
+
+    /// Get the IDs of the phenotypes of the patient.
+    pub fn phenotypes(&self) -> &[TermId] {
+        &self.phenotypes
+    }
+}
+
+/// Rank the diseases of the `patients` with the `matcher`,
+/// and report the ranks of the true diagnoses.
+pub fn evaluate<M>(matcher: &M, patients: &[Patient]) -> BenchmarkReport
+where
+    M: ProfileMatcher + ?Sized,
+{
+    let ranks = patients
+        .iter()
+        .map(|patient| {
+            matcher
+                .rank_diseases(patient.phenotypes())
+                .iter()
+                .position(|disease_id| disease_id == patient.disease_id())
+                .map(|i| i + 1)
+        })
+        .collect();
+    BenchmarkReport { ranks }
+}
+
+/// The ranks of the true diagnoses of the patients, computed by [`evaluate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchmarkReport {
+    ranks: Vec<Option<usize>>,
+}
+
+impl BenchmarkReport {
+    /// Get the 1-based rank of the true diagnosis of each patient,
+    /// or `None` if the matcher did not rank the diagnosis at all.
+    pub fn ranks(&self) -> &[Option<usize>] {
+        &self.ranks
+    }
+
+    /// Get the number of the patients.
+    pub fn len(&self) -> usize {
+        self.ranks.len()
+    }
+
+    /// Test if there are no patients.
+    pub fn is_empty(&self) -> bool {
+        self.ranks.is_empty()
+    }
+
+    /// Get the fraction of the patients with the true diagnosis among the `k` best matches,
+    /// or `0` if there are no patients.
+    pub fn top_k(&self, k: usize) -> f64 {
+        self.mean(|rank| if rank <= k { 1. } else { 0. })
+    }
+
+    /// Get the mean of the reciprocal ranks of the true diagnoses, or `0` if there are no patients.
+    ///
+    /// The diagnoses that were not ranked contribute `0`.
+    pub fn mean_reciprocal_rank(&self) -> f64 {
+        self.mean(|rank| 1. / rank as f64)
+    }
+
+    fn mean<F>(&self, score: F) -> f64
+    where
+        F: Fn(usize) -> f64,
+    {
+        if self.ranks.is_empty() {
+            return 0.;
+        }
+        let total: f64 = self.ranks.iter().flatten().map(|&rank| score(rank)).sum();
+        total / self.ranks.len() as f64
+    }
+}
+
+impl Display for BenchmarkReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "patients: {}, top-1: {:.3}, top-10: {:.3}, top-100: {:.3}, MRR: {:.3}",
+            self.len(),
+            self.top_k(1),
+            self.top_k(10),
+            self.top_k(100),
+            self.mean_reciprocal_rank()
+        )
+    }
+}
+
+#[cfg(feature = "rand")]
+pub use simulation::PatientSimulator;
+
+#[cfg(feature = "rand")]
+mod simulation {
+    use std::collections::BTreeSet;
+
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    use crate::base::TermId;
+    use crate::hierarchy::{AncestorNodes, ParentNodes};
+    use crate::ontology::Ontology;
+
+    use super::super::hpoa::DiseaseProfile;
+    use super::Patient;
+
+    /// Simulate the patients with a disease by sampling the phenotypes of the disease profile.
+    ///
+    /// Each of the most specific phenotypes of the profile is present with its frequency,
+    /// and at least one of them is present. The phenotypes can be made imprecise
+    /// by coarsening them into a random ancestor (see [`ParentNodes::fuzz`]),
+    /// and unrelated noise phenotypes can be added.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use curie_util::TrieCurieUtil;
+    /// use ontolius::annotation::bayes::NaiveBayesMatcher;
+    /// use ontolius::annotation::benchmark::{evaluate, PatientSimulator};
+    /// use ontolius::annotation::hpoa::{read_hpoa, DiseaseProfileBuilder};
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// # use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// # let loader = OntologyLoaderBuilder::new()
+    /// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+    /// #                .build();
+    /// # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                                    .expect("HPO should be loaded");
+    /// let file = std::fs::File::open("resources/phenotype.real-shortlist.hpoa").unwrap();
+    /// let records = read_hpoa(std::io::BufReader::new(file)).expect("Annotations should be readable");
+    /// let profiles = DiseaseProfileBuilder::new().build(&records, &hpo);
+    ///
+    /// let simulator = PatientSimulator::new(&hpo).noise(1).imprecision(0.2);
+    /// let patients = simulator.simulate_all(&profiles, 10, &mut StdRng::seed_from_u64(42));
+    /// assert_eq!(patients.len(), 10 * profiles.len());
+    ///
+    /// // The same seed simulates the same patients.
+    /// assert_eq!(patients, simulator.simulate_all(&profiles, 10, &mut StdRng::seed_from_u64(42)));
+    ///
+    /// let report = evaluate(&NaiveBayesMatcher::new(&hpo, &profiles), &patients);
+    /// assert_eq!(report.len(), patients.len());
+    /// println!("{report}");
+    /// ```
+    pub struct PatientSimulator<'o, O> {
+        ontology: &'o O,
+        term_ids: Vec<&'o TermId>,
+        noise_terms: usize,
+        imprecision: f64,
+    }
+
+    impl<'o, O> PatientSimulator<'o, O>
+    where
+        O: Ontology,
+    {
+        /// Create the simulator of the patients with the phenotypes from the `ontology`.
+        ///
+        /// By default, the phenotypes are precise and there is no noise.
+        pub fn new(ontology: &'o O) -> Self {
+            Self {
+                ontology,
+                term_ids: ontology.iter_term_ids().collect(),
+                noise_terms: 0,
+                imprecision: 0.,
+            }
+        }
+
+        /// Add `noise_terms` phenotypes chosen uniformly from the current terms of the ontology
+        /// to each patient.
+        #[must_use]
+        pub fn noise(mut self, noise_terms: usize) -> Self {
+            self.noise_terms = noise_terms;
+            self
+        }
+
+        /// Set the probability of coarsening each phenotype into a random ancestor.
+        ///
+        /// ## Panics
+        ///
+        /// Panics if the `imprecision` is not in `[0, 1]`.
+        #[must_use]
+        pub fn imprecision(mut self, imprecision: f64) -> Self {
+            assert!(
+                (0. ..=1.).contains(&imprecision),
+                "The imprecision must be in [0, 1]"
+            );
+            self.imprecision = imprecision;
+            self
+        }
+
+        /// Simulate a patient with the disease of the `profile`.
+        ///
+        /// The phenotypes missing from the ontology are ignored, hence the patient has
+        /// no phenotypes other than the noise if the profile has no phenotype of the ontology.
+        pub fn simulate<R>(&self, profile: &DiseaseProfile, rng: &mut R) -> Patient
+        where
+            R: Rng + ?Sized,
+        {
+            let hierarchy = self.ontology.hierarchy();
+            let mut terms: Vec<_> = profile
+                .iter()
+                .flat_map(|(term_id, frequency)| {
+                    self.ontology
+                        .id_to_idx(term_id)
+                        .map(|idx| (idx, frequency.clamp(0., 1.)))
+                })
+                .collect();
+            // The profile includes the ancestors of its phenotypes, with the same or
+            // a greater frequency, hence only the most specific phenotypes are sampled.
+            let ancestors: BTreeSet<_> = terms
+                .iter()
+                .flat_map(|&(idx, _)| hierarchy.ancestors_of(idx))
+                .copied()
+                .collect();
+            terms.retain(|(idx, _)| !ancestors.contains(idx));
+            // Sort the terms to sample in the same order on each run.
+            terms.sort_unstable_by_key(|&(idx, _)| idx);
+
+            let mut phenotypes: Vec<_> = terms
+                .iter()
+                .filter(|&&(_, frequency)| rng.gen_bool(frequency))
+                .map(|&(idx, _)| idx)
+                .collect();
+            if phenotypes.is_empty() {
+                if let Ok(&(idx, _)) = terms.choose_weighted(rng, |&(_, frequency)| frequency) {
+                    phenotypes.push(idx);
+                }
+            }
+            for idx in phenotypes.iter_mut() {
+                if rng.gen_bool(self.imprecision) {
+                    *idx = hierarchy.fuzz(*idx, rng);
+                }
+            }
+
+            let mut phenotypes: Vec<TermId> = phenotypes
+                .into_iter()
+                .flat_map(|idx| self.ontology.idx_to_term_id(idx))
+                .cloned()
+                .collect();
+            for &noise in self.term_ids.choose_multiple(rng, self.noise_terms) {
+                if !phenotypes.contains(noise) {
+                    phenotypes.push(noise.clone());
+                }
+            }
+
+            Patient::new(profile.disease_id().clone(), phenotypes)
+        }
+
+        /// Simulate `per_disease` patients with each disease of the `profiles`.
+        pub fn simulate_all<R>(
+            &self,
+            profiles: &[DiseaseProfile],
+            per_disease: usize,
+            rng: &mut R,
+        ) -> Vec<Patient>
+        where
+            R: Rng + ?Sized,
+        {
+            profiles
+                .iter()
+                .flat_map(|profile| std::iter::repeat_n(profile, per_disease))
+                .map(|profile| self.simulate(profile, rng))
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Rank the diseases by the number of the query phenotypes named after them.
+    struct NameMatcher(Vec<TermId>);
+
+    impl ProfileMatcher for NameMatcher {
+        fn rank_diseases(&self, query: &[TermId]) -> Vec<TermId> {
+            let mut ranked = self.0.clone();
+            ranked.sort_by_key(|disease_id| {
+                std::cmp::Reverse(
+                    query
+                        .iter()
+                        .filter(|term_id| {
+                            term_id
+                                .to_string()
+                                .ends_with(disease_id.to_string().as_str())
+                        })
+                        .count(),
+                )
+            });
+            ranked
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let diseases: Vec<_> = (1..=4)
+            .map(|i| TermId::from(("OMIM", i.to_string().as_str())))
+            .collect();
+        let matcher = NameMatcher(diseases.clone());
+        let patients = [
+            Patient::new(diseases[2].clone(), vec![TermId::from(("X", "OMIM:3"))]),
+            Patient::new(diseases[3].clone(), vec![TermId::from(("X", "OMIM:1"))]),
+            Patient::new(TermId::from(("OMIM", "5")), vec![]),
+        ];
+
+        let report = evaluate(&matcher, &patients);
+        assert_eq!(report.ranks(), [Some(1), Some(4), None]);
+        assert_eq!(report.top_k(1), 1. / 3.);
+        assert_eq!(report.top_k(10), 2. / 3.);
+        assert_eq!(report.mean_reciprocal_rank(), (1. + 0.25) / 3.);
+        assert_eq!(
+            report.to_string(),
+            "patients: 3, top-1: 0.333, top-10: 0.667, top-100: 0.667, MRR: 0.417"
+        );
+
+        let empty = evaluate(&matcher, &[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.mean_reciprocal_rank(), 0.);
+    }
+}
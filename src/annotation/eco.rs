@@ -0,0 +1,137 @@
+//! The evidence codes of the annotations and the corresponding Evidence & Conclusion
+//! Ontology (ECO) terms.
+//!
+//! The GAF and HPOA files use the three-letter evidence codes (e.g. `IDA` or `TAS`),
+//! which are grouped into the [`EvidenceCategory`]s for filtering the annotations,
+//! e.g. to keep only the experimentally supported ones.
+//!
+//! ## Examples
+//!
+//! ```
+//! use ontolius::annotation::eco::{EvidenceCategory, eco_id};
+//! use ontolius::prelude::*;
+//!
+//! assert_eq!(EvidenceCategory::of("IDA"), Some(EvidenceCategory::Experimental));
+//! assert_eq!(EvidenceCategory::of("IEA"), Some(EvidenceCategory::Computational));
+//! assert_eq!(EvidenceCategory::of("TAS"), Some(EvidenceCategory::AuthorStatement));
+//!
+//! assert_eq!(eco_id("IEA"), Some(TermId::from(("ECO", "0000501"))));
+//! ```
+use crate::base::TermId;
+
+/// The root of ECO, `evidence`.
+pub const EVIDENCE: (&str, &str) = ("ECO", "0000000");
+/// `experimental evidence`.
+pub const EXPERIMENTAL_EVIDENCE: (&str, &str) = ("ECO", "0000006");
+/// `author statement`.
+pub const AUTHOR_STATEMENT: (&str, &str) = ("ECO", "0000204");
+/// `curator inference`.
+pub const CURATOR_INFERENCE: (&str, &str) = ("ECO", "0000205");
+/// `evidence used in automatic assertion`, the evidence of the `IEA` annotations.
+pub const AUTOMATIC_ASSERTION: (&str, &str) = ("ECO", "0000501");
+
+/// The evidence codes, their categories, and the ECO IDs of the GO and HPO annotations.
+const CODES: [(&str, EvidenceCategory, Option<&str>); 28] = [
+    ("EXP", EvidenceCategory::Experimental, Some("0000269")),
+    ("IDA", EvidenceCategory::Experimental, Some("0000314")),
+    ("IPI", EvidenceCategory::Experimental, Some("0000353")),
+    ("IMP", EvidenceCategory::Experimental, Some("0000315")),
+    ("IGI", EvidenceCategory::Experimental, Some("0000316")),
+    ("IEP", EvidenceCategory::Experimental, Some("0000270")),
+    ("HTP", EvidenceCategory::Experimental, Some("0006056")),
+    ("HDA", EvidenceCategory::Experimental, Some("0007005")),
+    ("HMP", EvidenceCategory::Experimental, Some("0007001")),
+    ("HGI", EvidenceCategory::Experimental, Some("0007003")),
+    ("HEP", EvidenceCategory::Experimental, Some("0007007")),
+    // The `published clinical study` of the HPO annotations has no ECO term.
+    ("PCS", EvidenceCategory::Experimental, None),
+    ("IBA", EvidenceCategory::Phylogenetic, Some("0000318")),
+    ("IBD", EvidenceCategory::Phylogenetic, Some("0000319")),
+    ("IKR", EvidenceCategory::Phylogenetic, Some("0000320")),
+    ("IRD", EvidenceCategory::Phylogenetic, Some("0000321")),
+    ("ISS", EvidenceCategory::Computational, Some("0000250")),
+    ("ISO", EvidenceCategory::Computational, Some("0000266")),
+    ("ISA", EvidenceCategory::Computational, Some("0000247")),
+    ("ISM", EvidenceCategory::Computational, Some("0000255")),
+    ("IGC", EvidenceCategory::Computational, Some("0000317")),
+    ("RCA", EvidenceCategory::Computational, Some("0000245")),
+    ("IEA", EvidenceCategory::Computational, Some("0000501")),
+    ("TAS", EvidenceCategory::AuthorStatement, Some("0000304")),
+    ("NAS", EvidenceCategory::AuthorStatement, Some("0000303")),
+    ("IC", EvidenceCategory::CuratorStatement, Some("0000305")),
+    ("ND", EvidenceCategory::CuratorStatement, Some("0000307")),
+    ("NR", EvidenceCategory::CuratorStatement, None),
+];
+
+/// The kind of support of an annotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvidenceCategory {
+    /// Supported by an experiment, including the high-throughput experiments
+    /// and the published clinical studies.
+    Experimental,
+    /// Inferred from the phylogeny, e.g. `IBA`.
+    Phylogenetic,
+    /// Inferred by sequence similarity or by a computational analysis,
+    /// including the automatic annotations (`IEA`).
+    Computational,
+    /// Stated by the author of a publication, e.g. `TAS`.
+    AuthorStatement,
+    /// Inferred by the curator, or no data was available (`ND`).
+    CuratorStatement,
+}
+
+impl EvidenceCategory {
+    /// Get the category of the evidence `code`, e.g. `IDA`.
+    ///
+    /// Returns `None` if the code is unknown.
+    pub fn of(code: &str) -> Option<Self> {
+        CODES
+            .iter()
+            .find(|(c, _, _)| *c == code)
+            .map(|&(_, category, _)| category)
+    }
+
+    /// Test if the annotations in the category are supported by an experiment.
+    pub fn is_experimental(&self) -> bool {
+        matches!(self, EvidenceCategory::Experimental)
+    }
+}
+
+/// Get the ECO ID of the evidence `code`, e.g. `ECO:0000314` for `IDA`.
+///
+/// Returns `None` if the code is unknown or if it has no ECO term.
+pub fn eco_id(code: &str) -> Option<TermId> {
+    CODES
+        .iter()
+        .find(|(c, _, _)| *c == code)
+        .and_then(|&(_, _, id)| id)
+        .map(|id| TermId::from(("ECO", id)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_evidence_codes() {
+        assert_eq!(
+            EvidenceCategory::of("HTP"),
+            Some(EvidenceCategory::Experimental)
+        );
+        assert_eq!(
+            EvidenceCategory::of("IBA"),
+            Some(EvidenceCategory::Phylogenetic)
+        );
+        assert_eq!(
+            EvidenceCategory::of("IC"),
+            Some(EvidenceCategory::CuratorStatement)
+        );
+        assert_eq!(EvidenceCategory::of("ida"), None);
+        assert!(EvidenceCategory::of("PCS").unwrap().is_experimental());
+
+        assert_eq!(eco_id("IDA"), Some(TermId::from(("ECO", "0000314"))));
+        assert_eq!(eco_id("IEA"), Some(TermId::from(AUTOMATIC_ASSERTION)));
+        assert_eq!(eco_id("PCS"), None);
+        assert_eq!(eco_id("XYZ"), None);
+    }
+}
@@ -0,0 +1,563 @@
+//! Quality control of the Gene Ontology annotations in the GAF 2.x format.
+//!
+//! [`read_gaf`] reads the annotation records, and [`GafValidator`] checks them against GO
+//! and reports the [`Violation`]s:
+//!
+//! * the GO ID is not in GO, or it is obsolete,
+//! * the aspect (`P`, `F`, or `C`) does not match the GO branch of the term,
+//! * a qualifier is unknown, or the relation does not fit the aspect (e.g. `enables` for `C`),
+//! * the taxon violates a taxon constraint of the term, if the constraints are provided.
+//!
+//! ## Examples
+//!
+//! ```
+//! use std::str::FromStr;
+//! use ontolius::annotation::gaf::{read_gaf, GafIssue, GafValidator};
+//! use ontolius::base::term::simple::SimpleMinimalTerm;
+//! use ontolius::hierarchy::{GraphEdge, Relationship};
+//! use ontolius::io::OntologyData;
+//! use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//!
+//! # let go: CsrOntology<usize, _> = {
+//! #     let term = |curie: &str, name: &str| {
+//! #         SimpleMinimalTerm::new(TermId::from_str(curie).unwrap(), name, vec![], false)
+//! #     };
+//! #     let terms = vec![
+//! #         term("GO:0008150", "biological_process"),
+//! #         term("GO:0003674", "molecular_function"),
+//! #         term("GO:0005575", "cellular_component"),
+//! #         term("GO:0000001", "all"),
+//! #         term("GO:0005634", "nucleus"),
+//! #     ];
+//! #     let edges: Vec<_> = [(0, 3), (1, 3), (2, 3), (4, 2)]
+//! #         .into_iter()
+//! #         .map(|(sub, obj)| GraphEdge::from((sub, Relationship::Child, obj)))
+//! #         .collect();
+//! #     let data: OntologyData<usize, _> = OntologyData::from((
+//! #         terms.into_boxed_slice(),
+//! #         edges.into_boxed_slice(),
+//! #         Default::default(),
+//! #     ));
+//! #     CsrOntology::try_from(data).unwrap()
+//! # };
+//! // `go` is a GO `Ontology` where GO:0005634 (nucleus) is a cellular component.
+//! let gaf = "\
+//! !gaf-version: 2.2
+//! UniProtKB\tP12345\tABC1\tlocated_in\tGO:0005634\tPMID:1\tIDA\t\tC\t\t\tprotein\ttaxon:9606\t20240101\tUniProt
+//! UniProtKB\tP12345\tABC1\tenables\tGO:0005634\tPMID:1\tIDA\t\tF\t\t\tprotein\ttaxon:9606\t20240101\tUniProt
+//! ";
+//! let records = read_gaf(gaf.as_bytes()).expect("GAF should be readable");
+//! let violations = GafValidator::new().validate(&records, &go);
+//!
+//! // The second record annotates a cellular component as a molecular function.
+//! assert_eq!(violations.len(), 1);
+//! assert_eq!(violations[0].line(), 3);
+//! assert_eq!(
+//!     violations[0].issue(),
+//!     &GafIssue::AspectMismatch { aspect: 'F', expected: 'C' }
+//! );
+//! ```
+use std::fmt::Display;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::base::term::MinimalTerm;
+use crate::base::{Identified, TermId};
+use crate::error::OntoliusError;
+use crate::hierarchy::AncestorNodes;
+use crate::ontology::Ontology;
+
+use super::eco::EvidenceCategory;
+use super::find_column;
+
+/// The roots of the GO branches and the corresponding aspects.
+const ASPECTS: [(&str, &str, char); 3] = [
+    ("GO", "0008150", 'P'),
+    ("GO", "0003674", 'F'),
+    ("GO", "0005575", 'C'),
+];
+
+/// The relations allowed in the qualifier column of GAF 2.2, along with the aspects.
+const RELATIONS: [(&str, char); 14] = [
+    ("enables", 'F'),
+    ("contributes_to", 'F'),
+    ("involved_in", 'P'),
+    ("acts_upstream_of", 'P'),
+    ("acts_upstream_of_positive_effect", 'P'),
+    ("acts_upstream_of_negative_effect", 'P'),
+    ("acts_upstream_of_or_within", 'P'),
+    ("acts_upstream_of_or_within_positive_effect", 'P'),
+    ("acts_upstream_of_or_within_negative_effect", 'P'),
+    ("part_of", 'C'),
+    ("located_in", 'C'),
+    ("is_active_in", 'C'),
+    ("colocalizes_with", 'C'),
+    ("NOT", '*'),
+];
+
+/// A record of a GAF file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GafRecord {
+    line: usize,
+    db: String,
+    db_object_id: String,
+    qualifiers: Vec<String>,
+    go_id: TermId,
+    evidence_code: String,
+    aspect: char,
+    taxa: Vec<TermId>,
+}
+
+impl GafRecord {
+    /// Get the 1-based line of the record in the file.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get the database of the annotated entity, e.g. `UniProtKB`.
+    pub fn db(&self) -> &str {
+        &self.db
+    }
+
+    /// Get the ID of the annotated entity in the database.
+    pub fn db_object_id(&self) -> &str {
+        &self.db_object_id
+    }
+
+    /// Get the qualifiers, e.g. `NOT` or `enables`.
+    pub fn qualifiers(&self) -> &[String] {
+        &self.qualifiers
+    }
+
+    /// Get the GO ID.
+    pub fn go_id(&self) -> &TermId {
+        &self.go_id
+    }
+
+    /// Get the evidence code, e.g. `IDA`.
+    pub fn evidence_code(&self) -> &str {
+        &self.evidence_code
+    }
+
+    /// Get the category of the evidence code, e.g. to keep only the experimental annotations.
+    ///
+    /// Returns `None` if the evidence code is unknown.
+    pub fn evidence_category(&self) -> Option<EvidenceCategory> {
+        EvidenceCategory::of(&self.evidence_code)
+    }
+
+    /// Get the aspect: `P`, `F`, or `C`.
+    pub fn aspect(&self) -> char {
+        self.aspect
+    }
+
+    /// Get the taxa of the annotated entity as `NCBITaxon` IDs, e.g. `NCBITaxon:9606`.
+    ///
+    /// The second taxon, if any, is the interacting taxon.
+    pub fn taxa(&self) -> &[TermId] {
+        &self.taxa
+    }
+}
+
+/// Read the GAF records from the `reader`.
+///
+/// The header lines starting with `!` are skipped.
+/// Returns an error if a record has less than 15 columns, or if the GO ID is not a CURIE.
+pub fn read_gaf<R>(reader: R) -> Result<Vec<GafRecord>, OntoliusError>
+where
+    R: BufRead,
+{
+    let mut records = vec![];
+    for (i, line) in reader.lines().enumerate() {
+        let line =
+            line.map_err(|e| OntoliusError::Other(format!("Cannot read the GAF file: {e}")))?;
+        if line.is_empty() || line.starts_with('!') {
+            continue;
+        }
+        let fields: Vec<_> = line.split('\t').collect();
+        if fields.len() < 15 {
+            return Err(OntoliusError::Other(format!(
+                "Line {} has {} columns, expected at least 15",
+                i + 1,
+                fields.len()
+            )));
+        }
+        let go_id = TermId::from_str(fields[4]).map_err(|_| {
+            OntoliusError::Other(format!("Line {}: {} is not a CURIE", i + 1, fields[4]))
+        })?;
+        records.push(GafRecord {
+            line: i + 1,
+            db: fields[0].to_string(),
+            db_object_id: fields[1].to_string(),
+            qualifiers: split(fields[3]).map(ToString::to_string).collect(),
+            go_id,
+            evidence_code: fields[6].to_string(),
+            aspect: fields[8].chars().next().unwrap_or_default(),
+            taxa: split(fields[12])
+                .flat_map(|taxon| parse_taxon(taxon).ok())
+                .collect(),
+        });
+    }
+    Ok(records)
+}
+
+/// Split the `|`-separated values, skipping the empty ones.
+fn split(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split('|')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Parse a taxon written as `taxon:9606` or as `NCBITaxon:9606`.
+fn parse_taxon(value: &str) -> Result<TermId, OntoliusError> {
+    let id = value
+        .strip_prefix("taxon:")
+        .or_else(|| value.strip_prefix("NCBITaxon:"))
+        .ok_or_else(|| OntoliusError::Other(format!("{value} is not a taxon")))?;
+    Ok(TermId::from(("NCBITaxon", id)))
+}
+
+/// The kind of a taxon constraint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaxonRelation {
+    /// The term and its descendants apply only to the taxon.
+    OnlyInTaxon,
+    /// The term and its descendants never apply to the taxon.
+    NeverInTaxon,
+}
+
+/// The taxon constraints of GO terms.
+///
+/// The taxa are compared by ID, so the annotated taxon must be listed in the constraint
+/// to satisfy `only_in_taxon` or to violate `never_in_taxon`. Expand the constraints
+/// to the descendant taxa with NCBITaxon to check the annotations of the species.
+#[derive(Debug, Clone, Default)]
+pub struct TaxonConstraints {
+    constraints: Vec<(TermId, TaxonRelation, TermId)>,
+}
+
+impl TaxonConstraints {
+    /// Read the constraints from a tab-separated `reader` with the `go_id`, `relation`,
+    /// and `taxon_id` columns. The relation is either `only_in_taxon` or `never_in_taxon`.
+    ///
+    /// Returns an error if the header lacks a column,
+    /// or if a value of a row cannot be parsed.
+    pub fn from_reader<R>(reader: R) -> Result<Self, OntoliusError>
+    where
+        R: BufRead,
+    {
+        let mut lines = reader.lines();
+        let header = match lines.next() {
+            Some(header) => {
+                header.map_err(|e| OntoliusError::Other(format!("Cannot read the table: {e}")))?
+            }
+            None => return Ok(Self::default()),
+        };
+        let header: Vec<_> = header.split('\t').collect();
+        let go_idx = find_column(&header, "go_id")?;
+        let relation_idx = find_column(&header, "relation")?;
+        let taxon_idx = find_column(&header, "taxon_id")?;
+
+        let mut constraints = vec![];
+        for line in lines {
+            let line =
+                line.map_err(|e| OntoliusError::Other(format!("Cannot read the table: {e}")))?;
+            if line.is_empty() {
+                continue;
+            }
+            let fields: Vec<_> = line.split('\t').map(str::trim).collect();
+            let field = |idx: usize| fields.get(idx).copied().unwrap_or_default();
+            let go_id = TermId::from_str(field(go_idx))
+                .map_err(|_| OntoliusError::Other(format!("{} is not a CURIE", field(go_idx))))?;
+            let relation = match field(relation_idx) {
+                "only_in_taxon" => TaxonRelation::OnlyInTaxon,
+                "never_in_taxon" => TaxonRelation::NeverInTaxon,
+                other => {
+                    return Err(OntoliusError::Other(format!(
+                        "Unknown taxon relation {other}"
+                    )))
+                }
+            };
+            constraints.push((go_id, relation, parse_taxon(field(taxon_idx))?));
+        }
+        Ok(Self { constraints })
+    }
+}
+
+/// An issue of a GAF record.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GafIssue {
+    /// GO has no term with the ID.
+    Unknown,
+    /// The GO ID refers to an obsolete term, or it is an alternate ID
+    /// of the term with the `primary` ID.
+    Obsolete { primary: Option<TermId> },
+    /// The `aspect` of the record differs from the `expected` aspect of the GO branch.
+    AspectMismatch { aspect: char, expected: char },
+    /// The qualifier is unknown or does not fit the aspect of the record.
+    InvalidQualifier(String),
+    /// The `taxon` violates the constraint of the term `constrained`.
+    TaxonViolation {
+        taxon: TermId,
+        relation: TaxonRelation,
+        constrained: TermId,
+    },
+}
+
+impl Display for GafIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GafIssue::Unknown => write!(f, "unknown GO term"),
+            GafIssue::Obsolete {
+                primary: Some(primary),
+            } => write!(f, "obsolete GO term, replaced by {primary}"),
+            GafIssue::Obsolete { primary: None } => write!(f, "obsolete GO term"),
+            GafIssue::AspectMismatch { aspect, expected } => {
+                write!(f, "aspect mismatch: {aspect} != {expected}")
+            }
+            GafIssue::InvalidQualifier(qualifier) => write!(f, "invalid qualifier {qualifier}"),
+            GafIssue::TaxonViolation {
+                taxon,
+                relation: TaxonRelation::OnlyInTaxon,
+                constrained,
+            } => write!(f, "{constrained} is not in taxon {taxon}"),
+            GafIssue::TaxonViolation {
+                taxon,
+                relation: TaxonRelation::NeverInTaxon,
+                constrained,
+            } => write!(f, "{constrained} is never in taxon {taxon}"),
+        }
+    }
+}
+
+/// An issue of the record at a line of the GAF file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Violation {
+    line: usize,
+    go_id: TermId,
+    issue: GafIssue,
+}
+
+impl Violation {
+    /// Get the 1-based line of the record.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get the GO ID of the record.
+    pub fn go_id(&self) -> &TermId {
+        &self.go_id
+    }
+
+    /// Get the issue.
+    pub fn issue(&self) -> &GafIssue {
+        &self.issue
+    }
+}
+
+/// Check the GAF records against GO.
+///
+/// By default, the taxon constraints are not checked.
+#[derive(Debug, Clone, Default)]
+pub struct GafValidator {
+    taxon_constraints: Option<TaxonConstraints>,
+}
+
+impl GafValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check the taxa of the records against the `constraints`.
+    #[must_use]
+    pub fn taxon_constraints(mut self, constraints: TaxonConstraints) -> Self {
+        self.taxon_constraints = Some(constraints);
+        self
+    }
+
+    /// Check the `records` against the `go` ontology.
+    ///
+    /// Returns the violations in the order of the records.
+    /// The records with an unknown or obsolete GO ID are not checked further.
+    pub fn validate<O>(&self, records: &[GafRecord], go: &O) -> Vec<Violation>
+    where
+        O: Ontology,
+    {
+        let roots: Vec<_> = ASPECTS
+            .iter()
+            .flat_map(|&(prefix, id, aspect)| {
+                go.id_to_idx(&TermId::from((prefix, id)))
+                    .map(|idx| (idx, aspect))
+            })
+            .collect();
+        let constraints: Vec<_> = self
+            .taxon_constraints
+            .iter()
+            .flat_map(|constraints| &constraints.constraints)
+            .flat_map(|(go_id, relation, taxon)| {
+                go.id_to_idx(go_id)
+                    .map(|idx| (idx, go_id, *relation, taxon))
+            })
+            .collect();
+
+        let mut violations = vec![];
+        for record in records {
+            let mut report = |issue| {
+                violations.push(Violation {
+                    line: record.line,
+                    go_id: record.go_id.clone(),
+                    issue,
+                })
+            };
+            let Some(idx) = go.id_to_idx(&record.go_id) else {
+                report(GafIssue::Unknown);
+                continue;
+            };
+            let term = go.idx_to_term(idx).expect("The index should be valid");
+            if term.is_obsolete() {
+                report(GafIssue::Obsolete { primary: None });
+                continue;
+            }
+            if term.identifier() != &record.go_id {
+                report(GafIssue::Obsolete {
+                    primary: Some(term.identifier().clone()),
+                });
+                continue;
+            }
+
+            let ancestors: Vec<_> = std::iter::once(idx)
+                .chain(go.hierarchy().ancestors_of(idx).copied())
+                .collect();
+            let expected = roots
+                .iter()
+                .find(|(root, _)| ancestors.contains(root))
+                .map(|&(_, aspect)| aspect);
+            if let Some(expected) = expected.filter(|&expected| expected != record.aspect) {
+                report(GafIssue::AspectMismatch {
+                    aspect: record.aspect,
+                    expected,
+                });
+            }
+
+            for qualifier in &record.qualifiers {
+                let fits = RELATIONS.iter().any(|&(relation, aspect)| {
+                    relation == qualifier && (aspect == '*' || aspect == record.aspect)
+                });
+                if !fits {
+                    report(GafIssue::InvalidQualifier(qualifier.clone()));
+                }
+            }
+
+            for &(constrained_idx, constrained, relation, taxon) in &constraints {
+                if !ancestors.contains(&constrained_idx) {
+                    continue;
+                }
+                for annotated in record.taxa.iter().take(1) {
+                    let violated = match relation {
+                        TaxonRelation::OnlyInTaxon => annotated != taxon,
+                        TaxonRelation::NeverInTaxon => annotated == taxon,
+                    };
+                    if violated {
+                        report(GafIssue::TaxonViolation {
+                            taxon: annotated.clone(),
+                            relation,
+                            constrained: constrained.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::ontology::csr::CsrOntology;
+    use crate::test_util::toy_ontology;
+
+    fn build_go() -> CsrOntology<usize, SimpleMinimalTerm> {
+        let term = |curie: &str, name: &str, alt_ids: &[&str], obsolete: bool| {
+            SimpleMinimalTerm::new(
+                TermId::from_str(curie).unwrap(),
+                name,
+                alt_ids
+                    .iter()
+                    .map(|alt_id| TermId::from_str(alt_id).unwrap())
+                    .collect(),
+                obsolete,
+            )
+        };
+        let terms = vec![
+            term("GO:0000001", "all", &[], false),
+            term("GO:0008150", "biological_process", &[], false),
+            term("GO:0003674", "molecular_function", &[], false),
+            term("GO:0005575", "cellular_component", &[], false),
+            term("GO:0009579", "thylakoid", &["GO:0009999"], false),
+            term("GO:0007165", "signal transduction", &[], false),
+            term("GO:0000002", "obsolete process", &[], true),
+        ];
+        toy_ontology(terms, &[(1, 0), (2, 0), (3, 0), (4, 3), (5, 1)])
+    }
+
+    fn record(qualifier: &str, go_id: &str, aspect: &str, taxon: &str) -> String {
+        format!("UniProtKB\tP12345\tABC1\t{qualifier}\t{go_id}\tPMID:1\tIDA\t\t{aspect}\t\t\tprotein\t{taxon}\t20240101\tUniProt\n")
+    }
+
+    #[test]
+    fn test_validate() {
+        let go = build_go();
+        let gaf = [
+            "!gaf-version: 2.2\n".to_string(),
+            record("located_in", "GO:0009579", "C", "taxon:3702"),
+            record("involved_in", "GO:0009579", "P", "taxon:3702"),
+            record("NOT|involved_in", "GO:0007165", "P", "taxon:9606"),
+            record("enables", "GO:0007165", "P", "taxon:9606"),
+            record("located_in", "GO:0009579", "C", "taxon:9606|taxon:3702"),
+            record("involved_in", "GO:0000002", "P", "taxon:9606"),
+            record("located_in", "GO:0009999", "C", "taxon:3702"),
+            record("located_in", "GO:1234567", "C", "taxon:3702"),
+        ]
+        .concat();
+        let records = read_gaf(gaf.as_bytes()).unwrap();
+        assert_eq!(records.len(), 8);
+        assert_eq!(records[2].qualifiers(), ["NOT", "involved_in"]);
+        assert_eq!(
+            records[0].evidence_category(),
+            Some(EvidenceCategory::Experimental)
+        );
+        assert_eq!(
+            records[4].taxa(),
+            [
+                TermId::from(("NCBITaxon", "9606")),
+                TermId::from(("NCBITaxon", "3702"))
+            ]
+        );
+
+        let constraints = "go_id\trelation\ttaxon_id\nGO:0009579\tonly_in_taxon\tNCBITaxon:3702\n";
+        let constraints = TaxonConstraints::from_reader(constraints.as_bytes()).unwrap();
+        let violations = GafValidator::new()
+            .taxon_constraints(constraints)
+            .validate(&records, &go);
+
+        let actual: Vec<_> = violations
+            .iter()
+            .map(|violation| (violation.line(), violation.issue().to_string()))
+            .collect();
+        assert_eq!(
+            actual,
+            [
+                (3, "aspect mismatch: P != C".to_string()),
+                (5, "invalid qualifier enables".to_string()),
+                (6, "GO:0009579 is not in taxon NCBITaxon:9606".to_string()),
+                (7, "obsolete GO term".to_string()),
+                (8, "obsolete GO term, replaced by GO:0009579".to_string()),
+                (9, "unknown GO term".to_string()),
+            ]
+        );
+    }
+}
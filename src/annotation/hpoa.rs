@@ -0,0 +1,412 @@
+//! The HPO annotations (HPOA) of the diseases, and the probabilistic phenotype profiles
+//! of the diseases built from the annotations.
+//!
+//! The annotations are read from the tab-separated `phenotype.hpoa` file, with a header that
+//! includes at least the `database_id`, `disease_name`, `qualifier`, `hpo_id`, `evidence`,
+//! `frequency`, and `aspect` columns. The lines starting with `#` are skipped.
+//!
+//! [`DiseaseProfileBuilder`] turns the annotations into a [`DiseaseProfile`] per disease,
+//! with the frequency of each phenotype in the patients with the disease,
+//! e.g. for the likelihood ratio or the naive Bayes matchers.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! use ontolius::annotation::hpoa::{read_hpoa, DiseaseProfileBuilder};
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                    .expect("HPO should be loaded");
+//! #
+//! let table = "\
+//! #date: 2024-04-26
+//! database_id\tdisease_name\tqualifier\thpo_id\treference\tevidence\tonset\tfrequency\tsex\tmodifier\taspect\tbiocuration
+//! OMIM:154700\tMarfan syndrome\t\tHP:0001166\tOMIM:154700\tTAS\t\tHP:0040281\t\t\tP\tHPO:curator
+//! ";
+//! let records = read_hpoa(table.as_bytes()).expect("Annotations should be readable");
+//! let profiles = DiseaseProfileBuilder::new().build(&records, &hpo);
+//!
+//! // Arachnodactyly is very frequent in Marfan syndrome, and so are its ancestors,
+//! // such as Abnormality of limbs.
+//! let marfan = &profiles[0];
+//! assert_eq!(marfan.frequency(&TermId::from(("HP", "0001166"))), Some(0.9));
+//! assert_eq!(marfan.frequency(&TermId::from(("HP", "0040064"))), Some(0.9));
+//! ```
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::base::{Identified, TermId};
+use crate::error::OntoliusError;
+use crate::hierarchy::AncestorNodes;
+use crate::ontology::Ontology;
+
+use super::eco::EvidenceCategory;
+use super::find_column;
+
+/// The HPO frequency terms (`Frequency` subhierarchy) and the mean frequencies of their ranges.
+const FREQUENCY_TERMS: [(&str, f64); 6] = [
+    ("0040280", 1.),
+    ("0040281", 0.9),
+    ("0040282", 0.545),
+    ("0040283", 0.17),
+    ("0040284", 0.025),
+    ("0040285", 0.),
+];
+
+/// A row of the HPOA file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HpoaRecord {
+    disease_id: TermId,
+    disease_name: String,
+    negated: bool,
+    hpo_id: TermId,
+    evidence: String,
+    frequency: Option<String>,
+    aspect: char,
+}
+
+impl HpoaRecord {
+    /// Get the ID of the disease, e.g. `OMIM:154700`.
+    pub fn disease_id(&self) -> &TermId {
+        &self.disease_id
+    }
+
+    /// Get the name of the disease.
+    pub fn disease_name(&self) -> &str {
+        &self.disease_name
+    }
+
+    /// Test if the disease is annotated with the absence of the phenotype (the `NOT` qualifier).
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
+    /// Get the HPO ID of the phenotype.
+    pub fn hpo_id(&self) -> &TermId {
+        &self.hpo_id
+    }
+
+    /// Get the evidence code, e.g. `PCS` or `TAS`.
+    pub fn evidence(&self) -> &str {
+        &self.evidence
+    }
+
+    /// Get the category of the evidence code.
+    ///
+    /// Returns `None` if the evidence code is unknown.
+    pub fn evidence_category(&self) -> Option<EvidenceCategory> {
+        EvidenceCategory::of(&self.evidence)
+    }
+
+    /// Get the frequency as written in the file, e.g. `HP:0040281`, `3/7`, or `25%`.
+    pub fn raw_frequency(&self) -> Option<&str> {
+        self.frequency.as_deref()
+    }
+
+    /// Get the frequency of the phenotype in the patients with the disease, from 0 to 1.
+    ///
+    /// The HPO frequency terms are turned into the mean of their ranges, e.g. `0.9` for
+    /// `Very frequent` (80-99%). Returns `None` if the frequency is missing or invalid.
+    pub fn frequency(&self) -> Option<f64> {
+        let frequency = self.frequency.as_deref()?;
+        if let Some(id) = frequency.strip_prefix("HP:") {
+            FREQUENCY_TERMS
+                .iter()
+                .find(|(term, _)| *term == id)
+                .map(|&(_, value)| value)
+        } else if let Some((n, m)) = frequency.split_once('/') {
+            let (n, m): (f64, f64) = (n.trim().parse().ok()?, m.trim().parse().ok()?);
+            (m > 0. && n <= m).then(|| n / m)
+        } else {
+            let percent: f64 = frequency.strip_suffix('%')?.trim().parse().ok()?;
+            (0. ..=100.).contains(&percent).then(|| percent / 100.)
+        }
+    }
+
+    /// Get the aspect: `P` for the phenotypic abnormalities, `I` for the modes of inheritance,
+    /// `C` for the clinical course, and `M` for the clinical modifiers.
+    pub fn aspect(&self) -> char {
+        self.aspect
+    }
+}
+
+/// Read the HPOA records from a tab-separated `reader`.
+///
+/// Returns an error if the header lacks a required column,
+/// or if a disease or HPO ID is not a CURIE.
+pub fn read_hpoa<R>(reader: R) -> Result<Vec<HpoaRecord>, OntoliusError>
+where
+    R: BufRead,
+{
+    let mut lines = reader
+        .lines()
+        .filter(|line| !line.as_ref().is_ok_and(|line| line.starts_with('#')));
+    let header = match lines.next() {
+        Some(header) => header.map_err(io_error)?,
+        None => return Ok(vec![]),
+    };
+    let header: Vec<_> = header.split('\t').collect();
+    let disease_idx = find_column(&header, "database_id")?;
+    let name_idx = find_column(&header, "disease_name")?;
+    let qualifier_idx = find_column(&header, "qualifier")?;
+    let hpo_idx = find_column(&header, "hpo_id")?;
+    let evidence_idx = find_column(&header, "evidence")?;
+    let frequency_idx = find_column(&header, "frequency")?;
+    let aspect_idx = find_column(&header, "aspect")?;
+
+    let mut records = vec![];
+    for line in lines {
+        let line = line.map_err(io_error)?;
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<_> = line.split('\t').collect();
+        let field = |idx: usize| {
+            fields
+                .get(idx)
+                .map(|value| value.trim())
+                .unwrap_or_default()
+        };
+        records.push(HpoaRecord {
+            disease_id: parse_term_id(field(disease_idx))?,
+            disease_name: field(name_idx).to_string(),
+            negated: field(qualifier_idx) == "NOT",
+            hpo_id: parse_term_id(field(hpo_idx))?,
+            evidence: field(evidence_idx).to_string(),
+            frequency: Some(field(frequency_idx))
+                .filter(|value| !value.is_empty())
+                .map(ToString::to_string),
+            aspect: field(aspect_idx).chars().next().unwrap_or_default(),
+        });
+    }
+    Ok(records)
+}
+
+fn parse_term_id(value: &str) -> Result<TermId, OntoliusError> {
+    TermId::from_str(value).map_err(|_| OntoliusError::Other(format!("Invalid term ID {value:?}")))
+}
+
+fn io_error(e: std::io::Error) -> OntoliusError {
+    OntoliusError::Other(format!("Cannot read the annotations: {e}"))
+}
+
+/// The frequencies of the phenotypes in the patients with a disease.
+///
+/// The frequency of a phenotype is the maximum frequency of the phenotype
+/// and of its annotated descendants, since the patients with a phenotype
+/// have all its ancestor phenotypes as well.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiseaseProfile {
+    disease_id: TermId,
+    disease_name: String,
+    frequencies: HashMap<TermId, f64>,
+}
+
+impl DiseaseProfile {
+    /// Get the ID of the disease.
+    pub fn disease_id(&self) -> &TermId {
+        &self.disease_id
+    }
+
+    /// Get the name of the disease.
+    pub fn disease_name(&self) -> &str {
+        &self.disease_name
+    }
+
+    /// Get the frequency of the phenotype with the primary `hpo_id`.
+    ///
+    /// Returns `None` if neither the phenotype nor its descendants are annotated.
+    pub fn frequency(&self, hpo_id: &TermId) -> Option<f64> {
+        self.frequencies.get(hpo_id).copied()
+    }
+
+    /// Iterate over the phenotypes of the profile and their frequencies, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&TermId, f64)> {
+        self.frequencies
+            .iter()
+            .map(|(term_id, &frequency)| (term_id, frequency))
+    }
+
+    /// Get the number of the phenotypes of the profile, including the ancestors.
+    pub fn len(&self) -> usize {
+        self.frequencies.len()
+    }
+
+    /// Test if the profile has no phenotypes.
+    pub fn is_empty(&self) -> bool {
+        self.frequencies.is_empty()
+    }
+}
+
+impl Identified for DiseaseProfile {
+    fn identifier(&self) -> &TermId {
+        &self.disease_id
+    }
+}
+
+/// Builder of the [`DiseaseProfile`]s from the HPOA records.
+///
+/// Only the phenotypic abnormalities (the `P` aspect) contribute to the profiles.
+/// The negated records and the records with zero frequency (e.g. `Excluded`) are skipped,
+/// and so are the phenotypes missing from HPO.
+#[derive(Debug, Clone)]
+pub struct DiseaseProfileBuilder {
+    default_frequency: f64,
+    evidence: Option<Vec<EvidenceCategory>>,
+}
+
+impl Default for DiseaseProfileBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiseaseProfileBuilder {
+    /// Create a builder that uses the frequency `1` for the records with no frequency
+    /// and that keeps the records with any evidence.
+    pub fn new() -> Self {
+        Self {
+            default_frequency: 1.,
+            evidence: None,
+        }
+    }
+
+    /// Set the frequency of the phenotypes annotated with no frequency.
+    #[must_use]
+    pub fn default_frequency(mut self, frequency: f64) -> Self {
+        self.default_frequency = frequency;
+        self
+    }
+
+    /// Keep only the records with the evidence in the `categories`,
+    /// e.g. [`EvidenceCategory::Experimental`] for the published clinical studies (`PCS`).
+    #[must_use]
+    pub fn evidence(mut self, categories: &[EvidenceCategory]) -> Self {
+        self.evidence = Some(categories.to_vec());
+        self
+    }
+
+    /// Build the profiles of the diseases of the `records`, in the order of the first record
+    /// of each disease, with the phenotypes propagated to the ancestors in the `hpo`.
+    ///
+    /// The phenotypes are keyed by the primary IDs of the terms.
+    pub fn build<O>(&self, records: &[HpoaRecord], hpo: &O) -> Vec<DiseaseProfile>
+    where
+        O: Ontology,
+    {
+        let hierarchy = hpo.hierarchy();
+        let mut profiles: Vec<DiseaseProfile> = vec![];
+        let mut positions = HashMap::new();
+        for record in records {
+            if record.negated || record.aspect != 'P' || !self.accepts(record) {
+                continue;
+            }
+            let frequency = record.frequency().unwrap_or(self.default_frequency);
+            if frequency <= 0. {
+                continue;
+            }
+            let Some(idx) = hpo.id_to_idx(&record.hpo_id) else {
+                continue;
+            };
+
+            let position = *positions
+                .entry(record.disease_id.clone())
+                .or_insert_with(|| {
+                    profiles.push(DiseaseProfile {
+                        disease_id: record.disease_id.clone(),
+                        disease_name: record.disease_name.clone(),
+                        frequencies: HashMap::new(),
+                    });
+                    profiles.len() - 1
+                });
+            let frequencies = &mut profiles[position].frequencies;
+            for node in std::iter::once(idx).chain(hierarchy.ancestors_of(idx).copied()) {
+                if let Some(term) = hpo.idx_to_term(node) {
+                    let value = frequencies.entry(term.identifier().clone()).or_insert(0.);
+                    *value = value.max(frequency);
+                }
+            }
+        }
+        profiles
+    }
+
+    fn accepts(&self, record: &HpoaRecord) -> bool {
+        match &self.evidence {
+            Some(categories) => record
+                .evidence_category()
+                .is_some_and(|category| categories.contains(&category)),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_build_profiles() {
+        let terms = vec![
+            SimpleMinimalTerm::new(TermId::from(("HP", "1")), "Root", vec![], false),
+            SimpleMinimalTerm::new(TermId::from(("HP", "2")), "Left", vec![], false),
+            SimpleMinimalTerm::new(
+                TermId::from(("HP", "3")),
+                "Left child",
+                vec![TermId::from(("HP", "9"))],
+                false,
+            ),
+            SimpleMinimalTerm::new(TermId::from(("HP", "4")), "Right", vec![], false),
+        ];
+        let hpo = toy_ontology(terms, &[(1, 0), (2, 1), (3, 0)]);
+
+        let table = "#date: 2024-04-26\n\
+            database_id\tdisease_name\tqualifier\thpo_id\tevidence\tfrequency\taspect\n\
+            OMIM:1\tFirst\t\tHP:9\tPCS\t1/4\tP\n\
+            OMIM:1\tFirst\t\tHP:2\tTAS\t50%\tP\n\
+            OMIM:1\tFirst\tNOT\tHP:4\tPCS\t\tP\n\
+            OMIM:2\tSecond\t\tHP:4\tIEA\t\tP\n\
+            OMIM:2\tSecond\t\tHP:2\tPCS\tHP:0040285\tP\n\
+            OMIM:2\tSecond\t\tHP:5\tPCS\t\tI\n\
+            OMIM:3\tThird\t\tHP:7\tPCS\t\tP\n";
+        let records = read_hpoa(table.as_bytes()).unwrap();
+        assert_eq!(records.len(), 7);
+        assert!(records[2].is_negated());
+        assert_eq!(records[0].frequency(), Some(0.25));
+        assert_eq!(records[4].frequency(), Some(0.));
+        assert_eq!(records[3].raw_frequency(), None);
+
+        let profiles = DiseaseProfileBuilder::new().build(&records, &hpo);
+        assert_eq!(profiles.len(), 2);
+
+        let first = &profiles[0];
+        assert_eq!(first.disease_name(), "First");
+        assert_eq!(first.len(), 3);
+        assert_eq!(first.frequency(&TermId::from(("HP", "3"))), Some(0.25));
+        assert_eq!(first.frequency(&TermId::from(("HP", "2"))), Some(0.5));
+        assert_eq!(first.frequency(&TermId::from(("HP", "1"))), Some(0.5));
+        assert_eq!(first.frequency(&TermId::from(("HP", "4"))), None);
+
+        let second = &profiles[1];
+        assert_eq!(second.frequency(&TermId::from(("HP", "4"))), Some(1.));
+        assert_eq!(second.frequency(&TermId::from(("HP", "2"))), None);
+
+        let profiles = DiseaseProfileBuilder::new()
+            .evidence(&[EvidenceCategory::Experimental])
+            .default_frequency(0.5)
+            .build(&records, &hpo);
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(
+            profiles[0].frequency(&TermId::from(("HP", "1"))),
+            Some(0.25)
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! The MAxO annotations that link the medical actions to the HPO phenotypes.
+//!
+//! The annotations are read from the tab-separated `maxo_annotations.tsv` file,
+//! with a header that includes at least the `maxo_id` and `hpo_id` columns.
+//! The `maxo_label`, `predicate` (e.g. `TREATS`), and `disease_id` columns are read if present.
+//! The lines starting with `#` are skipped.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! use ontolius::annotation::maxo::MaxoAnnotations;
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                    .expect("HPO should be loaded");
+//! #
+//! let table = "\
+//! disease_id\tmaxo_id\tmaxo_label\tpredicate\thpo_id
+//! OMIM:154700\tMAXO:0000079\tphysical therapy\tTREATS\tHP:0001166
+//! ";
+//! let annotations = MaxoAnnotations::from_reader(table.as_bytes())
+//!                     .expect("Annotations should be readable");
+//!
+//! // Arachnodactyly is an abnormality of limbs.
+//! let abnormality_of_limbs = TermId::from(("HP", "0040064"));
+//! let treatments = annotations.treatments_for_phenotype(&hpo, &abnormality_of_limbs);
+//!
+//! assert_eq!(treatments.len(), 1);
+//! assert_eq!(treatments[0].maxo_label(), Some("physical therapy"));
+//! ```
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::base::term::AltTermIdAware;
+use crate::base::{Identified, TermId};
+use crate::error::OntoliusError;
+use crate::hierarchy::DescendantNodes;
+use crate::ontology::Ontology;
+
+use super::find_column;
+
+/// An annotation of a phenotype with a medical action.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MaxoAnnotation {
+    maxo_id: TermId,
+    maxo_label: Option<String>,
+    predicate: Option<String>,
+    hpo_id: TermId,
+    disease_id: Option<TermId>,
+}
+
+impl MaxoAnnotation {
+    /// Get the ID of the medical action.
+    pub fn maxo_id(&self) -> &TermId {
+        &self.maxo_id
+    }
+
+    /// Get the label of the medical action.
+    pub fn maxo_label(&self) -> Option<&str> {
+        self.maxo_label.as_deref()
+    }
+
+    /// Get the relation of the medical action to the phenotype, e.g. `TREATS` or `PREVENTS`.
+    pub fn predicate(&self) -> Option<&str> {
+        self.predicate.as_deref()
+    }
+
+    /// Get the ID of the phenotype.
+    pub fn hpo_id(&self) -> &TermId {
+        &self.hpo_id
+    }
+
+    /// Get the ID of the disease the annotation was curated for.
+    pub fn disease_id(&self) -> Option<&TermId> {
+        self.disease_id.as_ref()
+    }
+}
+
+/// The MAxO annotations indexed by the phenotype.
+#[derive(Debug, Clone, Default)]
+pub struct MaxoAnnotations {
+    annotations: Vec<MaxoAnnotation>,
+    by_phenotype: HashMap<TermId, Vec<usize>>,
+}
+
+impl MaxoAnnotations {
+    /// Read the annotations from a tab-separated `reader`.
+    ///
+    /// Returns an error if the header lacks a required column,
+    /// or if a MAxO or HPO ID is not a CURIE.
+    pub fn from_reader<R>(reader: R) -> Result<Self, OntoliusError>
+    where
+        R: BufRead,
+    {
+        let mut lines = reader
+            .lines()
+            .filter(|line| !line.as_ref().is_ok_and(|line| line.starts_with('#')));
+        let header = match lines.next() {
+            Some(header) => header.map_err(io_error)?,
+            None => return Ok(Self::default()),
+        };
+        let header: Vec<_> = header.split('\t').collect();
+        let maxo_idx = find_column(&header, "maxo_id")?;
+        let hpo_idx = find_column(&header, "hpo_id")?;
+        let label_idx = find_column(&header, "maxo_label").ok();
+        let predicate_idx = find_column(&header, "predicate").ok();
+        let disease_idx = find_column(&header, "disease_id").ok();
+
+        let mut annotations = vec![];
+        for line in lines {
+            let line = line.map_err(io_error)?;
+            if line.is_empty() {
+                continue;
+            }
+            let fields: Vec<_> = line.split('\t').collect();
+            let field = |idx: Option<usize>| {
+                idx.and_then(|idx| fields.get(idx))
+                    .map(|value| value.trim())
+                    .filter(|value| !value.is_empty())
+            };
+            annotations.push(MaxoAnnotation {
+                maxo_id: parse_term_id(field(Some(maxo_idx)))?,
+                maxo_label: field(label_idx).map(ToString::to_string),
+                predicate: field(predicate_idx).map(ToString::to_string),
+                hpo_id: parse_term_id(field(Some(hpo_idx)))?,
+                disease_id: field(disease_idx).and_then(|value| TermId::from_str(value).ok()),
+            });
+        }
+
+        Ok(Self::index(annotations))
+    }
+
+    /// Get all annotations, in the order of the input.
+    pub fn annotations(&self) -> &[MaxoAnnotation] {
+        &self.annotations
+    }
+
+    /// Get the annotations of the `phenotype` or of any of its descendants in the `hpo`.
+    ///
+    /// The annotations to an alternate ID are found under the primary ID of the term.
+    pub fn treatments_for_phenotype<O>(&self, hpo: &O, phenotype: &TermId) -> Vec<&MaxoAnnotation>
+    where
+        O: Ontology,
+    {
+        let Some(idx) = hpo.id_to_idx(phenotype) else {
+            return vec![];
+        };
+        let hierarchy = hpo.hierarchy();
+        let mut indices: Vec<_> = std::iter::once(idx)
+            .chain(hierarchy.descendants_of(idx).copied())
+            .flat_map(|node| hpo.idx_to_term(node))
+            .flat_map(|term| std::iter::once(term.identifier()).chain(term.iter_alt_term_ids()))
+            .flat_map(|term_id| self.by_phenotype.get(term_id))
+            .flatten()
+            .copied()
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+            .into_iter()
+            .map(|idx| &self.annotations[idx])
+            .collect()
+    }
+
+    fn index(annotations: Vec<MaxoAnnotation>) -> Self {
+        let mut by_phenotype: HashMap<_, Vec<_>> = HashMap::new();
+        for (idx, annotation) in annotations.iter().enumerate() {
+            by_phenotype
+                .entry(annotation.hpo_id.clone())
+                .or_default()
+                .push(idx);
+        }
+        Self {
+            annotations,
+            by_phenotype,
+        }
+    }
+}
+
+fn parse_term_id(value: Option<&str>) -> Result<TermId, OntoliusError> {
+    let value = value.unwrap_or_default();
+    TermId::from_str(value).map_err(|_| OntoliusError::Other(format!("Invalid term ID {value:?}")))
+}
+
+fn io_error(e: std::io::Error) -> OntoliusError {
+    OntoliusError::Other(format!("Cannot read the annotations: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_treatments_for_phenotype() {
+        let terms = vec![
+            SimpleMinimalTerm::new(TermId::from(("HP", "1")), "Root", vec![], false),
+            SimpleMinimalTerm::new(
+                TermId::from(("HP", "2")),
+                "Child",
+                vec![TermId::from(("HP", "3"))],
+                false,
+            ),
+        ];
+        let hpo = toy_ontology(terms, &[(1, 0)]);
+
+        let table = "#version: 2024-01-01\n\
+            hpo_id\tmaxo_id\tpredicate\n\
+            HP:1\tMAXO:1\tTREATS\n\
+            HP:3\tMAXO:2\tPREVENTS\n\
+            \n\
+            HP:9\tMAXO:3\t\n";
+        let annotations = MaxoAnnotations::from_reader(table.as_bytes()).unwrap();
+        assert_eq!(annotations.annotations().len(), 3);
+        assert_eq!(annotations.annotations()[2].predicate(), None);
+
+        let actions = |phenotype: &str| -> Vec<_> {
+            annotations
+                .treatments_for_phenotype(&hpo, &TermId::from(("HP", phenotype)))
+                .into_iter()
+                .map(|annotation| annotation.maxo_id().to_string())
+                .collect()
+        };
+        assert_eq!(actions("1"), ["MAXO:1", "MAXO:2"]);
+        assert_eq!(actions("2"), ["MAXO:2"]);
+        assert!(actions("9").is_empty());
+
+        let result = MaxoAnnotations::from_reader("hpo_id\nHP:1\n".as_bytes());
+        assert!(result.is_err());
+    }
+}
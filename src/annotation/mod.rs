@@ -0,0 +1,27 @@
+//! Read the annotation files that link the ontology terms to the external entities,
+//! and query the annotations along the ontology hierarchy.
+//!
+//! * [`maxo`] links the medical actions (MAxO) to the phenotypes (HPO).
+//! * [`gaf`] checks the Gene Ontology annotations (GAF).
+//! * [`hpoa`] links the diseases to the phenotypes (HPO), with the phenotype frequencies.
+//! * [`bayes`] ranks the diseases by the phenotypes with a naive Bayes classifier.
+//! * [`benchmark`] compares the disease matchers on the (simulated) patients.
+//! * [`eco`] classifies the evidence codes of the annotations.
+//! * [`split`] partitions the terms and the annotated entities by the top-level branches.
+pub mod bayes;
+pub mod benchmark;
+pub mod eco;
+pub mod gaf;
+pub mod hpoa;
+pub mod maxo;
+pub mod split;
+
+use crate::error::OntoliusError;
+
+/// Find the index of the `column` in the `header` of a tab-separated file.
+fn find_column(header: &[&str], column: &str) -> Result<usize, OntoliusError> {
+    header
+        .iter()
+        .position(|&name| name == column)
+        .ok_or_else(|| OntoliusError::Other(format!("Column {column} not found")))
+}
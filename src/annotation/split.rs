@@ -0,0 +1,386 @@
+//! Partition the terms and the annotated entities by the top-level branches of the ontology,
+//! such as the organ systems of HPO, e.g. to prepare the datasets for machine learning.
+//!
+//! [`Branches`] assigns the terms to the branches they descend from, and the entities
+//! to their majority branch. With the `rand` feature, [`Branches::stratified_split`]
+//! splits the entities into the train and test sets with the same proportion of each branch.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! use ontolius::annotation::split::Branches;
+//! # use ontolius::io::obographs::ObographsParser;
+//! use ontolius::prelude::*;
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: MinimalCsrOntology = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                 .expect("HPO should be loaded");
+//! #
+//! // The organ systems are the children of Phenotypic abnormality.
+//! let phenotypic_abnormality = TermId::from(("HP", "0000118"));
+//! let organ_systems = Branches::under(&hpo, &phenotypic_abnormality).unwrap();
+//!
+//! let arachnodactyly = TermId::from(("HP", "0001166"));
+//! let intellectual_disability = TermId::from(("HP", "0001249"));
+//! let branches: Vec<_> = organ_systems.branches_of(&intellectual_disability).collect();
+//! assert_eq!(branches, [&TermId::from(("HP", "0000707"))]);
+//!
+//! // Arachnodactyly is an abnormality of both the musculoskeletal system and the limbs.
+//! let terms = [&arachnodactyly, &intellectual_disability, &intellectual_disability];
+//! let partition = organ_systems.partition(terms);
+//! assert_eq!(partition.len(), 3);
+//! assert_eq!(
+//!     organ_systems.majority_branch(terms),
+//!     Some(&TermId::from(("HP", "0000707"))),
+//! );
+//! ```
+use crate::base::TermId;
+use crate::hierarchy::{AncestorNodes, ChildNodes, OntologyHierarchy};
+use crate::ontology::Ontology;
+
+/// The top-level branches of an ontology, such as the organ systems of HPO.
+///
+/// A term belongs to a branch if it is the branch root or its descendant,
+/// hence a term can belong to several branches.
+pub struct Branches<'o, O>
+where
+    O: Ontology,
+{
+    ontology: &'o O,
+    roots: Vec<O::Idx>,
+}
+
+impl<'o, O> Branches<'o, O>
+where
+    O: Ontology,
+{
+    /// Get the branches rooted at the children of the ontology root.
+    pub fn new(ontology: &'o O) -> Self {
+        let hierarchy = ontology.hierarchy();
+        Self {
+            ontology,
+            roots: hierarchy.children_of(*hierarchy.root()).copied().collect(),
+        }
+    }
+
+    /// Get the branches rooted at the children of the term with the `term_id`,
+    /// e.g. the organ systems under *Phenotypic abnormality* (`HP:0000118`).
+    ///
+    /// Returns `None` if the term is not in the ontology.
+    pub fn under(ontology: &'o O, term_id: &TermId) -> Option<Self> {
+        let idx = ontology.id_to_idx(term_id)?;
+        Some(Self {
+            ontology,
+            roots: ontology.hierarchy().children_of(idx).copied().collect(),
+        })
+    }
+
+    /// Iterate over the IDs of the branch roots.
+    pub fn iter(&self) -> impl Iterator<Item = &'o TermId> + '_ {
+        self.roots
+            .iter()
+            .filter_map(|&root| self.ontology.idx_to_term_id(root))
+    }
+
+    /// Get the number of the branches.
+    pub fn len(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Test if there are no branches.
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Iterate over the IDs of the roots of the branches that include the term with the `term_id`.
+    ///
+    /// Returns no branches if the term is not in the ontology.
+    pub fn branches_of(&self, term_id: &TermId) -> impl Iterator<Item = &'o TermId> + '_ {
+        self.positions_of(term_id)
+            .into_iter()
+            .filter_map(|pos| self.ontology.idx_to_term_id(self.roots[pos]))
+    }
+
+    /// Partition the terms by the branches, in the order of the branches.
+    ///
+    /// A term is included in all of its branches, and the terms that belong
+    /// to no branch are left out. The empty branches are left out too.
+    pub fn partition<'t, I>(&self, term_ids: I) -> Vec<(&'o TermId, Vec<&'t TermId>)>
+    where
+        I: IntoIterator<Item = &'t TermId>,
+    {
+        let mut partition = vec![vec![]; self.roots.len()];
+        for term_id in term_ids {
+            for pos in self.positions_of(term_id) {
+                partition[pos].push(term_id);
+            }
+        }
+        self.roots
+            .iter()
+            .zip(partition)
+            .filter(|(_, terms)| !terms.is_empty())
+            .filter_map(|(&root, terms)| Some((self.ontology.idx_to_term_id(root)?, terms)))
+            .collect()
+    }
+
+    /// Get the branch with the most of the terms, e.g. the phenotypes of a disease.
+    ///
+    /// The ties are broken by the order of the branches.
+    /// Returns `None` if no term belongs to any branch.
+    pub fn majority_branch<'t, I>(&self, term_ids: I) -> Option<&'o TermId>
+    where
+        I: IntoIterator<Item = &'t TermId>,
+    {
+        self.majority_position(term_ids)
+            .and_then(|pos| self.ontology.idx_to_term_id(self.roots[pos]))
+    }
+
+    fn positions_of(&self, term_id: &TermId) -> Vec<usize> {
+        let Some(idx) = self.ontology.id_to_idx(term_id) else {
+            return vec![];
+        };
+        let hierarchy = self.ontology.hierarchy();
+        self.roots
+            .iter()
+            .enumerate()
+            .filter(|&(_, &root)| root == idx || hierarchy.is_descendant_of(idx, root))
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    fn majority_position<'t, I>(&self, term_ids: I) -> Option<usize>
+    where
+        I: IntoIterator<Item = &'t TermId>,
+    {
+        let mut counts = vec![0usize; self.roots.len()];
+        for term_id in term_ids {
+            for pos in self.positions_of(term_id) {
+                counts[pos] += 1;
+            }
+        }
+        // `max_by_key` picks the last maximum, hence the reversal.
+        counts
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|&(_, &count)| count > 0)
+            .max_by_key(|&(_, &count)| count)
+            .map(|(pos, _)| pos)
+    }
+}
+
+/// The train and test sets of a [`Branches::stratified_split`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Split<X> {
+    train: Vec<X>,
+    test: Vec<X>,
+}
+
+impl<X> Split<X> {
+    /// Get the train set.
+    pub fn train(&self) -> &[X] {
+        &self.train
+    }
+
+    /// Get the test set.
+    pub fn test(&self) -> &[X] {
+        &self.test
+    }
+
+    /// Split into the train and test sets.
+    pub fn into_parts(self) -> (Vec<X>, Vec<X>) {
+        (self.train, self.test)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<'o, O> Branches<'o, O>
+where
+    O: Ontology,
+{
+    /// Split the `items` into the train and test sets, stratified by the majority branch
+    /// of the `terms` of each item (see [`Branches::majority_branch`]).
+    ///
+    /// The test set takes the `test_fraction` of the items of each branch, rounded
+    /// to the nearest integer, and the items with no branch make a stratum of their own.
+    /// The items keep their input order in both sets, and the split is reproducible
+    /// with a seeded random number generator.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the `test_fraction` is not in `[0, 1]`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use curie_util::TrieCurieUtil;
+    /// use ontolius::annotation::hpoa::{read_hpoa, DiseaseProfileBuilder};
+    /// use ontolius::annotation::split::Branches;
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// use ontolius::prelude::*;
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// # let loader = OntologyLoaderBuilder::new()
+    /// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+    /// #                .build();
+    /// # let hpo: MinimalCsrOntology = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                                 .expect("HPO should be loaded");
+    /// let file = std::fs::File::open("resources/phenotype.real-shortlist.hpoa").unwrap();
+    /// let records = read_hpoa(std::io::BufReader::new(file)).expect("Annotations should be readable");
+    /// let profiles = DiseaseProfileBuilder::new().build(&records, &hpo);
+    ///
+    /// let organ_systems = Branches::under(&hpo, &TermId::from(("HP", "0000118"))).unwrap();
+    /// let split = organ_systems.stratified_split(
+    ///     &profiles,
+    ///     |profile| profile.iter().map(|(term_id, _)| term_id),
+    ///     0.5,
+    ///     &mut StdRng::seed_from_u64(42),
+    /// );
+    /// assert_eq!(split.train().len() + split.test().len(), profiles.len());
+    /// ```
+    pub fn stratified_split<'x, X, F, I, R>(
+        &self,
+        items: &'x [X],
+        terms: F,
+        test_fraction: f64,
+        rng: &mut R,
+    ) -> Split<&'x X>
+    where
+        F: Fn(&'x X) -> I,
+        I: IntoIterator<Item = &'x TermId>,
+        R: rand::Rng + ?Sized,
+    {
+        assert!(
+            (0. ..=1.).contains(&test_fraction),
+            "The test fraction must be in [0, 1]"
+        );
+        // The last stratum holds the items with no branch.
+        let mut strata = vec![vec![]; self.roots.len() + 1];
+        for (i, item) in items.iter().enumerate() {
+            let pos = self.majority_position(terms(item));
+            strata[pos.unwrap_or(self.roots.len())].push(i);
+        }
+
+        let mut is_test = vec![false; items.len()];
+        for stratum in strata {
+            let n_test = (stratum.len() as f64 * test_fraction).round() as usize;
+            for i in rand::seq::index::sample(rng, stratum.len(), n_test) {
+                is_test[stratum[i]] = true;
+            }
+        }
+
+        let (test, train): (Vec<_>, Vec<_>) =
+            items.iter().zip(is_test).partition(|&(_, is_test)| is_test);
+        Split {
+            train: train.into_iter().map(|(item, _)| item).collect(),
+            test: test.into_iter().map(|(item, _)| item).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::ontology::csr::CsrOntology;
+    use crate::test_util::toy_ontology;
+
+    use super::*;
+
+    /// HP:1 is the root with the branches HP:2 and HP:3. HP:4 and HP:5 are the children of HP:2,
+    /// HP:6 is the child of HP:3, and HP:7 is the child of both HP:2 and HP:3.
+    fn ontology() -> CsrOntology<usize, SimpleMinimalTerm> {
+        let terms: Vec<_> = (1..=7)
+            .map(|i| {
+                let id = i.to_string();
+                SimpleMinimalTerm::new(TermId::from(("HP", id.as_str())), "Term", vec![], false)
+            })
+            .collect();
+        toy_ontology(
+            terms,
+            &[(1, 0), (2, 0), (3, 1), (4, 1), (5, 2), (6, 1), (6, 2)],
+        )
+    }
+
+    fn hp(id: &str) -> TermId {
+        TermId::from(("HP", id))
+    }
+
+    #[test]
+    fn test_branches() {
+        let ontology = ontology();
+        let branches = Branches::new(&ontology);
+        assert_eq!(branches.iter().collect::<Vec<_>>(), [&hp("2"), &hp("3")]);
+
+        assert_eq!(branches.branches_of(&hp("7")).count(), 2);
+        assert_eq!(branches.branches_of(&hp("1")).count(), 0);
+        assert_eq!(branches.branches_of(&hp("404")).count(), 0);
+
+        let terms = [hp("1"), hp("4"), hp("6"), hp("7")];
+        let partition = branches.partition(&terms);
+        assert_eq!(
+            partition,
+            [
+                (&hp("2"), vec![&hp("4"), &hp("7")]),
+                (&hp("3"), vec![&hp("6"), &hp("7")]),
+            ]
+        );
+
+        assert_eq!(
+            branches.majority_branch(&[hp("6"), hp("7")]),
+            Some(&hp("3"))
+        );
+        assert_eq!(branches.majority_branch(&[hp("7")]), Some(&hp("2")));
+        assert_eq!(branches.majority_branch(&[hp("1")]), None);
+
+        let under = Branches::under(&ontology, &hp("3")).unwrap();
+        assert_eq!(under.iter().collect::<Vec<_>>(), [&hp("6"), &hp("7")]);
+        assert!(Branches::under(&ontology, &hp("404")).is_none());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_stratified_split() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let ontology = ontology();
+        let branches = Branches::new(&ontology);
+        // Four items in HP:2, two in HP:3, and two with no branch.
+        let items = [
+            vec![hp("4")],
+            vec![hp("5")],
+            vec![hp("2")],
+            vec![hp("4"), hp("5")],
+            vec![hp("6")],
+            vec![hp("3")],
+            vec![hp("1")],
+            vec![],
+        ];
+        let split =
+            branches.stratified_split(&items, |terms| terms, 0.5, &mut StdRng::seed_from_u64(42));
+        assert_eq!(split.train().len(), 4);
+        assert_eq!(split.test().len(), 4);
+        for part in [split.train(), split.test()] {
+            let in_first = part
+                .iter()
+                .filter(|terms| branches.majority_branch(terms.iter()) == Some(&hp("2")))
+                .count();
+            let in_second = part
+                .iter()
+                .filter(|terms| branches.majority_branch(terms.iter()) == Some(&hp("3")))
+                .count();
+            assert_eq!((in_first, in_second), (2, 1));
+        }
+
+        let again =
+            branches.stratified_split(&items, |terms| terms, 0.5, &mut StdRng::seed_from_u64(42));
+        assert_eq!(split, again);
+
+        let all_train =
+            branches.stratified_split(&items, |terms| terms, 0., &mut StdRng::seed_from_u64(42));
+        assert_eq!(all_train.train().len(), items.len());
+        assert!(all_train.test().is_empty());
+    }
+}
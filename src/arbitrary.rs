@@ -0,0 +1,362 @@
+//! Generate random term IDs, terms, and small ontologies for property-based testing.
+//!
+//! With the `proptest` feature, [`TermId`], [`SimpleMinimalTerm`], and [`SmallDag`]
+//! implement `proptest::arbitrary::Arbitrary`, and `small_dag` makes the strategy
+//! for the DAGs of a chosen size. With the `quickcheck` feature, the same types
+//! implement `quickcheck::Arbitrary`.
+//!
+//! The term IDs use the well-known prefixes (e.g. `HP`) as well as random prefixes,
+//! to cover both representations of [`TermId`].
+use std::collections::HashMap;
+
+use crate::base::term::simple::SimpleMinimalTerm;
+use crate::base::TermId;
+use crate::hierarchy::{GraphEdge, Relationship};
+use crate::io::OntologyData;
+
+/// The prefixes with the compact representation in [`TermId`].
+const KNOWN_PREFIXES: [&str; 10] = [
+    "HP", "OMIM", "MONDO", "GO", "MAXO", "ORPHA", "GENO", "SO", "CHEBI", "NCIT",
+];
+
+/// The default maximum number of the terms of a [`SmallDag`].
+const MAX_DAG_TERMS: usize = 32;
+
+/// A small random ontology with a single root and the `is_a` edges
+/// that form a directed acyclic graph.
+///
+/// The term at index `0` is the root, and the other terms have one or two parents
+/// with a lower index, hence all terms descend from the root. There are at least two terms,
+/// since the ontology needs an edge to find the root. The terms are current
+/// and have unique IDs, `HP:0000001` for the root, `HP:0000002` for the next term, and so on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmallDag {
+    terms: Vec<SimpleMinimalTerm>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl SmallDag {
+    /// Create the DAG from the `parents` of the terms at indices `1..=parents.len()`.
+    fn from_parents(parents: Vec<Vec<usize>>) -> Self {
+        let terms = (0..=parents.len())
+            .map(|idx| {
+                let id = format!("{:07}", idx + 1);
+                SimpleMinimalTerm::new(
+                    TermId::from(("HP", id.as_str())),
+                    format!("Term {}", idx + 1),
+                    vec![],
+                    false,
+                )
+            })
+            .collect();
+        let mut edges = vec![];
+        for (child, mut parents) in (1..).zip(parents) {
+            parents.sort_unstable();
+            parents.dedup();
+            edges.extend(parents.into_iter().map(|parent| (child, parent)));
+        }
+        Self { terms, edges }
+    }
+
+    /// Get the terms.
+    pub fn terms(&self) -> &[SimpleMinimalTerm] {
+        &self.terms
+    }
+
+    /// Get the `(child, parent)` index pairs of the `is_a` edges.
+    pub fn edges(&self) -> &[(usize, usize)] {
+        &self.edges
+    }
+
+    /// Get the number of the terms.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Test if there are no terms, which is never the case.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Get the ontology data, e.g. to build a [`CsrOntology`](crate::ontology::csr::CsrOntology).
+    pub fn to_ontology_data(&self) -> OntologyData<usize, SimpleMinimalTerm> {
+        let edges: Vec<_> = self
+            .edges
+            .iter()
+            .map(|&(child, parent)| GraphEdge::from((child, Relationship::Child, parent)))
+            .collect();
+        OntologyData::from((
+            self.terms.clone().into_boxed_slice(),
+            edges.into_boxed_slice(),
+            HashMap::new(),
+        ))
+    }
+
+    /// Keep the first `len` terms and their edges.
+    #[cfg(feature = "quickcheck")]
+    fn truncated(&self, len: usize) -> Self {
+        Self {
+            terms: self.terms[..len].to_vec(),
+            edges: self
+                .edges
+                .iter()
+                .copied()
+                .filter(|&(child, _)| child < len)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+pub use proptest_impls::small_dag;
+
+#[cfg(feature = "proptest")]
+mod proptest_impls {
+    use proptest::arbitrary::{any, Arbitrary};
+    use proptest::collection::vec;
+    use proptest::prop_oneof;
+    use proptest::sample::select;
+    use proptest::strategy::{BoxedStrategy, Strategy};
+
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::base::TermId;
+
+    use super::{SmallDag, KNOWN_PREFIXES, MAX_DAG_TERMS};
+
+    impl Arbitrary for TermId {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            prop_oneof![
+                (select(&KNOWN_PREFIXES[..]), 0..10_000_000u32).prop_map(|(prefix, id)| {
+                    TermId::from((prefix, format!("{id:07}").as_str()))
+                }),
+                ("[A-Z][A-Za-z]{0,7}", "[0-9A-Za-z]{1,10}")
+                    .prop_map(|(prefix, id)| TermId::from((prefix.as_str(), id.as_str()))),
+            ]
+            .boxed()
+        }
+    }
+
+    impl Arbitrary for SimpleMinimalTerm {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            (
+                any::<TermId>(),
+                "[A-Za-z][a-z ]{0,30}",
+                vec(any::<TermId>(), 0..3),
+                any::<bool>(),
+            )
+                .prop_map(|(term_id, name, alt_term_ids, is_obsolete)| {
+                    SimpleMinimalTerm::new(term_id, name, alt_term_ids, is_obsolete)
+                })
+                .boxed()
+        }
+    }
+
+    /// Get the strategy for the [`SmallDag`]s with `2..=max_terms` terms.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use ontolius::arbitrary::small_dag;
+    /// use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    /// use proptest::prelude::*;
+    ///
+    /// proptest! {
+    ///     fn root_is_ancestor_of_all(dag in small_dag(10)) {
+    ///         let ontology: CsrOntology<usize, _> = CsrOntology::try_from(dag.to_ontology_data())?;
+    ///         let root = *ontology.hierarchy().root();
+    ///         prop_assert_eq!(ontology.hierarchy().descendants_of(root).count(), dag.len() - 1);
+    ///     }
+    /// }
+    /// # root_is_ancestor_of_all();
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `max_terms` is less than `2`.
+    pub fn small_dag(max_terms: usize) -> impl Strategy<Value = SmallDag> {
+        assert!(max_terms > 1, "The DAG must have at least two terms");
+        (2..=max_terms)
+            .prop_flat_map(|len| (1..len).map(|idx| vec(0..idx, 1..=2)).collect::<Vec<_>>())
+            .prop_map(SmallDag::from_parents)
+    }
+
+    impl Arbitrary for SmallDag {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            small_dag(MAX_DAG_TERMS).boxed()
+        }
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impls {
+    use quickcheck::{Arbitrary, Gen};
+
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::base::TermId;
+
+    use super::{SmallDag, KNOWN_PREFIXES, MAX_DAG_TERMS};
+
+    const UPPER: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+    /// Make a word of `1..=max_len` characters of the `alphabet`.
+    fn word(g: &mut Gen, alphabet: &[u8], max_len: usize) -> String {
+        let len = 1 + usize::arbitrary(g) % max_len;
+        (0..len)
+            .filter_map(|_| g.choose(alphabet).map(|&c| char::from(c)))
+            .collect()
+    }
+
+    impl Arbitrary for TermId {
+        fn arbitrary(g: &mut Gen) -> Self {
+            if bool::arbitrary(g) {
+                let prefix = g.choose(&KNOWN_PREFIXES).copied().unwrap_or("HP");
+                let id = u32::arbitrary(g) % 10_000_000;
+                TermId::from((prefix, format!("{id:07}").as_str()))
+            } else {
+                let prefix = word(g, UPPER, 8);
+                let id = word(g, ALPHANUMERIC, 10);
+                TermId::from((prefix.as_str(), id.as_str()))
+            }
+        }
+    }
+
+    impl Arbitrary for SimpleMinimalTerm {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let alt_term_ids = (0..usize::arbitrary(g) % 3)
+                .map(|_| TermId::arbitrary(g))
+                .collect();
+            SimpleMinimalTerm::new(
+                TermId::arbitrary(g),
+                word(g, ALPHANUMERIC, 30),
+                alt_term_ids,
+                bool::arbitrary(g),
+            )
+        }
+    }
+
+    /// The DAGs have up to `max(2, min(g.size(), 32))` terms and shrink by dropping the last terms.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use ontolius::arbitrary::SmallDag;
+    /// use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    /// use quickcheck::quickcheck;
+    ///
+    /// fn all_terms_are_present(dag: SmallDag) -> bool {
+    ///     let ontology: CsrOntology<usize, _> = CsrOntology::try_from(dag.to_ontology_data()).unwrap();
+    ///     ontology.len() == dag.len()
+    /// }
+    ///
+    /// quickcheck(all_terms_are_present as fn(SmallDag) -> bool);
+    /// ```
+    impl Arbitrary for SmallDag {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let max_terms = g.size().clamp(2, MAX_DAG_TERMS);
+            let len = 2 + usize::arbitrary(g) % (max_terms - 1);
+            let parents = (1..len)
+                .map(|idx| {
+                    let mut parents = vec![usize::arbitrary(g) % idx];
+                    if bool::arbitrary(g) {
+                        parents.push(usize::arbitrary(g) % idx);
+                    }
+                    parents
+                })
+                .collect();
+            SmallDag::from_parents(parents)
+        }
+
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            let dag = self.clone();
+            Box::new((2..dag.len()).rev().map(move |len| dag.truncated(len)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ontology::csr::CsrOntology;
+    use crate::prelude::*;
+
+    use super::*;
+
+    fn check_dag(dag: &SmallDag) {
+        let ontology: CsrOntology<usize, _> = CsrOntology::try_from(dag.to_ontology_data())
+            .expect("The DAG should be a valid ontology");
+        assert_eq!(ontology.len(), dag.len());
+        let root = *ontology.hierarchy().root();
+        assert_eq!(root, 0);
+        assert_eq!(
+            ontology.hierarchy().descendants_of(root).count(),
+            dag.len() - 1
+        );
+    }
+
+    #[test]
+    fn test_small_dag() {
+        let dag = SmallDag::from_parents(vec![vec![0], vec![0], vec![2, 1, 2]]);
+        assert_eq!(dag.len(), 4);
+        assert_eq!(dag.edges(), [(1, 0), (2, 0), (3, 1), (3, 2)]);
+        check_dag(&dag);
+        assert_eq!(
+            dag.terms()[0].identifier(),
+            &TermId::from(("HP", "0000001"))
+        );
+    }
+
+    #[cfg(feature = "proptest")]
+    mod proptest_test {
+        use std::str::FromStr;
+
+        use proptest::prelude::*;
+
+        use super::super::small_dag;
+        use super::check_dag;
+        use crate::base::TermId;
+
+        proptest! {
+            #[test]
+            fn test_term_id_round_trip(term_id in any::<TermId>()) {
+                let parsed = TermId::from_str(&term_id.to_string()).unwrap();
+                prop_assert_eq!(parsed, term_id);
+            }
+
+            #[test]
+            fn test_small_dag_is_ontology(dag in small_dag(16)) {
+                check_dag(&dag);
+            }
+        }
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn test_quickcheck() {
+        use std::str::FromStr;
+
+        use quickcheck::QuickCheck;
+
+        fn round_trip(term_id: TermId) -> bool {
+            TermId::from_str(&term_id.to_string()).is_ok_and(|parsed| parsed == term_id)
+        }
+
+        fn is_ontology(dag: SmallDag) -> bool {
+            check_dag(&dag);
+            true
+        }
+
+        QuickCheck::new().quickcheck(round_trip as fn(TermId) -> bool);
+        QuickCheck::new().quickcheck(is_ontology as fn(SmallDag) -> bool);
+    }
+}
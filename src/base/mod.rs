@@ -1,6 +1,7 @@
 //! The base building blocks for working with ontology data.
 
 use crate::error::OntoliusError;
+use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter, Write};
 use std::hash::Hash;
@@ -9,6 +10,9 @@ use std::str::FromStr;
 #[cfg(feature = "pyo3")]
 pub mod py;
 pub mod term;
+mod xref;
+
+pub use xref::XrefKind;
 
 /// `Identified` is implemented by entities that have a [`TermId`] as an identifier.
 ///
@@ -64,6 +68,101 @@ pub trait Identified {
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TermId(InnerTermId);
 
+impl TermId {
+    /// Get the prefix of the CURIE (e.g. `HP` for `HP:0001250`).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use ontolius::prelude::*;
+    ///
+    /// assert_eq!(TermId::from(("HP", "0001250")).prefix(), "HP");
+    /// assert_eq!(TermId::from(("MP", "0001250")).prefix(), "MP");
+    /// ```
+    pub fn prefix(&self) -> &str {
+        match &self.0 {
+            InnerTermId::Known(prefix, _, _) => prefix.as_str(),
+            InnerTermId::Random(val, idx) => &val[..*idx as usize],
+        }
+    }
+
+    /// Compare the term IDs in the order of their CURIEs, i.e. the order of their string representations.
+    ///
+    /// The default ordering of the term IDs is fast but it places the term IDs
+    /// with a few well-known prefixes (e.g. `HP`) before the other term IDs.
+    /// Use the lexicographic ordering, e.g. to sort the term IDs for output.
+    /// See also [`LexOrdered`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use ontolius::prelude::*;
+    ///
+    /// let hp = TermId::from_str("HP:0001250").unwrap();
+    /// let efo = TermId::from_str("EFO:0000001").unwrap();
+    ///
+    /// // `HP` is a well-known prefix.
+    /// assert!(hp < efo);
+    /// assert!(hp.cmp_lexicographic(&efo).is_gt());
+    /// ```
+    pub fn cmp_lexicographic(&self, other: &TermId) -> Ordering {
+        self.prefix().cmp(other.prefix()).then_with(|| match (&self.0, &other.0) {
+            // Zero-padded ids of the same length compare as the numbers.
+            (InnerTermId::Known(_, l_id, l_len), InnerTermId::Known(_, r_id, r_len))
+                if l_len == r_len =>
+            {
+                l_id.cmp(r_id)
+            }
+            _ => self.to_string().cmp(&other.to_string()),
+        })
+    }
+}
+
+/// A wrapper for ordering the term IDs lexicographically, in the order of their CURIEs.
+///
+/// See [`TermId::cmp_lexicographic`] for more info.
+///
+/// ## Examples
+///
+/// ```
+/// use std::str::FromStr;
+/// use ontolius::prelude::*;
+/// use ontolius::base::LexOrdered;
+///
+/// let mut term_ids: Vec<_> = ["MONDO:0000001", "EFO:0000001", "HP:0001250"]
+///     .into_iter()
+///     .map(|curie| TermId::from_str(curie).unwrap())
+///     .collect();
+///
+/// term_ids.sort_by(|a, b| LexOrdered(a).cmp(&LexOrdered(b)));
+///
+/// let curies: Vec<_> = term_ids.iter().map(ToString::to_string).collect();
+/// assert_eq!(curies, ["EFO:0000001", "HP:0001250", "MONDO:0000001"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LexOrdered<T>(pub T);
+
+impl<T: Borrow<TermId>> PartialEq for LexOrdered<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl<T: Borrow<TermId>> Eq for LexOrdered<T> {}
+
+impl<T: Borrow<TermId>> PartialOrd for LexOrdered<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Borrow<TermId>> Ord for LexOrdered<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.borrow().cmp_lexicographic(other.0.borrow())
+    }
+}
+
 /// Try to convert a CURIE `str` into a `TermId`.
 ///
 /// ## Examples
@@ -84,6 +183,28 @@ impl FromStr for TermId {
     }
 }
 
+/// `TermId` is serialized as a CURIE string, e.g. `"HP:0001250"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for TermId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TermId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let curie = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        TermId::from_str(&curie).map_err(serde::de::Error::custom)
+    }
+}
+
 /// Test if a tuple with *prefix* and *id* tuple is equal to a term ID.
 /// 
 /// ## Examples
@@ -189,6 +310,23 @@ enum Prefix {
     NCIT,
 }
 
+impl Prefix {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Prefix::HP => "HP",
+            Prefix::OMIM => "OMIM",
+            Prefix::MONDO => "MONDO",
+            Prefix::GO => "GO",
+            Prefix::MAXO => "MAXO",
+            Prefix::ORPHA => "ORPHA",
+            Prefix::GENO => "GENO",
+            Prefix::SO => "SO",
+            Prefix::CHEBI => "CHEBI",
+            Prefix::NCIT => "NCIT",
+        }
+    }
+}
+
 impl PartialEq<str> for Prefix {
     fn eq(&self, other: &str) -> bool {
         match self {
@@ -269,6 +407,12 @@ impl TryFrom<&str> for InnerTermId {
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let delimiter = InnerTermId::find_delimiter(value)?;
+        if delimiter > u8::MAX as usize {
+            return Err(OntoliusError::Other(format!(
+                "CURIE prefix longer than {} bytes",
+                u8::MAX
+            )));
+        }
         Ok(InnerTermId::from((
             &value[..delimiter],
             &value[delimiter + 1..],
@@ -283,14 +427,15 @@ impl From<(&str, &str)> for InnerTermId {
         let a: Result<u32, _> = ident.parse();
         let id_len: Result<_, _> = u8::try_from(ident.len());
         match (p, a) {
-            (Ok(prefix), Ok(id)) => {
+            (Ok(prefix), Ok(id)) if ident.len() <= u8::MAX as usize => {
                 // Prefix is known
                 InnerTermId::Known(prefix, id, id_len.expect("ID should not be longer than 255 chars!"))
             }
             _ => {
                 //
                 let val = Box::new([prefix, ident].concat());
-                let idx = u8::try_from(prefix.chars().count())
+                // The prefix is sliced by the byte index.
+                let idx = u8::try_from(prefix.len())
                     .expect("Curie prefix should not be longer than 255 chars!");
                 InnerTermId::Random(val, idx)
             }
@@ -425,6 +570,18 @@ mod test_creation {
         round_trip_from_curie!("OMIM:256000", "OMIM:256000");
         round_trip_from_curie!("NCIT_C2852", "NCIT:C2852");
         round_trip_from_curie!("WHATEVER:12", "WHATEVER:12");
+        round_trip_from_curie!("ÉTÉ:12", "ÉTÉ:12");
+    }
+
+    #[test]
+    fn test_term_id_from_malformed_curie() {
+        let long_prefix = format!("{}:1", "X".repeat(300));
+        assert!(TermId::from_str(&long_prefix).is_err());
+
+        let long_id = format!("HP:{}1", "0".repeat(300));
+        let term_id = TermId::from_str(&long_id).unwrap();
+        assert_eq!(term_id.to_string(), long_id);
+        assert_eq!(term_id.prefix(), "HP");
     }
 }
 
@@ -472,6 +629,39 @@ mod test_comparison_and_ordering {
         term_ids_compare_to_ordering!("WHATEVER:2", "WHATEVER:1", Ordering::Greater);
         term_ids_compare_to_ordering!("WHATEVER:10", "WHATEVER:1", Ordering::Greater);
     }
+
+    macro_rules! term_ids_compare_lexicographic {
+        ($left_curie: literal, $right_curie: literal, $val: expr) => {
+            let left = TermId::from_str($left_curie).expect("Left CURIE is invalid!");
+            let right = TermId::from_str($right_curie).expect("Right CURIE is invalid!");
+            assert_eq!(left.cmp_lexicographic(&right), $val);
+        };
+    }
+
+    #[test]
+    fn lexicographic() {
+        term_ids_compare_lexicographic!("HP:1234567", "WHATEVER:1234567", Ordering::Less);
+        term_ids_compare_lexicographic!("HP:1234567", "AAA:1234567", Ordering::Greater);
+        term_ids_compare_lexicographic!("HP:1", "HP_1", Ordering::Equal);
+        term_ids_compare_lexicographic!("HP:0000002", "HP:0000010", Ordering::Less);
+        term_ids_compare_lexicographic!("HP:10", "HP:2", Ordering::Less);
+        term_ids_compare_lexicographic!("WHATEVER:10", "WHATEVER:2", Ordering::Less);
+    }
+
+    #[test]
+    fn lex_ordered() {
+        use super::LexOrdered;
+
+        let mut term_ids: Vec<_> = ["HP:0000002", "AAA:1", "MONDO:0000001", "HP:0000001"]
+            .into_iter()
+            .map(|curie| TermId::from_str(curie).unwrap())
+            .map(LexOrdered)
+            .collect();
+        term_ids.sort();
+
+        let curies: Vec<_> = term_ids.iter().map(|t| t.0.to_string()).collect();
+        assert_eq!(curies, ["AAA:1", "HP:0000001", "HP:0000002", "MONDO:0000001"]);
+    }
 }
 
 #[cfg(test)]
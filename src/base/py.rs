@@ -21,7 +21,7 @@ use super::TermId;
 ///
 #[repr(transparent)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[pyclass(name = "TermId")]
+#[pyclass(name = "TermId", module = "ontolius.term")]
 pub struct PyTermId(TermId);
 
 /// Get the inner [`TermId`].
@@ -33,6 +33,12 @@ impl Deref for PyTermId {
     }
 }
 
+impl From<TermId> for PyTermId {
+    fn from(value: TermId) -> Self {
+        PyTermId(value)
+    }
+}
+
 #[pymethods]
 impl PyTermId {
     /// Create `TermId` from a CURIE string (e.g. `HP:0001250`).
@@ -59,7 +65,10 @@ impl PyTermId {
 
 impl<'source> FromPyObject<'source> for PyTermId {
     fn extract(ob: &'source PyAny) -> PyResult<Self> {
-        if ob.is_instance_of::<PyString>() {
+        if let Ok(term_id) = ob.extract::<PyRef<PyTermId>>() {
+            // `TermId` created in Python
+            Ok(PyTermId(term_id.0.clone()))
+        } else if ob.is_instance_of::<PyString>() {
             // CURIE str
             PyTermId::from_curie(ob_to_py_string(ob)?)
         } else if ob.hasattr(intern!(ob.py(), "prefix"))? && ob.hasattr(intern!(ob.py(), "id"))? {
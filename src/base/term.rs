@@ -1,4 +1,4 @@
-use crate::base::{Identified, TermId};
+use crate::base::{Identified, TermId, XrefKind};
 use std::fmt::Debug;
 
 pub trait AltTermIdAware {
@@ -29,12 +29,187 @@ pub trait Term: MinimalTerm {
     // TODO: add Xrefs and dbXrefs
 }
 
+/// The category of a [`Synonym`], corresponding to the `oboInOwl` synonym predicates
+/// (e.g. `hasExactSynonym`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SynonymCategory {
+    Exact,
+    Related,
+    Broad,
+    Narrow,
+}
+
+/// A synonym of an ontology term.
+///
+/// ## Examples
+///
+/// ```
+/// use std::str::FromStr;
+/// use ontolius::prelude::*;
+/// use ontolius::base::term::{Synonym, SynonymCategory};
+///
+/// let synonym = Synonym::new(
+///     "Long slender fingers",
+///     SynonymCategory::Exact,
+///     vec![TermId::from_str("ORCID:0000-0001-5208-3432").unwrap(), TermId::from_str("PMID:123").unwrap()],
+/// );
+///
+/// let contributors: Vec<_> = synonym.contributors().map(ToString::to_string).collect();
+/// assert_eq!(contributors, ["ORCID:0000-0001-5208-3432"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Synonym {
+    name: String,
+    category: SynonymCategory,
+    xrefs: Vec<TermId>,
+}
+
+impl Synonym {
+    pub fn new<T: ToString>(name: T, category: SynonymCategory, xrefs: Vec<TermId>) -> Self {
+        Self {
+            name: name.to_string(),
+            category,
+            xrefs,
+        }
+    }
+
+    /// Get the synonym text.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the synonym category.
+    pub fn category(&self) -> SynonymCategory {
+        self.category
+    }
+
+    /// Get the cross-references supporting the synonym,
+    /// such as publications or the contributors.
+    pub fn xrefs(&self) -> &[TermId] {
+        &self.xrefs
+    }
+
+    /// Get an iterator over the ORCID IDs of the synonym contributors.
+    pub fn contributors(&self) -> impl Iterator<Item = &TermId> {
+        self.xrefs
+            .iter()
+            .filter(|&xref| XrefKind::classify(xref) == XrefKind::Orcid)
+    }
+}
+
+/// The implementors have synonyms.
+pub trait SynonymAware {
+    type SynonymIter<'a>: Iterator<Item = &'a Synonym>
+    where
+        Self: 'a;
+
+    fn iter_synonyms(&self) -> Self::SynonymIter<'_>;
+
+    /// Test if any synonym was contributed by the curator with the `orcid` ID.
+    fn is_contributed_by(&self, orcid: &TermId) -> bool {
+        self.iter_synonyms()
+            .any(|synonym| synonym.contributors().any(|contributor| contributor == orcid))
+    }
+}
+
+/// The implementors belong to the subsets (slims) of the ontology,
+/// such as `goslim_generic` of GO or `hposlim_core` of HPO.
+pub trait SubsetAware {
+    /// Get the names of the subsets of the term, e.g. `goslim_generic`.
+    fn subsets(&self) -> &[String];
+
+    /// Test if the term belongs to the `subset`, e.g. `goslim_generic`.
+    fn in_subset(&self, subset: &str) -> bool {
+        self.subsets().iter().any(|name| name == subset)
+    }
+}
+
+/// A property value of an ontology term, such as the `hasOBONamespace`
+/// or the editor preferred term (`IAO:0000233`), with the predicate and the value as written in the input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PropertyValue {
+    pred: String,
+    val: String,
+}
+
+impl PropertyValue {
+    pub fn new<P: ToString, V: ToString>(pred: P, val: V) -> Self {
+        Self {
+            pred: pred.to_string(),
+            val: val.to_string(),
+        }
+    }
+
+    /// Get the predicate, e.g. `http://www.geneontology.org/formats/oboInOwl#hasOBONamespace`.
+    pub fn pred(&self) -> &str {
+        &self.pred
+    }
+
+    /// Get the value.
+    pub fn val(&self) -> &str {
+        &self.val
+    }
+}
+
+/// The provenance of an ontology term, i.e. who created the term and when,
+/// with the values as written in the input.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TermProvenance {
+    created_by: Option<String>,
+    creation_date: Option<String>,
+}
+
+impl TermProvenance {
+    /// Set the creator of the term.
+    #[must_use]
+    pub fn with_created_by<T: ToString>(mut self, created_by: T) -> Self {
+        self.created_by = Some(created_by.to_string());
+        self
+    }
+
+    /// Set the creation date of the term.
+    #[must_use]
+    pub fn with_creation_date<T: ToString>(mut self, creation_date: T) -> Self {
+        self.creation_date = Some(creation_date.to_string());
+        self
+    }
+
+    /// Get the creator of the term (`oboInOwl:created_by` or `dcterms:creator`),
+    /// e.g. `https://orcid.org/0000-0002-0736-9199`.
+    pub fn created_by(&self) -> Option<&str> {
+        self.created_by.as_deref()
+    }
+
+    /// Get the creation date of the term (`oboInOwl:creation_date` or `dc:date`),
+    /// e.g. `2008-02-27T02:20:00Z`.
+    pub fn creation_date(&self) -> Option<&str> {
+        self.creation_date.as_deref()
+    }
+}
+
+/// The implementors know the terms to use instead of an obsolete term.
+pub trait ReplacementAware {
+    /// Get the ID of the term that replaces the obsolete term (`IAO:0100001`, *term replaced by*).
+    fn replaced_by(&self) -> Option<&TermId>;
+
+    /// Get the IDs of the terms to consider instead of the obsolete term (`oboInOwl:consider`).
+    fn consider(&self) -> &[TermId];
+}
+
 pub mod simple {
 
-    use super::{AltTermIdAware, MinimalTerm};
+    use super::{
+        AltTermIdAware, MinimalTerm, PropertyValue, ReplacementAware, SubsetAware, Synonym,
+        SynonymAware, Term, TermProvenance,
+    };
     use crate::base::{Identified, TermId};
 
     #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SimpleMinimalTerm {
         term_id: TermId,
         alt_term_ids: Vec<TermId>,
@@ -87,4 +262,195 @@ pub mod simple {
             !self.is_obsolete
         }
     }
+
+    /// A term with the definition, comment, synonyms, and cross-references,
+    /// in addition to the data of [`SimpleMinimalTerm`].
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct SimpleTerm {
+        term_id: TermId,
+        alt_term_ids: Vec<TermId>,
+        name: String,
+        is_obsolete: bool,
+        definition: Option<String>,
+        comment: Option<String>,
+        synonyms: Vec<Synonym>,
+        xrefs: Vec<TermId>,
+        replaced_by: Option<TermId>,
+        consider: Vec<TermId>,
+        property_values: Vec<PropertyValue>,
+        subsets: Vec<String>,
+        provenance: Option<TermProvenance>,
+    }
+
+    impl SimpleTerm {
+        pub fn new<T: ToString>(
+            term_id: TermId,
+            name: T,
+            alt_term_ids: Vec<TermId>,
+            is_obsolete: bool,
+        ) -> Self {
+            SimpleTerm {
+                term_id,
+                name: name.to_string(),
+                alt_term_ids,
+                is_obsolete,
+                definition: None,
+                comment: None,
+                synonyms: vec![],
+                xrefs: vec![],
+                replaced_by: None,
+                consider: vec![],
+                property_values: vec![],
+                subsets: vec![],
+                provenance: None,
+            }
+        }
+
+        /// Set the term definition.
+        #[must_use]
+        pub fn with_definition<T: ToString>(mut self, definition: T) -> Self {
+            self.definition = Some(definition.to_string());
+            self
+        }
+
+        /// Set the term comment.
+        #[must_use]
+        pub fn with_comment<T: ToString>(mut self, comment: T) -> Self {
+            self.comment = Some(comment.to_string());
+            self
+        }
+
+        /// Set the term synonyms.
+        #[must_use]
+        pub fn with_synonyms(mut self, synonyms: Vec<Synonym>) -> Self {
+            self.synonyms = synonyms;
+            self
+        }
+
+        /// Set the cross-references of the term.
+        #[must_use]
+        pub fn with_xrefs(mut self, xrefs: Vec<TermId>) -> Self {
+            self.xrefs = xrefs;
+            self
+        }
+
+        /// Set the ID of the term that replaces the obsolete term.
+        #[must_use]
+        pub fn with_replaced_by(mut self, replaced_by: TermId) -> Self {
+            self.replaced_by = Some(replaced_by);
+            self
+        }
+
+        /// Set the IDs of the terms to consider instead of the obsolete term.
+        #[must_use]
+        pub fn with_consider(mut self, consider: Vec<TermId>) -> Self {
+            self.consider = consider;
+            self
+        }
+
+        /// Set the property values of the term.
+        #[must_use]
+        pub fn with_property_values(mut self, property_values: Vec<PropertyValue>) -> Self {
+            self.property_values = property_values;
+            self
+        }
+
+        /// Set the names of the subsets of the term, e.g. `goslim_generic`.
+        #[must_use]
+        pub fn with_subsets(mut self, subsets: Vec<String>) -> Self {
+            self.subsets = subsets;
+            self
+        }
+
+        /// Set the provenance of the term.
+        #[must_use]
+        pub fn with_provenance(mut self, provenance: TermProvenance) -> Self {
+            self.provenance = Some(provenance);
+            self
+        }
+
+        /// Get the cross-references of the term, e.g. to other ontologies.
+        pub fn xrefs(&self) -> &[TermId] {
+            &self.xrefs
+        }
+
+        /// Get the property values of the term that are not parsed into the alternate term IDs,
+        /// such as the `hasOBONamespace` or the editor preferred term (`IAO:0000233`).
+        pub fn property_values(&self) -> &[PropertyValue] {
+            &self.property_values
+        }
+
+        /// Get the provenance of the term, or `None` if the input has no creator
+        /// and no creation date of the term.
+        pub fn provenance(&self) -> Option<&TermProvenance> {
+            self.provenance.as_ref()
+        }
+    }
+
+    impl Identified for SimpleTerm {
+        fn identifier(&self) -> &TermId {
+            &self.term_id
+        }
+    }
+
+    impl AltTermIdAware for SimpleTerm {
+        type TermIdIter<'a> = std::slice::Iter<'a, TermId>
+        where
+            Self: 'a;
+
+        fn iter_alt_term_ids(&self) -> Self::TermIdIter<'_> {
+            self.alt_term_ids.iter()
+        }
+
+        fn alt_term_id_count(&self) -> usize {
+            self.alt_term_ids.len()
+        }
+    }
+
+    impl MinimalTerm for SimpleTerm {
+        fn name(&self) -> &str {
+            self.name.as_str()
+        }
+
+        fn is_current(&self) -> bool {
+            !self.is_obsolete
+        }
+    }
+
+    impl Term for SimpleTerm {
+        fn definition(&self) -> Option<&str> {
+            self.definition.as_deref()
+        }
+
+        fn comment(&self) -> Option<&str> {
+            self.comment.as_deref()
+        }
+    }
+
+    impl SynonymAware for SimpleTerm {
+        type SynonymIter<'a> = std::slice::Iter<'a, Synonym>
+        where
+            Self: 'a;
+
+        fn iter_synonyms(&self) -> Self::SynonymIter<'_> {
+            self.synonyms.iter()
+        }
+    }
+
+    impl SubsetAware for SimpleTerm {
+        fn subsets(&self) -> &[String] {
+            &self.subsets
+        }
+    }
+
+    impl ReplacementAware for SimpleTerm {
+        fn replaced_by(&self) -> Option<&TermId> {
+            self.replaced_by.as_ref()
+        }
+
+        fn consider(&self) -> &[TermId] {
+            &self.consider
+        }
+    }
 }
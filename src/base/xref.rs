@@ -0,0 +1,83 @@
+use super::TermId;
+
+/// The kind of a cross-reference (xref), such as a reference to literature,
+/// to a contributor, or to a term of another ontology.
+///
+/// ## Examples
+///
+/// ```
+/// use std::str::FromStr;
+/// use ontolius::prelude::*;
+/// use ontolius::base::XrefKind;
+///
+/// let pmid = TermId::from_str("PMID:12345").unwrap();
+/// assert_eq!(XrefKind::classify(&pmid), XrefKind::Pmid);
+/// assert!(XrefKind::classify(&pmid).is_literature());
+///
+/// let umls = TermId::from_str("UMLS:C0003706").unwrap();
+/// assert_eq!(XrefKind::classify(&umls), XrefKind::OntologyTerm("UMLS".into()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum XrefKind {
+    /// A PubMed article (e.g. `PMID:12345`).
+    Pmid,
+    /// A contributor (e.g. `ORCID:0000-0002-0736-9199`).
+    Orcid,
+    /// A book (e.g. `ISBN:978-0199334193`).
+    Isbn,
+    /// A digital object identifier (e.g. `DOI:10.1093/nar/gkaa1043`).
+    Doi,
+    /// A term of an ontology or a terminology with the CURIE prefix (e.g. `UMLS`).
+    OntologyTerm(String),
+    /// A web page or other resource.
+    Other,
+}
+
+impl XrefKind {
+    /// Classify the `xref` by its CURIE prefix.
+    ///
+    /// The well-known prefixes are compared case-insensitively.
+    /// The prefixes of URLs, such as `http` and `https`, are classified as [`XrefKind::Other`].
+    pub fn classify(xref: &TermId) -> XrefKind {
+        let prefix = xref.prefix();
+        match prefix.to_ascii_uppercase().as_str() {
+            "PMID" => XrefKind::Pmid,
+            "ORCID" => XrefKind::Orcid,
+            "ISBN" | "ISBN-10" | "ISBN-13" => XrefKind::Isbn,
+            "DOI" => XrefKind::Doi,
+            "HTTP" | "HTTPS" | "FTP" | "URL" => XrefKind::Other,
+            _ => XrefKind::OntologyTerm(prefix.to_string()),
+        }
+    }
+
+    /// Test if the xref points to literature, such as an article or a book.
+    pub fn is_literature(&self) -> bool {
+        matches!(self, XrefKind::Pmid | XrefKind::Isbn | XrefKind::Doi)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let cases = [
+            ("PMID:12345", XrefKind::Pmid),
+            ("pmid:12345", XrefKind::Pmid),
+            ("ORCID:0000-0002-0736-9199", XrefKind::Orcid),
+            ("ISBN:978-0199334193", XrefKind::Isbn),
+            ("DOI:10.1093/nar/gkaa1043", XrefKind::Doi),
+            ("https://example.org", XrefKind::Other),
+            ("MSH:D054119", XrefKind::OntologyTerm("MSH".into())),
+            ("HP:0001250", XrefKind::OntologyTerm("HP".into())),
+        ];
+
+        for (curie, expected) in cases {
+            let xref = TermId::from_str(curie).unwrap();
+            assert_eq!(XrefKind::classify(&xref), expected, "{curie}");
+        }
+    }
+}
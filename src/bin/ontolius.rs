@@ -0,0 +1,144 @@
+//! The `ontolius` command line interface.
+//!
+//! Run `ontolius validate <ONTOLOGY> <TABLE> <COLUMN> [--label-column <LABEL_COLUMN>] [--prefix <PREFIX>]... [--tsv]`
+//! to validate the CURIEs of the `COLUMN` of the CSV `TABLE` against the Obographs `ONTOLOGY`.
+//! The findings are written to the standard output as tab-separated values
+//! and the process exits with status `1` if any issues were found.
+//!
+//! Run `ontolius sample <ONTOLOGY> <OUTPUT> <N_TERMS> <SEED>...` to write a reproducible sample
+//! of about `N_TERMS` terms around the `SEED` terms of the Obographs `ONTOLOGY`
+//! into the Obographs `OUTPUT`, e.g. `hp.small.json.gz`. The output is gzipped if it ends with `.gz`.
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use curie_util::TrieCurieUtil;
+use ontolius::base::term::simple::SimpleMinimalTerm;
+use ontolius::io::obographs::{write_subgraph, ObographsParser};
+use ontolius::ontology::csr::CsrOntology;
+use ontolius::prelude::*;
+use ontolius::validate::CurieColumnValidator;
+
+const USAGE: &str = "Usage: ontolius validate <ONTOLOGY> <TABLE> <COLUMN> [--label-column <LABEL_COLUMN>] [--prefix <PREFIX>]... [--tsv]
+       ontolius sample <ONTOLOGY> <OUTPUT> <N_TERMS> <SEED>...";
+
+fn main() -> ExitCode {
+    let args: Vec<_> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("validate") => match validate(&args[1..]) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::from(1),
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::from(2)
+            }
+        },
+        Some("sample") => match sample(&args[1..]) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::from(2)
+            }
+        },
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Run the validation and return `true` if no issues were found.
+fn validate(args: &[String]) -> Result<bool, String> {
+    let mut positional = vec![];
+    let mut label_column = None;
+    let mut prefixes = vec![];
+    let mut tsv = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--label-column" => label_column = Some(args.next().ok_or(USAGE)?),
+            "--prefix" => prefixes.push(args.next().ok_or(USAGE)?),
+            "--tsv" => tsv = true,
+            _ => positional.push(arg),
+        }
+    }
+    let [ontology, table, column] = positional[..] else {
+        return Err(USAGE.to_string());
+    };
+
+    let loader = OntologyLoaderBuilder::new()
+        .parser(ObographsParser::new(TrieCurieUtil::default()))
+        .build();
+    let ontology: CsrOntology<usize, SimpleMinimalTerm> =
+        loader.load_from_path(ontology).map_err(|e| e.to_string())?;
+
+    let mut validator = CurieColumnValidator::new(column);
+    if let Some(label_column) = label_column {
+        validator = validator.label_column(label_column);
+    }
+    if !prefixes.is_empty() {
+        validator = validator.prefixes(prefixes);
+    }
+    if tsv {
+        validator = validator.delimiter(b'\t');
+    }
+
+    let file = File::open(table).map_err(|e| format!("Cannot open {table}: {e}"))?;
+    let findings = validator
+        .validate(file, &ontology)
+        .map_err(|e| e.to_string())?;
+
+    println!("row\tcurie\tissue");
+    for finding in &findings {
+        println!(
+            "{}\t{}\t{}",
+            finding.row(),
+            finding.curie(),
+            finding.issue()
+        );
+    }
+    Ok(findings.is_empty())
+}
+
+/// Write a sample of the ontology around the seed terms.
+fn sample(args: &[String]) -> Result<(), String> {
+    let [ontology, output, n_terms, seeds @ ..] = args else {
+        return Err(USAGE.to_string());
+    };
+    if seeds.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    let n_terms: usize = n_terms
+        .parse()
+        .map_err(|_| format!("Invalid number of terms {n_terms}"))?;
+    let seeds = seeds
+        .iter()
+        .map(|seed| TermId::from_str(seed).map_err(|_| format!("Invalid seed {seed}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let loader = OntologyLoaderBuilder::new()
+        .parser(ObographsParser::new(TrieCurieUtil::default()))
+        .build();
+    let ontology: CsrOntology<usize, SimpleMinimalTerm> =
+        loader.load_from_path(ontology).map_err(|e| e.to_string())?;
+
+    let subgraph = ontology.sample_subgraph(&seeds, n_terms);
+    let file = File::create(output).map_err(|e| format!("Cannot create {output}: {e}"))?;
+    let write: Box<dyn Write> = if output.ends_with(".gz") {
+        Box::new(GzEncoder::new(file, Compression::default()))
+    } else {
+        Box::new(BufWriter::new(file))
+    };
+    write_subgraph(&ontology, &subgraph, write).map_err(|e| e.to_string())?;
+
+    eprintln!(
+        "Wrote {} terms and {} edges to {output}",
+        subgraph.terms().len(),
+        subgraph.edges().len()
+    );
+    Ok(())
+}
@@ -0,0 +1,212 @@
+//! Annotate the [`polars`] data frames with the ontology terms.
+//!
+//! The helpers map a `String` column of CURIEs (e.g. `HP:0001166`) to the term labels,
+//! to the validity flags, or to the membership in the closure of a term,
+//! using a loaded ontology.
+//!
+//! The functions work with [`StringChunked`] arrays,
+//! and the `*_expr` functions wrap them into the lazy [`Expr`]essions.
+//! The missing CURIEs (nulls) are mapped to nulls.
+//!
+//! ## Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//! # use curie_util::TrieCurieUtil;
+//! use polars::prelude::*;
+//! use ontolius::dataframe::{closure_expr, label_expr};
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                    .expect("HPO should be loaded");
+//! let hpo = Arc::new(hpo);
+//!
+//! let cohort = df!(
+//!     "patient" => ["A", "B", "C"],
+//!     "phenotype" => [Some("HP:0001166"), Some("HP:9999999"), None],
+//! ).unwrap();
+//!
+//! let abnormality_of_limbs = TermId::from(("HP", "0040064"));
+//! let annotated = cohort
+//!     .lazy()
+//!     .with_columns([
+//!         label_expr(col("phenotype"), hpo.clone()).alias("label"),
+//!         closure_expr(col("phenotype"), hpo, abnormality_of_limbs).alias("limbs"),
+//!     ])
+//!     .collect()
+//!     .unwrap();
+//!
+//! let labels: Vec<_> = annotated.column("label").unwrap().str().unwrap().into_iter().collect();
+//! assert_eq!(labels, [Some("Arachnodactyly"), None, None]);
+//! let limbs: Vec<_> = annotated.column("limbs").unwrap().bool().unwrap().into_iter().collect();
+//! assert_eq!(limbs, [Some(true), Some(false), None]);
+//! ```
+use std::str::FromStr;
+use std::sync::Arc;
+
+use polars::prelude::*;
+
+use crate::base::{term::MinimalTerm, TermId};
+use crate::hierarchy::DescendantNodes;
+use crate::ontology::{Ontology, TermIdx};
+
+/// Map the `curies` to the labels of the terms.
+///
+/// The alternate term IDs are mapped to the labels of their primary terms.
+/// The unknown term IDs and the values that are not CURIEs are mapped to nulls.
+pub fn term_labels<O>(ontology: &O, curies: &StringChunked) -> StringChunked
+where
+    O: Ontology,
+{
+    curies
+        .iter()
+        .map(|curie| {
+            curie
+                .and_then(|curie| TermId::from_str(curie).ok())
+                .and_then(|term_id| ontology.id_to_term(&term_id))
+                .map(|term| term.name())
+        })
+        .collect::<StringChunked>()
+        .with_name(curies.name().clone())
+}
+
+/// Test if the `curies` are the primary or alternate IDs of the ontology terms.
+///
+/// The values that are not CURIEs are invalid.
+pub fn term_validity<O>(ontology: &O, curies: &StringChunked) -> BooleanChunked
+where
+    O: Ontology,
+{
+    curies
+        .iter()
+        .map(|curie| {
+            curie.map(|curie| {
+                TermId::from_str(curie)
+                    .ok()
+                    .and_then(|term_id| ontology.id_to_idx(&term_id))
+                    .is_some()
+            })
+        })
+        .collect::<BooleanChunked>()
+        .with_name(curies.name().clone())
+}
+
+/// Test if the `curies` are the IDs of the `term` or of its descendants.
+///
+/// The unknown term IDs and the values that are not CURIEs are not in the closure.
+/// All values are mapped to `false` if the `term` is not in the ontology.
+pub fn term_closure<O>(ontology: &O, curies: &StringChunked, term: &TermId) -> BooleanChunked
+where
+    O: Ontology,
+{
+    let mut closure = vec![false; ontology.len()];
+    if let Some(idx) = ontology.id_to_idx(term) {
+        let hierarchy = ontology.hierarchy();
+        for &node in std::iter::once(&idx).chain(hierarchy.descendants_of(idx)) {
+            if let Some(member) = closure.get_mut(TermIdx::index(node)) {
+                *member = true;
+            }
+        }
+    }
+
+    curies
+        .iter()
+        .map(|curie| {
+            curie.map(|curie| {
+                TermId::from_str(curie)
+                    .ok()
+                    .and_then(|term_id| ontology.id_to_idx(&term_id))
+                    .and_then(|idx| closure.get(TermIdx::index(idx)).copied())
+                    .unwrap_or(false)
+            })
+        })
+        .collect::<BooleanChunked>()
+        .with_name(curies.name().clone())
+}
+
+/// Map the `expr` with CURIEs to the term labels (see [`term_labels`]).
+pub fn label_expr<O>(expr: Expr, ontology: Arc<O>) -> Expr
+where
+    O: Ontology + Send + Sync + 'static,
+{
+    expr.map(
+        move |column| {
+            let curies = column.as_materialized_series().str()?;
+            Ok(Some(term_labels(&*ontology, curies).into_column()))
+        },
+        GetOutput::from_type(DataType::String),
+    )
+}
+
+/// Map the `expr` with CURIEs to the validity flags (see [`term_validity`]).
+pub fn validity_expr<O>(expr: Expr, ontology: Arc<O>) -> Expr
+where
+    O: Ontology + Send + Sync + 'static,
+{
+    expr.map(
+        move |column| {
+            let curies = column.as_materialized_series().str()?;
+            Ok(Some(term_validity(&*ontology, curies).into_column()))
+        },
+        GetOutput::from_type(DataType::Boolean),
+    )
+}
+
+/// Map the `expr` with CURIEs to the membership in the closure of the `term`
+/// (see [`term_closure`]).
+pub fn closure_expr<O>(expr: Expr, ontology: Arc<O>, term: TermId) -> Expr
+where
+    O: Ontology + Send + Sync + 'static,
+{
+    expr.map(
+        move |column| {
+            let curies = column.as_materialized_series().str()?;
+            Ok(Some(term_closure(&*ontology, curies, &term).into_column()))
+        },
+        GetOutput::from_type(DataType::Boolean),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_term_validity() {
+        let terms = vec![
+            SimpleMinimalTerm::new(TermId::from(("HP", "1")), "Root", vec![], false),
+            SimpleMinimalTerm::new(
+                TermId::from(("HP", "2")),
+                "Child",
+                vec![TermId::from(("HP", "3"))],
+                false,
+            ),
+        ];
+        let ontology = toy_ontology(terms, &[(1, 0)]);
+
+        let curies = StringChunked::from_iter_options(
+            "curie".into(),
+            [
+                Some("HP:2"),
+                Some("HP:3"),
+                Some("HP:4"),
+                Some("not a CURIE"),
+                None,
+            ]
+            .into_iter(),
+        );
+        let validity: Vec<_> = term_validity(&ontology, &curies).into_iter().collect();
+
+        assert_eq!(
+            validity,
+            [Some(true), Some(true), Some(false), Some(false), None]
+        );
+    }
+}
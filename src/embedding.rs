@@ -0,0 +1,419 @@
+//! Learn low-dimensional term embeddings that respect the hierarchy, e.g. as features
+//! for downstream machine learning.
+//!
+//! The module is experimental: the training algorithm and its defaults may change.
+//!
+//! [`PoincareTrainer`] learns the Poincaré embeddings (Nickel & Kiela, 2017)
+//! of the current terms, the points in the unit ball where the distance grows
+//! exponentially towards the boundary. The training pulls each term towards its ancestors
+//! and pushes it away from the other terms, hence the general terms end up near the origin,
+//! the specific terms near the boundary, and the hierarchy can be recovered from the distances
+//! and the norms of the embeddings.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! use ontolius::embedding::PoincareTrainer;
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//! use rand::{rngs::StdRng, SeedableRng};
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                    .expect("HPO should be loaded");
+//! #
+//! let embeddings = PoincareTrainer::new()
+//!                     .dim(5)
+//!                     .epochs(5)
+//!                     .train(&hpo, &mut StdRng::seed_from_u64(42));
+//!
+//! let arachnodactyly = TermId::from(("HP", "0001166"));
+//! assert_eq!(embeddings.dim(), 5);
+//! assert_eq!(embeddings.get(&arachnodactyly).map(<[f64]>::len), Some(5));
+//!
+//! // Export the embeddings as a TSV table.
+//! let mut buffer = vec![];
+//! let count = embeddings.write_tsv(&mut buffer).expect("Embeddings should be written");
+//! assert_eq!(count, embeddings.len());
+//! ```
+use std::collections::HashMap;
+use std::io::Write;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::base::term::MinimalTerm;
+use crate::base::{Identified, TermId};
+use crate::error::OntoliusError;
+use crate::hierarchy::{AncestorNodes, HierarchyIdx};
+use crate::ontology::{Ontology, TermIdx};
+
+/// The largest norm of an embedding, to keep the embeddings inside the unit ball.
+const MAX_NORM: f64 = 1. - 1e-5;
+
+/// Get the distance of the points `left` and `right` of the Poincaré ball.
+///
+/// ## Panics
+///
+/// Panics if the points have a different number of dimensions.
+pub fn poincare_distance(left: &[f64], right: &[f64]) -> f64 {
+    assert_eq!(
+        left.len(),
+        right.len(),
+        "The points must have the same dimension"
+    );
+    let alpha = 1. - dot(left, left);
+    let beta = 1. - dot(right, right);
+    let squared: f64 = left.iter().zip(right).map(|(l, r)| (l - r) * (l - r)).sum();
+    (1. + 2. * squared / (alpha * beta)).acosh()
+}
+
+fn dot(left: &[f64], right: &[f64]) -> f64 {
+    left.iter().zip(right).map(|(l, r)| l * r).sum()
+}
+
+/// Add the gradient of the distance from `theta` to `x` with respect to `theta`,
+/// scaled by `scale`, to the `gradient`.
+fn add_distance_gradient(theta: &[f64], x: &[f64], scale: f64, gradient: &mut [f64]) {
+    let theta_squared = dot(theta, theta);
+    let x_squared = dot(x, x);
+    let alpha = 1. - theta_squared;
+    let beta = 1. - x_squared;
+    let squared = theta_squared - 2. * dot(theta, x) + x_squared;
+    let gamma = 1. + 2. * squared / (alpha * beta);
+    let factor = scale * 4. / (beta * (gamma * gamma - 1.).max(1e-12).sqrt());
+    let theta_coef = (x_squared - 2. * dot(theta, x) + 1.) / (alpha * alpha);
+    for ((g, t), x) in gradient.iter_mut().zip(theta).zip(x) {
+        *g += factor * (theta_coef * t - x / alpha);
+    }
+}
+
+/// Move the point `theta` against the Euclidean `gradient`, along the Poincaré ball,
+/// and keep the point inside the ball.
+fn riemannian_step(theta: &mut [f64], gradient: &[f64], learning_rate: f64) {
+    let alpha = 1. - dot(theta, theta);
+    let scale = learning_rate * alpha * alpha / 4.;
+    for (t, g) in theta.iter_mut().zip(gradient) {
+        *t -= scale * g;
+    }
+    let norm = dot(theta, theta).sqrt();
+    if norm > MAX_NORM {
+        theta.iter_mut().for_each(|t| *t *= MAX_NORM / norm);
+    }
+}
+
+/// Trainer of the Poincaré embeddings of the ontology terms
+/// (see the [module](self) documentation).
+///
+/// By default, the trainer learns 10-dimensional embeddings in 50 epochs,
+/// with the learning rate of `0.1` and `10` negative samples per ancestor.
+/// The learning rate is reduced tenfold in the first `10` burn-in epochs
+/// to find a good angular layout before the terms move towards the boundary.
+pub struct PoincareTrainer {
+    dim: usize,
+    epochs: usize,
+    learning_rate: f64,
+    negatives: usize,
+    burn_in: usize,
+}
+
+impl Default for PoincareTrainer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PoincareTrainer {
+    pub fn new() -> Self {
+        Self {
+            dim: 10,
+            epochs: 50,
+            learning_rate: 0.1,
+            negatives: 10,
+            burn_in: 10,
+        }
+    }
+
+    /// Set the number of the dimensions of the embeddings.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `dim` is `0`.
+    #[must_use]
+    pub fn dim(mut self, dim: usize) -> Self {
+        assert!(dim > 0, "The embeddings must have at least one dimension");
+        self.dim = dim;
+        self
+    }
+
+    /// Set the number of the passes over all term-ancestor pairs.
+    #[must_use]
+    pub fn epochs(mut self, epochs: usize) -> Self {
+        self.epochs = epochs;
+        self
+    }
+
+    /// Set the learning rate of the Riemannian stochastic gradient descent.
+    #[must_use]
+    pub fn learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    /// Set the number of the terms sampled as the non-ancestors of a term for each of its ancestors.
+    #[must_use]
+    pub fn negatives(mut self, negatives: usize) -> Self {
+        self.negatives = negatives;
+        self
+    }
+
+    /// Set the number of the initial epochs with the reduced learning rate.
+    #[must_use]
+    pub fn burn_in(mut self, burn_in: usize) -> Self {
+        self.burn_in = burn_in;
+        self
+    }
+
+    /// Learn the embeddings of the current terms of the `ontology`,
+    /// with the randomness of the initialization and the sampling drawn from the `rng`.
+    pub fn train<O, R>(&self, ontology: &O, rng: &mut R) -> TermEmbeddings
+    where
+        O: Ontology,
+        R: Rng + ?Sized,
+    {
+        let hierarchy = ontology.hierarchy();
+        let current: Vec<_> = ontology
+            .iter_terms()
+            .enumerate()
+            .filter(|(_, term)| term.is_current())
+            .map(|(idx, term)| (idx, term.identifier().clone()))
+            .collect();
+        let mut rows = HashMap::with_capacity(current.len());
+        for (row, (idx, _)) in current.iter().enumerate() {
+            rows.insert(*idx, row);
+        }
+
+        // The sorted ancestor rows of each row.
+        let ancestors: Vec<Vec<usize>> = current
+            .iter()
+            .map(|&(idx, _)| {
+                let idx = <O::Idx as HierarchyIdx>::new(idx);
+                let mut ancestors: Vec<_> = hierarchy
+                    .ancestors_of(idx)
+                    .flat_map(|ancestor| rows.get(&TermIdx::index(*ancestor)).copied())
+                    .collect();
+                ancestors.sort_unstable();
+                ancestors.dedup();
+                ancestors
+            })
+            .collect();
+        let mut pairs: Vec<_> = ancestors
+            .iter()
+            .enumerate()
+            .flat_map(|(row, ancestors)| ancestors.iter().map(move |&ancestor| (row, ancestor)))
+            .collect();
+
+        let (n, dim) = (current.len(), self.dim);
+        let mut values: Vec<f64> = (0..n * dim).map(|_| rng.gen_range(-1e-3..1e-3)).collect();
+
+        let mut candidates = Vec::with_capacity(self.negatives + 1);
+        let mut distances = Vec::with_capacity(self.negatives + 1);
+        let mut gradient = vec![0.; dim];
+        let mut candidate_gradient = vec![0.; dim];
+        for epoch in 0..self.epochs {
+            let learning_rate = if epoch < self.burn_in {
+                self.learning_rate / 10.
+            } else {
+                self.learning_rate
+            };
+            pairs.shuffle(rng);
+            for &(row, ancestor) in &pairs {
+                candidates.clear();
+                candidates.push(ancestor);
+                for _ in 0..self.negatives {
+                    // A few attempts to draw a term that is neither the term nor its ancestor.
+                    let negative = (0..10).map(|_| rng.gen_range(0..n)).find(|&other| {
+                        other != row && ancestors[row].binary_search(&other).is_err()
+                    });
+                    candidates.extend(negative);
+                }
+
+                let theta = &values[row * dim..(row + 1) * dim];
+                distances.clear();
+                distances.extend(candidates.iter().map(|&candidate| {
+                    poincare_distance(theta, &values[candidate * dim..(candidate + 1) * dim])
+                }));
+                // The softmax of the negative distances, with the loss
+                // `d(u, v) + ln Σ exp(-d(u, c))` over the ancestor `v` and the negatives.
+                let min = distances.iter().copied().fold(f64::INFINITY, f64::min);
+                let total: f64 = distances.iter().map(|d| (min - d).exp()).sum();
+
+                gradient.iter_mut().for_each(|g| *g = 0.);
+                for (i, (&candidate, &distance)) in candidates.iter().zip(&distances).enumerate() {
+                    let probability = (min - distance).exp() / total;
+                    let scale = if i == 0 {
+                        1. - probability
+                    } else {
+                        -probability
+                    };
+                    let theta = &values[row * dim..(row + 1) * dim];
+                    let other = &values[candidate * dim..(candidate + 1) * dim];
+                    add_distance_gradient(theta, other, scale, &mut gradient);
+
+                    candidate_gradient.iter_mut().for_each(|g| *g = 0.);
+                    add_distance_gradient(other, theta, scale, &mut candidate_gradient);
+                    riemannian_step(
+                        &mut values[candidate * dim..(candidate + 1) * dim],
+                        &candidate_gradient,
+                        learning_rate,
+                    );
+                }
+                riemannian_step(
+                    &mut values[row * dim..(row + 1) * dim],
+                    &gradient,
+                    learning_rate,
+                );
+            }
+        }
+
+        let ids: Vec<_> = current.into_iter().map(|(_, term_id)| term_id).collect();
+        let index = ids
+            .iter()
+            .enumerate()
+            .map(|(row, term_id)| (term_id.clone(), row))
+            .collect();
+        TermEmbeddings {
+            ids,
+            index,
+            dim,
+            values,
+        }
+    }
+}
+
+/// The embeddings of the ontology terms, learned by [`PoincareTrainer`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermEmbeddings {
+    ids: Vec<TermId>,
+    index: HashMap<TermId, usize>,
+    dim: usize,
+    values: Vec<f64>,
+}
+
+impl TermEmbeddings {
+    /// Get the number of the dimensions of the embeddings.
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Get the number of the embedded terms.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Test if there are no embedded terms.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Get the embedding of the term with the `term_id`.
+    ///
+    /// Returns `None` if the term is obsolete or not in the ontology.
+    pub fn get<ID>(&self, term_id: &ID) -> Option<&[f64]>
+    where
+        ID: Identified,
+    {
+        self.index
+            .get(term_id.identifier())
+            .map(|&row| &self.values[row * self.dim..(row + 1) * self.dim])
+    }
+
+    /// Get the Poincaré distance of the embeddings of the terms.
+    ///
+    /// Returns `None` if a term has no embedding.
+    pub fn distance<L, R>(&self, left: &L, right: &R) -> Option<f64>
+    where
+        L: Identified,
+        R: Identified,
+    {
+        Some(poincare_distance(self.get(left)?, self.get(right)?))
+    }
+
+    /// Iterate over the term IDs and their embeddings, in the order of the term indices.
+    pub fn iter(&self) -> impl Iterator<Item = (&TermId, &[f64])> {
+        self.ids.iter().zip(self.values.chunks_exact(self.dim))
+    }
+
+    /// Write the embeddings as a TSV table with the term ID and the coordinates of each term,
+    /// and return the number of the written rows.
+    pub fn write_tsv<W: Write>(&self, mut write: W) -> Result<usize, OntoliusError> {
+        for (term_id, values) in self.iter() {
+            write!(write, "{term_id}").map_err(write_error)?;
+            for value in values {
+                write!(write, "\t{value}").map_err(write_error)?;
+            }
+            writeln!(write).map_err(write_error)?;
+        }
+        Ok(self.len())
+    }
+}
+
+fn write_error(e: std::io::Error) -> OntoliusError {
+    OntoliusError::Other(format!("Cannot write the embeddings: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_train() {
+        // HP:1 is the root of two subtrees, HP:2 with HP:4 and HP:5, and HP:3 with HP:6 and HP:7.
+        let terms: Vec<_> = (1..=7)
+            .map(|i| {
+                let id = i.to_string();
+                SimpleMinimalTerm::new(TermId::from(("HP", id.as_str())), "Term", vec![], false)
+            })
+            .collect();
+        let hpo = toy_ontology(terms, &[(1, 0), (2, 0), (3, 1), (4, 1), (5, 2), (6, 2)]);
+
+        let trainer = PoincareTrainer::new().dim(5).epochs(100);
+        let embeddings = trainer.train(&hpo, &mut StdRng::seed_from_u64(42));
+        assert_eq!(embeddings.len(), 7);
+        assert_eq!(
+            embeddings,
+            trainer.train(&hpo, &mut StdRng::seed_from_u64(42))
+        );
+
+        let id = |i: &str| TermId::from(("HP", i));
+        let norm = |i: &str| {
+            dot(
+                embeddings.get(&id(i)).unwrap(),
+                embeddings.get(&id(i)).unwrap(),
+            )
+        };
+        // The root is closer to the origin than the leaves.
+        assert!(norm("1") < norm("4"));
+        assert!(norm("1") < norm("7"));
+        // The leaves are closer to their parent than to the other subtree.
+        let distance = |l: &str, r: &str| embeddings.distance(&id(l), &id(r)).unwrap();
+        assert!(distance("4", "2") < distance("4", "3"));
+        assert!(distance("6", "3") < distance("6", "2"));
+        assert!(distance("4", "5") < distance("4", "6"));
+
+        let mut buffer = vec![];
+        assert_eq!(embeddings.write_tsv(&mut buffer).unwrap(), 7);
+        let tsv = String::from_utf8(buffer).unwrap();
+        assert!(tsv.starts_with("HP:1\t"));
+        assert_eq!(tsv.lines().count(), 7);
+        assert!(tsv.lines().all(|line| line.split('\t').count() == 6));
+    }
+}
@@ -0,0 +1,282 @@
+//! Search ontology terms with a persistent full-text index built with [`tantivy`].
+//!
+//! Unlike [`crate::search::Searcher`], which scores the term names in memory,
+//! [`TermIndex`] indexes the names, the synonyms, and the definitions of the current terms
+//! on disk, tolerates typos in the query, and can be reopened without the ontology.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! # use ontolius::base::term::simple::SimpleTerm;
+//! use ontolius::fulltext::TermIndex;
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::with_term_type(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, SimpleTerm> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                             .expect("HPO should be loaded");
+//! #
+//! let index = TermIndex::create_in_ram(&hpo).expect("The index should be built");
+//!
+//! // The query has a typo.
+//! let hits = index.search("arachnodactily", 5).expect("The search should succeed");
+//! assert_eq!(hits[0].0, TermId::from(("HP", "0001166")));
+//! ```
+use std::path::Path;
+use std::str::FromStr;
+
+use tantivy::collector::TopDocs;
+use tantivy::query::{BooleanQuery, BoostQuery, FuzzyTermQuery, Occur, Query, TermQuery};
+use tantivy::schema::{Field, IndexRecordOption, Schema, Value, STORED, STRING, TEXT};
+use tantivy::{Index, IndexReader, TantivyDocument, Term as IndexTerm};
+
+use crate::base::term::{MinimalTerm, SynonymAware, Term};
+use crate::base::{Identified, TermId};
+use crate::error::OntoliusError;
+use crate::ontology::Ontology;
+
+/// The memory budget of the index writer, in bytes.
+const WRITER_MEMORY: usize = 50_000_000;
+
+/// The boost of the name matches over the synonym and the definition matches.
+const NAME_BOOST: f32 = 2.;
+
+/// The fields of the term documents.
+#[derive(Debug, Clone, Copy)]
+struct Fields {
+    id: Field,
+    name: Field,
+    synonym: Field,
+    definition: Field,
+}
+
+impl Fields {
+    fn schema() -> (Schema, Fields) {
+        let mut builder = Schema::builder();
+        let fields = Fields {
+            id: builder.add_text_field("id", STRING | STORED),
+            name: builder.add_text_field("name", TEXT),
+            synonym: builder.add_text_field("synonym", TEXT),
+            definition: builder.add_text_field("definition", TEXT),
+        };
+        (builder.build(), fields)
+    }
+
+    fn from_schema(schema: &Schema) -> Result<Fields, OntoliusError> {
+        let field = |name: &str| {
+            schema
+                .get_field(name)
+                .map_err(|_| OntoliusError::Other(format!("The index has no `{name}` field")))
+        };
+        Ok(Fields {
+            id: field("id")?,
+            name: field("name")?,
+            synonym: field("synonym")?,
+            definition: field("definition")?,
+        })
+    }
+}
+
+/// A full-text index of the names, the synonyms, and the definitions of the current terms.
+pub struct TermIndex {
+    reader: IndexReader,
+    fields: Fields,
+}
+
+impl TermIndex {
+    /// Index the current terms of the `ontology` into a new index in the `directory`.
+    ///
+    /// The directory must exist and must not contain an index.
+    pub fn create<O, P>(ontology: &O, directory: P) -> Result<Self, OntoliusError>
+    where
+        O: Ontology,
+        O::T: Term + SynonymAware,
+        P: AsRef<Path>,
+    {
+        let (schema, fields) = Fields::schema();
+        let index = Index::create_in_dir(directory, schema).map_err(index_error)?;
+        Self::populate(index, fields, ontology)
+    }
+
+    /// Index the current terms of the `ontology` into a new index in memory.
+    pub fn create_in_ram<O>(ontology: &O) -> Result<Self, OntoliusError>
+    where
+        O: Ontology,
+        O::T: Term + SynonymAware,
+    {
+        let (schema, fields) = Fields::schema();
+        Self::populate(Index::create_in_ram(schema), fields, ontology)
+    }
+
+    /// Open the index created by [`TermIndex::create`] in the `directory`.
+    pub fn open<P>(directory: P) -> Result<Self, OntoliusError>
+    where
+        P: AsRef<Path>,
+    {
+        let index = Index::open_in_dir(directory).map_err(index_error)?;
+        let fields = Fields::from_schema(&index.schema())?;
+        Ok(Self {
+            reader: index.reader().map_err(index_error)?,
+            fields,
+        })
+    }
+
+    fn populate<O>(index: Index, fields: Fields, ontology: &O) -> Result<Self, OntoliusError>
+    where
+        O: Ontology,
+        O::T: Term + SynonymAware,
+    {
+        let mut writer = index.writer(WRITER_MEMORY).map_err(index_error)?;
+        for term in ontology.iter_terms().filter(|term| term.is_current()) {
+            let mut document = TantivyDocument::default();
+            document.add_text(fields.id, term.identifier().to_string());
+            document.add_text(fields.name, term.name());
+            for synonym in term.iter_synonyms() {
+                document.add_text(fields.synonym, synonym.name());
+            }
+            if let Some(definition) = term.definition() {
+                document.add_text(fields.definition, definition);
+            }
+            writer.add_document(document).map_err(index_error)?;
+        }
+        writer.commit().map_err(index_error)?;
+
+        Ok(Self {
+            reader: index.reader().map_err(index_error)?,
+            fields,
+        })
+    }
+
+    /// Find at most `limit` terms matching all words of the `query`, from the best match.
+    ///
+    /// The words match the term names, the synonyms, or the definitions,
+    /// with a tolerance of one typo in the words of 5 or more characters
+    /// and two typos in the words of 9 or more characters.
+    /// The name matches score higher than the synonym and the definition matches.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<(TermId, f32)>, OntoliusError> {
+        let words: Vec<_> = query
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        if words.is_empty() || limit == 0 {
+            return Ok(vec![]);
+        }
+        let query = BooleanQuery::new(
+            words
+                .iter()
+                .map(|word| (Occur::Must, self.word_query(word)))
+                .collect(),
+        );
+
+        let searcher = self.reader.searcher();
+        let top_docs = searcher
+            .search(&query, &TopDocs::with_limit(limit))
+            .map_err(index_error)?;
+        top_docs
+            .into_iter()
+            .map(|(score, address)| {
+                let document: TantivyDocument = searcher.doc(address).map_err(index_error)?;
+                document
+                    .get_first(self.fields.id)
+                    .and_then(|value| value.as_str())
+                    .and_then(|id| TermId::from_str(id).ok())
+                    .map(|term_id| (term_id, score))
+                    .ok_or_else(|| OntoliusError::Other("Indexed term has no valid ID".into()))
+            })
+            .collect()
+    }
+
+    /// Match the `word` in any of the text fields.
+    fn word_query(&self, word: &str) -> Box<dyn Query> {
+        let distance = match word.chars().count() {
+            0..=4 => 0,
+            5..=8 => 1,
+            _ => 2,
+        };
+        let field_query = |field: Field| -> Box<dyn Query> {
+            let term = IndexTerm::from_field_text(field, word);
+            match distance {
+                0 => Box::new(TermQuery::new(term, IndexRecordOption::WithFreqs)),
+                distance => Box::new(FuzzyTermQuery::new(term, distance, true)),
+            }
+        };
+        Box::new(BooleanQuery::new(vec![
+            (
+                Occur::Should,
+                Box::new(BoostQuery::new(field_query(self.fields.name), NAME_BOOST)),
+            ),
+            (Occur::Should, field_query(self.fields.synonym)),
+            (Occur::Should, field_query(self.fields.definition)),
+        ]))
+    }
+}
+
+fn index_error(e: tantivy::TantivyError) -> OntoliusError {
+    OntoliusError::Other(format!("Full-text index error: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleTerm;
+    use crate::base::term::{Synonym, SynonymCategory};
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_search() {
+        let terms = vec![
+            SimpleTerm::new(TermId::from(("HP", "0000001")), "All", vec![], false),
+            SimpleTerm::new(
+                TermId::from(("HP", "0001166")),
+                "Arachnodactyly",
+                vec![],
+                false,
+            )
+            .with_synonyms(vec![Synonym::new(
+                "Spider fingers",
+                SynonymCategory::Exact,
+                vec![],
+            )]),
+            SimpleTerm::new(TermId::from(("HP", "0001250")), "Seizure", vec![], false)
+                .with_definition(
+                    "A seizure is an intermittent abnormality of nervous system physiology.",
+                ),
+            SimpleTerm::new(TermId::from(("HP", "0000002")), "Seizures", vec![], true),
+        ];
+        let ontology = toy_ontology(terms, &[(1, 0), (2, 0)]);
+        let directory =
+            std::env::temp_dir().join(format!("ontolius-fulltext-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let index = TermIndex::create(&ontology, &directory).unwrap();
+        let ids = |hits: Vec<(TermId, f32)>| -> Vec<String> {
+            hits.into_iter()
+                .map(|(term_id, _)| term_id.to_string())
+                .collect()
+        };
+        assert_eq!(
+            ids(index.search("spider finger", 10).unwrap()),
+            ["HP:0001166"]
+        );
+        assert_eq!(ids(index.search("seizrue", 10).unwrap()), ["HP:0001250"]);
+        assert_eq!(
+            ids(index.search("nervous system", 10).unwrap()),
+            ["HP:0001250"]
+        );
+        assert!(index.search("spider seizure", 10).unwrap().is_empty());
+        assert!(index.search("  ", 10).unwrap().is_empty());
+        drop(index);
+
+        let index = TermIndex::open(&directory).unwrap();
+        assert_eq!(
+            ids(index.search("arachnodactyly", 10).unwrap()),
+            ["HP:0001166"]
+        );
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,325 @@
+//! Expose an ontology as a GraphQL schema using `async-graphql`.
+//!
+//! The schema supports getting a term by its primary or alternative ID,
+//! searching the terms by name, paging through the current terms and the hierarchy edges,
+//! and navigating the hierarchy from a term to its parents, children, ancestors, or descendants.
+//! The pages and the search results have at most [`MAX_PAGE_LIMIT`] items.
+//!
+//! Serve the schema with any web framework supported by `async-graphql`.
+//!
+//! ## Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! # use curie_util::TrieCurieUtil;
+//! use ontolius::graphql::build_schema;
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                    .expect("HPO should be loaded");
+//! #
+//! let schema = build_schema(Arc::new(hpo));
+//!
+//! let sdl = schema.sdl();
+//! assert!(sdl.contains("term(id: String!): Term"));
+//! ```
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_graphql::{EmptyMutation, EmptySubscription, Object, Result, Schema};
+
+use crate::base::term::{AltTermIdAware, MinimalTerm};
+use crate::base::{Identified, TermId};
+use crate::hierarchy::{
+    AncestorNodes, ChildNodes, DescendantNodes, HierarchyIdx, OntologyHierarchy, ParentNodes,
+};
+use crate::ontology::{Ontology, TermIdx};
+use crate::search::Searcher;
+
+/// The maximum number of the items in a page or in the search results.
+///
+/// A greater requested limit is clamped to this one.
+pub const MAX_PAGE_LIMIT: usize = 1000;
+
+/// The GraphQL schema of an ontology.
+pub type OntologySchema<O> = Schema<QueryRoot<O>, EmptyMutation, EmptySubscription>;
+
+/// Build a GraphQL schema for querying the `ontology`.
+pub fn build_schema<O>(ontology: Arc<O>) -> OntologySchema<O>
+where
+    O: Ontology + Send + Sync + 'static,
+{
+    Schema::new(QueryRoot { ontology }, EmptyMutation, EmptySubscription)
+}
+
+/// The root of the GraphQL queries.
+pub struct QueryRoot<O> {
+    ontology: Arc<O>,
+}
+
+#[Object]
+impl<O> QueryRoot<O>
+where
+    O: Ontology + Send + Sync + 'static,
+{
+    /// Get the ontology version.
+    async fn version(&self) -> &str {
+        self.ontology.version()
+    }
+
+    /// Get the root term of the ontology.
+    async fn root(&self) -> GqlTerm<O> {
+        GqlTerm::new(&self.ontology, *self.ontology.hierarchy().root())
+    }
+
+    /// Get a term by its primary or alternative ID (e.g. `HP:0001250`).
+    async fn term(&self, id: String) -> Result<Option<GqlTerm<O>>> {
+        let term_id = TermId::from_str(&id)?;
+        Ok(self
+            .ontology
+            .id_to_idx(&term_id)
+            .map(|idx| GqlTerm::new(&self.ontology, idx)))
+    }
+
+    /// Get at most `limit` current terms whose names match the `query`, the best match first.
+    ///
+    /// See [`Searcher::search`] for the ranking.
+    async fn search(
+        &self,
+        query: String,
+        #[graphql(default = 10)] limit: usize,
+    ) -> Vec<GqlTerm<O>> {
+        Searcher::new(self.ontology.as_ref())
+            .search(&query, limit.min(MAX_PAGE_LIMIT))
+            .into_iter()
+            .map(|hit| GqlTerm::new(&self.ontology, hit.idx()))
+            .collect()
+    }
+
+    /// Get a page of the current terms.
+    async fn terms(
+        &self,
+        #[graphql(default = 0)] offset: usize,
+        #[graphql(default = 100)] limit: usize,
+    ) -> Vec<GqlTerm<O>> {
+        self.ontology
+            .iter_terms()
+            .enumerate()
+            .filter(|(_, term)| term.is_current())
+            .skip(offset)
+            .take(limit.min(MAX_PAGE_LIMIT))
+            .map(|(idx, _)| GqlTerm::new(&self.ontology, HierarchyIdx::new(idx)))
+            .collect()
+    }
+
+    /// Get a page of the `is_a` edges of the hierarchy.
+    async fn edges(
+        &self,
+        #[graphql(default = 0)] offset: usize,
+        #[graphql(default = 100)] limit: usize,
+    ) -> Vec<GqlEdge<O>> {
+        let hierarchy = self.ontology.hierarchy();
+        (0..self.ontology.len())
+            .map(HierarchyIdx::new)
+            .flat_map(|child| {
+                hierarchy
+                    .parents_of(child)
+                    .map(move |&parent| (child, parent))
+            })
+            .skip(offset)
+            .take(limit.min(MAX_PAGE_LIMIT))
+            .map(|(child, parent)| GqlEdge {
+                child: GqlTerm::new(&self.ontology, child),
+                parent: GqlTerm::new(&self.ontology, parent),
+            })
+            .collect()
+    }
+}
+
+/// An ontology term.
+pub struct GqlTerm<O> {
+    ontology: Arc<O>,
+    idx: usize,
+}
+
+impl<O> GqlTerm<O>
+where
+    O: Ontology,
+{
+    fn new(ontology: &Arc<O>, idx: O::Idx) -> Self {
+        Self {
+            ontology: Arc::clone(ontology),
+            idx: TermIdx::index(idx),
+        }
+    }
+
+    fn term(&self) -> &O::T {
+        self.ontology
+            .idx_to_term(self.term_idx())
+            .expect("Term index should be valid")
+    }
+
+    fn term_idx(&self) -> O::Idx {
+        HierarchyIdx::new(self.idx)
+    }
+
+    fn wrap<'a>(&self, indices: impl Iterator<Item = &'a O::Idx>) -> Vec<GqlTerm<O>>
+    where
+        O::Idx: 'a,
+    {
+        indices
+            .map(|&idx| GqlTerm::new(&self.ontology, idx))
+            .collect()
+    }
+}
+
+#[Object(name = "Term")]
+impl<O> GqlTerm<O>
+where
+    O: Ontology + Send + Sync + 'static,
+{
+    /// The primary term ID (e.g. `HP:0001250`).
+    async fn id(&self) -> String {
+        self.term().identifier().to_string()
+    }
+
+    /// The term name (e.g. `Seizure`).
+    async fn name(&self) -> &str {
+        self.term().name()
+    }
+
+    /// The alternative term IDs.
+    async fn alt_ids(&self) -> Vec<String> {
+        self.term()
+            .iter_alt_term_ids()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// `true` if the term is obsolete.
+    async fn is_obsolete(&self) -> bool {
+        self.term().is_obsolete()
+    }
+
+    /// The parent terms.
+    async fn parents(&self) -> Vec<GqlTerm<O>> {
+        self.wrap(self.ontology.hierarchy().parents_of(self.term_idx()))
+    }
+
+    /// The child terms.
+    async fn children(&self) -> Vec<GqlTerm<O>> {
+        self.wrap(self.ontology.hierarchy().children_of(self.term_idx()))
+    }
+
+    /// The ancestor terms.
+    async fn ancestors(&self) -> Vec<GqlTerm<O>> {
+        self.wrap(self.ontology.hierarchy().ancestors_of(self.term_idx()))
+    }
+
+    /// The descendant terms.
+    async fn descendants(&self) -> Vec<GqlTerm<O>> {
+        self.wrap(self.ontology.hierarchy().descendants_of(self.term_idx()))
+    }
+}
+
+/// An `is_a` edge of the ontology hierarchy.
+pub struct GqlEdge<O> {
+    child: GqlTerm<O>,
+    parent: GqlTerm<O>,
+}
+
+#[Object(name = "Edge")]
+impl<O> GqlEdge<O>
+where
+    O: Ontology + Send + Sync + 'static,
+{
+    /// The subject of the `is_a` relationship.
+    async fn child(&self) -> &GqlTerm<O> {
+        &self.child
+    }
+
+    /// The object of the `is_a` relationship.
+    async fn parent(&self) -> &GqlTerm<O> {
+        &self.parent
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curie_util::TrieCurieUtil;
+
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::io::{obographs::ObographsParser, OntologyLoaderBuilder};
+    use crate::ontology::csr::CsrOntology;
+    use crate::test_util::toy_ontology;
+
+    #[tokio::test]
+    async fn test_query_term() {
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .build();
+        let hpo: CsrOntology<usize, _> = loader
+            .load_from_path("resources/hp.small.json.gz")
+            .expect("HPO should be loaded");
+        let schema = build_schema(Arc::new(hpo));
+
+        let response = schema
+            .execute(r#"{ term(id: "HP:0001166") { name parents { name } } }"#)
+            .await;
+
+        assert!(response.errors.is_empty());
+        let data = response.data.into_json().expect("Data should be JSON");
+        assert_eq!(data["term"]["name"], "Arachnodactyly");
+        assert_eq!(data["term"]["parents"].as_array().map(Vec::len), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_search_and_page_limit() {
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .build();
+        let hpo: CsrOntology<usize, _> = loader
+            .load_from_path("resources/hp.small.json.gz")
+            .expect("HPO should be loaded");
+        let schema = build_schema(Arc::new(hpo));
+
+        let response = schema
+            .execute(r#"{ search(query: "arachnodactyly", limit: 1) { id name } }"#)
+            .await;
+        assert!(response.errors.is_empty());
+        let data = response.data.into_json().expect("Data should be JSON");
+        assert_eq!(data["search"][0]["id"], "HP:0001166");
+        assert_eq!(data["search"].as_array().map(Vec::len), Some(1));
+
+        // A chain with more edges than fit in a page.
+        let terms: Vec<_> = (0..MAX_PAGE_LIMIT + 2)
+            .map(|i| {
+                let id = i.to_string();
+                SimpleMinimalTerm::new(TermId::from(("HP", id.as_str())), "Term", vec![], false)
+            })
+            .collect();
+        let edges: Vec<_> = (1..terms.len()).map(|i| (i, i - 1)).collect();
+        let schema = build_schema(Arc::new(toy_ontology(terms, &edges)));
+
+        for query in [
+            r#"{ edges(limit: 1000000) { child { id } } }"#,
+            r#"{ terms(limit: 1000000) { id } }"#,
+        ] {
+            let response = schema.execute(query).await;
+            assert!(response.errors.is_empty());
+            let data = response.data.into_json().expect("Data should be JSON");
+            let items = data.as_object().and_then(|data| data.values().next());
+            assert_eq!(
+                items.and_then(|items| items.as_array()).map(Vec::len),
+                Some(MAX_PAGE_LIMIT)
+            );
+        }
+    }
+}
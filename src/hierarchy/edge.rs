@@ -5,6 +5,7 @@ use super::HierarchyIdx;
 
 /// A relationship between the ontology concepts.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Relationship {
     /// Subject is the parent of the object.
     Parent,
@@ -19,6 +20,7 @@ pub enum Relationship {
 /// * [`Relationship`] with one of supported relationships
 /// * `I` with the index of the destination term
 #[derive(Debug, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GraphEdge<I: HierarchyIdx> {
     pub sub: I,
     pub pred: Relationship,
@@ -8,8 +8,12 @@
 //! Check out the [`OntologyHierarchy`] documentation
 //! for more info on the provided functionality.
 mod edge;
+mod page;
+
+use std::collections::BTreeSet;
 
 pub use edge::{GraphEdge, Relationship};
+pub use page::{Page, Paginate};
 
 /// Trait for types that can provide the child nodes of an ontology node.
 pub trait ChildNodes {
@@ -28,9 +32,14 @@ pub trait ChildNodes {
         self.children_of(obj).any(|&child| child == sub)
     }
 
+    /// Get the number of children of `node`.
+    fn child_count(&self, node: Self::I) -> usize {
+        self.children_of(node).count()
+    }
+
     /// Test if `node` is a leaf, i.e. a node with no child nodes.
     fn is_leaf(&self, node: Self::I) -> bool {
-        self.children_of(node).count() == 0
+        self.child_count(node) == 0
     }
 }
 
@@ -45,6 +54,11 @@ pub trait DescendantNodes {
 
     /// Returns an iterator of all nodes which are descendants of `node`.
     fn descendants_of(&self, node: Self::I) -> Self::DescendantIter<'_>;
+
+    /// Get the number of the descendants of `node`.
+    fn count_descendants(&self, node: Self::I) -> usize {
+        self.descendants_of(node).count()
+    }
 }
 
 /// Trait for types that can provide the parent nodes of an ontology node.
@@ -63,6 +77,93 @@ pub trait ParentNodes {
     fn is_parent_of(&self, sub: Self::I, obj: Self::I) -> bool {
         self.parents_of(obj).any(|&parent| parent == sub)
     }
+
+    /// Get the number of parents of `node`.
+    fn parent_count(&self, node: Self::I) -> usize {
+        self.parents_of(node).count()
+    }
+
+    /// Generalize `node` by moving `levels` hops up the hierarchy.
+    ///
+    /// Returns the sorted nodes whose shortest path distance from `node` is `levels`.
+    /// The result can include more than one node, because a node can have several parents.
+    ///
+    /// If there are no nodes `levels` hops above `node`, the most distant ancestors
+    /// (e.g. the root) are returned instead. Generalizing by `0` levels returns `node`.
+    fn generalize(&self, node: Self::I, levels: usize) -> Vec<Self::I> {
+        generalization_levels(self, node, Some(levels))
+            .pop()
+            .expect("Generalization levels should include at least the `node`")
+    }
+
+    /// Coarsen `node` into a randomly chosen ancestor.
+    ///
+    /// The number of levels is chosen uniformly from `1` to the maximal number of levels
+    /// that `node` can be generalized by, and the ancestor is chosen uniformly
+    /// from the nodes of that level (see [`ParentNodes::generalize`]).
+    ///
+    /// A node with no parents (e.g. the root) is returned as is.
+    #[cfg(feature = "rand")]
+    fn fuzz<R>(&self, node: Self::I, rng: &mut R) -> Self::I
+    where
+        R: rand::Rng + ?Sized,
+    {
+        use rand::seq::SliceRandom;
+
+        let levels = generalization_levels(self, node, None);
+        let level = if levels.len() > 1 {
+            rng.gen_range(1..levels.len())
+        } else {
+            0
+        };
+        *levels[level]
+            .choose(rng)
+            .expect("Generalization level should not be empty")
+    }
+}
+
+/// Get the nodes at increasing shortest path distance above `node`,
+/// starting with `node` itself at index `0`.
+///
+/// The search stops after `max_levels` levels, if provided, or once there are no more parents.
+fn generalization_levels<H>(hierarchy: &H, node: H::I, max_levels: Option<usize>) -> Vec<Vec<H::I>>
+where
+    H: ParentNodes + ?Sized,
+{
+    bfs_levels(node, max_levels, |current| hierarchy.parents_of(current))
+}
+
+/// Breadth-first search from `node`, grouping the visited nodes by their shortest path distance
+/// from `node`. The first level includes `node` only.
+///
+/// `neighbors` provides the nodes adjacent to a node, e.g. the parents or children.
+/// The search stops after `max_levels` levels, if provided, or once there are no more nodes to visit.
+pub(crate) fn bfs_levels<'a, I, F, N>(node: I, max_levels: Option<usize>, neighbors: F) -> Vec<Vec<I>>
+where
+    I: HierarchyIdx + 'a,
+    F: Fn(I) -> N,
+    N: Iterator<Item = &'a I>,
+{
+    let mut seen = BTreeSet::from([node]);
+    let mut levels = vec![vec![node]];
+
+    while max_levels.is_none_or(|max| levels.len() <= max) {
+        let mut next = BTreeSet::new();
+        for &current in levels.last().expect("Levels should not be empty") {
+            for &neighbor in neighbors(current) {
+                if seen.insert(neighbor) {
+                    next.insert(neighbor);
+                }
+            }
+        }
+
+        if next.is_empty() {
+            break;
+        }
+        levels.push(next.into_iter().collect());
+    }
+
+    levels
 }
 
 /// Trait for types that can provide the ancestor nodes of an ontology node.
@@ -77,6 +178,11 @@ pub trait AncestorNodes {
     /// Returns an iterator of all nodes which are ancestors of `node`.
     fn ancestors_of(&self, node: Self::I) -> Self::AncestorIter<'_>;
 
+    /// Get the number of the ancestors of `node`.
+    fn count_ancestors(&self, node: Self::I) -> usize {
+        self.ancestors_of(node).count()
+    }
+
     /// Test if `sub` is an ancestor of `obj`.
     fn is_ancestor_of(&self, sub: Self::I, obj: Self::I) -> bool {
         self.ancestors_of(obj).any(|&anc| anc == sub)
@@ -107,6 +213,47 @@ pub trait OntologyHierarchy:
     // TODO: augment a container with descendants & self
 
     fn subhierarchy(&self, subroot_idx: Self::HI) -> Self;
+
+    /// Get the neighborhood of `node`, i.e. the nodes that are at most `up` hops above
+    /// and at most `down` hops below the `node`.
+    ///
+    /// The sorted neighborhood includes the `node` itself.
+    /// Use the neighborhood e.g. to build a context subgraph for visualization.
+    fn neighborhood(&self, node: Self::HI, up: usize, down: usize) -> Vec<Self::HI> {
+        let ancestors = bfs_levels(node, Some(up), |current| self.parents_of(current));
+        let descendants = bfs_levels(node, Some(down), |current| self.children_of(current));
+
+        let neighborhood: BTreeSet<_> = ancestors
+            .into_iter()
+            .chain(descendants)
+            .flatten()
+            .collect();
+        neighborhood.into_iter().collect()
+    }
+
+    /// Iterate over the parents and children of `node`, ignoring the edge direction.
+    ///
+    /// The parents are yielded before the children.
+    /// Use the iterator e.g. for the undirected graph algorithms, such as finding connected components.
+    fn iter_neighbors(&self, node: Self::HI) -> impl Iterator<Item = &Self::HI> {
+        self.parents_of(node).chain(self.children_of(node))
+    }
+
+    /// Iterate over the parents and children of `node`, tagged with the [`Relationship`]
+    /// of the neighbor to the `node`.
+    ///
+    /// For instance, a parent of `node` is tagged with [`Relationship::Parent`].
+    fn iter_tagged_neighbors(
+        &self,
+        node: Self::HI,
+    ) -> impl Iterator<Item = (Relationship, &Self::HI)> {
+        self.parents_of(node)
+            .map(|parent| (Relationship::Parent, parent))
+            .chain(
+                self.children_of(node)
+                    .map(|child| (Relationship::Child, child)),
+            )
+    }
 }
 
 /// The implementors can be used to index the [`super::OntologyHierarchy`].
@@ -0,0 +1,149 @@
+use std::collections::BinaryHeap;
+
+use super::HierarchyIdx;
+
+/// A page of ontology node indices sorted in ascending order.
+///
+/// The last index of the page acts as a cursor for getting the next page.
+/// See [`Paginate`] for more info.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<I> {
+    items: Vec<I>,
+    next: Option<I>,
+}
+
+impl<I: HierarchyIdx> Page<I> {
+    /// Get the indices of the page.
+    pub fn items(&self) -> &[I] {
+        &self.items
+    }
+
+    /// Get the cursor for getting the next page
+    /// or `None` if this is the last page.
+    pub fn next_cursor(&self) -> Option<I> {
+        self.next
+    }
+
+    /// Test if this is the last page.
+    pub fn is_last(&self) -> bool {
+        self.next.is_none()
+    }
+
+    /// Consume the page and return the indices.
+    pub fn into_items(self) -> Vec<I> {
+        self.items
+    }
+}
+
+/// Adaptor for splitting the node indices into pages.
+///
+/// The pages are stable - the indices are sorted in ascending order
+/// and a page is identified by the cursor, the last index of the previous page.
+/// Therefore, a service backend can get the next page without keeping
+/// any state between the requests.
+///
+/// Getting a page needs a single pass over the iterator and keeps
+/// at most `limit` indices in memory.
+///
+/// ## Examples
+///
+/// Paginate the descendants of a node:
+///
+/// ```
+/// use ontolius::hierarchy::Paginate;
+///
+/// // E.g. the result of `hierarchy.descendants_of(idx)`.
+/// let descendants = [7, 2, 5, 3, 9];
+///
+/// let first = descendants.iter().page(None, 2);
+/// assert_eq!(first.items(), &[2, 3]);
+///
+/// let second = descendants.iter().page(first.next_cursor(), 2);
+/// assert_eq!(second.items(), &[5, 7]);
+///
+/// let third = descendants.iter().page(second.next_cursor(), 2);
+/// assert_eq!(third.items(), &[9]);
+/// assert!(third.is_last());
+/// ```
+pub trait Paginate<I: HierarchyIdx> {
+    /// Get a page with at most `limit` smallest indices that are greater than the `after` cursor.
+    ///
+    /// Use `None` as the cursor to get the first page.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is `0`, since an empty page cannot advance the cursor.
+    fn page(self, after: Option<I>, limit: usize) -> Page<I>;
+}
+
+impl<'a, I, T> Paginate<I> for T
+where
+    I: HierarchyIdx + 'a,
+    T: Iterator<Item = &'a I>,
+{
+    fn page(self, after: Option<I>, limit: usize) -> Page<I> {
+        assert!(limit > 0, "The page limit must be positive");
+        let mut heap = BinaryHeap::with_capacity(limit + 1);
+        let mut has_more = false;
+
+        for &item in self.filter(|&&item| after.is_none_or(|cursor| item > cursor)) {
+            heap.push(item);
+            if heap.len() > limit {
+                // Drop the greatest index.
+                heap.pop();
+                has_more = true;
+            }
+        }
+
+        let items = heap.into_sorted_vec();
+        let next = if has_more {
+            items.last().copied()
+        } else {
+            None
+        };
+
+        Page { items, next }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pages_cover_all_items() {
+        let items = [8, 1, 6, 3, 0, 4, 2, 7, 5];
+
+        let mut cursor = None;
+        let mut seen = vec![];
+        loop {
+            let page = items.iter().page(cursor, 4);
+            assert!(page.items().len() <= 4);
+            seen.extend_from_slice(page.items());
+            if page.is_last() {
+                break;
+            }
+            cursor = page.next_cursor();
+        }
+
+        assert_eq!(seen, (0..9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_page_after_last_item() {
+        let items: [u16; 3] = [1, 2, 3];
+
+        let page = items.iter().page(Some(3), 10);
+
+        assert!(page.items().is_empty());
+        assert!(page.is_last());
+    }
+
+    #[test]
+    #[should_panic(expected = "The page limit must be positive")]
+    fn test_zero_limit_is_rejected() {
+        let items: [u16; 3] = [1, 2, 3];
+
+        items.iter().page(None, 0);
+    }
+}
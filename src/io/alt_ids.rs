@@ -0,0 +1,255 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::base::{term::MinimalTerm, TermId};
+use crate::hierarchy::HierarchyIdx;
+
+use super::{ObsoleteEdge, OntologyData, UnresolvedEdge};
+
+/// The policy for resolving an alternate term ID claimed by more than one term.
+///
+/// Some ontology releases include several terms with the same alternate ID.
+/// The policy decides which term the alternate ID resolves to.
+/// An alternate ID that is also the primary ID of a term always resolves to that term,
+/// and the `Error` policy fails the loading.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AltIdCollisionPolicy {
+    /// Fail the ontology loading.
+    Error,
+    /// The alternate ID resolves to the first term claiming the ID.
+    FirstWins,
+    /// The alternate ID resolves to the last term claiming the ID.
+    #[default]
+    LastWins,
+}
+
+/// An alternate term ID claimed by more than one term,
+/// or by a term other than the term with the same primary ID.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AltIdCollision {
+    alt_id: TermId,
+    terms: Vec<TermId>,
+}
+
+impl AltIdCollision {
+    /// Get the alternate term ID.
+    pub fn alt_id(&self) -> &TermId {
+        &self.alt_id
+    }
+
+    /// Get the primary IDs of the terms claiming the alternate ID, in the order of the input.
+    ///
+    /// The term with the alternate ID as the primary ID, if any, comes first.
+    pub fn terms(&self) -> &[TermId] {
+        &self.terms
+    }
+}
+
+/// The summary of the issues found when loading an ontology.
+///
+/// Get the report with e.g. [`super::OntologyLoader::load_with_report_from_path`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadReport {
+    pub(super) alt_id_collisions: Vec<AltIdCollision>,
+    pub(super) skipped_nodes: Vec<String>,
+    pub(super) skipped_edges: Vec<String>,
+    pub(super) duplicate_edge_count: usize,
+    pub(super) unresolved_edges: Vec<UnresolvedEdge>,
+    pub(super) obsolete_edges: Vec<ObsoleteEdge>,
+}
+
+impl LoadReport {
+    /// Get the alternate term IDs claimed by more than one term.
+    pub fn alt_id_collisions(&self) -> &[AltIdCollision] {
+        &self.alt_id_collisions
+    }
+
+    /// Get the ids of the input nodes that could not be turned into terms,
+    /// e.g. the nodes with no label or with an id that is not a CURIE.
+    pub fn skipped_nodes(&self) -> &[String] {
+        &self.skipped_nodes
+    }
+
+    /// Get the input edges that could not be interpreted, e.g. due to an unknown predicate,
+    /// as `sub pred obj` of the input.
+    pub fn skipped_edges(&self) -> &[String] {
+        &self.skipped_edges
+    }
+
+    /// Get the number of the duplicate `is_a` edges removed from the input.
+    pub fn duplicate_edge_count(&self) -> usize {
+        self.duplicate_edge_count
+    }
+
+    /// Get the input edges dropped because an endpoint does not resolve to a term.
+    pub fn unresolved_edges(&self) -> &[UnresolvedEdge] {
+        &self.unresolved_edges
+    }
+
+    /// Get the input edges with an obsolete parent that were rerouted or dropped
+    /// (see [`super::ObsoleteEdgePolicy`]).
+    pub fn obsolete_edges(&self) -> &[ObsoleteEdge] {
+        &self.obsolete_edges
+    }
+
+    /// Test if no issues were found.
+    pub fn is_clean(&self) -> bool {
+        self.alt_id_collisions.is_empty()
+            && self.skipped_nodes.is_empty()
+            && self.skipped_edges.is_empty()
+            && self.duplicate_edge_count == 0
+            && self.unresolved_edges.is_empty()
+            && self.obsolete_edges.is_empty()
+    }
+}
+
+impl<HI, T> OntologyData<HI, T>
+where
+    HI: HierarchyIdx,
+    T: MinimalTerm,
+{
+    /// Find the alternate term IDs claimed by more than one term,
+    /// including the alternate IDs that are the primary IDs of other terms.
+    ///
+    /// The collisions are ordered by the first occurrence of the alternate ID in the terms.
+    pub fn alt_id_collisions(&self) -> Vec<AltIdCollision> {
+        let primary: HashSet<_> = self.terms.iter().map(|term| term.identifier()).collect();
+        let mut claims: HashMap<&TermId, Vec<TermId>> = HashMap::new();
+        let mut order = vec![];
+        for term in self.terms.iter() {
+            for alt_id in term.iter_alt_term_ids() {
+                let claimants = claims.entry(alt_id).or_insert_with(|| {
+                    order.push(alt_id);
+                    // The term with the primary ID claims the ID first.
+                    primary.get(alt_id).map(|&id| id.clone()).into_iter().collect()
+                });
+                if !claimants.contains(term.identifier()) {
+                    claimants.push(term.identifier().clone());
+                }
+            }
+        }
+
+        order
+            .into_iter()
+            .filter_map(|alt_id| {
+                let terms = claims.remove(alt_id)?;
+                (terms.len() > 1).then(|| AltIdCollision {
+                    alt_id: alt_id.clone(),
+                    terms,
+                })
+            })
+            .collect()
+    }
+
+    /// Get the policy for resolving the alternate IDs claimed by more than one term.
+    pub fn alt_id_policy(&self) -> AltIdCollisionPolicy {
+        self.alt_id_policy
+    }
+
+    /// Set the policy for resolving the alternate IDs claimed by more than one term.
+    ///
+    /// The policy is honored by the ontologies assembled from the data, such as
+    /// [`crate::ontology::csr::CsrOntology`].
+    pub fn with_alt_id_policy(mut self, policy: AltIdCollisionPolicy) -> Self {
+        self.alt_id_policy = policy;
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::hierarchy::{GraphEdge, Relationship};
+    use crate::ontology::csr::CsrOntology;
+    use crate::ontology::TermAware;
+
+    fn term(curie: &str, alt_ids: &[&str]) -> SimpleMinimalTerm {
+        SimpleMinimalTerm::new(
+            TermId::from_str(curie).unwrap(),
+            curie,
+            alt_ids
+                .iter()
+                .map(|alt| TermId::from_str(alt).unwrap())
+                .collect(),
+            false,
+        )
+    }
+
+    #[test]
+    fn test_alt_id_collisions() {
+        let terms = vec![
+            term("HP:1", &["HP:10", "HP:11"]),
+            term("HP:2", &["HP:11", "HP:12"]),
+            term("HP:3", &["HP:12", "HP:11"]),
+        ];
+        let data: OntologyData<usize, _> = OntologyData::from((
+            terms.into_boxed_slice(),
+            Box::default(),
+            HashMap::new(),
+        ));
+
+        let collisions = data.alt_id_collisions();
+
+        assert_eq!(collisions.len(), 2);
+        assert_eq!(collisions[0].alt_id().to_string(), "HP:11");
+        assert_eq!(
+            collisions[0].terms(),
+            [
+                TermId::from_str("HP:1").unwrap(),
+                TermId::from_str("HP:2").unwrap(),
+                TermId::from_str("HP:3").unwrap()
+            ]
+        );
+        assert_eq!(collisions[1].alt_id().to_string(), "HP:12");
+    }
+
+    #[test]
+    fn test_alt_id_colliding_with_primary_id() {
+        let terms = vec![
+            term("HP:1", &[]),
+            term("HP:2", &["HP:1"]),
+            term("HP:3", &["HP:3"]),
+        ];
+        let data: OntologyData<usize, _> = OntologyData::from((
+            terms.into_boxed_slice(),
+            Box::default(),
+            HashMap::new(),
+        ));
+
+        let collisions = data.alt_id_collisions();
+
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].alt_id().to_string(), "HP:1");
+        assert_eq!(
+            collisions[0].terms(),
+            [
+                TermId::from_str("HP:1").unwrap(),
+                TermId::from_str("HP:2").unwrap()
+            ]
+        );
+
+        for policy in [
+            AltIdCollisionPolicy::FirstWins,
+            AltIdCollisionPolicy::LastWins,
+        ] {
+            let terms = vec![term("HP:1", &[]), term("HP:2", &["HP:1"])];
+            let edges = vec![GraphEdge::from((1, Relationship::Child, 0))];
+            let data: OntologyData<usize, _> = OntologyData::from((
+                terms.into_boxed_slice(),
+                edges.into_boxed_slice(),
+                HashMap::new(),
+            ))
+            .with_alt_id_policy(policy);
+            let ontology = CsrOntology::try_from(data).unwrap();
+
+            assert_eq!(
+                ontology.id_to_idx(&TermId::from_str("HP:1").unwrap()),
+                Some(0),
+                "{policy:?}"
+            );
+        }
+    }
+}
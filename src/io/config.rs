@@ -0,0 +1,138 @@
+use crate::error::OntoliusError;
+
+use super::{AltIdCollisionPolicy, ObsoleteEdgePolicy};
+
+/// The options of the [`OntologyLoader`](super::OntologyLoader), e.g. to declare
+/// the loading behavior of a pipeline in a configuration file.
+///
+/// With the `serde` feature, the config can be (de)serialized. The missing options
+/// take the default values and the unknown options are rejected.
+///
+/// ## Examples
+///
+/// ```
+/// use curie_util::TrieCurieUtil;
+/// use ontolius::io::{LoaderConfig, ObsoleteEdgePolicy};
+/// use ontolius::io::obographs::ObographsParser;
+/// use ontolius::prelude::*;
+///
+/// let config = LoaderConfig {
+///     keep_prefixes: Some(vec!["HP".to_string()]),
+///     obsolete_edge_policy: ObsoleteEdgePolicy::Reroute,
+///     ..Default::default()
+/// };
+/// config.validate().expect("The config should be valid");
+///
+/// let loader = OntologyLoaderBuilder::new()
+///                .parser(ObographsParser::new(TrieCurieUtil::default()))
+///                .config(config.clone())
+///                .build();
+/// assert_eq!(loader.config(), &config);
+///
+/// let hpo: MinimalCsrOntology = loader.load_from_path("resources/hp.small.json.gz")
+///                                 .expect("HPO should be loaded");
+/// assert!(hpo.iter_term_ids().all(|term_id| term_id.prefix() == "HP"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct LoaderConfig {
+    /// The CURIE prefixes of the terms to keep, or `None` to keep all terms
+    /// (see [`OntologyLoaderBuilder::keep_prefixes`](super::OntologyLoaderBuilder::keep_prefixes)).
+    pub keep_prefixes: Option<Vec<String>>,
+    /// The policy for the alternate term IDs claimed by more than one term.
+    pub alt_id_collision_policy: AltIdCollisionPolicy,
+    /// Remove the duplicate `is_a` edges.
+    pub deduplicate_edges: bool,
+    /// Fail the loading if an `is_a` edge endpoint does not resolve to a term.
+    pub strict_edges: bool,
+    /// The ID of the graph to load, or `None` to load the first graph.
+    pub graph_id: Option<String>,
+    /// The policy for the `is_a` edges whose parent is an obsolete term.
+    pub obsolete_edge_policy: ObsoleteEdgePolicy,
+}
+
+impl Default for LoaderConfig {
+    fn default() -> Self {
+        Self {
+            keep_prefixes: None,
+            alt_id_collision_policy: AltIdCollisionPolicy::default(),
+            deduplicate_edges: true,
+            strict_edges: false,
+            graph_id: None,
+            obsolete_edge_policy: ObsoleteEdgePolicy::default(),
+        }
+    }
+}
+
+impl LoaderConfig {
+    /// Check that the options make sense together, e.g. that the prefix filter keeps some terms.
+    pub fn validate(&self) -> Result<(), OntoliusError> {
+        if let Some(prefixes) = &self.keep_prefixes {
+            if prefixes.is_empty() {
+                return Err(OntoliusError::Other(
+                    "No prefixes to keep, all terms would be dropped".to_string(),
+                ));
+            }
+            if let Some(prefix) = prefixes
+                .iter()
+                .find(|prefix| prefix.is_empty() || prefix.contains(':'))
+            {
+                return Err(OntoliusError::Other(format!(
+                    "Invalid prefix to keep: {prefix:?}"
+                )));
+            }
+        }
+        if self.graph_id.as_ref().is_some_and(|id| id.is_empty()) {
+            return Err(OntoliusError::Other("Empty graph ID".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(LoaderConfig::default().validate().is_ok());
+
+        let invalid = [
+            LoaderConfig {
+                keep_prefixes: Some(vec![]),
+                ..Default::default()
+            },
+            LoaderConfig {
+                keep_prefixes: Some(vec!["HP".to_string(), "HP:".to_string()]),
+                ..Default::default()
+            },
+            LoaderConfig {
+                graph_id: Some(String::new()),
+                ..Default::default()
+            },
+        ];
+        for config in invalid {
+            assert!(config.validate().is_err(), "{config:?}");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize() {
+        let config: LoaderConfig =
+            serde_json::from_str(r#"{"keep_prefixes": ["MONDO"], "obsolete_edge_policy": "Drop"}"#)
+                .unwrap();
+        assert_eq!(
+            config,
+            LoaderConfig {
+                keep_prefixes: Some(vec!["MONDO".to_string()]),
+                obsolete_edge_policy: ObsoleteEdgePolicy::Drop,
+                ..Default::default()
+            }
+        );
+
+        let unknown = serde_json::from_str::<LoaderConfig>(r#"{"strict": true}"#);
+        assert!(unknown.is_err());
+    }
+}
@@ -1,21 +1,45 @@
 //! Routines for loading ontology data.
+mod alt_ids;
+mod config;
+mod namespace;
+mod obsolete;
+mod unresolved;
+pub mod obo;
 #[cfg(feature = "obographs")]
 pub mod obographs;
+#[cfg(feature = "ols")]
+pub mod ols;
+#[cfg(feature = "owl")]
+pub mod owl;
+#[cfg(feature = "turtle")]
+pub mod turtle;
+
+pub use alt_ids::{AltIdCollision, AltIdCollisionPolicy, LoadReport};
+pub use config::LoaderConfig;
+pub use namespace::{CrossLink, NamespacePartition};
+pub use obsolete::{ObsoleteEdge, ObsoleteEdgePolicy};
+pub use unresolved::{UnresolvedEdge, UnresolvedReason};
 
 use flate2::read::GzDecoder;
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fs::File,
     io::{BufRead, BufReader, Read},
     path::Path,
 };
 
 use crate::{
-    base::term::MinimalTerm,
-    hierarchy::{GraphEdge, HierarchyIdx},
+    base::{term::MinimalTerm, TermId},
+    hierarchy::{GraphEdge, HierarchyIdx, Relationship},
+    ontology::Relationships,
     prelude::{OntoliusError, Ontology, TermIdx},
 };
 
+/// The prefix of the OBO PURLs, such as `http://purl.obolibrary.org/obo/HP_0001166`.
+#[cfg(any(feature = "obographs", feature = "ols", feature = "owl"))]
+pub(crate) const OBO_PURL: &str = "http://purl.obolibrary.org/obo/";
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OntologyData<HI, T>
 where
     HI: HierarchyIdx,
@@ -24,6 +48,12 @@ where
     terms: Box<[T]>,
     edges: Box<[GraphEdge<HI>]>,
     metadata: HashMap<String, String>,
+    alt_id_policy: AltIdCollisionPolicy,
+    skipped_nodes: Vec<String>,
+    skipped_edges: Vec<String>,
+    unresolved_edges: Vec<UnresolvedEdge>,
+    relationships: Relationships,
+    replacements: HashMap<TermId, TermId>,
 }
 
 impl<HI: HierarchyIdx, T: MinimalTerm> OntologyData<HI, T> {
@@ -39,6 +69,72 @@ impl<HI: HierarchyIdx, T: MinimalTerm> OntologyData<HI, T> {
         // TODO: the signature overpromises. We should probably only promise an iterator over (String, String).
         &self.metadata
     }
+
+    /// Remove the duplicate edges and return the number of the removed edges.
+    ///
+    /// The edges are duplicate if they link the same child and parent,
+    /// regardless of the [`crate::hierarchy::Relationship`] used to express the link.
+    /// The first occurrence of each edge is kept.
+    pub fn dedup_edges(&mut self) -> usize {
+        let mut seen = BTreeSet::new();
+        let mut edges = std::mem::take(&mut self.edges).into_vec();
+        let len = edges.len();
+        edges.retain(|edge| {
+            let pair = match edge.pred {
+                Relationship::Child => (edge.sub, edge.obj),
+                Relationship::Parent => (edge.obj, edge.sub),
+            };
+            seen.insert(pair)
+        });
+        let removed = len - edges.len();
+        self.edges = edges.into_boxed_slice();
+        removed
+    }
+
+    /// Get the ids of the input nodes that the parser could not turn into terms.
+    pub fn skipped_nodes(&self) -> &[String] {
+        &self.skipped_nodes
+    }
+
+    /// Set the ids of the input nodes that the parser could not turn into terms.
+    pub fn with_skipped_nodes(mut self, skipped_nodes: Vec<String>) -> Self {
+        self.skipped_nodes = skipped_nodes;
+        self
+    }
+
+    /// Get the input edges that the parser could not interpret, e.g. due to an unknown predicate,
+    /// as `sub pred obj` of the input.
+    pub fn skipped_edges(&self) -> &[String] {
+        &self.skipped_edges
+    }
+
+    /// Set the input edges that the parser could not interpret.
+    pub fn with_skipped_edges(mut self, skipped_edges: Vec<String>) -> Self {
+        self.skipped_edges = skipped_edges;
+        self
+    }
+
+    /// Get the input edges dropped because an endpoint does not resolve to a term.
+    pub fn unresolved_edges(&self) -> &[UnresolvedEdge] {
+        &self.unresolved_edges
+    }
+
+    /// Set the input edges dropped because an endpoint does not resolve to a term.
+    pub fn with_unresolved_edges(mut self, unresolved_edges: Vec<UnresolvedEdge>) -> Self {
+        self.unresolved_edges = unresolved_edges;
+        self
+    }
+
+    /// Get the relationships between the terms other than `is_a`.
+    pub fn relationships(&self) -> &Relationships {
+        &self.relationships
+    }
+
+    /// Set the relationships between the terms other than `is_a`.
+    pub fn with_relationships(mut self, relationships: Relationships) -> Self {
+        self.relationships = relationships;
+        self
+    }
 }
 
 impl<HI, T> From<(Box<[T]>, Box<[GraphEdge<HI>]>, HashMap<String, String>)> for OntologyData<HI, T>
@@ -51,6 +147,12 @@ where
             terms: value.0,
             edges: value.1,
             metadata: value.2,
+            alt_id_policy: AltIdCollisionPolicy::default(),
+            skipped_nodes: vec![],
+            skipped_edges: vec![],
+            unresolved_edges: vec![],
+            relationships: Relationships::default(),
+            replacements: HashMap::new(),
         }
     }
 }
@@ -67,6 +169,23 @@ pub trait OntologyDataParser {
     ) -> Result<OntologyData<Self::HI, Self::T>, OntoliusError>
     where
         R: BufRead;
+
+    /// Load ontology data of the graph with the `graph_id` from the buffered reader,
+    /// for the formats with more than one graph in a document.
+    ///
+    /// The default implementation fails, since most formats have a single graph.
+    fn load_graph_from_buf_read<R>(
+        &self,
+        _read: &mut R,
+        graph_id: &str,
+    ) -> Result<OntologyData<Self::HI, Self::T>, OntoliusError>
+    where
+        R: BufRead,
+    {
+        Err(OntoliusError::OntologyDataParseError(format!(
+            "Cannot select graph {graph_id}: the format has a single graph"
+        )))
+    }
 }
 
 /// [`OntologyLoader`] parses the input into [`OntologyData`] using supplied [`OntologyDataParser`]
@@ -76,6 +195,7 @@ where
     P: OntologyDataParser,
 {
     parser: P,
+    config: LoaderConfig,
 }
 
 impl<P> OntologyLoader<P>
@@ -83,7 +203,15 @@ where
     P: OntologyDataParser,
 {
     pub fn new(parser: P) -> Self {
-        Self { parser }
+        Self {
+            parser,
+            config: LoaderConfig::default(),
+        }
+    }
+
+    /// Get the options of the loader.
+    pub fn config(&self) -> &LoaderConfig {
+        &self.config
     }
 }
 
@@ -101,25 +229,8 @@ where
             + Ontology<Idx = Parser::HI, T = Parser::T>,
         P: AsRef<Path>,
     {
-        let path = path.as_ref();
-        if let Ok(mut file) = File::open(path) {
-            if let Some(extension) = path.extension() {
-                if extension == "gz" {
-                    // Decompress gzipped file on the fly.
-                    let mut read = GzDecoder::new(file);
-                    self.load_from_read(&mut read)
-                } else {
-                    // All other extensions, e.g. JSON
-                    self.load_from_read(&mut file)
-                }
-            } else {
-                // We will also read from a file with no extension,
-                // assuming plain text.
-                self.load_from_read(&mut file)
-            }
-        } else {
-            Err(OntoliusError::Other(format!("Cannot load ontology from {path:?}")))
-        }
+        let mut read = open_path(path.as_ref())?;
+        self.load_from_read(&mut read)
     }
 
     /// Load ontology from a reader.
@@ -140,8 +251,177 @@ where
         O: TryFrom<OntologyData<Parser::HI, Parser::T>, Error = OntoliusError>
             + Ontology<Idx = Parser::HI, T = Parser::T>,
     {
-        let data = self.parser.load_from_buf_read(read)?;
-        O::try_from(data)
+        self.load_with_report_from_buf_read(read)
+            .map(|(ontology, _)| ontology)
+    }
+
+    /// Load ontology from a path along with the [`LoadReport`]
+    /// with the issues found in the input.
+    ///
+    /// Gzipped content is uncompressed on the fly,
+    /// as long as the `path` is suffixed with `*.gz`.
+    pub fn load_with_report_from_path<O, P>(
+        &self,
+        path: P,
+    ) -> Result<(O, LoadReport), OntoliusError>
+    where
+        O: TryFrom<OntologyData<Parser::HI, Parser::T>, Error = OntoliusError>
+            + Ontology<Idx = Parser::HI, T = Parser::T>,
+        P: AsRef<Path>,
+    {
+        let read = open_path(path.as_ref())?;
+        self.load_with_report_from_buf_read(&mut BufReader::new(read))
+    }
+
+    /// Load ontology from a buffered reader along with the [`LoadReport`]
+    /// with the issues found in the input.
+    pub fn load_with_report_from_buf_read<R, O>(
+        &self,
+        read: &mut R,
+    ) -> Result<(O, LoadReport), OntoliusError>
+    where
+        R: BufRead,
+        O: TryFrom<OntologyData<Parser::HI, Parser::T>, Error = OntoliusError>
+            + Ontology<Idx = Parser::HI, T = Parser::T>,
+    {
+        let (data, report) = self.load_data_with_report_from_buf_read(read)?;
+        O::try_from(data).map(|ontology| (ontology, report))
+    }
+
+    /// Load the ontology data from a buffered reader along with the [`LoadReport`],
+    /// with the loader configuration applied, e.g. to assemble the ontology
+    /// with [`crate::ontology::csr::CsrOntologyBuilder`].
+    pub fn load_data_with_report_from_buf_read<R>(
+        &self,
+        read: &mut R,
+    ) -> Result<(ParsedData<Parser>, LoadReport), OntoliusError>
+    where
+        R: BufRead,
+    {
+        let mut report = LoadReport::default();
+        let data = self.prepare(self.parse(read)?, &mut report);
+        self.check(&data, &mut report)?;
+        Ok((data, report))
+    }
+
+    /// Load ontology from a path and split it into ontologies
+    /// with the terms of the same CURIE prefix.
+    ///
+    /// Each part must be a valid ontology, e.g. with a single root.
+    /// Gzipped content is uncompressed on the fly,
+    /// as long as the `path` is suffixed with `*.gz`.
+    pub fn load_partitions_from_path<O, P>(
+        &self,
+        path: P,
+    ) -> Result<NamespacePartition<O>, OntoliusError>
+    where
+        O: TryFrom<OntologyData<Parser::HI, Parser::T>, Error = OntoliusError>
+            + Ontology<Idx = Parser::HI, T = Parser::T>,
+        P: AsRef<Path>,
+    {
+        let read = open_path(path.as_ref())?;
+        self.load_partitions_from_buf_read(&mut BufReader::new(read))
+    }
+
+    /// Load ontology from a buffered reader and split it into ontologies
+    /// with the terms of the same CURIE prefix.
+    ///
+    /// Each part must be a valid ontology, e.g. with a single root.
+    pub fn load_partitions_from_buf_read<R, O>(
+        &self,
+        read: &mut R,
+    ) -> Result<NamespacePartition<O>, OntoliusError>
+    where
+        R: BufRead,
+        O: TryFrom<OntologyData<Parser::HI, Parser::T>, Error = OntoliusError>
+            + Ontology<Idx = Parser::HI, T = Parser::T>,
+    {
+        let (data, _) = self.load_data_with_report_from_buf_read(read)?;
+        data.partition_by_prefix().try_map(O::try_from)
+    }
+
+    /// Parse the selected graph of the input, if any.
+    fn parse<R: BufRead>(
+        &self,
+        read: &mut R,
+    ) -> Result<OntologyData<Parser::HI, Parser::T>, OntoliusError> {
+        match &self.config.graph_id {
+            Some(graph_id) => self.parser.load_graph_from_buf_read(read, graph_id),
+            None => self.parser.load_from_buf_read(read),
+        }
+    }
+
+    /// Apply the loader configuration to the parsed `data`
+    /// and record the changes into the `report`.
+    fn prepare(
+        &self,
+        mut data: OntologyData<Parser::HI, Parser::T>,
+        report: &mut LoadReport,
+    ) -> OntologyData<Parser::HI, Parser::T> {
+        report.skipped_nodes = data.skipped_nodes.clone();
+        report.skipped_edges = data.skipped_edges.clone();
+        report.obsolete_edges = data.apply_obsolete_edge_policy(self.config.obsolete_edge_policy);
+        let mut data = match &self.config.keep_prefixes {
+            Some(prefixes) => data.retain_prefixes(prefixes),
+            None => data,
+        };
+        if self.config.deduplicate_edges {
+            report.duplicate_edge_count = data.dedup_edges();
+        }
+        data.with_alt_id_policy(self.config.alt_id_collision_policy)
+    }
+
+    /// Check the `data` for issues and record them into the `report`.
+    ///
+    /// Returns an error if an issue is not tolerated by the loader configuration.
+    fn check(
+        &self,
+        data: &OntologyData<Parser::HI, Parser::T>,
+        report: &mut LoadReport,
+    ) -> Result<(), OntoliusError> {
+        let alt_id_collisions = data.alt_id_collisions();
+        if self.config.alt_id_collision_policy == AltIdCollisionPolicy::Error {
+            if let Some(collision) = alt_id_collisions.first() {
+                let terms: Vec<_> = collision.terms().iter().map(ToString::to_string).collect();
+                return Err(OntoliusError::OntologyAssemblyError(format!(
+                    "Alternate ID {} is claimed by more than one term: {}",
+                    collision.alt_id(),
+                    terms.join(", ")
+                )));
+            }
+        }
+        report.alt_id_collisions = alt_id_collisions;
+
+        if self.config.strict_edges {
+            if let Some(edge) = data.unresolved_edges.first() {
+                return Err(OntoliusError::OntologyAssemblyError(format!(
+                    "Unresolved edge {edge} ({} unresolved edges in total)",
+                    data.unresolved_edges.len()
+                )));
+            }
+        }
+        report.unresolved_edges = data.unresolved_edges.clone();
+
+        Ok(())
+    }
+}
+
+/// The ontology data read by the parser `P`.
+type ParsedData<P> = OntologyData<<P as OntologyDataParser>::HI, <P as OntologyDataParser>::T>;
+
+/// Open the file at `path` and decompress gzipped content on the fly,
+/// as long as the `path` is suffixed with `*.gz`.
+fn open_path(path: &Path) -> Result<Box<dyn Read>, OntoliusError> {
+    match File::open(path) {
+        Ok(file) => match path.extension() {
+            // Decompress gzipped file on the fly.
+            Some(extension) if extension == "gz" => Ok(Box::new(GzDecoder::new(file))),
+            // All other extensions, e.g. JSON, and no extension, assuming plain text.
+            _ => Ok(Box::new(file)),
+        },
+        Err(_) => Err(OntoliusError::Other(format!(
+            "Cannot load ontology from {path:?}"
+        ))),
     }
 }
 
@@ -152,6 +432,7 @@ where
     P: OntologyDataParser,
 {
     parser: P,
+    config: LoaderConfig,
 }
 
 pub struct OntologyLoaderBuilder<State> {
@@ -179,14 +460,133 @@ impl OntologyLoaderBuilder<Uninitialized> {
         P: OntologyDataParser,
     {
         OntologyLoaderBuilder {
-            state: WithParser { parser },
+            state: WithParser {
+                parser,
+                config: LoaderConfig::default(),
+            },
         }
     }
 }
 
 impl<P: OntologyDataParser> OntologyLoaderBuilder<WithParser<P>> {
+    /// Keep only the terms with one of the CURIE `prefixes` (e.g. `MONDO`),
+    /// and drop the foreign terms embedded in the input.
+    ///
+    /// The edges with a dropped term are dropped as well.
+    #[must_use]
+    pub fn keep_prefixes<I, S>(mut self, prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.state.config.keep_prefixes = Some(prefixes.into_iter().map(|p| p.to_string()).collect());
+        self
+    }
+
+    /// Set the policy for resolving an alternate term ID claimed by more than one term.
+    ///
+    /// The last claiming term wins by default.
+    #[must_use]
+    pub fn alt_id_collision_policy(mut self, policy: AltIdCollisionPolicy) -> Self {
+        self.state.config.alt_id_collision_policy = policy;
+        self
+    }
+
+    /// Set whether to remove the duplicate `is_a` edges from the input.
+    ///
+    /// The duplicates are removed by default,
+    /// and the number of the removed edges is included in the [`LoadReport`].
+    #[must_use]
+    pub fn deduplicate_edges(mut self, dedup_edges: bool) -> Self {
+        self.state.config.deduplicate_edges = dedup_edges;
+        self
+    }
+
+    /// Set whether to fail the loading if an `is_a` edge endpoint does not resolve to a term,
+    /// e.g. because the node was skipped by the parser or the term was filtered out by its prefix.
+    ///
+    /// By default, such edges are dropped and listed in [`LoadReport::unresolved_edges`].
+    #[must_use]
+    pub fn strict_edges(mut self, strict_edges: bool) -> Self {
+        self.state.config.strict_edges = strict_edges;
+        self
+    }
+
+    /// Set the policy for the `is_a` edges whose parent is an obsolete term.
+    ///
+    /// The edges are kept by default. The rerouted and the dropped edges
+    /// are listed in [`LoadReport::obsolete_edges`].
+    #[must_use]
+    pub fn obsolete_edge_policy(mut self, policy: ObsoleteEdgePolicy) -> Self {
+        self.state.config.obsolete_edge_policy = policy;
+        self
+    }
+
+    /// Load the graph with the `graph_id`, e.g. `http://purl.obolibrary.org/obo/hp.owl`,
+    /// from a document with more than one graph.
+    ///
+    /// The first graph is loaded by default. The loading fails if the document
+    /// has no graph with the `graph_id`, or if the parser supports no graph selection
+    /// (see [`OntologyDataParser::load_graph_from_buf_read`]).
+    #[must_use]
+    pub fn graph_id<S: ToString>(mut self, graph_id: S) -> Self {
+        self.state.config.graph_id = Some(graph_id.to_string());
+        self
+    }
+
+    /// Set all options of the loader from the `config`, e.g. one read from a configuration file.
+    ///
+    /// The config is not validated, see [`LoaderConfig::validate`].
+    #[must_use]
+    pub fn config(mut self, config: LoaderConfig) -> Self {
+        self.state.config = config;
+        self
+    }
+
     /// Build the ontology loader.
     pub fn build(self) -> OntologyLoader<P> {
-        OntologyLoader::new(self.state.parser)
+        OntologyLoader {
+            parser: self.state.parser,
+            config: self.state.config,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::base::{term::simple::SimpleMinimalTerm, TermId};
+
+    #[test]
+    fn test_dedup_edges() {
+        let terms: Vec<_> = ["HP:1", "HP:2", "HP:3"]
+            .into_iter()
+            .map(|curie| {
+                SimpleMinimalTerm::new(TermId::from_str(curie).unwrap(), curie, vec![], false)
+            })
+            .collect();
+        let edges: Vec<_> = [
+            (1, Relationship::Child, 0),
+            (2, Relationship::Child, 0),
+            (1, Relationship::Child, 0),
+            (0, Relationship::Parent, 2),
+            (2, Relationship::Child, 1),
+        ]
+        .into_iter()
+        .map(GraphEdge::from)
+        .collect();
+        let mut data: OntologyData<usize, _> = OntologyData::from((
+            terms.into_boxed_slice(),
+            edges.into_boxed_slice(),
+            HashMap::new(),
+        ));
+
+        assert_eq!(data.dedup_edges(), 2);
+
+        let pairs: Vec<_> = data.edges().iter().map(|e| (e.sub, e.obj)).collect();
+        assert_eq!(pairs, [(1, 0), (2, 0), (2, 1)]);
+        assert_eq!(data.dedup_edges(), 0);
     }
 }
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::base::{term::MinimalTerm, TermId};
+use crate::hierarchy::{GraphEdge, HierarchyIdx, Relationship};
+use crate::ontology::TermIdx;
+
+use super::{OntologyData, UnresolvedEdge, UnresolvedReason};
+
+/// An edge between terms of different namespaces (CURIE prefixes).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CrossLink {
+    pub sub: TermId,
+    pub pred: Relationship,
+    pub obj: TermId,
+}
+
+/// The parts of an ontology split by the namespaces (CURIE prefixes) of the terms,
+/// and the edges between the parts.
+///
+/// The parts are e.g. [`OntologyData`] or an [`crate::ontology::Ontology`].
+pub struct NamespacePartition<P> {
+    parts: HashMap<String, P>,
+    cross_links: Vec<CrossLink>,
+}
+
+impl<P> NamespacePartition<P> {
+    /// Get the part with the CURIE `prefix` (e.g. `MONDO`).
+    pub fn get(&self, prefix: &str) -> Option<&P> {
+        self.parts.get(prefix)
+    }
+
+    /// Get an iterator over the CURIE prefixes of the parts.
+    pub fn prefixes(&self) -> impl Iterator<Item = &str> {
+        self.parts.keys().map(String::as_str)
+    }
+
+    /// Get the edges between the terms of different parts.
+    pub fn cross_links(&self) -> &[CrossLink] {
+        &self.cross_links
+    }
+
+    /// Consume the partition and return the parts, keyed by the CURIE prefix, and the cross-links.
+    pub fn into_parts(self) -> (HashMap<String, P>, Vec<CrossLink>) {
+        (self.parts, self.cross_links)
+    }
+
+    pub(crate) fn try_map<Q, E, F>(self, f: F) -> Result<NamespacePartition<Q>, E>
+    where
+        F: Fn(P) -> Result<Q, E>,
+    {
+        let parts = self
+            .parts
+            .into_iter()
+            .map(|(prefix, part)| f(part).map(|part| (prefix, part)))
+            .collect::<Result<_, _>>()?;
+        Ok(NamespacePartition {
+            parts,
+            cross_links: self.cross_links,
+        })
+    }
+}
+
+impl<HI, T> OntologyData<HI, T>
+where
+    HI: HierarchyIdx + TermIdx,
+    T: MinimalTerm,
+{
+    /// Keep only the terms with one of the CURIE `prefixes` and the edges between them.
+    ///
+    /// The edges between a kept and a dropped term are added to [`OntologyData::unresolved_edges`].
+    pub fn retain_prefixes<S: AsRef<str>>(mut self, prefixes: &[S]) -> Self {
+        let metadata = self.metadata.clone();
+        let alt_id_policy = self.alt_id_policy;
+        let mut unresolved_edges = std::mem::take(&mut self.unresolved_edges);
+        let mut split = split(self, |term| {
+            let prefix = term.identifier().prefix();
+            prefixes.iter().any(|p| p.as_ref() == prefix).then_some(())
+        });
+        unresolved_edges.append(&mut split.dropped);
+
+        split
+            .parts
+            .remove(&())
+            .unwrap_or_else(|| {
+                OntologyData::from((Box::default(), Box::default(), metadata))
+                    .with_alt_id_policy(alt_id_policy)
+            })
+            .with_unresolved_edges(unresolved_edges)
+    }
+
+    /// Split the data into parts with the terms of the same CURIE prefix.
+    ///
+    /// The edges between the terms of different parts are returned as [`CrossLink`]s.
+    /// All parts share the metadata and the relationships other than `is_a`.
+    pub fn partition_by_prefix(self) -> NamespacePartition<Self> {
+        let split = split(self, |term| Some(term.identifier().prefix().to_string()));
+        NamespacePartition {
+            parts: split.parts,
+            cross_links: split.cross_links,
+        }
+    }
+}
+
+/// The result of [`split`].
+struct Split<K, HI, T>
+where
+    HI: HierarchyIdx,
+    T: MinimalTerm,
+{
+    parts: HashMap<K, OntologyData<HI, T>>,
+    cross_links: Vec<CrossLink>,
+    dropped: Vec<UnresolvedEdge>,
+}
+
+/// Split the data into the parts with the same key.
+///
+/// The terms with no key are dropped, and the edges between a kept and a dropped term
+/// are returned as [`UnresolvedEdge`]s.
+fn split<HI, T, K, F>(data: OntologyData<HI, T>, key: F) -> Split<K, HI, T>
+where
+    HI: HierarchyIdx + TermIdx,
+    T: MinimalTerm,
+    K: Clone + Eq + Hash,
+    F: Fn(&T) -> Option<K>,
+{
+    // The key and the index within the part for each term.
+    let mut keys = Vec::with_capacity(data.terms.len());
+    let mut sizes: HashMap<K, usize> = HashMap::new();
+    for term in data.terms.iter() {
+        keys.push(key(term).map(|k| {
+            let size = sizes.entry(k.clone()).or_default();
+            *size += 1;
+            (k, *size - 1)
+        }));
+    }
+
+    let mut edges: HashMap<K, Vec<GraphEdge<HI>>> = HashMap::new();
+    let mut cross_links = vec![];
+    let mut dropped = vec![];
+    for edge in data.edges.iter() {
+        let sub = TermIdx::index(edge.sub);
+        let obj = TermIdx::index(edge.obj);
+        match (
+            keys.get(sub).cloned().flatten(),
+            keys.get(obj).cloned().flatten(),
+        ) {
+            (Some((sub_key, sub_idx)), Some((obj_key, obj_idx))) if sub_key == obj_key => {
+                edges.entry(sub_key).or_default().push(GraphEdge::from((
+                    HierarchyIdx::new(sub_idx),
+                    edge.pred,
+                    HierarchyIdx::new(obj_idx),
+                )));
+            }
+            (Some(_), Some(_)) => cross_links.push(CrossLink {
+                sub: data.terms[sub].identifier().clone(),
+                pred: edge.pred,
+                obj: data.terms[obj].identifier().clone(),
+            }),
+            (Some(_), None) | (None, Some(_)) if sub < keys.len() && obj < keys.len() => {
+                let reason =
+                    |key: &Option<(K, usize)>| key.is_none().then_some(UnresolvedReason::Filtered);
+                let (child, parent) = match edge.pred {
+                    Relationship::Child => (sub, obj),
+                    Relationship::Parent => (obj, sub),
+                };
+                dropped.push(UnresolvedEdge {
+                    sub: data.terms[child].identifier().to_string(),
+                    obj: data.terms[parent].identifier().to_string(),
+                    sub_reason: reason(&keys[child]),
+                    obj_reason: reason(&keys[parent]),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let mut terms: HashMap<K, Vec<T>> = HashMap::new();
+    for (term, key) in data.terms.into_vec().into_iter().zip(keys) {
+        if let Some((key, _)) = key {
+            terms.entry(key).or_default().push(term);
+        }
+    }
+
+    let parts = terms
+        .into_iter()
+        .map(|(key, terms)| {
+            let edges = edges.remove(&key).unwrap_or_default();
+            let part = OntologyData::from((
+                terms.into_boxed_slice(),
+                edges.into_boxed_slice(),
+                data.metadata.clone(),
+            ))
+            .with_alt_id_policy(data.alt_id_policy)
+            .with_relationships(data.relationships.clone());
+            (key, part)
+        })
+        .collect();
+
+    Split {
+        parts,
+        cross_links,
+        dropped,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::base::Identified;
+
+    fn build_data() -> OntologyData<usize, SimpleMinimalTerm> {
+        let terms: Vec<_> = ["MONDO:1", "MONDO:2", "NCIT:1", "NCIT:2", "MONDO:3"]
+            .into_iter()
+            .map(|curie| {
+                SimpleMinimalTerm::new(TermId::from_str(curie).unwrap(), curie, vec![], false)
+            })
+            .collect();
+        let edges: Vec<_> = [(1, 0), (4, 1), (3, 2), (2, 1)]
+            .into_iter()
+            .map(|(sub, obj)| GraphEdge::from((sub, Relationship::Child, obj)))
+            .collect();
+        OntologyData::from((
+            terms.into_boxed_slice(),
+            edges.into_boxed_slice(),
+            HashMap::new(),
+        ))
+    }
+
+    fn edge_curies(data: &OntologyData<usize, SimpleMinimalTerm>) -> Vec<(String, String)> {
+        data.edges()
+            .iter()
+            .map(|edge| {
+                (
+                    data.terms()[edge.sub].identifier().to_string(),
+                    data.terms()[edge.obj].identifier().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_retain_prefixes() {
+        let data = build_data().retain_prefixes(&["MONDO"]);
+
+        let curies: Vec<_> = data
+            .terms()
+            .iter()
+            .map(|t| t.identifier().to_string())
+            .collect();
+        assert_eq!(curies, ["MONDO:1", "MONDO:2", "MONDO:3"]);
+        assert_eq!(
+            edge_curies(&data),
+            [
+                ("MONDO:2".to_string(), "MONDO:1".to_string()),
+                ("MONDO:3".to_string(), "MONDO:2".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_retain_unknown_prefix() {
+        let data = build_data().retain_prefixes(&["HP"]);
+
+        assert!(data.terms().is_empty());
+        assert!(data.edges().is_empty());
+    }
+
+    #[test]
+    fn test_retain_prefixes_reports_dropped_edges() {
+        let data = build_data().retain_prefixes(&["NCIT"]);
+
+        let unresolved = data.unresolved_edges();
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].sub(), "NCIT:1");
+        assert_eq!(unresolved[0].obj(), "MONDO:2");
+        assert_eq!(unresolved[0].sub_reason(), None);
+        assert_eq!(
+            unresolved[0].obj_reason(),
+            Some(&UnresolvedReason::Filtered)
+        );
+    }
+
+    #[test]
+    fn test_partition_by_prefix() {
+        let partition = build_data().partition_by_prefix();
+
+        let mut prefixes: Vec<_> = partition.prefixes().collect();
+        prefixes.sort();
+        assert_eq!(prefixes, ["MONDO", "NCIT"]);
+
+        let ncit = partition.get("NCIT").unwrap();
+        assert_eq!(
+            edge_curies(ncit),
+            [("NCIT:2".to_string(), "NCIT:1".to_string())]
+        );
+        assert_eq!(
+            partition.cross_links(),
+            [CrossLink {
+                sub: TermId::from_str("NCIT:1").unwrap(),
+                pred: Relationship::Child,
+                obj: TermId::from_str("MONDO:2").unwrap(),
+            }]
+        );
+    }
+}
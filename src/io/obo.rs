@@ -0,0 +1,283 @@
+//! Write ontologies in the OBO 1.4 flat file format, for the tools that cannot read
+//! Obographs JSON or OWL.
+//!
+//! Each term is written as a `[Term]` stanza with the `id`, `name`, `alt_id`, `def`, `comment`,
+//! `synonym`, `is_a`, and `is_obsolete` tags:
+//!
+//! ```text
+//! [Term]
+//! id: HP:0001166
+//! name: Arachnodactyly
+//! def: "Abnormally long and slender fingers." []
+//! synonym: "Spider fingers" EXACT []
+//! is_a: HP:0100807 ! Long fingers
+//! ```
+//!
+//! The definitions have no cross-references, since [`Term`] does not provide them.
+use std::collections::BTreeSet;
+use std::io::Write;
+
+use crate::base::term::{MinimalTerm, SynonymAware, SynonymCategory, Term};
+use crate::error::OntoliusError;
+use crate::hierarchy::{HierarchyIdx, ParentNodes};
+use crate::ontology::Ontology;
+
+/// Write all terms of the `ontology` as OBO stanzas, in the order of the term indices.
+///
+/// The header includes the `data-version` if the ontology has a version.
+/// Returns the number of the written stanzas.
+///
+/// ## Examples
+///
+/// ```
+/// # use curie_util::TrieCurieUtil;
+/// # use ontolius::base::term::simple::SimpleTerm;
+/// # use ontolius::io::obographs::ObographsParser;
+/// use ontolius::io::obo::write_obo;
+/// # use ontolius::ontology::csr::CsrOntology;
+/// use ontolius::prelude::*;
+///
+/// # let loader = OntologyLoaderBuilder::new()
+/// #                .parser(ObographsParser::with_term_type(TrieCurieUtil::default()))
+/// #                .build();
+/// # let hpo: CsrOntology<usize, SimpleTerm> = loader.load_from_path("resources/hp.small.json.gz")
+/// #                                             .expect("HPO should be loaded");
+/// #
+/// let mut buffer = vec![];
+/// let count = write_obo(&hpo, &mut buffer).expect("OBO should be written");
+///
+/// assert_eq!(count, hpo.len());
+/// let obo = String::from_utf8(buffer).unwrap();
+/// assert!(obo.starts_with("format-version: 1.4\n"));
+/// assert!(obo.contains("[Term]\nid: HP:0001166\nname: Arachnodactyly\n"));
+/// ```
+pub fn write_obo<O, W>(ontology: &O, write: W) -> Result<usize, OntoliusError>
+where
+    O: Ontology,
+    O::T: Term + SynonymAware,
+    W: Write,
+{
+    let terms = (0..ontology.len()).map(<O::Idx as HierarchyIdx>::new);
+    write_obo_terms(ontology, terms, write)
+}
+
+/// Write the terms at the indices `terms` as OBO stanzas, in the order of the iterator,
+/// e.g. to export a module of the ontology.
+///
+/// Only the `is_a` edges between the written terms are included,
+/// the obsolete terms are written without the `is_a` edges,
+/// and the indices that are not in the `ontology` are skipped.
+/// Returns the number of the written stanzas.
+pub fn write_obo_terms<O, I, W>(
+    ontology: &O,
+    terms: I,
+    mut write: W,
+) -> Result<usize, OntoliusError>
+where
+    O: Ontology,
+    O::T: Term + SynonymAware,
+    I: IntoIterator<Item = O::Idx>,
+    W: Write,
+{
+    let terms: Vec<_> = terms.into_iter().collect();
+    let written: BTreeSet<_> = terms.iter().copied().collect();
+    let hierarchy = ontology.hierarchy();
+
+    write_header(ontology, &mut write).map_err(write_error)?;
+    let mut count = 0;
+    for &idx in &terms {
+        let Some(term) = ontology.idx_to_term(idx) else {
+            continue;
+        };
+        let parents: Vec<_> = if term.is_obsolete() {
+            vec![]
+        } else {
+            hierarchy
+                .parents_of(idx)
+                .filter(|parent| written.contains(parent))
+                .filter_map(|&parent| ontology.idx_to_term(parent))
+                .collect()
+        };
+        write_stanza(term, &parents, &mut write).map_err(write_error)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn write_header<O, W>(ontology: &O, write: &mut W) -> std::io::Result<()>
+where
+    O: Ontology,
+    W: Write,
+{
+    writeln!(write, "format-version: 1.4")?;
+    if !ontology.version().is_empty() {
+        writeln!(write, "data-version: {}", escape(ontology.version()))?;
+    }
+    Ok(())
+}
+
+fn write_stanza<T, W>(term: &T, parents: &[&T], write: &mut W) -> std::io::Result<()>
+where
+    T: Term + SynonymAware,
+    W: Write,
+{
+    writeln!(write)?;
+    writeln!(write, "[Term]")?;
+    writeln!(write, "id: {}", term.identifier())?;
+    writeln!(write, "name: {}", escape(term.name()))?;
+    for alt_id in term.iter_alt_term_ids() {
+        writeln!(write, "alt_id: {alt_id}")?;
+    }
+    if let Some(definition) = term.definition() {
+        writeln!(write, "def: \"{}\" []", quote(definition))?;
+    }
+    if let Some(comment) = term.comment() {
+        writeln!(write, "comment: {}", escape(comment))?;
+    }
+    for synonym in term.iter_synonyms() {
+        let xrefs: Vec<_> = synonym.xrefs().iter().map(ToString::to_string).collect();
+        writeln!(
+            write,
+            "synonym: \"{}\" {} [{}]",
+            quote(synonym.name()),
+            scope(synonym.category()),
+            xrefs.join(", ")
+        )?;
+    }
+    for parent in parents {
+        writeln!(
+            write,
+            "is_a: {} ! {}",
+            parent.identifier(),
+            escape(parent.name())
+        )?;
+    }
+    if term.is_obsolete() {
+        writeln!(write, "is_obsolete: true")?;
+    }
+    Ok(())
+}
+
+fn scope(category: SynonymCategory) -> &'static str {
+    match category {
+        SynonymCategory::Exact => "EXACT",
+        SynonymCategory::Related => "RELATED",
+        SynonymCategory::Broad => "BROAD",
+        SynonymCategory::Narrow => "NARROW",
+    }
+}
+
+/// Escape the characters with a special meaning in the unquoted tag values.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | '!' | '{' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape the characters with a special meaning in the quoted strings.
+fn quote(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | '"' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn write_error(e: std::io::Error) -> OntoliusError {
+    OntoliusError::Other(format!("Cannot write the OBO file: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::base::term::simple::SimpleTerm;
+    use crate::base::term::Synonym;
+    use crate::base::TermId;
+    use crate::hierarchy::{GraphEdge, Relationship};
+    use crate::io::OntologyData;
+    use crate::ontology::csr::CsrOntology;
+
+    #[test]
+    fn test_write_obo() {
+        let terms = vec![
+            SimpleTerm::new(TermId::from(("HP", "0000001")), "All", vec![], false),
+            SimpleTerm::new(
+                TermId::from(("HP", "0001166")),
+                "Arachnodactyly",
+                vec![TermId::from(("HP", "0001505"))],
+                false,
+            )
+            .with_definition("Abnormally long and \"slender\" fingers.")
+            .with_comment("See also {HP:0100807}!")
+            .with_synonyms(vec![Synonym::new(
+                "Spider fingers",
+                SynonymCategory::Exact,
+                vec![TermId::from(("PMID", "123"))],
+            )]),
+            SimpleTerm::new(TermId::from(("HP", "0000002")), "obsolete", vec![], true),
+        ];
+        // The obsolete term keeps its edge, but it is not written.
+        let edges = vec![
+            GraphEdge::from((1, Relationship::Child, 0)),
+            GraphEdge::from((2, Relationship::Child, 0)),
+        ];
+        let data: OntologyData<usize, _> = OntologyData::from((
+            terms.into_boxed_slice(),
+            edges.into_boxed_slice(),
+            HashMap::from([("version".to_string(), "2024-04-26".to_string())]),
+        ));
+        let ontology = CsrOntology::try_from(data).unwrap();
+
+        let mut buffer = vec![];
+        assert_eq!(write_obo(&ontology, &mut buffer).unwrap(), 3);
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            r#"format-version: 1.4
+data-version: 2024-04-26
+
+[Term]
+id: HP:0000001
+name: All
+
+[Term]
+id: HP:0001166
+name: Arachnodactyly
+alt_id: HP:0001505
+def: "Abnormally long and \"slender\" fingers." []
+comment: See also \{HP:0100807}\!
+synonym: "Spider fingers" EXACT [PMID:123]
+is_a: HP:0000001 ! All
+
+[Term]
+id: HP:0000002
+name: obsolete
+is_obsolete: true
+"#
+        );
+
+        // The edges to the terms out of the module are dropped.
+        let mut buffer = vec![];
+        assert_eq!(write_obo_terms(&ontology, [1, 5], &mut buffer).unwrap(), 1);
+        let obo = String::from_utf8(buffer).unwrap();
+        assert!(obo.contains("id: HP:0001166\n"));
+        assert!(!obo.contains("is_a:"));
+    }
+}
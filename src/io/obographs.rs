@@ -1,40 +1,737 @@
-use std::io::BufRead;
+use std::fmt;
+use std::io::{BufRead, Write};
 use std::str::FromStr;
 use std::{collections::HashMap, marker::PhantomData};
 
 use curie_util::{CurieUtil, TrieCurieUtil};
-use obographs::model::{Edge, GraphDocument, Meta, Node};
+use obographs::model::{Meta, Node};
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
 
 use crate::{
-    base::{term::simple::SimpleMinimalTerm, Identified, TermId},
+    base::{
+        term::{
+            simple::{SimpleMinimalTerm, SimpleTerm},
+            MinimalTerm, PropertyValue, Synonym, SynonymCategory, TermProvenance,
+        },
+        Identified, TermId,
+    },
     error::OntoliusError,
     hierarchy::{GraphEdge, HierarchyIdx, Relationship},
-    ontology::TermIdx,
+    ontology::{Ontology, Relationships, Subgraph, TermIdx},
 };
 
-use super::{OntologyData, OntologyDataParser, OntologyLoaderBuilder, Uninitialized, WithParser};
+use super::{
+    OntologyData, OntologyDataParser, OntologyLoaderBuilder, Uninitialized, UnresolvedEdge,
+    UnresolvedReason, WithParser, OBO_PURL,
+};
+
+/// The `replaced by` predicate of an obsolete term, as an IRI or as a CURIE.
+const REPLACED_BY: [&str; 2] = ["IAO_0100001", "IAO:0100001"];
 
+/// The `consider` predicate of an obsolete term, as an IRI or as a CURIE.
+const CONSIDER: [&str; 2] = ["#consider", "oboInOwl:consider"];
+
+/// Parse the alternate term IDs of the node.
+///
+/// The older releases write the IDs as OBO PURLs and the predicate
+/// as a CURIE (e.g. `oboInOwl:hasAlternativeId`) rather than an IRI.
 fn parse_alt_term_ids(node_meta: &Meta) -> Vec<TermId> {
+    parse_term_references(node_meta, &["hasAlternativeId"]).collect()
+}
+
+/// Get the version of the ontology from the graph metadata.
+///
+/// The `owl:versionInfo` property value is preferred over the version IRI.
+fn parse_version(graph_meta: &Meta) -> Option<String> {
+    graph_meta
+        .basic_property_values
+        .iter()
+        .find(|bpv| bpv.pred.ends_with("versionInfo"))
+        .map(|bpv| bpv.val.clone())
+        .or_else(|| graph_meta.version.clone())
+}
+
+/// Get the property values of the node other than the alternate term IDs.
+fn parse_property_values(node_meta: &Meta) -> Vec<PropertyValue> {
     node_meta
         .basic_property_values
         .iter()
-        .filter(|&bpv| bpv.pred.ends_with("#hasAlternativeId"))
-        .flat_map(|bpv| match TermId::from_str(&bpv.val) {
-            Ok(term_id) => Some(term_id),
-            Err(e) => {
-                eprintln!("{}", e); // TODO: really?
-                None
+        .filter(|bpv| !bpv.pred.ends_with("hasAlternativeId"))
+        .map(|bpv| PropertyValue::new(&bpv.pred, &bpv.val))
+        .collect()
+}
+
+/// Get the creator and the creation date of the node, or `None` if there are none.
+///
+/// The first `oboInOwl:created_by` or Dublin Core `creator` value is the creator, and the first
+/// `oboInOwl:creation_date` or Dublin Core `date` value is the creation date.
+fn parse_provenance(node_meta: &Meta) -> Option<TermProvenance> {
+    const CREATOR: [&str; 3] = [
+        "created_by",
+        "http://purl.org/dc/terms/creator",
+        "http://purl.org/dc/elements/1.1/creator",
+    ];
+    const DATE: [&str; 3] = [
+        "creation_date",
+        "http://purl.org/dc/terms/date",
+        "http://purl.org/dc/elements/1.1/date",
+    ];
+    let find = |preds: &[&str]| {
+        node_meta
+            .basic_property_values
+            .iter()
+            .find(|bpv| preds.iter().any(|pred| bpv.pred.ends_with(pred)))
+            .map(|bpv| &bpv.val)
+    };
+
+    match (find(&CREATOR), find(&DATE)) {
+        (None, None) => None,
+        (created_by, creation_date) => {
+            let mut provenance = TermProvenance::default();
+            if let Some(created_by) = created_by {
+                provenance = provenance.with_created_by(created_by);
+            }
+            if let Some(creation_date) = creation_date {
+                provenance = provenance.with_creation_date(creation_date);
             }
+            Some(provenance)
+        }
+    }
+}
+
+/// Get the names of the subsets of the node, such as `goslim_generic`
+/// for `http://purl.obolibrary.org/obo/go#goslim_generic`.
+///
+/// The subsets are read from the `subsets` and from the `oboInOwl:inSubset` property values.
+fn parse_subsets(node_meta: &Meta) -> Vec<String> {
+    let inline = node_meta
+        .basic_property_values
+        .iter()
+        .filter(|bpv| bpv.pred.ends_with("inSubset"))
+        .map(|bpv| &bpv.val);
+    let mut subsets: Vec<String> = vec![];
+    for iri in node_meta.subsets.iter().chain(inline) {
+        let name = iri.rsplit(['#', '/']).next().unwrap_or(iri);
+        if !subsets.iter().any(|subset| subset == name) {
+            subsets.push(name.to_string());
+        }
+    }
+    subsets
+}
+
+/// Test if the node is obsolete.
+///
+/// The releases written by the older Obographs versions have no `deprecated` field
+/// and mark the obsolete terms with the `owl:deprecated` property value instead.
+fn is_deprecated(node_meta: &Meta) -> bool {
+    node_meta.deprecated.unwrap_or_else(|| {
+        node_meta
+            .basic_property_values
+            .iter()
+            .any(|bpv| bpv.pred.ends_with("deprecated") && bpv.val == "true")
+    })
+}
+
+/// Parse the term IDs of the property values with any of the `preds`,
+/// such as the replacements of an obsolete term.
+///
+/// A predicate matches if it ends with any of the `preds`.
+///
+/// The values are CURIEs (e.g. `HP:0001166`) or OBO PURLs
+/// (e.g. `http://purl.obolibrary.org/obo/HP_0001166`).
+fn parse_term_references<'a>(
+    node_meta: &'a Meta,
+    preds: &'a [&'a str],
+) -> impl Iterator<Item = TermId> + 'a {
+    node_meta
+        .basic_property_values
+        .iter()
+        .filter(move |&bpv| preds.iter().any(|pred| bpv.pred.ends_with(pred)))
+        .flat_map(|bpv| match bpv.val.strip_prefix(OBO_PURL) {
+            Some(id) => id
+                .rsplit_once('_')
+                .map(|(prefix, id)| TermId::from((prefix, id))),
+            None => TermId::from_str(&bpv.val).ok(),
+        })
+}
+
+fn parse_synonyms(node_meta: &Meta) -> Vec<Synonym> {
+    node_meta
+        .synonyms
+        .iter()
+        .flat_map(|spv| {
+            let category = parse_synonym_category(&spv.pred)?;
+            let xrefs = spv
+                .xrefs
+                .iter()
+                .flat_map(|xref| parse_xref(xref))
+                .collect();
+            Some(Synonym::new(&spv.val, category, xrefs))
         })
         .collect()
 }
 
-pub struct ObographsParser<CU, HI>
+/// Parse a CURIE or an ORCID URL (e.g. `https://orcid.org/0000-0002-0736-9199`) into a [`TermId`].
+fn parse_xref(xref: &str) -> Option<TermId> {
+    match xref
+        .strip_prefix("https://orcid.org/")
+        .or_else(|| xref.strip_prefix("http://orcid.org/"))
+    {
+        Some(orcid) => Some(TermId::from(("ORCID", orcid))),
+        None => TermId::from_str(xref).ok(),
+    }
+}
+
+fn parse_synonym_category(pred: &str) -> Option<SynonymCategory> {
+    if pred.ends_with("hasExactSynonym") {
+        Some(SynonymCategory::Exact)
+    } else if pred.ends_with("hasRelatedSynonym") {
+        Some(SynonymCategory::Related)
+    } else if pred.ends_with("hasBroadSynonym") {
+        Some(SynonymCategory::Broad)
+    } else if pred.ends_with("hasNarrowSynonym") {
+        Some(SynonymCategory::Narrow)
+    } else {
+        None
+    }
+}
+
+/// The optional parts of the Obographs node metadata to parse.
+///
+/// Skipping the unused parts speeds up loading, e.g. for [`SimpleMinimalTerm`]s
+/// that need neither the synonyms nor the definitions.
+/// The parts that are not selected are skipped over without being deserialized.
+/// The alternate term IDs, the subsets, and the obsoletion status are always parsed.
+///
+/// There is no default set, since the parser default depends on the term type
+/// (see [`ObographsTerm::META_FIELDS`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MetaFields {
+    pub synonyms: bool,
+    pub definitions: bool,
+    pub comments: bool,
+    pub xrefs: bool,
+}
+
+impl MetaFields {
+    /// Parse all parts of the metadata.
+    pub const ALL: MetaFields = MetaFields {
+        synonyms: true,
+        definitions: true,
+        comments: true,
+        xrefs: true,
+    };
+
+    /// Skip all optional parts of the metadata.
+    pub const NONE: MetaFields = MetaFields {
+        synonyms: false,
+        definitions: false,
+        comments: false,
+        xrefs: false,
+    };
+}
+
+/// The terms that can be created from the Obographs nodes.
+pub trait ObographsTerm: MinimalTerm {
+    /// The parts of the node metadata used by [`ObographsTerm::from_node_meta`].
+    const META_FIELDS: MetaFields = MetaFields::ALL;
+
+    /// Create the term with `term_id` and `name` from the node metadata.
+    fn from_node_meta(term_id: TermId, name: &str, meta: Option<&Meta>) -> Self;
+}
+
+impl ObographsTerm for SimpleMinimalTerm {
+    const META_FIELDS: MetaFields = MetaFields::NONE;
+
+    fn from_node_meta(term_id: TermId, name: &str, meta: Option<&Meta>) -> Self {
+        let (alt_term_ids, is_obsolete) = match meta {
+            Some(meta) => (parse_alt_term_ids(meta), is_deprecated(meta)),
+            None => (vec![], false),
+        };
+        SimpleMinimalTerm::new(term_id, name, alt_term_ids, is_obsolete)
+    }
+}
+
+impl ObographsTerm for SimpleTerm {
+    fn from_node_meta(term_id: TermId, name: &str, meta: Option<&Meta>) -> Self {
+        match meta {
+            Some(meta) => {
+                let mut term = SimpleTerm::new(
+                    term_id,
+                    name,
+                    parse_alt_term_ids(meta),
+                    is_deprecated(meta),
+                )
+                .with_synonyms(parse_synonyms(meta))
+                .with_property_values(parse_property_values(meta))
+                .with_subsets(parse_subsets(meta))
+                .with_xrefs(
+                    meta.xrefs
+                        .iter()
+                        .flat_map(|xref| parse_xref(&xref.val))
+                        .collect(),
+                );
+                if let Some(definition) = &meta.definition {
+                    term = term.with_definition(&definition.val);
+                }
+                if let Some(comment) = meta.comments.first() {
+                    term = term.with_comment(comment);
+                }
+                if let Some(replaced_by) = parse_term_references(meta, &REPLACED_BY).next() {
+                    term = term.with_replaced_by(replaced_by);
+                }
+                if let Some(provenance) = parse_provenance(meta) {
+                    term = term.with_provenance(provenance);
+                }
+                term.with_consider(parse_term_references(meta, &CONSIDER).collect())
+            }
+            None => SimpleTerm::new(term_id, name, vec![], false),
+        }
+    }
+}
+
+/// The policy for the Obographs nodes with an id that cannot be parsed into a CURIE,
+/// such as the blank nodes (e.g. `_:b12`) or the IRIs with no known prefix.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum UnparsableIdPolicy {
+    /// Skip the node and its edges, and report the node id in [`OntologyData::skipped_nodes`].
+    #[default]
+    Skip,
+    /// Create a term with a synthetic id with `_` prefix and the node id,
+    /// e.g. `_:b12` for the blank node `_:b12`
+    /// and `_:http://example.org/term` for the IRI `http://example.org/term`.
+    ///
+    /// The node id is used as the term name if the node has no label.
+    Synthetic,
+}
+
+/// Make a synthetic term ID for the node `id` that is not a CURIE.
+fn synthetic_term_id(id: &str) -> TermId {
+    TermId::from(("_", id.strip_prefix("_:").unwrap_or(id)))
+}
+
+/// Obographs edge that skips the edge metadata.
+#[derive(Deserialize)]
+struct LeanEdge {
+    sub: String,
+    pred: String,
+    obj: String,
+}
+
+/// The keys of an Obographs node.
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "camelCase")]
+enum NodeKey {
+    Id,
+    Lbl,
+    #[serde(rename = "type")]
+    NodeType,
+    Meta,
+    #[serde(other)]
+    Other,
+}
+
+/// The keys of the Obographs node metadata.
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "camelCase")]
+enum MetaKey {
+    Definition,
+    Comments,
+    Subsets,
+    Xrefs,
+    Synonyms,
+    BasicPropertyValues,
+    Deprecated,
+    Version,
+    #[serde(other)]
+    Other,
+}
+
+/// Deserialize an Obographs node with the selected parts of the metadata.
+struct NodeSeed(MetaFields);
+
+impl<'de> DeserializeSeed<'de> for NodeSeed {
+    type Value = Node;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Node, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for NodeSeed {
+    type Value = Node;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an Obographs node")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Node, A::Error> {
+        let mut id = None;
+        let mut node = Node::default();
+        while let Some(key) = map.next_key()? {
+            match key {
+                NodeKey::Id => id = Some(map.next_value()?),
+                NodeKey::Lbl => node.lbl = map.next_value()?,
+                NodeKey::NodeType => node.node_type = map.next_value()?,
+                NodeKey::Meta => node.meta = map.next_value_seed(MetaSeed(self.0))?,
+                NodeKey::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        node.id = id.ok_or_else(|| serde::de::Error::missing_field("id"))?;
+        Ok(node)
+    }
+}
+
+/// Deserialize the optional Obographs node metadata,
+/// skipping over the parts that are not selected.
+struct MetaSeed(MetaFields);
+
+impl<'de> DeserializeSeed<'de> for MetaSeed {
+    type Value = Option<Box<Meta>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de> Visitor<'de> for MetaSeed {
+    type Value = Option<Box<Meta>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Obographs node metadata")
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let fields = self.0;
+        let mut meta = Meta::default();
+        while let Some(key) = map.next_key()? {
+            match key {
+                MetaKey::Definition if fields.definitions => meta.definition = map.next_value()?,
+                MetaKey::Comments if fields.comments => meta.comments = map.next_value()?,
+                MetaKey::Xrefs if fields.xrefs => meta.xrefs = map.next_value()?,
+                MetaKey::Synonyms if fields.synonyms => meta.synonyms = map.next_value()?,
+                MetaKey::Subsets => meta.subsets = map.next_value()?,
+                MetaKey::BasicPropertyValues => meta.basic_property_values = map.next_value()?,
+                MetaKey::Deprecated => meta.deprecated = map.next_value()?,
+                MetaKey::Version => meta.version = map.next_value()?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(Some(Box::new(meta)))
+    }
+}
+
+/// The receiver of the parts of an Obographs document, in the order of deserialization.
+///
+/// Only the nodes and the edges of the selected graph are received, the other graphs are skipped.
+trait GraphHandler {
+    /// Get the parts of the node metadata to deserialize.
+    fn fields(&self) -> MetaFields;
+
+    fn context(&mut self, context: serde_json::Value);
+
+    fn graph_meta(&mut self, meta: Box<Meta>);
+
+    fn node(&mut self, node: Node);
+
+    fn edge(&mut self, edge: LeanEdge);
+}
+
+/// The nodes and the edges of a graph that are read before the graph id.
+struct GraphBuffer {
+    fields: MetaFields,
+    meta: Option<Box<Meta>>,
+    nodes: Vec<Node>,
+    edges: Vec<LeanEdge>,
+}
+
+impl GraphHandler for GraphBuffer {
+    fn fields(&self) -> MetaFields {
+        self.fields
+    }
+
+    fn context(&mut self, _context: serde_json::Value) {
+        // The context belongs to the document rather than to a graph.
+    }
+
+    fn graph_meta(&mut self, meta: Box<Meta>) {
+        self.meta = Some(meta);
+    }
+
+    fn node(&mut self, node: Node) {
+        self.nodes.push(node);
+    }
+
+    fn edge(&mut self, edge: LeanEdge) {
+        self.edges.push(edge);
+    }
+}
+
+/// Deserialize the document and return the number of its graphs,
+/// and whether a graph was selected.
+///
+/// The graph with the id is selected, or the first graph if the id is `None`.
+struct DocumentSeed<'h, H>(&'h mut H, Option<&'h str>);
+
+impl<'de, H: GraphHandler> DeserializeSeed<'de> for DocumentSeed<'_, H> {
+    type Value = (usize, bool);
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, H: GraphHandler> Visitor<'de> for DocumentSeed<'_, H> {
+    type Value = (usize, bool);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an Obographs document")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let (mut graphs, mut selected) = (0, false);
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "@context" => self.0.context(map.next_value()?),
+                "graphs" => {
+                    let (count, found) = map.next_value_seed(GraphsSeed(&mut *self.0, self.1))?;
+                    graphs += count;
+                    selected |= found;
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok((graphs, selected))
+    }
+}
+
+/// Deserialize the graphs and return their number, and whether a graph was selected.
+struct GraphsSeed<'h, H>(&'h mut H, Option<&'h str>);
+
+impl<'de, H: GraphHandler> DeserializeSeed<'de> for GraphsSeed<'_, H> {
+    type Value = (usize, bool);
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, H: GraphHandler> Visitor<'de> for GraphsSeed<'_, H> {
+    type Value = (usize, bool);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of Obographs graphs")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let (mut graphs, mut selected) = (0, false);
+        while !selected {
+            match seq.next_element_seed(GraphSeed(&mut *self.0, self.1))? {
+                Some(found) => {
+                    graphs += 1;
+                    selected = found;
+                }
+                None => return Ok((graphs, false)),
+            }
+        }
+        while seq.next_element::<IgnoredAny>()?.is_some() {
+            graphs += 1;
+        }
+        Ok((graphs, true))
+    }
+}
+
+/// Deserialize the graph and return whether it was selected.
+///
+/// The nodes and the edges of a graph that precede its id are buffered until the id is read.
+struct GraphSeed<'h, H>(&'h mut H, Option<&'h str>);
+
+impl<'de, H: GraphHandler> DeserializeSeed<'de> for GraphSeed<'_, H> {
+    type Value = bool;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<bool, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, H: GraphHandler> Visitor<'de> for GraphSeed<'_, H> {
+    type Value = bool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an Obographs graph")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<bool, A::Error> {
+        // `None` until the id of the graph is read.
+        let mut selected = if self.1.is_none() { Some(true) } else { None };
+        let mut buffer = GraphBuffer {
+            fields: self.0.fields(),
+            meta: None,
+            nodes: vec![],
+            edges: vec![],
+        };
+        while let Some(key) = map.next_key::<String>()? {
+            match (key.as_str(), selected) {
+                ("id", None) => {
+                    let id: String = map.next_value()?;
+                    let found = self.1 == Some(id.as_str());
+                    if found {
+                        if let Some(meta) = buffer.meta.take() {
+                            self.0.graph_meta(meta);
+                        }
+                        buffer.nodes.drain(..).for_each(|node| self.0.node(node));
+                        buffer.edges.drain(..).for_each(|edge| self.0.edge(edge));
+                    }
+                    selected = Some(found);
+                }
+                ("meta", Some(true)) => {
+                    if let Some(meta) = map.next_value_seed(MetaSeed(MetaFields::NONE))? {
+                        self.0.graph_meta(meta);
+                    }
+                }
+                ("meta", None) => buffer.meta = map.next_value_seed(MetaSeed(MetaFields::NONE))?,
+                ("nodes", Some(true)) => map.next_value_seed(NodesSeed(&mut *self.0))?,
+                ("edges", Some(true)) => map.next_value_seed(EdgesSeed(&mut *self.0))?,
+                ("nodes", None) => map.next_value_seed(NodesSeed(&mut buffer))?,
+                ("edges", None) => map.next_value_seed(EdgesSeed(&mut buffer))?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(selected == Some(true))
+    }
+}
+
+struct NodesSeed<'h, H>(&'h mut H);
+
+impl<'de, H: GraphHandler> DeserializeSeed<'de> for NodesSeed<'_, H> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, H: GraphHandler> Visitor<'de> for NodesSeed<'_, H> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of Obographs nodes")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let fields = self.0.fields();
+        while let Some(node) = seq.next_element_seed(NodeSeed(fields))? {
+            self.0.node(node);
+        }
+        Ok(())
+    }
+}
+
+struct EdgesSeed<'h, H>(&'h mut H);
+
+impl<'de, H: GraphHandler> DeserializeSeed<'de> for EdgesSeed<'_, H> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, H: GraphHandler> Visitor<'de> for EdgesSeed<'_, H> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of Obographs edges")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(edge) = seq.next_element::<LeanEdge>()? {
+            self.0.edge(edge);
+        }
+        Ok(())
+    }
+}
+
+/// Parse the pairs of a CURIE prefix and its IRI expansion from the JSON-LD `context`.
+///
+/// The context can be an object or an array of objects. The prefix is expanded either
+/// into a string (e.g. `"HP": "http://purl.obolibrary.org/obo/HP_"`) or into an object with `@id`.
+/// The JSON-LD keywords (e.g. `@vocab`) and the remote contexts are ignored.
+fn parse_context(context: &serde_json::Value) -> Vec<(String, String)> {
+    match context {
+        serde_json::Value::Array(contexts) => contexts.iter().flat_map(parse_context).collect(),
+        serde_json::Value::Object(declarations) => declarations
+            .iter()
+            .filter(|(prefix, _)| !prefix.starts_with('@'))
+            .filter_map(|(prefix, expansion)| {
+                let expansion = match expansion {
+                    serde_json::Value::String(iri) => iri,
+                    serde_json::Value::Object(definition) => definition.get("@id")?.as_str()?,
+                    _ => return None,
+                };
+                Some((prefix.clone(), expansion.to_string()))
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Parse the IRI `id` into a term ID using the longest matching expansion of the `prefix_map`.
+///
+/// The `prefix_map` must be sorted by the expansion length in descending order.
+fn expand_with(prefix_map: &[(String, String)], id: &str) -> Option<TermId> {
+    prefix_map.iter().find_map(|(prefix, expansion)| {
+        id.strip_prefix(expansion.as_str())
+            .filter(|local| !local.is_empty())
+            .map(|local| TermId::from((prefix.as_str(), local)))
+    })
+}
+
+/// Sort the prefix map to check the longest expansion first.
+fn sort_prefix_map(prefix_map: &mut [(String, String)]) {
+    prefix_map.sort_by_key(|(_, expansion)| std::cmp::Reverse(expansion.len()));
+}
+
+/// Parser of the Obographs JSON files.
+///
+/// [`ObographsParser::new`] creates [`SimpleMinimalTerm`]s and skips the node metadata
+/// they do not need (see [`ObographsTerm::META_FIELDS`]).
+/// Use [`ObographsParser::with_term_type`] to parse other [`ObographsTerm`]s,
+/// such as [`SimpleTerm`] with the synonyms and the definition.
+///
+/// Unless configured otherwise, the node ids are resolved by the CURIE util
+/// and by the JSON-LD context of the document, and the nodes with other ids are skipped
+/// (see [`UnparsableIdPolicy`]).
+pub struct ObographsParser<CU, HI, T = SimpleMinimalTerm>
 where
     CU: CurieUtil,
 {
     curie_util: CU,
-    _marker: PhantomData<HI>,
+    prefix_map: Vec<(String, String)>,
+    unparsable_id_policy: UnparsableIdPolicy,
+    meta_fields: Option<MetaFields>,
+    _marker: PhantomData<(HI, T)>,
 }
 
 impl<CU, HI> ObographsParser<CU, HI>
@@ -42,137 +739,722 @@ where
     CU: CurieUtil,
 {
     pub fn new(curie_util: CU) -> Self {
+        Self::with_term_type(curie_util)
+    }
+}
+
+impl<CU, HI, T> ObographsParser<CU, HI, T>
+where
+    CU: CurieUtil,
+{
+    /// Create a parser for terms of type `T`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use curie_util::TrieCurieUtil;
+    /// use ontolius::base::term::simple::SimpleTerm;
+    /// use ontolius::io::obographs::ObographsParser;
+    /// use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    ///
+    /// let loader = OntologyLoaderBuilder::new()
+    ///                .parser(ObographsParser::with_term_type(TrieCurieUtil::default()))
+    ///                .build();
+    /// // The term type is inferred from the ontology type.
+    /// let hpo: CsrOntology<usize, SimpleTerm> = loader.load_from_path("resources/hp.small.json.gz")
+    ///                                             .expect("HPO should be loaded");
+    ///
+    /// let arachnodactyly = hpo.id_to_term(&TermId::from(("HP", "0001166")))
+    ///                         .expect("Arachnodactyly should be present");
+    /// assert!(arachnodactyly.definition().is_some());
+    /// ```
+    pub fn with_term_type(curie_util: CU) -> Self {
         Self {
             curie_util,
+            prefix_map: vec![],
+            unparsable_id_policy: UnparsableIdPolicy::default(),
+            meta_fields: None,
             _marker: PhantomData,
         }
     }
 
-    fn create(&self, data: &Node) -> Result<SimpleMinimalTerm, OntoliusError> {
-        let cp = self.curie_util.get_curie_data(&data.id);
-        let name = &data.lbl;
+    /// Add the `prefix_map` with the pairs of a CURIE prefix (e.g. `MESH`)
+    /// and its IRI expansion (e.g. `http://id.nlm.nih.gov/mesh/`).
+    ///
+    /// The prefix map takes precedence over the CURIE util, e.g. to resolve the IRIs
+    /// outside of the OBO namespace. The longest matching expansion wins.
+    ///
+    /// The prefixes declared in the JSON-LD `@context` of the document are used as well,
+    /// as a fallback for the IRIs that neither the prefix map nor the CURIE util can resolve.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use curie_util::TrieCurieUtil;
+    /// use ontolius::io::obographs::ObographsParser;
+    /// use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    ///
+    /// let document = r#"{"graphs": [{
+    ///     "nodes": [
+    ///         {"id": "http://id.nlm.nih.gov/mesh/D009139", "lbl": "Musculoskeletal Abnormalities"},
+    ///         {"id": "http://id.nlm.nih.gov/mesh/D054119", "lbl": "Arachnodactyly"}
+    ///     ],
+    ///     "edges": [
+    ///         {"sub": "http://id.nlm.nih.gov/mesh/D054119", "pred": "is_a", "obj": "http://id.nlm.nih.gov/mesh/D009139"}
+    ///     ]
+    /// }]}"#;
+    ///
+    /// let parser = ObographsParser::new(TrieCurieUtil::default())
+    ///                .with_prefix_map([("MESH", "http://id.nlm.nih.gov/mesh/")]);
+    /// let loader = OntologyLoaderBuilder::new()
+    ///                .parser(parser)
+    ///                .build();
+    /// let mesh: CsrOntology<usize, _> = loader.load_from_buf_read(&mut document.as_bytes())
+    ///                                     .expect("The document should be loaded");
+    ///
+    /// let arachnodactyly = mesh.id_to_term(&TermId::from(("MESH", "D054119")))
+    ///                          .expect("The MeSH term should be resolved");
+    /// assert_eq!(arachnodactyly.name(), "Arachnodactyly");
+    /// ```
+    #[must_use]
+    pub fn with_prefix_map<I, P, E>(mut self, prefix_map: I) -> Self
+    where
+        I: IntoIterator<Item = (P, E)>,
+        P: ToString,
+        E: ToString,
+    {
+        self.prefix_map.extend(
+            prefix_map
+                .into_iter()
+                .map(|(prefix, expansion)| (prefix.to_string(), expansion.to_string())),
+        );
+        sort_prefix_map(&mut self.prefix_map);
+        self
+    }
+
+    /// Set the parts of the node metadata to parse.
+    ///
+    /// By default, the parser parses the parts used by the term type (see [`ObographsTerm::META_FIELDS`]).
+    /// See [`MetaFields`] for the set that speeds up the loading.
+    #[must_use]
+    pub fn meta_fields(mut self, fields: MetaFields) -> Self {
+        self.meta_fields = Some(fields);
+        self
+    }
 
-        match (cp, name) {
-            (Some(cp), Some(name)) => {
-                let term_id = TermId::from((cp.get_prefix(), cp.get_id()));
-                let (alt_term_ids, is_obsolete) = match &data.meta {
-                    Some(meta) => (parse_alt_term_ids(meta), meta.deprecated.unwrap_or(false)),
-                    None => (vec![], false),
-                };
-                Ok(SimpleMinimalTerm::new(
-                    term_id,
-                    name,
-                    alt_term_ids,
-                    is_obsolete,
-                ))
-            }
-            (Some(cp), None) => Err(OntoliusError::OntologyDataParseError(format!(
-                "Missing term label for {}:{}",
-                cp.get_prefix(),
-                cp.get_id()
-            ))),
-            (None, Some(lbl)) => Err(OntoliusError::OntologyDataParseError(format!(
-                "Unparsable term id of {lbl}: {}",
-                &data.id
-            ))),
-            _ => Err(OntoliusError::OntologyDataParseError(
-                "Unparsable node".to_owned(),
-            )),
-        }
+    /// Set the policy for the nodes with an id that cannot be parsed into a CURIE.
+    ///
+    /// The nodes are skipped by default.
+    #[must_use]
+    pub fn unparsable_id_policy(mut self, policy: UnparsableIdPolicy) -> Self {
+        self.unparsable_id_policy = policy;
+        self
     }
 }
 
-impl<CU, I> OntologyDataParser for ObographsParser<CU, I>
+impl<CU, HI, T> ObographsParser<CU, HI, T>
 where
     CU: CurieUtil,
-    I: HierarchyIdx + TermIdx,
+    T: ObographsTerm,
+{
+    /// Parse the node IRI into a term ID using the prefix map, the CURIE util,
+    /// or the `context` prefixes of the document, in this order.
+    fn parse_node_id(&self, context: &[(String, String)], id: &str) -> Option<TermId> {
+        expand_with(&self.prefix_map, id)
+            .or_else(|| {
+                self.curie_util
+                    .get_curie_data(id)
+                    .map(|cp| TermId::from((cp.get_prefix(), cp.get_id())))
+            })
+            .or_else(|| expand_with(context, id))
+    }
+
+    /// Get the CURIE of the node `id`, or the `id` if it is not a CURIE.
+    ///
+    /// The CURIE is the key for matching the edge endpoints to the nodes and for reporting.
+    fn endpoint_id(&self, context: &[(String, String)], id: &str) -> String {
+        self.parse_node_id(context, id)
+            .map(|term_id| term_id.to_string())
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    fn create(&self, context: &[(String, String)], data: &Node) -> Result<T, OntoliusError> {
+        let (term_id, is_synthetic) = match self.parse_node_id(context, &data.id) {
+            Some(term_id) => (term_id, false),
+            None if self.unparsable_id_policy == UnparsableIdPolicy::Synthetic => {
+                (synthetic_term_id(&data.id), true)
+            }
+            None => {
+                return Err(OntoliusError::OntologyDataParseError(format!(
+                    "Unparsable term id of {}: {}",
+                    data.lbl.as_deref().unwrap_or("unlabeled node"),
+                    &data.id
+                )))
+            }
+        };
+
+        let name = match &data.lbl {
+            Some(name) => name,
+            None if is_synthetic => &data.id,
+            None => {
+                return Err(OntoliusError::OntologyDataParseError(format!(
+                    "Missing term label for {term_id}"
+                )))
+            }
+        };
+
+        Ok(T::from_node_meta(term_id, name, data.meta.as_deref()))
+    }
+}
+
+/// The parser reads the document in a single pass. The terms and the edges are created
+/// as the nodes and the edges are deserialized, hence the document is never materialized
+/// to keep the memory bounded for large ontologies, such as NCIT or MONDO.
+///
+/// With the `rayon` feature, the terms are created from the nodes and the edges are resolved
+/// in parallel, in batches of the nodes and the edges as they are read.
+impl<CU, I, T> OntologyDataParser for ObographsParser<CU, I, T>
+where
+    CU: CurieUtil + ParallelSafe,
+    I: HierarchyIdx + TermIdx + ParallelSafe,
+    T: ObographsTerm + ParallelSafe,
 {
     type HI = I;
-    type T = SimpleMinimalTerm;
+    type T = T;
 
     fn load_from_buf_read<R: BufRead>(
         &self,
         read: &mut R,
     ) -> Result<OntologyData<Self::HI, Self::T>, OntoliusError> {
-        let gd = match GraphDocument::from_reader(read) {
-            Ok(g) => g,
-            Err(_) => {
-                return Err(OntoliusError::OntologyDataParseError(
-                    "Unable to read obographs document".into(),
-                ))
+        self.load_graph(read, None)
+    }
+
+    /// Load the graph with the `graph_id`, e.g. `http://purl.obolibrary.org/obo/hp.owl`,
+    /// from a document with more than one graph.
+    fn load_graph_from_buf_read<R>(
+        &self,
+        read: &mut R,
+        graph_id: &str,
+    ) -> Result<OntologyData<Self::HI, Self::T>, OntoliusError>
+    where
+        R: BufRead,
+    {
+        self.load_graph(read, Some(graph_id))
+    }
+}
+
+impl<CU, I, T> ObographsParser<CU, I, T>
+where
+    CU: CurieUtil + ParallelSafe,
+    I: HierarchyIdx + TermIdx + ParallelSafe,
+    T: ObographsTerm + ParallelSafe,
+{
+    /// Load the graph with the `graph_id`, or the first graph if the id is `None`.
+    fn load_graph<R>(
+        &self,
+        read: &mut R,
+        graph_id: Option<&str>,
+    ) -> Result<OntologyData<I, T>, OntoliusError>
+    where
+        R: BufRead,
+    {
+        let fields = self.meta_fields.unwrap_or(T::META_FIELDS);
+        let mut stream = GraphStream::new(self, fields);
+
+        let mut deserializer = serde_json::Deserializer::from_reader(read);
+        let (graphs, selected) = DocumentSeed(&mut stream, graph_id)
+            .deserialize(&mut deserializer)
+            .and_then(|graphs| deserializer.end().map(|_| graphs))
+            .map_err(|_| {
+                OntoliusError::OntologyDataParseError("Unable to read obographs document".into())
+            })?;
+
+        match graph_id {
+            Some(graph_id) if graphs > 0 && !selected => {
+                Err(OntoliusError::OntologyDataParseError(format!(
+                    "Graph document had no graph with id {graph_id}"
+                )))
+            }
+            _ => stream.finish(graphs),
+        }
+    }
+}
+
+/// The number of the nodes or the edges that are processed in parallel with the `rayon` feature.
+#[cfg(feature = "rayon")]
+const PARALLEL_BATCH_SIZE: usize = 4096;
+
+/// The bounds of the parser parts that are shared by the worker threads with the `rayon` feature.
+///
+/// The bounds are empty if the feature is disabled.
+#[cfg(feature = "rayon")]
+pub trait ParallelSafe: Send + Sync {}
+
+#[cfg(feature = "rayon")]
+impl<X: Send + Sync> ParallelSafe for X {}
+
+/// The bounds of the parser parts that are shared by the worker threads with the `rayon` feature.
+///
+/// The bounds are empty if the feature is disabled.
+#[cfg(not(feature = "rayon"))]
+pub trait ParallelSafe {}
+
+#[cfg(not(feature = "rayon"))]
+impl<X> ParallelSafe for X {}
+
+/// The state of parsing the first graph of a document, node by node and edge by edge.
+///
+/// The nodes and the edges are usually resolved as soon as they are read,
+/// or in batches with the `rayon` feature.
+/// The few that depend on the rest of the document are kept until the document ends:
+/// the nodes with an id that may need the prefixes of a JSON-LD context declared after the graphs,
+/// and the edges with an endpoint that is not among the nodes read so far.
+/// The terms of the kept nodes are renumbered into the document order at the end.
+struct GraphStream<'p, CU, I, T>
+where
+    CU: CurieUtil,
+    I: HierarchyIdx,
+{
+    parser: &'p ObographsParser<CU, I, T>,
+    fields: MetaFields,
+    /// The prefixes of the document context, or `None` if the context has not been read yet.
+    context: Option<Vec<(String, String)>>,
+    terms: Vec<T>,
+    /// The positions of the nodes of the `terms` in the document.
+    positions: Vec<usize>,
+    /// The number of the nodes read so far.
+    node_count: usize,
+    edges: Vec<GraphEdge<I>>,
+    /// The term indices by the node key (see [`ObographsParser::endpoint_id`]).
+    nodeid2idx: HashMap<String, I>,
+    /// The errors of the skipped nodes by the node key.
+    skipped: HashMap<String, String>,
+    /// The ids of the skipped class nodes.
+    skipped_nodes: Vec<String>,
+    skipped_edges: Vec<String>,
+    /// The nodes that wait for the context, along with their positions in the document.
+    pending_nodes: Vec<(usize, Node)>,
+    pending_edges: Vec<LeanEdge>,
+    #[cfg(feature = "rayon")]
+    node_batch: Vec<(usize, Node)>,
+    #[cfg(feature = "rayon")]
+    edge_batch: Vec<LeanEdge>,
+    unresolved_edges: Vec<UnresolvedEdge>,
+    relationships: Relationships,
+    /// The replacements of the obsolete terms.
+    replacements: HashMap<TermId, TermId>,
+    /// The version of the ontology from the graph metadata.
+    version: Option<String>,
+}
+
+/// The outcome of resolving an edge against the nodes read so far.
+enum EdgeResolution<I> {
+    Child(I, I),
+    Relationship(I, TermId, I),
+    Unresolved(UnresolvedEdge),
+    /// The predicate of the edge is unknown.
+    Unknown,
+    /// The edge may be resolved once the rest of the document is read.
+    Pending,
+}
+
+/// Resolve the edges against the nodes read so far, independently of each other.
+struct EdgeResolver<'a, CU, I, T>
+where
+    CU: CurieUtil,
+{
+    parser: &'a ObographsParser<CU, I, T>,
+    context: Option<&'a [(String, String)]>,
+    nodeid2idx: &'a HashMap<String, I>,
+    skipped: &'a HashMap<String, String>,
+}
+
+impl<CU, I, T> EdgeResolver<'_, CU, I, T>
+where
+    CU: CurieUtil,
+    I: HierarchyIdx + TermIdx,
+    T: ObographsTerm,
+{
+    /// Resolve the `edge`, or postpone it unless this is the `last` chance to resolve it.
+    fn resolve(&self, edge: &LeanEdge, last: bool) -> EdgeResolution<I> {
+        let context = self.context.unwrap_or_default();
+        // The endpoints may be IRIs or CURIEs, regardless of the form of the node ids.
+        let sub_key = self.parser.endpoint_id(context, &edge.sub);
+        let obj_key = self.parser.endpoint_id(context, &edge.obj);
+        let (Some(&sub), Some(&obj)) =
+            (self.nodeid2idx.get(&sub_key), self.nodeid2idx.get(&obj_key))
+        else {
+            if !last {
+                return EdgeResolution::Pending;
             }
+            return match parse_relationship(&edge.pred) {
+                Ok(_) => EdgeResolution::Unresolved(UnresolvedEdge {
+                    sub_reason: (!self.nodeid2idx.contains_key(&sub_key))
+                        .then(|| unresolved_reason(self.skipped, &sub_key)),
+                    obj_reason: (!self.nodeid2idx.contains_key(&obj_key))
+                        .then(|| unresolved_reason(self.skipped, &obj_key)),
+                    sub: sub_key,
+                    obj: obj_key,
+                }),
+                Err(_) => EdgeResolution::Unknown,
+            };
         };
 
-        if let Some(graph) = gd.graphs.first() {
-            let terms: Vec<_> = graph
-                .nodes
-                .iter()
-                .flat_map(|node| self.create(node).ok())
-                .collect();
+        if is_subclass_of(&edge.pred) {
+            return EdgeResolution::Child(sub, obj);
+        }
+        match self.parser.parse_node_id(context, &edge.pred) {
+            // The context prefixes may resolve the predicate.
+            None if self.context.is_none() && !last => EdgeResolution::Pending,
+            pred => match pred.or_else(|| TermId::from_str(&edge.pred).ok()) {
+                Some(pred) => EdgeResolution::Relationship(sub, pred, obj),
+                None => EdgeResolution::Unknown,
+            },
+        }
+    }
+}
 
-            let term_ids: Vec<_> = terms.iter().map(Identified::identifier).collect();
-            let termid2idx: HashMap<_, _> = term_ids
-                .iter()
-                .enumerate()
-                .map(|(i, &t)| (t.to_string(), I::new(i)))
-                .collect();
+impl<'p, CU, I, T> GraphStream<'p, CU, I, T>
+where
+    CU: CurieUtil + ParallelSafe,
+    I: HierarchyIdx + TermIdx + ParallelSafe,
+    T: ObographsTerm + ParallelSafe,
+{
+    fn new(parser: &'p ObographsParser<CU, I, T>, fields: MetaFields) -> Self {
+        Self {
+            parser,
+            fields,
+            context: None,
+            terms: vec![],
+            positions: vec![],
+            node_count: 0,
+            edges: vec![],
+            nodeid2idx: HashMap::new(),
+            skipped: HashMap::new(),
+            skipped_nodes: vec![],
+            skipped_edges: vec![],
+            pending_nodes: vec![],
+            pending_edges: vec![],
+            #[cfg(feature = "rayon")]
+            node_batch: vec![],
+            #[cfg(feature = "rayon")]
+            edge_batch: vec![],
+            unresolved_edges: vec![],
+            relationships: Relationships::default(),
+            replacements: HashMap::new(),
+            version: None,
+        }
+    }
 
-            let edges: Vec<GraphEdge<_>> = graph
-                .edges
-                .iter()
-                .flat_map(|edge| parse_edge(edge, &self.curie_util, &termid2idx))
-                .collect();
+    /// Add the term created from the `node` at the `position` with the `key`,
+    /// or report the `node` as skipped.
+    ///
+    /// The skipped nodes that are not classes, such as the unlabeled properties,
+    /// are not reported in [`OntologyData::skipped_nodes`].
+    fn add_node(
+        &mut self,
+        position: usize,
+        node: &Node,
+        key: String,
+        term: Result<T, OntoliusError>,
+    ) {
+        match term {
+            Ok(term) => {
+                if let (true, Some(meta)) = (term.is_obsolete(), &node.meta) {
+                    if let Some(replaced_by) = parse_term_references(meta, &REPLACED_BY).next() {
+                        self.replacements
+                            .insert(term.identifier().clone(), replaced_by);
+                    }
+                }
+                self.nodeid2idx.insert(key, I::new(self.terms.len()));
+                self.terms.push(term);
+                self.positions.push(position);
+            }
+            Err(e) => {
+                self.skipped.insert(key, e.to_string());
+                if node.node_type.as_deref().is_none_or(|t| t == "CLASS") {
+                    self.skipped_nodes.push(node.id.clone());
+                }
+            }
+        }
+    }
+
+    /// Create the terms from the `nodes`, in parallel with the `rayon` feature.
+    fn add_nodes(&mut self, nodes: Vec<(usize, Node)>) {
+        let context = self.context.as_deref().unwrap_or_default();
+        #[cfg(feature = "rayon")]
+        let terms: Vec<_> = {
+            use rayon::prelude::*;
+            nodes
+                .par_iter()
+                .map(|(_, node)| {
+                    let key = self.parser.endpoint_id(context, &node.id);
+                    (key, self.parser.create(context, node))
+                })
+                .collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let terms: Vec<_> = nodes
+            .iter()
+            .map(|(_, node)| {
+                let key = self.parser.endpoint_id(context, &node.id);
+                (key, self.parser.create(context, node))
+            })
+            .collect();
 
-            let metadata = HashMap::new(); // TODO: parse out metadata
+        for ((position, node), (key, term)) in nodes.iter().zip(terms) {
+            self.add_node(*position, node, key, term);
+        }
+    }
 
-            Ok(OntologyData::from((
-                terms.into_boxed_slice(),
-                edges.into_boxed_slice(),
-                metadata,
-            )))
-        } else {
-            Err(OntoliusError::OntologyDataParseError(format!(
-                "Graph document had {}!=1 graphs",
-                gd.graphs.len()
-            )))
+    fn resolver(&self) -> EdgeResolver<'_, CU, I, T> {
+        EdgeResolver {
+            parser: self.parser,
+            context: self.context.as_deref(),
+            nodeid2idx: &self.nodeid2idx,
+            skipped: &self.skipped,
         }
     }
+
+    /// Add the `edges` to the hierarchy edges or to the relationships,
+    /// or keep them for later unless this is the `last` chance to resolve them.
+    ///
+    /// The edges are resolved in parallel with the `rayon` feature.
+    fn add_edges(&mut self, edges: Vec<LeanEdge>, last: bool) {
+        let resolver = self.resolver();
+        #[cfg(feature = "rayon")]
+        let resolutions: Vec<_> = {
+            use rayon::prelude::*;
+            edges
+                .par_iter()
+                .map(|edge| resolver.resolve(edge, last))
+                .collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let resolutions: Vec<_> = edges
+            .iter()
+            .map(|edge| resolver.resolve(edge, last))
+            .collect();
+
+        for (edge, resolution) in edges.into_iter().zip(resolutions) {
+            match resolution {
+                EdgeResolution::Child(sub, obj) => {
+                    self.edges
+                        .push(GraphEdge::from((sub, Relationship::Child, obj)))
+                }
+                EdgeResolution::Relationship(sub, pred, obj) => {
+                    self.relationships.insert(
+                        self.terms[TermIdx::index(sub)].identifier().clone(),
+                        pred,
+                        self.terms[TermIdx::index(obj)].identifier().clone(),
+                    );
+                }
+                EdgeResolution::Unresolved(unresolved) => self.unresolved_edges.push(unresolved),
+                EdgeResolution::Unknown => self
+                    .skipped_edges
+                    .push(format!("{} {} {}", edge.sub, edge.pred, edge.obj)),
+                EdgeResolution::Pending => self.pending_edges.push(edge),
+            }
+        }
+    }
+
+    /// Process the batched nodes, and then the batched edges that may refer to the nodes.
+    #[cfg(feature = "rayon")]
+    fn flush(&mut self) {
+        if !self.node_batch.is_empty() {
+            let nodes = std::mem::take(&mut self.node_batch);
+            self.add_nodes(nodes);
+        }
+        if !self.edge_batch.is_empty() {
+            let edges = std::mem::take(&mut self.edge_batch);
+            self.add_edges(edges, false);
+        }
+    }
+
+    /// Put the terms into the document order of their nodes and update the edges accordingly.
+    fn renumber(&mut self) {
+        let mut order: Vec<_> = (0..self.terms.len()).collect();
+        order.sort_unstable_by_key(|&idx| self.positions[idx]);
+        let mut new_idx = vec![0; order.len()];
+        for (new, &old) in order.iter().enumerate() {
+            new_idx[old] = new;
+        }
+
+        let mut terms: Vec<_> = std::mem::take(&mut self.terms)
+            .into_iter()
+            .map(Some)
+            .collect();
+        self.terms = order
+            .iter()
+            .map(|&old| terms[old].take().expect("Each term should be moved once"))
+            .collect();
+        for edge in self.edges.iter_mut() {
+            edge.sub = I::new(new_idx[TermIdx::index(edge.sub)]);
+            edge.obj = I::new(new_idx[TermIdx::index(edge.obj)]);
+        }
+    }
+
+    /// Resolve the pending nodes and edges once the whole document with `graphs` has been read.
+    fn finish(mut self, graphs: usize) -> Result<OntologyData<I, T>, OntoliusError> {
+        if graphs == 0 {
+            return Err(OntoliusError::OntologyDataParseError(format!(
+                "Graph document had {graphs}!=1 graphs"
+            )));
+        }
+
+        #[cfg(feature = "rayon")]
+        self.flush();
+        self.context.get_or_insert_with(Vec::new);
+        let nodes = std::mem::take(&mut self.pending_nodes);
+        let renumber = !nodes.is_empty();
+        self.add_nodes(nodes);
+        let edges = std::mem::take(&mut self.pending_edges);
+        self.add_edges(edges, true);
+        if renumber {
+            self.renumber();
+        }
+
+        let metadata = self
+            .version
+            .map(|version| HashMap::from([("version".to_string(), version)]))
+            .unwrap_or_default();
+
+        Ok(OntologyData::from((
+            self.terms.into_boxed_slice(),
+            self.edges.into_boxed_slice(),
+            metadata,
+        ))
+        .with_skipped_nodes(self.skipped_nodes)
+        .with_skipped_edges(self.skipped_edges)
+        .with_unresolved_edges(self.unresolved_edges)
+        .with_relationships(self.relationships)
+        .with_replacements(self.replacements))
+    }
 }
 
-fn parse_edge<HI: HierarchyIdx>(
-    edge: &Edge,
-    curie_util: &dyn CurieUtil,
-    termid2idx: &HashMap<String, HI>,
-) -> Option<GraphEdge<HI>> {
-    let sub_parts = curie_util.get_curie_data(&edge.sub);
-    let rel = parse_relationship(&edge.pred);
-    let obj_parts = curie_util.get_curie_data(&edge.obj);
-    match (sub_parts, rel, obj_parts) {
-        (Some(sub), Ok(pred), Some(obj)) => {
-            // TODO: the matching is hacky and likely inefficient. Improve!
-            let sub = format!("{}:{}", sub.get_prefix(), sub.get_id());
-            let obj = format!("{}:{}", obj.get_prefix(), obj.get_id());
-            match (termid2idx.get(&sub), termid2idx.get(&obj)) {
-                (Some(sub_idx), Some(obj_idx)) => Some(GraphEdge::from((*sub_idx, pred, *obj_idx))),
-                _ => None,
+impl<CU, I, T> GraphHandler for GraphStream<'_, CU, I, T>
+where
+    CU: CurieUtil + ParallelSafe,
+    I: HierarchyIdx + TermIdx + ParallelSafe,
+    T: ObographsTerm + ParallelSafe,
+{
+    fn fields(&self) -> MetaFields {
+        self.fields
+    }
+
+    fn context(&mut self, context: serde_json::Value) {
+        let mut context = parse_context(&context);
+        sort_prefix_map(&mut context);
+        self.context = Some(context);
+    }
+
+    fn graph_meta(&mut self, meta: Box<Meta>) {
+        self.version = parse_version(&meta);
+    }
+
+    fn node(&mut self, node: Node) {
+        let position = self.node_count;
+        self.node_count += 1;
+        if self.context.is_none() && self.parser.parse_node_id(&[], &node.id).is_none() {
+            // The prefixes of a context declared after the graphs may resolve the id.
+            self.pending_nodes.push((position, node));
+            return;
+        }
+
+        #[cfg(feature = "rayon")]
+        {
+            self.node_batch.push((position, node));
+            if self.node_batch.len() >= PARALLEL_BATCH_SIZE {
+                self.flush();
             }
         }
-        (_, Err(e), _) => {
-            println!("Missing relationship: {e}");
-            None
+        #[cfg(not(feature = "rayon"))]
+        {
+            let context = self.context.as_deref().unwrap_or_default();
+            let key = self.parser.endpoint_id(context, &node.id);
+            let term = self.parser.create(context, &node);
+            self.add_node(position, &node, key, term);
         }
-        _ => None,
+    }
+
+    fn edge(&mut self, edge: LeanEdge) {
+        #[cfg(feature = "rayon")]
+        {
+            if !self.node_batch.is_empty() {
+                // The edges may refer to the batched nodes.
+                self.flush();
+            }
+            self.edge_batch.push(edge);
+            if self.edge_batch.len() >= PARALLEL_BATCH_SIZE {
+                self.flush();
+            }
+        }
+        #[cfg(not(feature = "rayon"))]
+        self.add_edges(vec![edge], false);
     }
 }
 
+/// Explain why the node `id` is not among the parsed nodes.
+fn unresolved_reason(skipped: &HashMap<String, String>, id: &str) -> UnresolvedReason {
+    match skipped.get(id) {
+        Some(message) => UnresolvedReason::Skipped(message.clone()),
+        None => UnresolvedReason::Undeclared,
+    }
+}
+
+/// Test if the edge `pred`icate is the subclass relationship.
+///
+/// The older releases spell out `rdfs:subClassOf` as a CURIE or an IRI instead of `is_a`.
+fn is_subclass_of(pred: &str) -> bool {
+    matches!(
+        pred,
+        "is_a"
+            | "subClassOf"
+            | "rdfs:subClassOf"
+            | "http://www.w3.org/2000/01/rdf-schema#subClassOf"
+    )
+}
+
 fn parse_relationship(pred: &str) -> Result<Relationship, OntoliusError> {
-    match pred {
-        // This may be too simplistic
-        "is_a" => Ok(Relationship::Child),
-        _ => Err(OntoliusError::OntologyDataParseError(format!(
+    if is_subclass_of(pred) {
+        Ok(Relationship::Child)
+    } else {
+        Err(OntoliusError::OntologyDataParseError(format!(
             "Unknown predicate {}",
             pred
-        ))),
+        )))
+    }
+}
+
+impl<CU, HI, T> OntologyLoaderBuilder<WithParser<ObographsParser<CU, HI, T>>>
+where
+    CU: CurieUtil + ParallelSafe,
+    HI: HierarchyIdx + TermIdx + ParallelSafe,
+    T: ObographsTerm + ParallelSafe,
+{
+    /// Add the `prefix_map` to the [`ObographsParser`] to resolve the IRIs with custom prefixes.
+    ///
+    /// See [`ObographsParser::with_prefix_map`] for more info.
+    #[must_use]
+    pub fn prefix_map<I, P, E>(mut self, prefix_map: I) -> Self
+    where
+        I: IntoIterator<Item = (P, E)>,
+        P: ToString,
+        E: ToString,
+    {
+        self.state.parser = self.state.parser.with_prefix_map(prefix_map);
+        self
+    }
+
+    /// Set the parts of the node metadata for the [`ObographsParser`] to parse.
+    ///
+    /// See [`ObographsParser::meta_fields`] for more info.
+    #[must_use]
+    pub fn meta_fields(mut self, fields: MetaFields) -> Self {
+        self.state.parser = self.state.parser.meta_fields(fields);
+        self
     }
 }
 
@@ -180,12 +1462,977 @@ fn parse_relationship(pred: &str) -> Result<Relationship, OntoliusError> {
 impl OntologyLoaderBuilder<Uninitialized> {
     /// Load ontology graphs using [`ObographsParser`].        
     #[must_use]
-    pub fn obographs_parser<HI: HierarchyIdx + TermIdx>(
+    pub fn obographs_parser<HI: HierarchyIdx + TermIdx + ParallelSafe>(
         self,
     ) -> OntologyLoaderBuilder<WithParser<ObographsParser<TrieCurieUtil, HI>>> {
         let parser = ObographsParser::new(TrieCurieUtil::default());
-        OntologyLoaderBuilder {
-            state: WithParser { parser },
+        OntologyLoaderBuilder::new().parser(parser)
+    }
+}
+
+/// Write the `subgraph` of the `ontology` as an Obographs JSON document,
+/// e.g. a sample created by [`Ontology::sample_subgraph`] to use in tests.
+///
+/// The nodes include the labels and the obsolete flags of the terms,
+/// and the edges are the `is_a` edges of the subgraph. The term IDs are written as OBO PURLs
+/// (e.g. `http://purl.obolibrary.org/obo/HP_0001166`). Wrap the `write`
+/// into [`flate2::write::GzEncoder`] to get a gzipped document such as `hp.small.json.gz`.
+pub fn write_subgraph<O, W>(
+    ontology: &O,
+    subgraph: &Subgraph<'_>,
+    write: W,
+) -> Result<(), OntoliusError>
+where
+    O: Ontology,
+    W: Write,
+{
+    let nodes: Vec<_> = subgraph
+        .terms()
+        .iter()
+        .flat_map(|&term_id| ontology.id_to_term(term_id))
+        .map(|term| {
+            let mut node = serde_json::json!({
+                "id": to_purl(term.identifier()),
+                "lbl": term.name(),
+                "type": "CLASS",
+            });
+            if term.is_obsolete() {
+                node["meta"] = serde_json::json!({"deprecated": true});
+            }
+            node
+        })
+        .collect();
+    let edges: Vec<_> = subgraph
+        .edges()
+        .iter()
+        .map(|(child, parent)| {
+            serde_json::json!({
+                "sub": to_purl(child),
+                "pred": "is_a",
+                "obj": to_purl(parent),
+            })
+        })
+        .collect();
+    let document = serde_json::json!({
+        "graphs": [{
+            "nodes": nodes,
+            "edges": edges,
+        }]
+    });
+
+    serde_json::to_writer(write, &document)
+        .map_err(|e| OntoliusError::Other(format!("Cannot write the Obographs document: {e}")))
+}
+
+fn to_purl(term_id: &TermId) -> String {
+    format!("{OBO_PURL}{}", term_id.to_string().replacen(':', "_", 1))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::{AltTermIdAware, ReplacementAware, SubsetAware, SynonymAware, Term};
+    use crate::base::Identified;
+    use crate::io::ObsoleteEdgePolicy;
+    use crate::ontology::csr::CsrOntology;
+    use crate::ontology::{RelationshipAware, TermAware};
+
+    #[test]
+    fn test_parse_xref() {
+        assert_eq!(
+            parse_xref("https://orcid.org/0000-0002-0736-9199").map(|xref| xref.to_string()),
+            Some("ORCID:0000-0002-0736-9199".to_string())
+        );
+        assert_eq!(
+            parse_xref("PMID:12345").map(|xref| xref.to_string()),
+            Some("PMID:12345".to_string())
+        );
+        assert!(parse_xref("not a curie").is_none());
+    }
+
+    #[test]
+    fn test_write_subgraph() {
+        let loader = OntologyLoaderBuilder::new().obographs_parser().build();
+        let hpo: CsrOntology<usize, SimpleMinimalTerm> = loader
+            .load_from_path("resources/hp.small.json.gz")
+            .unwrap();
+        let arachnodactyly = TermId::from(("HP", "0001166"));
+        let subgraph = hpo.sample_subgraph(std::slice::from_ref(&arachnodactyly), 20);
+
+        let mut document = vec![];
+        write_subgraph(&hpo, &subgraph, &mut document).unwrap();
+        let sample: CsrOntology<usize, SimpleMinimalTerm> =
+            loader.load_from_read(&mut document.as_slice()).unwrap();
+
+        assert_eq!(sample.len(), subgraph.terms().len());
+        assert_eq!(sample.iter_child_parent_pairs().count(), subgraph.edges().len());
+        assert_eq!(sample.root_term_id(), hpo.root_term_id());
+        assert_eq!(
+            sample.id_to_term(&arachnodactyly).map(|term| term.name()),
+            Some("Arachnodactyly")
+        );
+    }
+
+    #[test]
+    fn test_unparsable_id_policy() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All"},
+                {"id": "http://purl.obolibrary.org/obo/HP_0000118", "lbl": "Phenotypic abnormality"},
+                {"id": "_:b1"}
+            ],
+            "edges": [
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000118", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000001"},
+                {"sub": "_:b1", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000118"}
+            ]
+        }]}"#;
+
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+        assert_eq!(data.terms().len(), 2);
+        assert_eq!(data.edges().len(), 1);
+        assert_eq!(data.skipped_nodes(), ["_:b1"]);
+
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default())
+            .unparsable_id_policy(UnparsableIdPolicy::Synthetic);
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+        assert_eq!(data.terms().len(), 3);
+        assert_eq!(data.terms()[2].identifier().to_string(), "_:b1");
+        assert_eq!(data.terms()[2].name(), "_:b1");
+        assert_eq!(data.edges().len(), 2);
+        assert!(data.skipped_nodes().is_empty());
+    }
+
+    #[test]
+    fn test_edge_endpoints_match_in_any_form() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All"},
+                {"id": "http://purl.obolibrary.org/obo/HP_0000118", "lbl": "Phenotypic abnormality"},
+                {"id": "http://purl.obolibrary.org/obo/HP_0000707", "lbl": "Abnormality of the nervous system"}
+            ],
+            "edges": [
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000118", "pred": "is_a", "obj": "HP:0000001"},
+                {"sub": "HP:0000707", "pred": "is_a", "obj": "HP:0000118"}
+            ]
+        }]}"#;
+
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+
+        assert_eq!(data.edges().len(), 2);
+        assert!(data.unresolved_edges().is_empty());
+    }
+
+    #[test]
+    fn test_unlabeled_property_is_not_reported() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All", "type": "CLASS"},
+                {"id": "http://purl.obolibrary.org/obo/RO_0002573", "type": "PROPERTY"},
+                {"id": "http://purl.obolibrary.org/obo/HP_0000118", "type": "CLASS"}
+            ]
+        }]}"#;
+
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+        assert_eq!(
+            data.skipped_nodes(),
+            ["http://purl.obolibrary.org/obo/HP_0000118"]
+        );
+
+        let parser: ObographsParser<_, usize, SimpleTerm> =
+            ObographsParser::with_term_type(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+        assert_eq!(
+            data.skipped_nodes(),
+            ["http://purl.obolibrary.org/obo/HP_0000118"]
+        );
+    }
+
+    #[test]
+    fn test_skipped_edges() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All"},
+                {"id": "http://purl.obolibrary.org/obo/HP_0000118", "lbl": "Phenotypic abnormality"}
+            ],
+            "edges": [
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000118", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000001"},
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000118", "pred": "not a predicate", "obj": "http://purl.obolibrary.org/obo/HP_0000001"}
+            ]
+        }]}"#;
+
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .build();
+        let (ontology, report): (CsrOntology<usize, SimpleMinimalTerm>, _) = loader
+            .load_with_report_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+
+        assert!(ontology.relationships().is_empty());
+        assert_eq!(
+            report.skipped_edges(),
+            ["http://purl.obolibrary.org/obo/HP_0000118 not a predicate http://purl.obolibrary.org/obo/HP_0000001"]
+        );
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_unresolved_edges() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All"},
+                {"id": "http://purl.obolibrary.org/obo/HP_0000118", "lbl": "Phenotypic abnormality"},
+                {"id": "http://purl.obolibrary.org/obo/HP_0000707"}
+            ],
+            "edges": [
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000118", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000001"},
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000707", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000118"},
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000478", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000118"}
+            ]
+        }]}"#;
+
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+        assert_eq!(data.edges().len(), 1);
+
+        let unresolved = data.unresolved_edges();
+        assert_eq!(unresolved.len(), 2);
+        assert_eq!(
+            unresolved[0].to_string(),
+            "HP:0000707 is_a HP:0000118: HP:0000707 was skipped (Missing term label for HP:0000707)"
+        );
+        assert_eq!(unresolved[1].sub(), "HP:0000478");
+        assert_eq!(unresolved[1].sub_reason(), Some(&UnresolvedReason::Undeclared));
+        assert_eq!(unresolved[1].obj_reason(), None);
+
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .build();
+        let result: Result<(CsrOntology<usize, SimpleMinimalTerm>, _), _> =
+            loader.load_with_report_from_buf_read(&mut document.as_bytes());
+        let (_, report) = result.expect("Unresolved edges are dropped by default");
+        assert_eq!(report.unresolved_edges().len(), 2);
+
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .strict_edges(true)
+            .build();
+        let result: Result<CsrOntology<usize, SimpleMinimalTerm>, _> =
+            loader.load_from_buf_read(&mut document.as_bytes());
+        let error = result.err().expect("Strict loading should fail");
+        assert!(error.to_string().contains("HP:0000707 is_a HP:0000118"));
+    }
+
+    #[test]
+    fn test_prefix_map() {
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default())
+            .with_prefix_map([
+                ("MESH", "http://id.nlm.nih.gov/mesh/"),
+                ("MESHD", "http://id.nlm.nih.gov/mesh/D"),
+            ]);
+
+        let parse = |id| parser.parse_node_id(&[], id).map(|term_id| term_id.to_string());
+        assert_eq!(
+            parse("http://id.nlm.nih.gov/mesh/C0003706"),
+            Some("MESH:C0003706".to_string())
+        );
+        assert_eq!(
+            parse("http://id.nlm.nih.gov/mesh/D054119"),
+            Some("MESHD:054119".to_string())
+        );
+        assert_eq!(
+            parse("http://purl.obolibrary.org/obo/HP_0001166"),
+            Some("HP:0001166".to_string())
+        );
+        assert_eq!(parse("http://example.org/term"), None);
+    }
+
+    #[test]
+    fn test_parse_context() {
+        let context = serde_json::json!([
+            "https://example.org/remote-context.jsonld",
+            {
+                "@vocab": "http://example.org/vocab/",
+                "MESH": "http://id.nlm.nih.gov/mesh/",
+                "UMLS": {"@id": "http://linkedlifedata.com/resource/umls/id/"},
+                "skip": 1
+            }
+        ]);
+
+        let mut prefixes = parse_context(&context);
+        prefixes.sort();
+
+        assert_eq!(
+            prefixes,
+            [
+                ("MESH".to_string(), "http://id.nlm.nih.gov/mesh/".to_string()),
+                (
+                    "UMLS".to_string(),
+                    "http://linkedlifedata.com/resource/umls/id/".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_document_context() {
+        let document = r#"{
+            "@context": {"EX": "http://example.org/ex/"},
+            "graphs": [{
+                "nodes": [
+                    {"id": "http://example.org/ex/1", "lbl": "Root"},
+                    {"id": "http://example.org/ex/2", "lbl": "Child"}
+                ],
+                "edges": [
+                    {"sub": "http://example.org/ex/2", "pred": "is_a", "obj": "http://example.org/ex/1"}
+                ]
+            }]
+        }"#;
+
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+
+        let curies: Vec<_> = data
+            .terms()
+            .iter()
+            .map(|term| term.identifier().to_string())
+            .collect();
+        assert_eq!(curies, ["EX:1", "EX:2"]);
+        assert_eq!(data.edges().len(), 1);
+    }
+
+    #[test]
+    fn test_meta_fields() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All"},
+                {
+                    "id": "http://purl.obolibrary.org/obo/HP_0001166",
+                    "lbl": "Arachnodactyly",
+                    "meta": {
+                        "definition": {"val": "Abnormally long and slender fingers."},
+                        "comments": ["A comment."],
+                        "synonyms": [{"pred": "hasExactSynonym", "val": "Spider fingers"}],
+                        "xrefs": [{"val": "UMLS:C0003706"}],
+                        "basicPropertyValues": [
+                            {"pred": "http://www.geneontology.org/formats/oboInOwl#hasAlternativeId", "val": "HP:0001505"}
+                        ]
+                    }
+                }
+            ],
+            "edges": [
+                {"sub": "http://purl.obolibrary.org/obo/HP_0001166", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000001"}
+            ]
+        }]}"#;
+
+        let parser: ObographsParser<_, usize, SimpleTerm> =
+            ObographsParser::with_term_type(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+        let term = &data.terms()[1];
+        assert!(term.definition().is_some());
+        assert_eq!(term.iter_synonyms().count(), 1);
+
+        let parser: ObographsParser<_, usize, SimpleTerm> =
+            ObographsParser::with_term_type(TrieCurieUtil::default()).meta_fields(MetaFields {
+                synonyms: false,
+                ..MetaFields::ALL
+            });
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+        let term = &data.terms()[1];
+        assert!(term.definition().is_some());
+        assert_eq!(term.iter_synonyms().count(), 0);
+        assert!(term.comment().is_some());
+        assert_eq!(term.iter_alt_term_ids().count(), 1);
+
+        let parser: ObographsParser<_, usize, SimpleTerm> =
+            ObographsParser::with_term_type(TrieCurieUtil::default())
+                .meta_fields(MetaFields::NONE);
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+        let term = &data.terms()[1];
+        assert!(term.definition().is_none());
+        assert!(term.comment().is_none());
+        assert!(term.xrefs().is_empty());
+        assert_eq!(term.iter_alt_term_ids().count(), 1);
+        assert_eq!(data.edges().len(), 1);
+    }
+
+    #[test]
+    fn test_legacy_schema() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All"},
+                {
+                    "id": "http://purl.obolibrary.org/obo/HP_0001166",
+                    "lbl": "Arachnodactyly",
+                    "meta": {
+                        "synonyms": [
+                            {"pred": "http://www.geneontology.org/formats/oboInOwl#hasExactSynonym", "val": "Spider fingers"}
+                        ],
+                        "basicPropertyValues": [
+                            {"pred": "oboInOwl:hasAlternativeId", "val": "http://purl.obolibrary.org/obo/HP_0001505"}
+                        ]
+                    }
+                },
+                {
+                    "id": "http://purl.obolibrary.org/obo/HP_0000002",
+                    "lbl": "obsolete Something",
+                    "meta": {
+                        "basicPropertyValues": [
+                            {"pred": "http://www.w3.org/2002/07/owl#deprecated", "val": "true"}
+                        ]
+                    }
+                }
+            ],
+            "edges": [
+                {"sub": "http://purl.obolibrary.org/obo/HP_0001166", "pred": "http://www.w3.org/2000/01/rdf-schema#subClassOf", "obj": "http://purl.obolibrary.org/obo/HP_0000001"}
+            ]
+        }]}"#;
+
+        let parser: ObographsParser<_, usize, SimpleTerm> =
+            ObographsParser::with_term_type(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+
+        let arachnodactyly = &data.terms()[1];
+        assert_eq!(arachnodactyly.iter_synonyms().count(), 1);
+        assert_eq!(
+            arachnodactyly.iter_alt_term_ids().collect::<Vec<_>>(),
+            [&TermId::from(("HP", "0001505"))]
+        );
+        assert!(arachnodactyly.is_current());
+        assert!(data.terms()[2].is_obsolete());
+        assert_eq!(data.edges().len(), 1);
+        assert!(data.relationships().is_empty());
+    }
+
+    #[test]
+    fn test_replacements() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All"},
+                {
+                    "id": "http://purl.obolibrary.org/obo/HP_0000002",
+                    "lbl": "obsolete Something",
+                    "meta": {
+                        "deprecated": true,
+                        "basicPropertyValues": [
+                            {"pred": "http://purl.obolibrary.org/obo/IAO_0100001", "val": "http://purl.obolibrary.org/obo/HP_0000001"},
+                            {"pred": "http://www.geneontology.org/formats/oboInOwl#consider", "val": "HP:0000003"},
+                            {"pred": "http://www.geneontology.org/formats/oboInOwl#consider", "val": "HP:0000004"}
+                        ]
+                    }
+                }
+            ]
+        }]}"#;
+
+        let parser: ObographsParser<_, usize, SimpleTerm> =
+            ObographsParser::with_term_type(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+
+        let term = &data.terms()[1];
+        assert!(term.is_obsolete());
+        assert_eq!(term.replaced_by(), Some(&TermId::from(("HP", "0000001"))));
+        assert_eq!(
+            term.consider(),
+            [TermId::from(("HP", "0000003")), TermId::from(("HP", "0000004"))]
+        );
+        assert!(data.terms()[0].replaced_by().is_none());
+    }
+
+    #[test]
+    fn test_replacements_with_curie_predicates() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All"},
+                {
+                    "id": "http://purl.obolibrary.org/obo/HP_0000002",
+                    "lbl": "obsolete Something",
+                    "meta": {
+                        "deprecated": true,
+                        "basicPropertyValues": [
+                            {"pred": "IAO:0100001", "val": "HP:0000001"},
+                            {"pred": "oboInOwl:consider", "val": "HP:0000003"}
+                        ]
+                    }
+                }
+            ]
+        }]}"#;
+
+        let parser: ObographsParser<_, usize, SimpleTerm> =
+            ObographsParser::with_term_type(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+
+        let term = &data.terms()[1];
+        assert_eq!(term.replaced_by(), Some(&TermId::from(("HP", "0000001"))));
+        assert_eq!(term.consider(), [TermId::from(("HP", "0000003"))]);
+        assert_eq!(
+            data.replacements().get(&TermId::from(("HP", "0000002"))),
+            Some(&TermId::from(("HP", "0000001")))
+        );
+    }
+
+    #[test]
+    fn test_property_values() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {
+                    "id": "http://purl.obolibrary.org/obo/GO_0008150",
+                    "lbl": "biological_process",
+                    "meta": {
+                        "basicPropertyValues": [
+                            {"pred": "http://www.geneontology.org/formats/oboInOwl#hasAlternativeId", "val": "GO:0000004"},
+                            {"pred": "http://www.geneontology.org/formats/oboInOwl#hasOBONamespace", "val": "biological_process"},
+                            {"pred": "http://purl.obolibrary.org/obo/IAO_0000233", "val": "https://github.com/geneontology/go-ontology/issues/24968"}
+                        ]
+                    }
+                }
+            ]
+        }]}"#;
+
+        let parser: ObographsParser<_, usize, SimpleTerm> =
+            ObographsParser::with_term_type(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+
+        let term = &data.terms()[0];
+        assert_eq!(term.alt_term_id_count(), 1);
+        let values: Vec<_> = term
+            .property_values()
+            .iter()
+            .map(|pv| (pv.pred(), pv.val()))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                (
+                    "http://www.geneontology.org/formats/oboInOwl#hasOBONamespace",
+                    "biological_process"
+                ),
+                (
+                    "http://purl.obolibrary.org/obo/IAO_0000233",
+                    "https://github.com/geneontology/go-ontology/issues/24968"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_provenance() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {
+                    "id": "http://purl.obolibrary.org/obo/HP_0000002",
+                    "lbl": "Abnormality of body height",
+                    "meta": {
+                        "basicPropertyValues": [
+                            {"pred": "http://purl.org/dc/terms/creator", "val": "https://orcid.org/0000-0002-0736-9199"},
+                            {"pred": "http://www.geneontology.org/formats/oboInOwl#creation_date", "val": "2008-02-27T02:20:00Z"}
+                        ]
+                    }
+                },
+                {
+                    "id": "http://purl.obolibrary.org/obo/GO_0008150",
+                    "lbl": "biological_process",
+                    "meta": {
+                        "basicPropertyValues": [
+                            {"pred": "http://www.geneontology.org/formats/oboInOwl#created_by", "val": "jl"}
+                        ]
+                    }
+                },
+                {
+                    "id": "http://purl.obolibrary.org/obo/GO_0003674",
+                    "lbl": "molecular_function",
+                    "meta": {}
+                }
+            ]
+        }]}"#;
+
+        let parser: ObographsParser<_, usize, SimpleTerm> =
+            ObographsParser::with_term_type(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+
+        let provenance = data.terms()[0].provenance().unwrap();
+        assert_eq!(
+            provenance.created_by(),
+            Some("https://orcid.org/0000-0002-0736-9199")
+        );
+        assert_eq!(provenance.creation_date(), Some("2008-02-27T02:20:00Z"));
+
+        let provenance = data.terms()[1].provenance().unwrap();
+        assert_eq!(provenance.created_by(), Some("jl"));
+        assert_eq!(provenance.creation_date(), None);
+
+        assert!(data.terms()[2].provenance().is_none());
+    }
+
+    #[test]
+    fn test_subsets() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {
+                    "id": "http://purl.obolibrary.org/obo/GO_0008150",
+                    "lbl": "biological_process",
+                    "meta": {
+                        "subsets": ["http://purl.obolibrary.org/obo/go#goslim_generic"],
+                        "basicPropertyValues": [
+                            {"pred": "http://www.geneontology.org/formats/oboInOwl#inSubset", "val": "http://purl.obolibrary.org/obo/go#goslim_generic"},
+                            {"pred": "http://www.geneontology.org/formats/oboInOwl#inSubset", "val": "http://purl.obolibrary.org/obo/go#goslim_plant"}
+                        ]
+                    }
+                }
+            ]
+        }]}"#;
+
+        let parser: ObographsParser<_, usize, SimpleTerm> =
+            ObographsParser::with_term_type(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+
+        let term = &data.terms()[0];
+        assert_eq!(term.subsets(), ["goslim_generic", "goslim_plant"]);
+        assert!(term.in_subset("goslim_plant"));
+        assert!(!term.in_subset("goslim_yeast"));
+    }
+
+    #[test]
+    fn test_obsolete_edge_policy() {
+        // HP:0000003 is a child of HP:0000002, which was obsoleted in favor of HP:0000001.
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All"},
+                {
+                    "id": "http://purl.obolibrary.org/obo/HP_0000002",
+                    "lbl": "obsolete Something",
+                    "meta": {
+                        "deprecated": true,
+                        "basicPropertyValues": [
+                            {"pred": "http://purl.obolibrary.org/obo/IAO_0100001", "val": "HP:0000001"}
+                        ]
+                    }
+                },
+                {"id": "http://purl.obolibrary.org/obo/HP_0000003", "lbl": "Other"}
+            ],
+            "edges": [
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000003", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000002"}
+            ]
+        }]}"#;
+
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .obsolete_edge_policy(ObsoleteEdgePolicy::Reroute)
+            .build();
+        let (hpo, report): (CsrOntology<usize, SimpleMinimalTerm>, _) = loader
+            .load_with_report_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+        let pairs: Vec<_> = hpo
+            .iter_child_parent_pairs()
+            .map(|(child, parent)| (child.to_string(), parent.to_string()))
+            .collect();
+        assert_eq!(pairs, [("HP:0000003".into(), "HP:0000001".into())]);
+        let obsolete_edges: Vec<_> = report
+            .obsolete_edges()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            obsolete_edges,
+            ["HP:0000003 is_a obsolete HP:0000002: rerouted to HP:0000001"]
+        );
+        assert!(!report.is_clean());
+
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default());
+        let mut data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+        assert_eq!(data.replacements().len(), 1);
+        let dropped = data.apply_obsolete_edge_policy(ObsoleteEdgePolicy::Drop);
+        assert_eq!(dropped[0].replacement(), None);
+        assert!(data.edges().is_empty());
+    }
+
+    #[test]
+    fn test_relationships() {
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/CHEBI_24431", "lbl": "chemical entity"},
+                {"id": "http://purl.obolibrary.org/obo/CHEBI_50860", "lbl": "organic molecular entity"},
+                {"id": "http://purl.obolibrary.org/obo/CHEBI_15365", "lbl": "acetylsalicylic acid"},
+                {"id": "http://purl.obolibrary.org/obo/CHEBI_50906", "lbl": "role"},
+                {"id": "http://purl.obolibrary.org/obo/CHEBI_35475", "lbl": "NSAID"}
+            ],
+            "edges": [
+                {"sub": "http://purl.obolibrary.org/obo/CHEBI_50860", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/CHEBI_24431"},
+                {"sub": "http://purl.obolibrary.org/obo/CHEBI_15365", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/CHEBI_50860"},
+                {"sub": "http://purl.obolibrary.org/obo/CHEBI_50906", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/CHEBI_24431"},
+                {"sub": "http://purl.obolibrary.org/obo/CHEBI_35475", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/CHEBI_50906"},
+                {"sub": "http://purl.obolibrary.org/obo/CHEBI_15365", "pred": "http://purl.obolibrary.org/obo/RO_0000087", "obj": "http://purl.obolibrary.org/obo/CHEBI_35475"},
+                {"sub": "http://purl.obolibrary.org/obo/CHEBI_15365", "pred": "http://purl.obolibrary.org/obo/RO_0000087", "obj": "http://purl.obolibrary.org/obo/CHEBI_99999"}
+            ]
+        }]}"#;
+
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+        assert_eq!(data.edges().len(), 4);
+        assert_eq!(data.relationships().len(), 1);
+        assert!(data.unresolved_edges().is_empty());
+
+        let chebi = CsrOntology::try_from(data).unwrap();
+        let chemicals = |role: &str| -> Vec<_> {
+            chebi
+                .iter_chemicals_with_role(&TermId::from(("CHEBI", role)))
+                .map(ToString::to_string)
+                .collect()
+        };
+        assert_eq!(chemicals("35475"), ["CHEBI:15365"]);
+        assert_eq!(chemicals("50906"), ["CHEBI:15365"]);
+        assert!(chemicals("50860").is_empty());
+    }
+
+    #[test]
+    fn test_late_context_keeps_document_order() {
+        let document = r#"{
+            "graphs": [{
+                "nodes": [
+                    {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All"},
+                    {"id": "http://example.org/ex/1", "lbl": "Example"},
+                    {"id": "http://purl.obolibrary.org/obo/HP_0000118", "lbl": "Phenotypic abnormality"}
+                ]
+            }],
+            "@context": {"EX": "http://example.org/ex/"}
+        }"#;
+
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+
+        let ids: Vec<_> = data
+            .terms()
+            .iter()
+            .map(|term| term.identifier().to_string())
+            .collect();
+        assert_eq!(ids, ["HP:0000001", "EX:1", "HP:0000118"]);
+    }
+
+
+    #[test]
+    fn test_unresolvable_node_does_not_stall_the_stream() {
+        // A node outside of the OBO namespace precedes the OBO nodes in a document with no context.
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default())
+            .unparsable_id_policy(UnparsableIdPolicy::Synthetic);
+        let mut stream = GraphStream::new(&parser, MetaFields::NONE);
+        let node = |id: &str| -> Node {
+            serde_json::from_value(serde_json::json!({"id": id, "lbl": id})).unwrap()
+        };
+        let edge = |sub: &str, obj: &str| LeanEdge {
+            sub: sub.to_string(),
+            pred: "is_a".to_string(),
+            obj: obj.to_string(),
+        };
+        let hp = |i: usize| format!("http://purl.obolibrary.org/obo/HP_{i:07}");
+
+        stream.node(node("http://identifiers.org/mesh/D054119"));
+        stream.node(node(&hp(0)));
+        for i in 1..1000 {
+            stream.node(node(&hp(i)));
+            stream.edge(edge(&hp(i), &hp(i - 1)));
         }
+        stream.edge(edge("http://identifiers.org/mesh/D054119", &hp(0)));
+        // Only the unresolvable node and its edge wait for the end of the document.
+        // With the `rayon` feature, the edge may still be in the batch.
+        assert_eq!(stream.pending_nodes.len(), 1);
+        assert!(stream.pending_edges.len() <= 1);
+
+        let data = stream.finish(1).unwrap();
+        assert_eq!(data.terms().len(), 1001);
+        assert_eq!(
+            data.terms()[0].identifier().to_string(),
+            "_:http://identifiers.org/mesh/D054119"
+        );
+        assert_eq!(data.terms()[1].identifier().to_string(), "HP:0000000");
+        assert_eq!(data.terms()[1000].identifier().to_string(), "HP:0000999");
+        assert_eq!(data.edges().len(), 1000);
+        // The edges follow the renumbered terms.
+        assert!(data
+            .edges()
+            .iter()
+            .all(|edge| edge.sub == edge.obj + 1 || (edge.sub, edge.obj) == (0, 1)));
+    }
+    #[test]
+    fn test_streaming_out_of_order_document() {
+        // The edges precede the nodes, the context follows the graphs,
+        // and the second graph is ignored.
+        let document = r#"{
+            "graphs": [{
+                "edges": [
+                    {"sub": "http://example.org/ex/2", "pred": "is_a", "obj": "http://example.org/ex/1"},
+                    {"sub": "http://example.org/ex/3", "pred": "is_a", "obj": "http://example.org/ex/2"},
+                    {"sub": "http://example.org/ex/3", "pred": "http://example.org/ex/partOf", "obj": "http://example.org/ex/1"}
+                ],
+                "meta": {"version": "2024-04-26"},
+                "nodes": [
+                    {"id": "http://example.org/ex/1", "lbl": "Root"},
+                    {"id": "http://example.org/ex/2", "lbl": "Child"},
+                    {"id": "http://example.org/ex/3", "lbl": "Grandchild"}
+                ]
+            }, {
+                "nodes": [{"id": "http://example.org/ex/4", "lbl": "Ignored"}]
+            }],
+            "@context": {"EX": "http://example.org/ex/"}
+        }"#;
+
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+
+        let ids: Vec<_> = data
+            .terms()
+            .iter()
+            .map(|term| term.identifier().to_string())
+            .collect();
+        assert_eq!(ids, ["EX:1", "EX:2", "EX:3"]);
+        assert_eq!(data.edges().len(), 2);
+        assert_eq!(data.relationships().len(), 1);
+        assert_eq!(
+            data.metadata().get("version").map(String::as_str),
+            Some("2024-04-26")
+        );
+        assert!(data.skipped_nodes().is_empty());
+        assert!(data.unresolved_edges().is_empty());
+
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default());
+        assert!(parser
+            .load_from_buf_read(&mut r#"{"graphs": []}"#.as_bytes())
+            .is_err());
+        assert!(parser
+            .load_from_buf_read(&mut r#"{"graphs": [{"nodes": []}]} trailing"#.as_bytes())
+            .is_err());
+    }
+
+    #[test]
+    fn test_graph_id() {
+        // The nodes and the edges of the selected graph precede its id.
+        let document = r#"{"graphs": [{
+            "id": "http://purl.obolibrary.org/obo/hp/imports/uberon.owl",
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/UBERON_0001062", "lbl": "anatomical entity"},
+                {"id": "http://purl.obolibrary.org/obo/UBERON_0000061", "lbl": "anatomical structure"}
+            ],
+            "edges": [
+                {"sub": "http://purl.obolibrary.org/obo/UBERON_0000061", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/UBERON_0001062"}
+            ]
+        }, {
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All"},
+                {"id": "http://purl.obolibrary.org/obo/HP_0000118", "lbl": "Phenotypic abnormality"}
+            ],
+            "edges": [
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000118", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000001"}
+            ],
+            "id": "http://purl.obolibrary.org/obo/hp.owl"
+        }]}"#;
+
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .graph_id("http://purl.obolibrary.org/obo/hp.owl")
+            .build();
+        let hpo: CsrOntology<usize, SimpleMinimalTerm> =
+            loader.load_from_buf_read(&mut document.as_bytes()).unwrap();
+        assert_eq!(hpo.len(), 2);
+        assert_eq!(hpo.root_term_id().to_string(), "HP:0000001");
+
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .build();
+        let uberon: CsrOntology<usize, SimpleMinimalTerm> =
+            loader.load_from_buf_read(&mut document.as_bytes()).unwrap();
+        assert_eq!(uberon.root_term_id().to_string(), "UBERON:0001062");
+
+        let parser: ObographsParser<_, usize> = ObographsParser::new(TrieCurieUtil::default());
+        assert!(parser
+            .load_graph_from_buf_read(
+                &mut document.as_bytes(),
+                "http://purl.obolibrary.org/obo/mp.owl"
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_large_document() {
+        // More nodes and edges than fit into a single parallel batch.
+        let n = 10_000;
+        let nodes: Vec<_> = (0..n)
+            .map(|i| serde_json::json!({"id": format!("{OBO_PURL}HP_{i:07}"), "lbl": format!("Term {i}")}))
+            .collect();
+        let edges: Vec<_> = (1..n)
+            .map(|i| {
+                serde_json::json!({
+                    "sub": format!("{OBO_PURL}HP_{i:07}"),
+                    "pred": "is_a",
+                    "obj": format!("{OBO_PURL}HP_{:07}", i / 2),
+                })
+            })
+            .collect();
+        let document =
+            serde_json::json!({"graphs": [{"nodes": nodes, "edges": edges}]}).to_string();
+
+        let parser: ObographsParser<_, u32> = ObographsParser::new(TrieCurieUtil::default());
+        let data = parser
+            .load_from_buf_read(&mut document.as_bytes())
+            .unwrap();
+
+        assert_eq!(data.terms().len(), n);
+        assert!(data.terms().iter().enumerate().all(
+            |(i, term)| term.identifier() == &TermId::from(("HP", format!("{i:07}").as_str()))
+        ));
+        assert_eq!(data.edges().len(), n - 1);
+        assert!(data.unresolved_edges().is_empty());
+    }
+
+    #[test]
+    fn test_parse_synonym_category() {
+        assert_eq!(
+            parse_synonym_category("hasExactSynonym"),
+            Some(SynonymCategory::Exact)
+        );
+        assert_eq!(
+            parse_synonym_category("http://www.geneontology.org/formats/oboInOwl#hasBroadSynonym"),
+            Some(SynonymCategory::Broad)
+        );
+        assert_eq!(parse_synonym_category("hasSynonym"), None);
     }
 }
@@ -0,0 +1,215 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use crate::base::{term::MinimalTerm, TermId};
+use crate::hierarchy::{GraphEdge, HierarchyIdx, Relationship};
+use crate::ontology::TermIdx;
+
+use super::OntologyData;
+
+/// The policy for the `is_a` edges whose parent is an obsolete term.
+///
+/// Some ontology releases include edges that point to the terms obsoleted in the release.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ObsoleteEdgePolicy {
+    /// Keep the edges, along with the obsolete parents in the hierarchy.
+    #[default]
+    Keep,
+    /// Point the edges to the terms replacing the obsolete parents (`replaced_by`),
+    /// following the replacements until a current term.
+    ///
+    /// The edges of the obsolete parents with no current replacement are dropped.
+    Reroute,
+    /// Drop the edges.
+    Drop,
+}
+
+/// An `is_a` edge with an obsolete parent that was rerouted or dropped
+/// due to the [`ObsoleteEdgePolicy`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObsoleteEdge {
+    sub: TermId,
+    obj: TermId,
+    replacement: Option<TermId>,
+}
+
+impl ObsoleteEdge {
+    /// Get the subject (child) of the edge.
+    pub fn sub(&self) -> &TermId {
+        &self.sub
+    }
+
+    /// Get the obsolete object (parent) of the edge.
+    pub fn obj(&self) -> &TermId {
+        &self.obj
+    }
+
+    /// Get the current term that replaced the parent of the edge,
+    /// or `None` if the edge was dropped.
+    pub fn replacement(&self) -> Option<&TermId> {
+        self.replacement.as_ref()
+    }
+}
+
+impl Display for ObsoleteEdge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is_a obsolete {}", self.sub, self.obj)?;
+        match &self.replacement {
+            Some(replacement) => write!(f, ": rerouted to {replacement}"),
+            None => write!(f, ": dropped"),
+        }
+    }
+}
+
+impl<HI, T> OntologyData<HI, T>
+where
+    HI: HierarchyIdx + TermIdx,
+    T: MinimalTerm,
+{
+    /// Get the replacements of the obsolete terms (`replaced_by`) declared in the input,
+    /// keyed by the ID of the obsolete term.
+    pub fn replacements(&self) -> &HashMap<TermId, TermId> {
+        &self.replacements
+    }
+
+    /// Set the replacements of the obsolete terms, keyed by the ID of the obsolete term.
+    pub fn with_replacements(mut self, replacements: HashMap<TermId, TermId>) -> Self {
+        self.replacements = replacements;
+        self
+    }
+
+    /// Reroute or drop the `is_a` edges whose parent is an obsolete term, according to the `policy`,
+    /// and return the changed edges in the order of the input.
+    ///
+    /// The rerouted edges that would make a term its own parent are dropped.
+    pub fn apply_obsolete_edge_policy(&mut self, policy: ObsoleteEdgePolicy) -> Vec<ObsoleteEdge> {
+        if policy == ObsoleteEdgePolicy::Keep {
+            return vec![];
+        }
+        let terms = &self.terms;
+        let is_obsolete = |idx: HI| {
+            terms
+                .get(TermIdx::index(idx))
+                .is_some_and(MinimalTerm::is_obsolete)
+        };
+        let id2idx: HashMap<_, _> = terms
+            .iter()
+            .enumerate()
+            .map(|(idx, term)| (term.identifier(), idx))
+            .collect();
+        let replace = |term_id: &TermId| {
+            let mut visited = HashSet::from([term_id]);
+            let mut current = term_id;
+            loop {
+                let replacement = self.replacements.get(current)?;
+                let &idx = id2idx.get(replacement)?;
+                if terms[idx].is_current() {
+                    return Some(HI::new(idx));
+                }
+                if !visited.insert(replacement) {
+                    return None;
+                }
+                current = replacement;
+            }
+        };
+        let term_id = |idx: HI| terms[TermIdx::index(idx)].identifier().clone();
+
+        let mut edges = Vec::with_capacity(self.edges.len());
+        let mut changed = vec![];
+        for edge in self.edges.iter() {
+            let (child, parent) = match edge.pred {
+                Relationship::Child => (edge.sub, edge.obj),
+                Relationship::Parent => (edge.obj, edge.sub),
+            };
+            if !is_obsolete(parent) {
+                edges.push(edge.clone());
+                continue;
+            }
+            let replacement = match policy {
+                ObsoleteEdgePolicy::Reroute => {
+                    replace(&term_id(parent)).filter(|&replacement| replacement != child)
+                }
+                _ => None,
+            };
+            if let Some(replacement) = replacement {
+                edges.push(GraphEdge::from((child, Relationship::Child, replacement)));
+            }
+            changed.push(ObsoleteEdge {
+                sub: term_id(child),
+                obj: term_id(parent),
+                replacement: replacement.map(term_id),
+            });
+        }
+        if !changed.is_empty() {
+            self.edges = edges.into_boxed_slice();
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+
+    /// HP:1 is the root, HP:3, HP:4, and HP:5 are obsolete, and HP:2 is the child of HP:1 and of
+    /// the obsolete HP:3 and HP:5. HP:3 is replaced by HP:4, which is replaced by HP:1.
+    fn data() -> OntologyData<usize, SimpleMinimalTerm> {
+        let terms: Vec<_> = (1..=5)
+            .map(|i| {
+                let id = i.to_string();
+                SimpleMinimalTerm::new(TermId::from(("HP", id.as_str())), "Term", vec![], i >= 3)
+            })
+            .collect();
+        let edges: Vec<_> = [(1, 0), (1, 2), (3, 1), (4, 1)]
+            .into_iter()
+            .map(|(sub, obj)| GraphEdge::from((sub, Relationship::Child, obj)))
+            .chain([GraphEdge::from((4, Relationship::Parent, 1))])
+            .collect();
+        let replacements = HashMap::from([
+            (TermId::from(("HP", "3")), TermId::from(("HP", "4"))),
+            (TermId::from(("HP", "4")), TermId::from(("HP", "1"))),
+        ]);
+        OntologyData::from((
+            terms.into_boxed_slice(),
+            edges.into_boxed_slice(),
+            HashMap::new(),
+        ))
+        .with_replacements(replacements)
+    }
+
+    #[test]
+    fn test_apply_obsolete_edge_policy() {
+        let mut kept = data();
+        assert!(kept
+            .apply_obsolete_edge_policy(ObsoleteEdgePolicy::Keep)
+            .is_empty());
+        assert_eq!(kept.edges().len(), 5);
+
+        let mut dropped = data();
+        let changed = dropped.apply_obsolete_edge_policy(ObsoleteEdgePolicy::Drop);
+        let changed: Vec<_> = changed.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            changed,
+            [
+                "HP:2 is_a obsolete HP:3: dropped",
+                "HP:2 is_a obsolete HP:5: dropped"
+            ]
+        );
+        assert_eq!(dropped.edges().len(), 3);
+
+        let mut rerouted = data();
+        let changed = rerouted.apply_obsolete_edge_policy(ObsoleteEdgePolicy::Reroute);
+        assert_eq!(changed.len(), 2);
+        assert_eq!(changed[0].replacement(), Some(&TermId::from(("HP", "1"))));
+        assert_eq!(changed[1].replacement(), None);
+        let edges: Vec<_> = rerouted
+            .edges()
+            .iter()
+            .map(|edge| (edge.sub, edge.obj))
+            .collect();
+        assert_eq!(edges, [(1, 0), (1, 0), (3, 1), (4, 1)]);
+    }
+}
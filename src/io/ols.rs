@@ -0,0 +1,156 @@
+//! Export the ontology terms as OLS-style JSON documents
+//! for indexing into a search engine, such as Elasticsearch or OpenSearch.
+//!
+//! Each document describes one term:
+//!
+//! ```json
+//! {
+//!   "id": "HP:0001166",
+//!   "iri": "http://purl.obolibrary.org/obo/HP_0001166",
+//!   "label": "Arachnodactyly",
+//!   "synonyms": ["Spider fingers"],
+//!   "definition": "Abnormally long and slender fingers.",
+//!   "ancestors": ["HP:0100807", "HP:0000001"],
+//!   "is_obsolete": false
+//! }
+//! ```
+//!
+//! The `ancestors` are the IDs of all `is_a` ancestors of the term, and the obsolete terms have none.
+use std::io::Write;
+
+use serde_json::{json, Value};
+
+use crate::base::term::{MinimalTerm, SynonymAware, Term};
+use crate::base::Identified;
+use crate::error::OntoliusError;
+use crate::hierarchy::{AncestorNodes, HierarchyIdx};
+use crate::ontology::Ontology;
+
+use super::OBO_PURL;
+
+/// Create the document of the term at `idx`.
+///
+/// Returns `None` if the term is not in the `ontology`.
+pub fn term_document<O>(ontology: &O, idx: O::Idx) -> Option<Value>
+where
+    O: Ontology,
+    O::T: Term + SynonymAware,
+{
+    let term = ontology.idx_to_term(idx)?;
+    let term_id = term.identifier();
+    let synonyms: Vec<_> = term.iter_synonyms().map(|synonym| synonym.name()).collect();
+    let ancestors: Vec<_> = if term.is_current() {
+        ontology
+            .hierarchy()
+            .ancestors_of(idx)
+            .flat_map(|&ancestor| ontology.idx_to_term_id(ancestor))
+            .map(|ancestor| ancestor.to_string())
+            .collect()
+    } else {
+        vec![]
+    };
+
+    Some(json!({
+        "id": term_id.to_string(),
+        "iri": format!("{OBO_PURL}{}", term_id.to_string().replacen(':', "_", 1)),
+        "label": term.name(),
+        "synonyms": synonyms,
+        "definition": term.definition(),
+        "ancestors": ancestors,
+        "is_obsolete": term.is_obsolete(),
+    }))
+}
+
+/// Write the documents of all terms of the `ontology` as newline-delimited JSON,
+/// one document per line, in the order of the term indices.
+///
+/// Returns the number of the written documents.
+///
+/// ## Examples
+///
+/// ```
+/// # use curie_util::TrieCurieUtil;
+/// # use ontolius::base::term::simple::SimpleTerm;
+/// # use ontolius::io::obographs::ObographsParser;
+/// use ontolius::io::ols::write_term_documents;
+/// # use ontolius::ontology::csr::CsrOntology;
+/// use ontolius::prelude::*;
+///
+/// # let loader = OntologyLoaderBuilder::new()
+/// #                .parser(ObographsParser::with_term_type(TrieCurieUtil::default()))
+/// #                .build();
+/// # let hpo: CsrOntology<usize, SimpleTerm> = loader.load_from_path("resources/hp.small.json.gz")
+/// #                                             .expect("HPO should be loaded");
+/// #
+/// let mut buffer = vec![];
+/// let count = write_term_documents(&hpo, &mut buffer).expect("Documents should be written");
+///
+/// assert_eq!(count, hpo.len());
+/// assert_eq!(String::from_utf8(buffer).unwrap().lines().count(), hpo.len());
+/// ```
+pub fn write_term_documents<O, W>(ontology: &O, mut write: W) -> Result<usize, OntoliusError>
+where
+    O: Ontology,
+    O::T: Term + SynonymAware,
+    W: Write,
+{
+    let mut count = 0;
+    for idx in (0..ontology.len()).map(<O::Idx as HierarchyIdx>::new) {
+        if let Some(document) = term_document(ontology, idx) {
+            serde_json::to_writer(&mut write, &document)
+                .map_err(|e| OntoliusError::Other(format!("Cannot write the document: {e}")))?;
+            writeln!(write)
+                .map_err(|e| OntoliusError::Other(format!("Cannot write the document: {e}")))?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleTerm;
+    use crate::base::term::{Synonym, SynonymCategory};
+    use crate::base::TermId;
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_term_document() {
+        let terms = vec![
+            SimpleTerm::new(TermId::from(("HP", "0000001")), "All", vec![], false),
+            SimpleTerm::new(
+                TermId::from(("HP", "0001166")),
+                "Arachnodactyly",
+                vec![],
+                false,
+            )
+            .with_definition("Abnormally long and slender fingers.")
+            .with_synonyms(vec![Synonym::new(
+                "Spider fingers",
+                SynonymCategory::Exact,
+                vec![],
+            )]),
+            SimpleTerm::new(TermId::from(("HP", "0000002")), "obsolete", vec![], true),
+        ];
+        let ontology = toy_ontology(terms, &[(1, 0)]);
+
+        assert_eq!(
+            term_document(&ontology, 1),
+            Some(json!({
+                "id": "HP:0001166",
+                "iri": "http://purl.obolibrary.org/obo/HP_0001166",
+                "label": "Arachnodactyly",
+                "synonyms": ["Spider fingers"],
+                "definition": "Abnormally long and slender fingers.",
+                "ancestors": ["HP:0000001"],
+                "is_obsolete": false,
+            }))
+        );
+        let obsolete = term_document(&ontology, 2).unwrap();
+        assert_eq!(obsolete["definition"], Value::Null);
+        assert_eq!(obsolete["ancestors"], json!([]));
+        assert_eq!(obsolete["is_obsolete"], json!(true));
+        assert!(term_document(&ontology, 3).is_none());
+    }
+}
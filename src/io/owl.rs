@@ -0,0 +1,749 @@
+//! Load ontologies from OWL RDF/XML files, such as `hp.owl`,
+//! for the ontologies that do not publish an Obographs release.
+//!
+//! The parser reads the named OWL classes along with their `rdfs:subClassOf` edges
+//! to other named classes, and the OBO annotations of the classes,
+//! such as the labels, the synonyms, the definitions, or the deprecation flags.
+//! The class expressions (e.g. the `owl:Restriction`s) and the axiom annotations are skipped.
+//!
+//! ## Examples
+//!
+//! ```
+//! use curie_util::TrieCurieUtil;
+//! use ontolius::io::owl::OwlParser;
+//! use ontolius::io::OntologyDataParser;
+//! use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//!
+//! let document = r#"<?xml version="1.0"?>
+//! <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+//!          xmlns:rdfs="http://www.w3.org/2000/01/rdf-schema#"
+//!          xmlns:owl="http://www.w3.org/2002/07/owl#">
+//!     <owl:Class rdf:about="http://purl.obolibrary.org/obo/HP_0000001">
+//!         <rdfs:label>All</rdfs:label>
+//!     </owl:Class>
+//!     <owl:Class rdf:about="http://purl.obolibrary.org/obo/HP_0000118">
+//!         <rdfs:subClassOf rdf:resource="http://purl.obolibrary.org/obo/HP_0000001"/>
+//!         <rdfs:label>Phenotypic abnormality</rdfs:label>
+//!     </owl:Class>
+//! </rdf:RDF>"#;
+//!
+//! let parser = OwlParser::new(TrieCurieUtil::default());
+//! let data = parser.load_from_buf_read(&mut document.as_bytes())
+//!                  .expect("The document should be parsable");
+//! let ontology: CsrOntology<usize, _> = CsrOntology::try_from(data)
+//!                  .expect("The data should form an ontology");
+//!
+//! assert_eq!(ontology.len(), 2);
+//! assert_eq!(ontology.root_term().name(), "All");
+//! ```
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use curie_util::{CurieUtil, TrieCurieUtil};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::ResolveResult;
+use quick_xml::NsReader;
+
+use crate::base::term::simple::{SimpleMinimalTerm, SimpleTerm};
+use crate::base::term::{MinimalTerm, Synonym, SynonymCategory, TermProvenance};
+use crate::base::TermId;
+use crate::error::OntoliusError;
+use crate::hierarchy::{GraphEdge, HierarchyIdx, Relationship};
+use crate::ontology::TermIdx;
+
+use super::{
+    OntologyData, OntologyDataParser, OntologyLoaderBuilder, Uninitialized, UnresolvedEdge,
+    UnresolvedReason, WithParser, OBO_PURL,
+};
+
+const RDF: &[u8] = b"http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const RDFS: &[u8] = b"http://www.w3.org/2000/01/rdf-schema#";
+const OWL: &[u8] = b"http://www.w3.org/2002/07/owl#";
+const OBO_IN_OWL: &[u8] = b"http://www.geneontology.org/formats/oboInOwl#";
+const OBO: &[u8] = OBO_PURL.as_bytes();
+const DC: &[u8] = b"http://purl.org/dc/elements/1.1/";
+const DCTERMS: &[u8] = b"http://purl.org/dc/terms/";
+
+/// The annotations of a named OWL class.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OwlClass {
+    label: Option<String>,
+    alt_term_ids: Vec<TermId>,
+    is_deprecated: bool,
+    synonyms: Vec<Synonym>,
+    definition: Option<String>,
+    comment: Option<String>,
+    xrefs: Vec<TermId>,
+    replaced_by: Option<TermId>,
+    consider: Vec<TermId>,
+    subsets: Vec<String>,
+    created_by: Option<String>,
+    creation_date: Option<String>,
+}
+
+impl OwlClass {
+    /// Get the `rdfs:label` of the class.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Get the alternate IDs (`oboInOwl:hasAlternativeId`).
+    pub fn alt_term_ids(&self) -> &[TermId] {
+        &self.alt_term_ids
+    }
+
+    /// Test if the class is deprecated (`owl:deprecated`).
+    pub fn is_deprecated(&self) -> bool {
+        self.is_deprecated
+    }
+
+    /// Get the exact, related, broad, and narrow synonyms.
+    pub fn synonyms(&self) -> &[Synonym] {
+        &self.synonyms
+    }
+
+    /// Get the definition (`IAO:0000115`).
+    pub fn definition(&self) -> Option<&str> {
+        self.definition.as_deref()
+    }
+
+    /// Get the first `rdfs:comment`.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Get the cross-references (`oboInOwl:hasDbXref`).
+    pub fn xrefs(&self) -> &[TermId] {
+        &self.xrefs
+    }
+
+    /// Get the ID of the replacement of the deprecated class (`IAO:0100001`).
+    pub fn replaced_by(&self) -> Option<&TermId> {
+        self.replaced_by.as_ref()
+    }
+
+    /// Get the IDs of the classes to consider instead of the deprecated class (`oboInOwl:consider`).
+    pub fn consider(&self) -> &[TermId] {
+        &self.consider
+    }
+
+    /// Get the names of the subsets of the class (`oboInOwl:inSubset`), e.g. `goslim_generic`.
+    pub fn subsets(&self) -> &[String] {
+        &self.subsets
+    }
+
+    /// Get the creator of the class (`oboInOwl:created_by` or Dublin Core `creator`).
+    pub fn created_by(&self) -> Option<&str> {
+        self.created_by.as_deref()
+    }
+
+    /// Get the creation date of the class (`oboInOwl:creation_date` or Dublin Core `date`).
+    pub fn creation_date(&self) -> Option<&str> {
+        self.creation_date.as_deref()
+    }
+}
+
+impl OwlClass {
+    /// Set the annotation `property` to the IRI `resource` or to the literal `value`.
+    ///
+    /// The `rdfs:subClassOf` and the ontology properties are ignored.
+    pub(super) fn annotate(&mut self, property: Property, resource: Option<&str>, value: String) {
+        match property {
+            Property::Label => self.label = Some(value),
+            Property::Deprecated => self.is_deprecated = value == "true" || value == "1",
+            Property::AltId => self.alt_term_ids.extend(parse_term_reference(&value)),
+            Property::Synonym(category) => {
+                self.synonyms.push(Synonym::new(value, category, vec![]))
+            }
+            Property::Definition => self.definition = Some(value),
+            Property::Comment => {
+                self.comment.get_or_insert(value);
+            }
+            Property::Xref => self.xrefs.extend(TermId::from_str(&value).ok()),
+            Property::ReplacedBy => {
+                self.replaced_by = parse_term_reference(resource.unwrap_or(&value))
+            }
+            Property::Consider => self
+                .consider
+                .extend(parse_term_reference(resource.unwrap_or(&value))),
+            Property::Subset => {
+                let iri = resource.unwrap_or(&value);
+                let name = iri.rsplit(['#', '/']).next().unwrap_or(iri);
+                if !self.subsets.iter().any(|subset| subset == name) {
+                    self.subsets.push(name.to_string());
+                }
+            }
+            Property::CreatedBy => {
+                self.created_by
+                    .get_or_insert_with(|| resource.map_or(value, str::to_string));
+            }
+            Property::CreationDate => {
+                self.creation_date.get_or_insert(value);
+            }
+            Property::SubClassOf | Property::VersionInfo | Property::VersionIri => {}
+        }
+    }
+}
+
+/// The terms that can be created from the OWL classes.
+pub trait OwlTerm: MinimalTerm {
+    /// Create the term with `term_id` and `name` from the class annotations.
+    fn from_owl_class(term_id: TermId, name: &str, class: &OwlClass) -> Self;
+}
+
+impl OwlTerm for SimpleMinimalTerm {
+    fn from_owl_class(term_id: TermId, name: &str, class: &OwlClass) -> Self {
+        SimpleMinimalTerm::new(
+            term_id,
+            name,
+            class.alt_term_ids.clone(),
+            class.is_deprecated,
+        )
+    }
+}
+
+impl OwlTerm for SimpleTerm {
+    fn from_owl_class(term_id: TermId, name: &str, class: &OwlClass) -> Self {
+        let mut term = SimpleTerm::new(
+            term_id,
+            name,
+            class.alt_term_ids.clone(),
+            class.is_deprecated,
+        )
+        .with_synonyms(class.synonyms.clone())
+        .with_xrefs(class.xrefs.clone())
+        .with_consider(class.consider.clone())
+        .with_subsets(class.subsets.clone());
+        if let Some(definition) = &class.definition {
+            term = term.with_definition(definition);
+        }
+        if let Some(comment) = &class.comment {
+            term = term.with_comment(comment);
+        }
+        if let Some(replaced_by) = &class.replaced_by {
+            term = term.with_replaced_by(replaced_by.clone());
+        }
+        if class.created_by.is_some() || class.creation_date.is_some() {
+            let mut provenance = TermProvenance::default();
+            if let Some(created_by) = &class.created_by {
+                provenance = provenance.with_created_by(created_by);
+            }
+            if let Some(creation_date) = &class.creation_date {
+                provenance = provenance.with_creation_date(creation_date);
+            }
+            term = term.with_provenance(provenance);
+        }
+        term
+    }
+}
+
+/// The properties of the classes and of the ontology read by the parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Property {
+    Label,
+    SubClassOf,
+    Deprecated,
+    AltId,
+    Synonym(SynonymCategory),
+    Definition,
+    Comment,
+    Xref,
+    ReplacedBy,
+    Consider,
+    Subset,
+    CreatedBy,
+    CreationDate,
+    VersionInfo,
+    VersionIri,
+}
+
+impl Property {
+    /// Get the property of the predicate `iri`, such as `http://www.w3.org/2000/01/rdf-schema#label`.
+    pub(super) fn from_iri(iri: &str) -> Option<Self> {
+        [RDFS, OWL, OBO_IN_OWL, OBO, DC, DCTERMS]
+            .into_iter()
+            .find_map(|namespace| {
+                iri.as_bytes()
+                    .strip_prefix(namespace)
+                    .and_then(|local| Property::from_name(namespace, local))
+            })
+    }
+
+    fn from_name(namespace: &[u8], local: &[u8]) -> Option<Self> {
+        match (namespace, local) {
+            (RDFS, b"label") => Some(Property::Label),
+            (RDFS, b"subClassOf") => Some(Property::SubClassOf),
+            (RDFS, b"comment") => Some(Property::Comment),
+            (OWL, b"deprecated") => Some(Property::Deprecated),
+            (OWL, b"versionInfo") => Some(Property::VersionInfo),
+            (OWL, b"versionIRI") => Some(Property::VersionIri),
+            (OBO_IN_OWL, b"hasAlternativeId") => Some(Property::AltId),
+            (OBO_IN_OWL, b"hasExactSynonym") => Some(Property::Synonym(SynonymCategory::Exact)),
+            (OBO_IN_OWL, b"hasRelatedSynonym") => Some(Property::Synonym(SynonymCategory::Related)),
+            (OBO_IN_OWL, b"hasBroadSynonym") => Some(Property::Synonym(SynonymCategory::Broad)),
+            (OBO_IN_OWL, b"hasNarrowSynonym") => Some(Property::Synonym(SynonymCategory::Narrow)),
+            (OBO_IN_OWL, b"hasDbXref") => Some(Property::Xref),
+            (OBO_IN_OWL, b"consider") => Some(Property::Consider),
+            (OBO_IN_OWL, b"inSubset") => Some(Property::Subset),
+            (OBO_IN_OWL, b"created_by") | (DC | DCTERMS, b"creator") => Some(Property::CreatedBy),
+            (OBO_IN_OWL, b"creation_date") | (DC | DCTERMS, b"date") => {
+                Some(Property::CreationDate)
+            }
+            (OBO, b"IAO_0000115") => Some(Property::Definition),
+            (OBO, b"IAO_0100001") => Some(Property::ReplacedBy),
+            _ => None,
+        }
+    }
+}
+
+/// The top-level element being read.
+enum Subject {
+    Class(String, Box<OwlClass>),
+    Ontology,
+    Other,
+}
+
+/// The classes, the `rdfs:subClassOf` edges, and the version of the ontology
+/// collected from the document.
+struct Document {
+    depth: usize,
+    subject: Subject,
+    property: Option<(Property, Option<String>)>,
+    text: String,
+    classes: Vec<(String, OwlClass)>,
+    parents: Vec<(String, String)>,
+    version: Option<String>,
+}
+
+impl Document {
+    fn read<R: BufRead>(read: R) -> Result<Self, OntoliusError> {
+        let mut reader = NsReader::from_reader(read);
+        reader.config_mut().trim_text(true);
+        let mut document = Document {
+            depth: 0,
+            subject: Subject::Other,
+            property: None,
+            text: String::new(),
+            classes: vec![],
+            parents: vec![],
+            version: None,
+        };
+
+        let mut buf = vec![];
+        loop {
+            match reader.read_event_into(&mut buf).map_err(parse_error)? {
+                Event::Start(element) => {
+                    document.depth += 1;
+                    document.start(&reader, &element)?;
+                }
+                Event::Empty(element) => {
+                    document.depth += 1;
+                    document.start(&reader, &element)?;
+                    document.end();
+                    document.depth -= 1;
+                }
+                Event::Text(text) if document.depth == 3 => {
+                    document
+                        .text
+                        .push_str(&text.unescape().map_err(parse_error)?);
+                }
+                Event::CData(text) if document.depth == 3 => {
+                    document.text.push_str(&String::from_utf8_lossy(&text));
+                }
+                Event::End(_) => {
+                    document.end();
+                    document.depth = document.depth.saturating_sub(1);
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Ok(document)
+    }
+
+    /// Start reading the `element` at the current depth.
+    ///
+    /// The `rdf:RDF` element is at depth 1, the classes at depth 2, and their properties at depth 3.
+    fn start<R>(
+        &mut self,
+        reader: &NsReader<R>,
+        element: &BytesStart,
+    ) -> Result<(), OntoliusError> {
+        let (namespace, local) = reader.resolve_element(element.name());
+        let ResolveResult::Bound(namespace) = namespace else {
+            return Ok(());
+        };
+        match self.depth {
+            2 => {
+                self.subject = match (namespace.as_ref(), local.as_ref()) {
+                    (OWL, b"Class") => match rdf_attribute(reader, element, b"about")? {
+                        Some(iri) => Subject::Class(iri, Box::default()),
+                        None => Subject::Other,
+                    },
+                    (OWL, b"Ontology") => Subject::Ontology,
+                    _ => Subject::Other,
+                };
+            }
+            3 => {
+                self.text.clear();
+                self.property = match Property::from_name(namespace.as_ref(), local.as_ref()) {
+                    Some(property) => {
+                        Some((property, rdf_attribute(reader, element, b"resource")?))
+                    }
+                    None => None,
+                };
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Finish reading the element at the current depth.
+    fn end(&mut self) {
+        match self.depth {
+            2 => {
+                if let Subject::Class(iri, class) =
+                    std::mem::replace(&mut self.subject, Subject::Other)
+                {
+                    self.classes.push((iri, *class));
+                }
+            }
+            3 => {
+                if let Some((property, resource)) = self.property.take() {
+                    let value = std::mem::take(&mut self.text);
+                    self.apply(property, resource, value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Apply the `property` with the `resource` or the text `value` to the current subject.
+    fn apply(&mut self, property: Property, resource: Option<String>, value: String) {
+        match &mut self.subject {
+            Subject::Class(iri, class) => match property {
+                Property::SubClassOf => {
+                    // The anonymous class expressions have no `rdf:resource`.
+                    if let Some(parent) = resource {
+                        self.parents.push((iri.clone(), parent));
+                    }
+                }
+                property => class.annotate(property, resource.as_deref(), value),
+            },
+            Subject::Ontology => match property {
+                Property::VersionInfo => self.version = Some(value),
+                Property::VersionIri if self.version.is_none() => self.version = resource,
+                _ => {}
+            },
+            Subject::Other => {}
+        }
+    }
+}
+
+/// Get the value of the `rdf:`-namespaced attribute with the `local` name.
+fn rdf_attribute<R>(
+    reader: &NsReader<R>,
+    element: &BytesStart,
+    local: &[u8],
+) -> Result<Option<String>, OntoliusError> {
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(parse_error)?;
+        if let (ResolveResult::Bound(namespace), name) = reader.resolve_attribute(attribute.key) {
+            if namespace.as_ref() == RDF && name.as_ref() == local {
+                let value = attribute.unescape_value().map_err(parse_error)?;
+                return Ok(Some(value.into_owned()));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Parse a CURIE (e.g. `HP:0001166`) or an OBO PURL
+/// (e.g. `http://purl.obolibrary.org/obo/HP_0001166`) into a term ID.
+fn parse_term_reference(value: &str) -> Option<TermId> {
+    match value.strip_prefix(OBO_PURL) {
+        Some(id) => id
+            .rsplit_once('_')
+            .map(|(prefix, id)| TermId::from((prefix, id))),
+        None => TermId::from_str(value).ok(),
+    }
+}
+
+fn parse_error<E: std::fmt::Display>(e: E) -> OntoliusError {
+    OntoliusError::OntologyDataParseError(format!("Unable to read OWL document: {e}"))
+}
+
+/// Parser of the OWL RDF/XML files.
+///
+/// [`OwlParser::new`] creates [`SimpleMinimalTerm`]s from the label, the alternate IDs,
+/// and the deprecation status of the classes.
+/// Use [`OwlParser::with_term_type`] to parse other [`OwlTerm`]s,
+/// such as [`SimpleTerm`] with the synonyms and the definition.
+/// The whole document is read before the terms are created.
+///
+/// The classes with an IRI that the CURIE util cannot parse or with no label are skipped,
+/// and reported in [`OntologyData::skipped_nodes`].
+/// The version of the ontology is taken from `owl:versionInfo` or `owl:versionIRI`.
+pub struct OwlParser<CU, HI, T = SimpleMinimalTerm>
+where
+    CU: CurieUtil,
+{
+    curie_util: CU,
+    _marker: PhantomData<(HI, T)>,
+}
+
+impl<CU, HI> OwlParser<CU, HI>
+where
+    CU: CurieUtil,
+{
+    pub fn new(curie_util: CU) -> Self {
+        Self::with_term_type(curie_util)
+    }
+}
+
+impl<CU, HI, T> OwlParser<CU, HI, T>
+where
+    CU: CurieUtil,
+{
+    /// Create a parser for terms of type `T`.
+    pub fn with_term_type(curie_util: CU) -> Self {
+        Self {
+            curie_util,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<CU, I, T> OntologyDataParser for OwlParser<CU, I, T>
+where
+    CU: CurieUtil,
+    I: HierarchyIdx + TermIdx,
+    T: OwlTerm,
+{
+    type HI = I;
+    type T = T;
+
+    fn load_from_buf_read<R: BufRead>(
+        &self,
+        read: &mut R,
+    ) -> Result<OntologyData<Self::HI, Self::T>, OntoliusError> {
+        let document = Document::read(read)?;
+        Ok(assemble(
+            &self.curie_util,
+            document.classes,
+            document.parents,
+            document.version,
+        ))
+    }
+}
+
+/// Turn the `classes` and the `(child, parent)` IRI pairs of the `rdfs:subClassOf` edges
+/// into the ontology data.
+pub(super) fn assemble<CU, I, T>(
+    curie_util: &CU,
+    classes: Vec<(String, OwlClass)>,
+    parents: Vec<(String, String)>,
+    version: Option<String>,
+) -> OntologyData<I, T>
+where
+    CU: CurieUtil,
+    I: HierarchyIdx + TermIdx,
+    T: OwlTerm,
+{
+    let mut terms = vec![];
+    let mut iri2idx = HashMap::new();
+    let mut skipped = HashMap::new();
+    let mut skipped_nodes = vec![];
+    let mut replacements = HashMap::new();
+    for (iri, class) in classes.iter() {
+        let term = match (parse_iri(curie_util, iri), class.label()) {
+            (Some(term_id), Some(name)) => Ok(T::from_owl_class(term_id, name, class)),
+            (Some(term_id), None) => Err(format!("Missing term label for {term_id}")),
+            (None, label) => Err(format!(
+                "Unparsable term id of {}: {iri}",
+                label.unwrap_or("unlabeled class")
+            )),
+        };
+        match term {
+            Ok(term) => {
+                if let (true, Some(replaced_by)) = (class.is_deprecated, &class.replaced_by) {
+                    replacements.insert(term.identifier().clone(), replaced_by.clone());
+                }
+                iri2idx.insert(iri.as_str(), I::new(terms.len()));
+                terms.push(term);
+            }
+            Err(message) => {
+                skipped.insert(iri.as_str(), message);
+                skipped_nodes.push(iri.clone());
+            }
+        }
+    }
+
+    let reason = |iri: &str| match skipped.get(iri) {
+        Some(message) => UnresolvedReason::Skipped(message.clone()),
+        None => UnresolvedReason::Undeclared,
+    };
+    let mut edges = vec![];
+    let mut unresolved_edges = vec![];
+    for (child, parent) in parents.iter() {
+        match (iri2idx.get(child.as_str()), iri2idx.get(parent.as_str())) {
+            (Some(&sub), Some(&obj)) => {
+                edges.push(GraphEdge::from((sub, Relationship::Child, obj)))
+            }
+            (sub, obj) => unresolved_edges.push(UnresolvedEdge {
+                sub: endpoint_id(curie_util, child),
+                obj: endpoint_id(curie_util, parent),
+                sub_reason: sub.is_none().then(|| reason(child)),
+                obj_reason: obj.is_none().then(|| reason(parent)),
+            }),
+        }
+    }
+
+    let metadata = version
+        .map(|version| HashMap::from([("version".to_string(), version)]))
+        .unwrap_or_default();
+
+    OntologyData::from((terms.into_boxed_slice(), edges.into_boxed_slice(), metadata))
+        .with_skipped_nodes(skipped_nodes)
+        .with_unresolved_edges(unresolved_edges)
+        .with_replacements(replacements)
+}
+
+fn parse_iri<CU: CurieUtil>(curie_util: &CU, iri: &str) -> Option<TermId> {
+    curie_util
+        .get_curie_data(iri)
+        .map(|cp| TermId::from((cp.get_prefix(), cp.get_id())))
+}
+
+/// Get the CURIE of the class `iri` for reporting, or the `iri` if it is not a CURIE.
+fn endpoint_id<CU: CurieUtil>(curie_util: &CU, iri: &str) -> String {
+    parse_iri(curie_util, iri)
+        .map(|term_id| term_id.to_string())
+        .unwrap_or_else(|| iri.to_string())
+}
+
+/// Add a convenience function for using [`OwlParser`] to [`OntologyLoaderBuilder`].
+impl OntologyLoaderBuilder<Uninitialized> {
+    /// Load ontology graphs using [`OwlParser`].
+    #[must_use]
+    pub fn owl_parser<HI: HierarchyIdx + TermIdx>(
+        self,
+    ) -> OntologyLoaderBuilder<WithParser<OwlParser<TrieCurieUtil, HI>>> {
+        let parser = OwlParser::new(TrieCurieUtil::default());
+        OntologyLoaderBuilder::new().parser(parser)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::{AltTermIdAware, ReplacementAware, SubsetAware, SynonymAware, Term};
+    use crate::base::Identified;
+
+    const DOCUMENT: &str = r#"<?xml version="1.0"?>
+<rdf:RDF xmlns="http://purl.obolibrary.org/obo/hp.owl#"
+     xmlns:obo="http://purl.obolibrary.org/obo/"
+     xmlns:owl="http://www.w3.org/2002/07/owl#"
+     xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+     xmlns:rdfs="http://www.w3.org/2000/01/rdf-schema#"
+     xmlns:oboInOwl="http://www.geneontology.org/formats/oboInOwl#">
+    <owl:Ontology rdf:about="http://purl.obolibrary.org/obo/hp.owl">
+        <owl:versionIRI rdf:resource="http://purl.obolibrary.org/obo/hp/releases/2023-04-05/hp.owl"/>
+        <owl:versionInfo>2023-04-05</owl:versionInfo>
+    </owl:Ontology>
+    <owl:Class rdf:about="http://purl.obolibrary.org/obo/HP_0000001">
+        <rdfs:label>All</rdfs:label>
+    </owl:Class>
+    <owl:Class rdf:about="http://purl.obolibrary.org/obo/HP_0001166">
+        <rdfs:subClassOf rdf:resource="http://purl.obolibrary.org/obo/HP_0000001"/>
+        <rdfs:subClassOf>
+            <owl:Restriction>
+                <owl:onProperty rdf:resource="http://purl.obolibrary.org/obo/RO_0002573"/>
+                <owl:someValuesFrom rdf:resource="http://purl.obolibrary.org/obo/PATO_0000460"/>
+            </owl:Restriction>
+        </rdfs:subClassOf>
+        <rdfs:subClassOf rdf:resource="http://purl.obolibrary.org/obo/HP_0009999"/>
+        <obo:IAO_0000115>Abnormally long and slender fingers (&quot;spider fingers&quot;).</obo:IAO_0000115>
+        <oboInOwl:hasAlternativeId>HP:0001505</oboInOwl:hasAlternativeId>
+        <oboInOwl:hasDbXref>UMLS:C0003706</oboInOwl:hasDbXref>
+        <oboInOwl:hasExactSynonym>Spider fingers</oboInOwl:hasExactSynonym>
+        <oboInOwl:hasRelatedSynonym>Long slender fingers</oboInOwl:hasRelatedSynonym>
+        <oboInOwl:inSubset rdf:resource="http://purl.obolibrary.org/obo/hp#hposlim_core"/>
+        <oboInOwl:created_by>peter</oboInOwl:created_by>
+        <oboInOwl:creation_date>2008-02-27T02:20:00Z</oboInOwl:creation_date>
+        <rdfs:label>Arachnodactyly</rdfs:label>
+    </owl:Class>
+    <owl:Class rdf:about="http://purl.obolibrary.org/obo/HP_0000002">
+        <obo:IAO_0100001 rdf:resource="http://purl.obolibrary.org/obo/HP_0001166"/>
+        <oboInOwl:consider>HP:0000001</oboInOwl:consider>
+        <rdfs:label>obsolete Something</rdfs:label>
+        <owl:deprecated rdf:datatype="http://www.w3.org/2001/XMLSchema#boolean">true</owl:deprecated>
+    </owl:Class>
+    <owl:Class rdf:about="http://example.org/Unknown">
+        <rdfs:label>Unknown</rdfs:label>
+    </owl:Class>
+    <owl:Axiom>
+        <owl:annotatedSource rdf:resource="http://purl.obolibrary.org/obo/HP_0001166"/>
+        <rdfs:label>Not a class label</rdfs:label>
+    </owl:Axiom>
+</rdf:RDF>"#;
+
+    #[test]
+    fn test_load_owl() {
+        let parser: OwlParser<_, usize, SimpleTerm> =
+            OwlParser::with_term_type(TrieCurieUtil::default());
+        let data = parser.load_from_buf_read(&mut DOCUMENT.as_bytes()).unwrap();
+
+        let curies: Vec<_> = data
+            .terms()
+            .iter()
+            .map(|term| term.identifier().to_string())
+            .collect();
+        assert_eq!(curies, ["HP:0000001", "HP:0001166", "HP:0000002"]);
+        assert_eq!(data.skipped_nodes(), ["http://example.org/Unknown"]);
+        assert_eq!(
+            data.metadata().get("version").map(String::as_str),
+            Some("2023-04-05")
+        );
+
+        let arachnodactyly = &data.terms()[1];
+        assert_eq!(arachnodactyly.name(), "Arachnodactyly");
+        assert_eq!(
+            arachnodactyly.definition(),
+            Some("Abnormally long and slender fingers (\"spider fingers\").")
+        );
+        assert_eq!(arachnodactyly.iter_synonyms().count(), 2);
+        assert_eq!(
+            arachnodactyly.iter_alt_term_ids().collect::<Vec<_>>(),
+            [&TermId::from(("HP", "0001505"))]
+        );
+        assert_eq!(arachnodactyly.xrefs(), [TermId::from(("UMLS", "C0003706"))]);
+        assert_eq!(arachnodactyly.subsets(), ["hposlim_core"]);
+        let provenance = arachnodactyly.provenance().unwrap();
+        assert_eq!(provenance.created_by(), Some("peter"));
+        assert_eq!(provenance.creation_date(), Some("2008-02-27T02:20:00Z"));
+        assert!(arachnodactyly.is_current());
+
+        let obsolete = &data.terms()[2];
+        assert!(obsolete.is_obsolete());
+        assert_eq!(
+            obsolete.replaced_by(),
+            Some(&TermId::from(("HP", "0001166")))
+        );
+        assert_eq!(obsolete.consider(), [TermId::from(("HP", "0000001"))]);
+
+        // The edge to the undeclared `HP:0009999` is unresolved, and the restriction is skipped.
+        assert_eq!(data.edges().len(), 1);
+        assert_eq!(data.unresolved_edges().len(), 1);
+        assert_eq!(data.unresolved_edges()[0].obj(), "HP:0009999");
+        assert_eq!(
+            data.unresolved_edges()[0].obj_reason(),
+            Some(&UnresolvedReason::Undeclared)
+        );
+    }
+}
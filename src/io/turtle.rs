@@ -0,0 +1,284 @@
+//! Load ontologies from Turtle (`.ttl`) files, for the ontologies that publish
+//! neither an Obographs nor an OWL RDF/XML release.
+//!
+//! The parser reads the same classes and annotations as [`super::owl::OwlParser`]:
+//! the named classes (`rdf:type owl:Class`), their `rdfs:subClassOf` edges to other named classes,
+//! and the OBO annotations, such as the labels, the synonyms, or the deprecation flags.
+//! The blank nodes, such as the class expressions or the axiom annotations, are skipped.
+//!
+//! ## Examples
+//!
+//! ```
+//! use curie_util::TrieCurieUtil;
+//! use ontolius::io::turtle::TurtleParser;
+//! use ontolius::io::OntologyDataParser;
+//! use ontolius::ontology::csr::MinimalCsrOntology;
+//! use ontolius::prelude::*;
+//!
+//! let document = r#"
+//! @prefix obo: <http://purl.obolibrary.org/obo/> .
+//! @prefix owl: <http://www.w3.org/2002/07/owl#> .
+//! @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+//!
+//! obo:HP_0000001 a owl:Class ;
+//!     rdfs:label "All" .
+//! obo:HP_0000118 a owl:Class ;
+//!     rdfs:subClassOf obo:HP_0000001 ;
+//!     rdfs:label "Phenotypic abnormality" .
+//! "#;
+//!
+//! let parser = TurtleParser::new(TrieCurieUtil::default());
+//! let data = parser.load_from_buf_read(&mut document.as_bytes())
+//!                  .expect("The document should be parsable");
+//! let ontology = MinimalCsrOntology::try_from(data)
+//!                  .expect("The data should form an ontology");
+//!
+//! assert_eq!(ontology.len(), 2);
+//! assert_eq!(ontology.root_term().name(), "All");
+//! ```
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use std::marker::PhantomData;
+
+use curie_util::{CurieUtil, TrieCurieUtil};
+use oxrdf::{Subject, Term};
+
+use crate::base::term::simple::SimpleMinimalTerm;
+use crate::error::OntoliusError;
+use crate::hierarchy::HierarchyIdx;
+use crate::ontology::TermIdx;
+
+use super::owl::{assemble, OwlClass, OwlTerm, Property};
+use super::{OntologyData, OntologyDataParser, OntologyLoaderBuilder, Uninitialized, WithParser};
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const OWL_CLASS: &str = "http://www.w3.org/2002/07/owl#Class";
+const OWL_ONTOLOGY: &str = "http://www.w3.org/2002/07/owl#Ontology";
+
+/// Parser of the Turtle files.
+///
+/// [`TurtleParser::new`] creates [`SimpleMinimalTerm`]s, like [`super::owl::OwlParser::new`].
+/// Use [`TurtleParser::with_term_type`] to parse other [`OwlTerm`]s,
+/// such as [`crate::base::term::simple::SimpleTerm`] with the synonyms and the definition.
+/// The triples can come in any order, hence the annotations of all named subjects
+/// are kept until the end of the document, when the classes are known.
+///
+/// The classes with an IRI that the CURIE util cannot parse or with no label are skipped,
+/// and reported in [`OntologyData::skipped_nodes`].
+/// The version of the ontology is taken from `owl:versionInfo` or `owl:versionIRI`.
+pub struct TurtleParser<CU, HI, T = SimpleMinimalTerm>
+where
+    CU: CurieUtil,
+{
+    curie_util: CU,
+    _marker: PhantomData<(HI, T)>,
+}
+
+impl<CU, HI> TurtleParser<CU, HI>
+where
+    CU: CurieUtil,
+{
+    pub fn new(curie_util: CU) -> Self {
+        Self::with_term_type(curie_util)
+    }
+}
+
+impl<CU, HI, T> TurtleParser<CU, HI, T>
+where
+    CU: CurieUtil,
+{
+    /// Create a parser for terms of type `T`.
+    pub fn with_term_type(curie_util: CU) -> Self {
+        Self {
+            curie_util,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<CU, I, T> OntologyDataParser for TurtleParser<CU, I, T>
+where
+    CU: CurieUtil,
+    I: HierarchyIdx + TermIdx,
+    T: OwlTerm,
+{
+    type HI = I;
+    type T = T;
+
+    fn load_from_buf_read<R: BufRead>(
+        &self,
+        read: &mut R,
+    ) -> Result<OntologyData<Self::HI, Self::T>, OntoliusError> {
+        // The annotations of all named subjects, in the order of their first triple,
+        // since the `rdf:type` triple of a class can come after its annotations.
+        let mut subjects: Vec<(String, OwlClass)> = vec![];
+        let mut subject_idx = HashMap::new();
+        let mut classes = HashSet::new();
+        let mut ontologies = HashSet::new();
+        let mut parents = vec![];
+        let mut version_info = None;
+        let mut version_iri = None;
+
+        for triple in oxttl::TurtleParser::new().for_reader(read) {
+            let triple = triple.map_err(|e| {
+                OntoliusError::OntologyDataParseError(format!(
+                    "Unable to read Turtle document: {e}"
+                ))
+            })?;
+            let Subject::NamedNode(subject) = triple.subject else {
+                continue;
+            };
+            let subject = subject.into_string();
+            let predicate = triple.predicate.as_str();
+            let (resource, value) = match triple.object {
+                Term::NamedNode(node) => (Some(node.into_string()), String::new()),
+                Term::Literal(literal) => (None, literal.value().to_string()),
+                _ => continue,
+            };
+
+            if predicate == RDF_TYPE {
+                match resource.as_deref() {
+                    Some(OWL_CLASS) => {
+                        classes.insert(subject.clone());
+                    }
+                    Some(OWL_ONTOLOGY) => {
+                        ontologies.insert(subject);
+                        continue;
+                    }
+                    _ => continue,
+                }
+            }
+            let idx = *subject_idx.entry(subject.clone()).or_insert_with(|| {
+                subjects.push((subject.clone(), OwlClass::default()));
+                subjects.len() - 1
+            });
+            match Property::from_iri(predicate) {
+                Some(Property::SubClassOf) => {
+                    if let Some(parent) = resource {
+                        parents.push((subject, parent));
+                    }
+                }
+                Some(Property::VersionInfo) => version_info = Some((subject, value)),
+                Some(Property::VersionIri) => version_iri = resource.map(|iri| (subject, iri)),
+                Some(property) => subjects[idx]
+                    .1
+                    .annotate(property, resource.as_deref(), value),
+                None => {}
+            }
+        }
+
+        let version = [version_info, version_iri]
+            .into_iter()
+            .flatten()
+            .find(|(subject, _)| ontologies.contains(subject))
+            .map(|(_, version)| version);
+        let subjects: Vec<_> = subjects
+            .into_iter()
+            .filter(|(iri, _)| classes.contains(iri))
+            .collect();
+        let parents: Vec<_> = parents
+            .into_iter()
+            .filter(|(child, _)| classes.contains(child))
+            .collect();
+
+        Ok(assemble(&self.curie_util, subjects, parents, version))
+    }
+}
+
+/// Add a convenience function for using [`TurtleParser`] to [`OntologyLoaderBuilder`].
+impl OntologyLoaderBuilder<Uninitialized> {
+    /// Load ontology graphs using [`TurtleParser`].
+    #[must_use]
+    pub fn turtle_parser<HI: HierarchyIdx + TermIdx>(
+        self,
+    ) -> OntologyLoaderBuilder<WithParser<TurtleParser<TrieCurieUtil, HI>>> {
+        let parser = TurtleParser::new(TrieCurieUtil::default());
+        OntologyLoaderBuilder::new().parser(parser)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleTerm;
+    use crate::base::term::{AltTermIdAware, MinimalTerm, ReplacementAware, SynonymAware, Term};
+    use crate::base::{Identified, TermId};
+    use crate::io::UnresolvedReason;
+
+    const DOCUMENT: &str = r#"
+@prefix obo: <http://purl.obolibrary.org/obo/> .
+@prefix oboInOwl: <http://www.geneontology.org/formats/oboInOwl#> .
+@prefix owl: <http://www.w3.org/2002/07/owl#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+<http://purl.obolibrary.org/obo/hp.owl> a owl:Ontology ;
+    owl:versionInfo "2023-04-05" .
+
+obo:HP_0000001 a owl:Class ;
+    rdfs:label "All" .
+
+obo:HP_0001166 rdfs:label "Arachnodactyly"@en ;
+    rdfs:subClassOf obo:HP_0000001, obo:HP_0009999 ;
+    rdfs:subClassOf [ a owl:Restriction ;
+        owl:onProperty obo:RO_0002573 ;
+        owl:someValuesFrom obo:PATO_0000460 ] ;
+    obo:IAO_0000115 "Abnormally long and slender fingers." ;
+    oboInOwl:hasAlternativeId "HP:0001505" ;
+    oboInOwl:hasExactSynonym "Spider fingers" ;
+    a owl:Class .
+
+obo:HP_0000002 a owl:Class ;
+    rdfs:label "obsolete Something" ;
+    owl:deprecated "true"^^xsd:boolean ;
+    obo:IAO_0100001 obo:HP_0001166 .
+
+obo:RO_0002573 a owl:ObjectProperty ;
+    rdfs:label "has modifier" .
+
+<http://example.org/Unknown> a owl:Class ;
+    rdfs:label "Unknown" .
+"#;
+
+    #[test]
+    fn test_load_turtle() {
+        let parser: TurtleParser<_, usize, SimpleTerm> =
+            TurtleParser::with_term_type(TrieCurieUtil::default());
+        let data = parser.load_from_buf_read(&mut DOCUMENT.as_bytes()).unwrap();
+
+        let curies: Vec<_> = data
+            .terms()
+            .iter()
+            .map(|term| term.identifier().to_string())
+            .collect();
+        assert_eq!(curies, ["HP:0000001", "HP:0001166", "HP:0000002"]);
+        assert_eq!(data.skipped_nodes(), ["http://example.org/Unknown"]);
+        assert_eq!(
+            data.metadata().get("version").map(String::as_str),
+            Some("2023-04-05")
+        );
+
+        let arachnodactyly = &data.terms()[1];
+        assert_eq!(arachnodactyly.name(), "Arachnodactyly");
+        assert_eq!(
+            arachnodactyly.definition(),
+            Some("Abnormally long and slender fingers.")
+        );
+        assert_eq!(arachnodactyly.iter_synonyms().count(), 1);
+        assert_eq!(arachnodactyly.iter_alt_term_ids().count(), 1);
+
+        let obsolete = &data.terms()[2];
+        assert!(obsolete.is_obsolete());
+        assert_eq!(
+            obsolete.replaced_by(),
+            Some(&TermId::from(("HP", "0001166")))
+        );
+
+        assert_eq!(data.edges().len(), 1);
+        assert_eq!(data.unresolved_edges().len(), 1);
+        assert_eq!(
+            data.unresolved_edges()[0].obj_reason(),
+            Some(&UnresolvedReason::Undeclared)
+        );
+    }
+}
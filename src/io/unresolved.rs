@@ -0,0 +1,96 @@
+use std::fmt::Display;
+
+/// The reason why an edge endpoint does not resolve to a term.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnresolvedReason {
+    /// The input declares no node with the id.
+    Undeclared,
+    /// The parser could not turn the node into a term, e.g. due to a missing label.
+    ///
+    /// Includes the message of the parser.
+    Skipped(String),
+    /// The term was removed by the loader, e.g. due to its CURIE prefix
+    /// (see [`super::OntologyLoaderBuilder::keep_prefixes`]).
+    Filtered,
+}
+
+impl Display for UnresolvedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnresolvedReason::Undeclared => write!(f, "not declared"),
+            UnresolvedReason::Skipped(message) => write!(f, "skipped ({message})"),
+            UnresolvedReason::Filtered => write!(f, "filtered out"),
+        }
+    }
+}
+
+/// An `is_a` edge dropped from the input because one or both endpoints do not resolve to a term.
+///
+/// The endpoints are CURIEs, or the original node ids if the ids cannot be parsed into CURIEs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnresolvedEdge {
+    pub(super) sub: String,
+    pub(super) obj: String,
+    pub(super) sub_reason: Option<UnresolvedReason>,
+    pub(super) obj_reason: Option<UnresolvedReason>,
+}
+
+impl UnresolvedEdge {
+    /// Get the subject (child) of the edge.
+    pub fn sub(&self) -> &str {
+        &self.sub
+    }
+
+    /// Get the object (parent) of the edge.
+    pub fn obj(&self) -> &str {
+        &self.obj
+    }
+
+    /// Get the reason why the subject does not resolve to a term,
+    /// or `None` if the subject resolves.
+    pub fn sub_reason(&self) -> Option<&UnresolvedReason> {
+        self.sub_reason.as_ref()
+    }
+
+    /// Get the reason why the object does not resolve to a term,
+    /// or `None` if the object resolves.
+    pub fn obj_reason(&self) -> Option<&UnresolvedReason> {
+        self.obj_reason.as_ref()
+    }
+}
+
+impl Display for UnresolvedEdge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is_a {}", self.sub, self.obj)?;
+        for (endpoint, reason) in [(&self.sub, &self.sub_reason), (&self.obj, &self.obj_reason)] {
+            if let Some(reason) = reason {
+                write!(f, ": {endpoint} was {reason}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let edge = UnresolvedEdge {
+            sub: "HP:2".into(),
+            obj: "HP:1".into(),
+            sub_reason: None,
+            obj_reason: Some(UnresolvedReason::Skipped(
+                "Missing term label for HP:1".into(),
+            )),
+        };
+
+        assert_eq!(
+            edge.to_string(),
+            "HP:2 is_a HP:1: HP:1 was skipped (Missing term label for HP:1)"
+        );
+    }
+}
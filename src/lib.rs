@@ -1,8 +1,40 @@
 #![doc = include_str!("../README.md")]
 
+pub mod align;
+#[cfg(feature = "hnsw")]
+pub mod ann;
+pub mod anatomy;
+pub mod annotation;
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+pub mod arbitrary;
 pub mod base;
+#[cfg(feature = "polars")]
+pub mod dataframe;
+#[cfg(feature = "embedding")]
+pub mod embedding;
 pub mod error;
+#[cfg(feature = "tantivy")]
+pub mod fulltext;
+#[cfg(feature = "graphql")]
+pub mod graphql;
 pub mod hierarchy;
 pub mod io;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod ontology;
 pub mod prelude;
+#[cfg(feature = "pyo3")]
+pub mod py;
+pub mod ranking;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod search;
+#[cfg(feature = "service")]
+pub mod service;
+#[cfg(feature = "rayon")]
+pub mod similarity;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "csv")]
+pub mod validate;
+pub mod viz;
@@ -0,0 +1,116 @@
+//! Record the usage of the ontology API with the [`metrics`](https://docs.rs/metrics) crate.
+//!
+//! When the `metrics` feature is enabled, [`crate::ontology::csr::CsrOntology`]
+//! and [`crate::ontology::csr::CsrOntologyHierarchy`] increment the following counters:
+//!
+//! * [`CALLS`] - the number of API calls, labeled by the `method` (e.g. `children_of`),
+//! * [`TERM_LOOKUPS`] - the number of term lookups by ID, labeled by the `term_id` (e.g. `HP:0001250`).
+//!
+//! The counters are sent to the recorder installed by the application,
+//! such as `metrics-exporter-prometheus`. No data is recorded if no recorder is installed.
+//!
+//! Note that [`TERM_LOOKUPS`] has one time series per looked up term,
+//! which may be too many for some monitoring backends.
+
+/// The name of the counter of the API calls.
+pub const CALLS: &str = "ontolius_calls_total";
+
+/// The name of the counter of the term lookups.
+pub const TERM_LOOKUPS: &str = "ontolius_term_lookups_total";
+
+/// Count a call of the API `method`.
+pub(crate) fn record_call(method: &'static str) {
+    ::metrics::counter!(CALLS, "method" => method).increment(1);
+}
+
+/// Count a lookup of the term with `term_id`.
+pub(crate) fn record_term_lookup(term_id: String) {
+    ::metrics::counter!(TERM_LOOKUPS, "term_id" => term_id).increment(1);
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use ::metrics::{
+        Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct AtomicCounter(AtomicU64);
+
+    impl CounterFn for AtomicCounter {
+        fn increment(&self, value: u64) {
+            self.0.fetch_add(value, Ordering::Relaxed);
+        }
+
+        fn absolute(&self, value: u64) {
+            self.0.fetch_max(value, Ordering::Relaxed);
+        }
+    }
+
+    /// A recorder that keeps the counters in memory.
+    #[derive(Default)]
+    struct TestRecorder {
+        counters: Mutex<HashMap<Key, Arc<AtomicCounter>>>,
+    }
+
+    impl TestRecorder {
+        fn value(&self, name: &'static str, label: (&'static str, &'static str)) -> u64 {
+            let key = Key::from_parts(name, &[label]);
+            self.counters
+                .lock()
+                .unwrap()
+                .get(&key)
+                .map_or(0, |c| c.0.load(Ordering::Relaxed))
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let counter = self
+                .counters
+                .lock()
+                .unwrap()
+                .entry(key.clone())
+                .or_default()
+                .clone();
+            Counter::from_arc(counter)
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn test_record_calls_and_lookups() {
+        let recorder = TestRecorder::default();
+
+        ::metrics::with_local_recorder(&recorder, || {
+            record_call("children_of");
+            record_call("children_of");
+            record_call("parents_of");
+            record_term_lookup("HP:0001250".into());
+        });
+
+        assert_eq!(recorder.value(CALLS, ("method", "children_of")), 2);
+        assert_eq!(recorder.value(CALLS, ("method", "parents_of")), 1);
+        assert_eq!(recorder.value(CALLS, ("method", "ancestors_of")), 0);
+        assert_eq!(recorder.value(TERM_LOOKUPS, ("term_id", "HP:0001250")), 1);
+    }
+}
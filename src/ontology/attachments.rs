@@ -0,0 +1,66 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A set of values attached to an ontology, keyed by the value type.
+///
+/// The values are shared between the clones of the ontology.
+#[derive(Clone, Default)]
+pub(crate) struct Attachments {
+    values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Attachments {
+    /// Attach the `value` and return the previous value of the same type, if any.
+    pub(crate) fn insert<A>(&mut self, value: A) -> Option<Arc<A>>
+    where
+        A: Any + Send + Sync,
+    {
+        self.values
+            .insert(TypeId::of::<A>(), Arc::new(value))
+            .and_then(|previous| previous.downcast().ok())
+    }
+
+    pub(crate) fn get<A>(&self) -> Option<&A>
+    where
+        A: Any + Send + Sync,
+    {
+        self.values
+            .get(&TypeId::of::<A>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    pub(crate) fn remove<A>(&mut self) -> Option<Arc<A>>
+    where
+        A: Any + Send + Sync,
+    {
+        self.values
+            .remove(&TypeId::of::<A>())
+            .and_then(|value| value.downcast().ok())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Counts(Vec<u32>);
+
+    #[test]
+    fn test_attachments() {
+        let mut attachments = Attachments::default();
+        assert!(attachments.get::<Counts>().is_none());
+
+        assert!(attachments.insert(Counts(vec![1, 2])).is_none());
+        attachments.insert(String::from("embeddings"));
+        assert_eq!(attachments.get::<Counts>().unwrap().0, [1, 2]);
+
+        let previous = attachments.insert(Counts(vec![3]));
+        assert_eq!(previous.unwrap().0, [1, 2]);
+
+        let removed = attachments.remove::<Counts>().unwrap();
+        assert_eq!(removed.0, [3]);
+        assert!(attachments.get::<Counts>().is_none());
+        assert_eq!(attachments.get::<String>().unwrap(), "embeddings");
+    }
+}
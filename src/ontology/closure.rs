@@ -0,0 +1,201 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::base::TermId;
+use crate::hierarchy::{ChildNodes, DescendantNodes, ParentNodes};
+
+use super::{Ontology, TermIdx};
+
+pub(super) fn find_ancestors_until<'o, O>(
+    ontology: &'o O,
+    query: &TermId,
+    stop: &TermId,
+) -> Vec<&'o TermId>
+where
+    O: Ontology + ?Sized,
+{
+    let Some(idx) = ontology.id_to_idx(query) else {
+        return vec![];
+    };
+    let stop = ontology.id_to_idx(stop);
+    if stop == Some(idx) {
+        return vec![];
+    }
+    let hierarchy = ontology.hierarchy();
+    let seen = vec![false; ontology.len()];
+    traverse(ontology, idx, seen, stop, |node| {
+        hierarchy.parents_of(node).copied()
+    })
+}
+
+pub(super) fn find_ancestors_excluding<'o, O>(
+    ontology: &'o O,
+    query: &TermId,
+    excluded: &HashSet<TermId>,
+) -> Vec<&'o TermId>
+where
+    O: Ontology + ?Sized,
+{
+    let Some(idx) = ontology.id_to_idx(query) else {
+        return vec![];
+    };
+    let seen = mark_excluded(ontology, excluded);
+    let hierarchy = ontology.hierarchy();
+    traverse(ontology, idx, seen, None, |node| {
+        hierarchy.parents_of(node).copied()
+    })
+}
+
+pub(super) fn find_descendants_excluding<'o, O>(
+    ontology: &'o O,
+    query: &TermId,
+    excluded: &HashSet<TermId>,
+) -> Vec<&'o TermId>
+where
+    O: Ontology + ?Sized,
+{
+    let Some(idx) = ontology.id_to_idx(query) else {
+        return vec![];
+    };
+    let seen = mark_excluded(ontology, excluded);
+    if seen[TermIdx::index(idx)] {
+        return vec![];
+    }
+    let hierarchy = ontology.hierarchy();
+    traverse(ontology, idx, seen, None, |node| {
+        hierarchy.children_of(node).copied()
+    })
+}
+
+/// Mark the `excluded` terms and their descendants.
+fn mark_excluded<O>(ontology: &O, excluded: &HashSet<TermId>) -> Vec<bool>
+where
+    O: Ontology + ?Sized,
+{
+    let hierarchy = ontology.hierarchy();
+    let mut marked = vec![false; ontology.len()];
+    for idx in excluded
+        .iter()
+        .flat_map(|term_id| ontology.id_to_idx(term_id))
+    {
+        if !std::mem::replace(&mut marked[TermIdx::index(idx)], true) {
+            for &descendant in hierarchy.descendants_of(idx) {
+                marked[TermIdx::index(descendant)] = true;
+            }
+        }
+    }
+    marked
+}
+
+/// Visit the `neighbors` of the `start` node breadth-first, skipping the `seen` nodes
+/// and not visiting the neighbors of the `stop` node.
+fn traverse<O, F, I>(
+    ontology: &O,
+    start: O::Idx,
+    mut seen: Vec<bool>,
+    stop: Option<O::Idx>,
+    neighbors: F,
+) -> Vec<&TermId>
+where
+    O: Ontology + ?Sized,
+    F: Fn(O::Idx) -> I,
+    I: Iterator<Item = O::Idx>,
+{
+    seen[TermIdx::index(start)] = true;
+    let mut queue = VecDeque::from([start]);
+    let mut visited = vec![];
+    while let Some(current) = queue.pop_front() {
+        for node in neighbors(current) {
+            if !std::mem::replace(&mut seen[TermIdx::index(node)], true) {
+                visited.extend(ontology.idx_to_term_id(node));
+                if Some(node) != stop {
+                    queue.push_back(node);
+                }
+            }
+        }
+    }
+    visited
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::base::TermId;
+    use crate::ontology::csr::CsrOntology;
+    use crate::ontology::Ontology;
+    use crate::test_util::toy_ontology;
+
+    /// A small ontology, where `HP:5` is a descendant of both the phenotypic abnormality (`HP:2`)
+    /// and the clinical modifier (`HP:3`).
+    fn build_ontology() -> CsrOntology<usize, SimpleMinimalTerm> {
+        let terms: Vec<_> = ["HP:1", "HP:2", "HP:3", "HP:4", "HP:5"]
+            .into_iter()
+            .map(|curie| {
+                SimpleMinimalTerm::new(TermId::from_str(curie).unwrap(), curie, vec![], false)
+            })
+            .collect();
+        toy_ontology(terms, &[(1, 0), (2, 0), (3, 1), (4, 3), (4, 2)])
+    }
+
+    fn ancestors_until(
+        ontology: &CsrOntology<usize, SimpleMinimalTerm>,
+        query: &str,
+        stop: &str,
+    ) -> Vec<String> {
+        ontology
+            .iter_ancestor_ids_until(
+                &TermId::from_str(query).unwrap(),
+                &TermId::from_str(stop).unwrap(),
+            )
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_iter_ancestor_ids_until() {
+        let ontology = build_ontology();
+
+        assert_eq!(ancestors_until(&ontology, "HP:4", "HP:2"), ["HP:2"]);
+        assert_eq!(ancestors_until(&ontology, "HP:4", "HP:9"), ["HP:2", "HP:1"]);
+        assert!(ancestors_until(&ontology, "HP:2", "HP:2").is_empty());
+        assert!(ancestors_until(&ontology, "HP:9", "HP:2").is_empty());
+        // The path through the clinical modifier is followed up to the root.
+        assert_eq!(
+            ancestors_until(&ontology, "HP:5", "HP:2"),
+            ["HP:3", "HP:4", "HP:1", "HP:2"]
+        );
+    }
+
+    #[test]
+    fn test_closure_excluding() {
+        let ontology = build_ontology();
+        let term_id = |curie: &str| TermId::from_str(curie).unwrap();
+        let to_strings = |term_ids: Vec<&TermId>| -> Vec<_> {
+            term_ids.into_iter().map(ToString::to_string).collect()
+        };
+        let clinical_modifier = HashSet::from([term_id("HP:3")]);
+
+        let ancestors: Vec<_> = ontology
+            .iter_ancestor_ids_excluding(&term_id("HP:5"), &clinical_modifier)
+            .collect();
+        assert_eq!(to_strings(ancestors), ["HP:4", "HP:2", "HP:1"]);
+
+        let descendants: Vec<_> = ontology
+            .iter_descendant_ids_excluding(&term_id("HP:1"), &clinical_modifier)
+            .collect();
+        assert_eq!(to_strings(descendants), ["HP:2", "HP:4"]);
+
+        let ancestors: Vec<_> = ontology
+            .iter_ancestor_ids_excluding(&term_id("HP:5"), &HashSet::from([term_id("HP:2")]))
+            .collect();
+        assert_eq!(to_strings(ancestors), ["HP:3", "HP:1"]);
+        assert_eq!(
+            ontology
+                .iter_descendant_ids_excluding(&term_id("HP:4"), &clinical_modifier)
+                .count(),
+            0
+        );
+    }
+}
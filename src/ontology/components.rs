@@ -0,0 +1,103 @@
+use crate::base::term::MinimalTerm;
+use crate::hierarchy::{HierarchyIdx, OntologyHierarchy};
+
+use super::{Ontology, TermIdx};
+
+pub(super) fn find_orphan_terms<O>(ontology: &O) -> Vec<&O::T>
+where
+    O: Ontology + ?Sized,
+{
+    let hierarchy = ontology.hierarchy();
+    ontology
+        .iter_terms()
+        .enumerate()
+        .filter(|(idx, term)| {
+            !term.is_obsolete()
+                && hierarchy
+                    .iter_neighbors(<O::Idx as HierarchyIdx>::new(*idx))
+                    .next()
+                    .is_none()
+        })
+        .map(|(_, term)| term)
+        .collect()
+}
+
+pub(super) fn find_connected_components<O>(ontology: &O) -> Vec<Vec<O::Idx>>
+where
+    O: Ontology + ?Sized,
+{
+    let hierarchy = ontology.hierarchy();
+    let mut seen = vec![false; ontology.len()];
+    let mut components = vec![];
+
+    for (idx, term) in ontology.iter_terms().enumerate() {
+        if term.is_obsolete() || seen[idx] {
+            continue;
+        }
+        seen[idx] = true;
+
+        // Depth-first search, ignoring the edge direction.
+        let mut component = vec![];
+        let mut stack = vec![<O::Idx as HierarchyIdx>::new(idx)];
+        while let Some(node) = stack.pop() {
+            component.push(node);
+            for &neighbor in hierarchy.iter_neighbors(node) {
+                if !seen[TermIdx::index(neighbor)] {
+                    seen[TermIdx::index(neighbor)] = true;
+                    stack.push(neighbor);
+                }
+            }
+        }
+        component.sort();
+        components.push(component);
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::base::{Identified, TermId};
+    use crate::ontology::csr::CsrOntology;
+    use crate::ontology::Ontology;
+    use crate::test_util::toy_ontology;
+
+    fn build_ontology() -> CsrOntology<usize, SimpleMinimalTerm> {
+        let terms: Vec<_> = [
+            ("HP:1", false),
+            ("HP:2", false),
+            ("HP:3", false),
+            ("HP:4", true),
+            ("HP:5", false),
+        ]
+        .into_iter()
+        .map(|(curie, is_obsolete)| {
+            SimpleMinimalTerm::new(TermId::from_str(curie).unwrap(), curie, vec![], is_obsolete)
+        })
+        .collect();
+        toy_ontology(terms, &[(1, 0), (2, 1)])
+    }
+
+    #[test]
+    fn test_orphan_terms() {
+        let ontology = build_ontology();
+
+        let orphans: Vec<_> = ontology
+            .orphan_terms()
+            .into_iter()
+            .map(|term| term.identifier().to_string())
+            .collect();
+
+        assert_eq!(orphans, ["HP:5"]);
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let ontology = build_ontology();
+
+        assert_eq!(ontology.connected_components(), [vec![0, 1, 2], vec![4]]);
+    }
+}
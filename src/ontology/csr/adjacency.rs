@@ -0,0 +1,205 @@
+use graph_builder::{CsrLayout, DirectedCsrGraph, DirectedNeighbors, Graph, GraphBuilder};
+
+use crate::hierarchy::HierarchyIdx;
+use crate::ontology::TermIdx;
+
+use super::Idx;
+
+/// The implementation of the adjacency matrix of [`super::CsrOntology`].
+///
+/// The backends differ in the memory layout and in the dependencies,
+/// but not in the hierarchy they represent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CsrBackend {
+    /// A directed CSR graph of the `graph_builder` crate,
+    /// with separate matrices for the outgoing and the incoming edges.
+    #[default]
+    GraphBuilder,
+    /// A CSR matrix built by ontolius,
+    /// with the parents and the children of a node stored next to each other.
+    ///
+    /// Walking the hierarchy in both directions, e.g. to get the neighborhood of a term,
+    /// touches a single contiguous region of memory per node.
+    Interleaved,
+}
+
+/// The adjacency matrix of the `is_a` hierarchy, with the parents as the outgoing edges
+/// and with the children as the incoming edges.
+#[derive(Clone)]
+pub(super) enum Adjacency<I>
+where
+    I: Idx,
+{
+    GraphBuilder(DirectedCsrGraph<I>),
+    Interleaved(InterleavedCsr<I>),
+}
+
+impl<I> Adjacency<I>
+where
+    I: Idx,
+{
+    /// Build the matrix from the `(child, parent)` pairs.
+    pub(super) fn new(pairs: Vec<(I, I)>, layout: CsrLayout, backend: CsrBackend) -> Self {
+        match backend {
+            CsrBackend::GraphBuilder => {
+                Adjacency::GraphBuilder(GraphBuilder::new().csr_layout(layout).edges(pairs).build())
+            }
+            CsrBackend::Interleaved => Adjacency::Interleaved(InterleavedCsr::new(&pairs, layout)),
+        }
+    }
+
+    pub(super) fn node_count(&self) -> usize {
+        match self {
+            Adjacency::GraphBuilder(graph) => TermIdx::index(graph.node_count()),
+            Adjacency::Interleaved(csr) => csr.node_count(),
+        }
+    }
+
+    /// Get the approximate number of bytes used by the offsets and the targets.
+    pub(super) fn memory_usage(&self) -> usize {
+        match self {
+            Adjacency::GraphBuilder(graph) => {
+                let node_count = TermIdx::index(graph.node_count());
+                let edge_count = TermIdx::index(graph.edge_count());
+                2 * (node_count + 1 + edge_count) * std::mem::size_of::<I>()
+            }
+            Adjacency::Interleaved(csr) => {
+                csr.offsets.len() * std::mem::size_of::<usize>()
+                    + csr.targets.len() * std::mem::size_of::<I>()
+            }
+        }
+    }
+
+    /// Get the parents of the `node`, which must be a node of the matrix.
+    pub(super) fn out_neighbors(&self, node: I) -> std::slice::Iter<'_, I> {
+        match self {
+            Adjacency::GraphBuilder(graph) => graph.out_neighbors(node),
+            Adjacency::Interleaved(csr) => csr.neighbors(2 * TermIdx::index(node)),
+        }
+    }
+
+    /// Get the children of the `node`, which must be a node of the matrix.
+    pub(super) fn in_neighbors(&self, node: I) -> std::slice::Iter<'_, I> {
+        match self {
+            Adjacency::GraphBuilder(graph) => graph.in_neighbors(node),
+            Adjacency::Interleaved(csr) => csr.neighbors(2 * TermIdx::index(node) + 1),
+        }
+    }
+}
+
+/// A CSR matrix with two adjacency lists per node: the parents and then the children.
+///
+/// The parents of the node `i` are the `targets[offsets[2i]..offsets[2i + 1]]`
+/// and the children are the `targets[offsets[2i + 1]..offsets[2i + 2]]`.
+#[derive(Clone)]
+pub(super) struct InterleavedCsr<I> {
+    offsets: Vec<usize>,
+    targets: Vec<I>,
+}
+
+impl<I> InterleavedCsr<I>
+where
+    I: Idx,
+{
+    fn new(pairs: &[(I, I)], layout: CsrLayout) -> Self {
+        let node_count = pairs
+            .iter()
+            .map(|&(child, parent)| TermIdx::index(child).max(TermIdx::index(parent)) + 1)
+            .max()
+            .unwrap_or(0);
+
+        // Count the list lengths, shifted by one to turn the counts into the offsets in place.
+        let mut offsets = vec![0; 2 * node_count + 1];
+        for &(child, parent) in pairs {
+            offsets[2 * TermIdx::index(child) + 1] += 1;
+            offsets[2 * TermIdx::index(parent) + 2] += 1;
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+
+        // Fill the lists in the order of the pairs.
+        let mut cursors = offsets[..2 * node_count].to_vec();
+        let mut targets = vec![<I as HierarchyIdx>::new(0); pairs.len() * 2];
+        for &(child, parent) in pairs {
+            let parents = &mut cursors[2 * TermIdx::index(child)];
+            targets[*parents] = parent;
+            *parents += 1;
+            let children = &mut cursors[2 * TermIdx::index(parent) + 1];
+            targets[*children] = child;
+            *children += 1;
+        }
+
+        let mut csr = Self { offsets, targets };
+        match layout {
+            CsrLayout::Unsorted => {}
+            CsrLayout::Sorted => csr.sort(),
+            CsrLayout::Deduplicated => {
+                csr.sort();
+                csr.dedup();
+            }
+        }
+        csr
+    }
+
+    fn node_count(&self) -> usize {
+        self.offsets.len() / 2
+    }
+
+    fn neighbors(&self, list: usize) -> std::slice::Iter<'_, I> {
+        self.targets[self.offsets[list]..self.offsets[list + 1]].iter()
+    }
+
+    fn sort(&mut self) {
+        for list in self.offsets.windows(2) {
+            self.targets[list[0]..list[1]].sort_unstable();
+        }
+    }
+
+    /// Remove the duplicate targets from the sorted lists and compact the targets.
+    fn dedup(&mut self) {
+        let mut end = 0;
+        let mut start = self.offsets[0];
+        for list in 0..self.offsets.len() - 1 {
+            let stop = self.offsets[list + 1];
+            let first = end;
+            for i in start..stop {
+                if end == first || self.targets[end - 1] != self.targets[i] {
+                    self.targets[end] = self.targets[i];
+                    end += 1;
+                }
+            }
+            start = stop;
+            self.offsets[list + 1] = end;
+        }
+        self.targets.truncate(end);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_interleaved_layouts() {
+        let pairs: Vec<(u32, u32)> = vec![(2, 0), (1, 0), (2, 1), (2, 0)];
+
+        let unsorted = InterleavedCsr::new(&pairs, CsrLayout::Unsorted);
+        assert_eq!(unsorted.node_count(), 3);
+        assert_eq!(unsorted.neighbors(1).as_slice(), [2, 1, 2]);
+        assert_eq!(unsorted.neighbors(4).as_slice(), [0, 1, 0]);
+
+        let sorted = InterleavedCsr::new(&pairs, CsrLayout::Sorted);
+        assert_eq!(sorted.neighbors(1).as_slice(), [1, 2, 2]);
+        assert_eq!(sorted.neighbors(4).as_slice(), [0, 0, 1]);
+
+        let deduplicated = InterleavedCsr::new(&pairs, CsrLayout::Deduplicated);
+        assert_eq!(deduplicated.neighbors(0).as_slice(), [0u32; 0]);
+        assert_eq!(deduplicated.neighbors(1).as_slice(), [1, 2]);
+        assert_eq!(deduplicated.neighbors(2).as_slice(), [0]);
+        assert_eq!(deduplicated.neighbors(3).as_slice(), [2]);
+        assert_eq!(deduplicated.neighbors(4).as_slice(), [0, 1]);
+        assert_eq!(deduplicated.neighbors(5).as_slice(), [0u32; 0]);
+        assert_eq!(deduplicated.targets.len(), 6);
+    }
+}
@@ -0,0 +1,81 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// A Bloom filter for a fast negative membership check of the term IDs.
+///
+/// The filter uses 10 bits and 7 hash functions per item, for about 1% false positives.
+#[derive(Clone)]
+pub(super) struct BloomFilter {
+    bits: Box<[u64]>,
+}
+
+impl BloomFilter {
+    const BITS_PER_ITEM: usize = 10;
+    const HASHES: u64 = 7;
+
+    /// Create a filter that contains the `items`.
+    pub(super) fn new<'a, I, T>(items: I) -> Self
+    where
+        I: ExactSizeIterator<Item = &'a T>,
+        T: Hash + 'a,
+    {
+        let n_bits = (items.len() * Self::BITS_PER_ITEM).max(64);
+        let mut filter = Self {
+            bits: vec![0; n_bits.div_ceil(64)].into_boxed_slice(),
+        };
+        for item in items {
+            for bit in filter.bit_positions(item) {
+                filter.bits[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+        filter
+    }
+
+    /// Test if the `item` might be in the filter.
+    ///
+    /// Returns `false` only if the item is definitely not in the filter.
+    pub(super) fn might_contain<T>(&self, item: &T) -> bool
+    where
+        T: Hash,
+    {
+        self.bit_positions(item)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Get the number of bytes used by the filter.
+    pub(super) fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(&*self.bits)
+    }
+
+    /// Derive the bit positions of the `item` from two halves of a single hash
+    /// (Kirsch & Mitzenmacher, 2006).
+    fn bit_positions<T>(&self, item: &T) -> impl Iterator<Item = usize>
+    where
+        T: Hash,
+    {
+        let mut hasher = DefaultHasher::new();
+        item.hash(&mut hasher);
+        let hash = hasher.finish();
+        let (h1, h2) = (hash & 0xFFFF_FFFF, hash >> 32);
+        let n_bits = self.bits.len() as u64 * 64;
+        (0..Self::HASHES).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % n_bits) as usize)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bloom_filter() {
+        let items: Vec<_> = (0..1_000).map(|i| format!("HP:{i:07}")).collect();
+        let filter = BloomFilter::new(items.iter());
+
+        assert!(items.iter().all(|item| filter.might_contain(item)));
+
+        let false_positives = (1_000..11_000)
+            .map(|i| format!("HP:{i:07}"))
+            .filter(|item| filter.might_contain(item))
+            .count();
+        assert!(false_positives < 300, "{false_positives} false positives");
+    }
+}
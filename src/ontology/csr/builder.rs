@@ -0,0 +1,396 @@
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::io::BufRead;
+
+use graph_builder::CsrLayout;
+
+use crate::base::term::MinimalTerm;
+use crate::error::OntoliusError;
+use crate::hierarchy::HierarchyIdx;
+use crate::io::{OntologyData, OntologyDataParser, OntologyLoader};
+use crate::ontology::TermIdx;
+
+use super::adjacency::CsrBackend;
+use super::hierarchy::{find_root_idx, make_edge_iterator};
+use super::{CsrOntology, Idx};
+
+/// The strategy for numbering the terms of [`CsrOntology`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Renumbering {
+    /// Keep the order of the terms in the input data.
+    #[default]
+    Keep,
+    /// Number the terms in the breadth-first order from the root.
+    ///
+    /// The neighboring terms get close indices, which improves the locality
+    /// of the hierarchy traversals on large ontologies.
+    /// The children are visited in the order of the input data, hence the numbering
+    /// is deterministic. The terms unreachable from the root (e.g. the obsolete terms)
+    /// are numbered last, in the order of the input data.
+    ///
+    /// The term IDs resolve to the same terms as before the renumbering, and the mapping
+    /// from the input indices is available via [`CsrOntology::index_mapping`].
+    Bfs,
+}
+
+/// Builder of [`CsrOntology`] with options for tuning the memory layout.
+///
+/// ## Examples
+///
+/// ```
+/// use curie_util::TrieCurieUtil;
+/// use ontolius::io::obographs::ObographsParser;
+/// use ontolius::ontology::csr::{CsrLayout, CsrOntology, CsrOntologyBuilder, Renumbering};
+/// use ontolius::prelude::*;
+///
+/// let loader = OntologyLoaderBuilder::new()
+///                .parser(ObographsParser::new(TrieCurieUtil::default()))
+///                .build();
+/// let file = std::fs::File::open("resources/hp.small.json.gz").unwrap();
+/// let mut read = std::io::BufReader::new(flate2::read::GzDecoder::new(file));
+///
+/// let hpo: CsrOntology<usize, _> = CsrOntologyBuilder::new()
+///     .layout(CsrLayout::Deduplicated)
+///     .renumbering(Renumbering::Bfs)
+///     .load_from_buf_read(&loader, &mut read)
+///     .expect("HPO should be loaded");
+///
+/// // The root comes first.
+/// assert_eq!(hpo.hierarchy().root(), &0);
+/// assert_eq!(hpo.len(), 614);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CsrOntologyBuilder {
+    layout: CsrLayout,
+    backend: CsrBackend,
+    renumbering: Renumbering,
+    bloom_filter: bool,
+}
+
+impl Default for CsrOntologyBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsrOntologyBuilder {
+    /// Create a builder with the sorted layout of the `graph_builder` backend,
+    /// with no renumbering, and with no Bloom filter.
+    pub fn new() -> Self {
+        Self {
+            layout: CsrLayout::Sorted,
+            backend: CsrBackend::GraphBuilder,
+            renumbering: Renumbering::Keep,
+            bloom_filter: false,
+        }
+    }
+
+    /// Set the layout of the adjacency lists of the CSR matrix.
+    #[must_use]
+    pub fn layout(mut self, layout: CsrLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Set the implementation of the adjacency matrix.
+    #[must_use]
+    pub fn backend(mut self, backend: CsrBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Set the strategy for numbering the terms.
+    #[must_use]
+    pub fn renumbering(mut self, renumbering: Renumbering) -> Self {
+        self.renumbering = renumbering;
+        self
+    }
+
+    /// Put a Bloom filter in front of the term ID lookups if `enabled` is `true`.
+    ///
+    /// See [`CsrOntology::enable_bloom_filter`] for more info.
+    #[must_use]
+    pub fn bloom_filter(mut self, enabled: bool) -> Self {
+        self.bloom_filter = enabled;
+        self
+    }
+
+    /// Assemble the ontology from the `data`.
+    pub fn build<HI, T>(
+        &self,
+        data: OntologyData<HI, T>,
+    ) -> Result<CsrOntology<HI, T>, OntoliusError>
+    where
+        HI: Idx,
+        T: MinimalTerm,
+    {
+        let pairs = make_edge_iterator(data.edges()).collect();
+        self.assemble(data, pairs)
+    }
+
+    /// Load the ontology from the buffered reader using the `loader`.
+    ///
+    /// The input is parsed and checked with the loader configuration, such as the prefixes
+    /// to keep or the obsolete edge policy, as in [`OntologyLoader::load_from_buf_read`].
+    pub fn load_from_buf_read<P, R>(
+        &self,
+        loader: &OntologyLoader<P>,
+        read: &mut R,
+    ) -> Result<CsrOntology<P::HI, P::T>, OntoliusError>
+    where
+        P: OntologyDataParser,
+        P::HI: Idx,
+        R: BufRead,
+    {
+        let (data, _) = loader.load_data_with_report_from_buf_read(read)?;
+        self.build(data)
+    }
+
+    /// Assemble the ontology from the terms of the `data` and the `(child, parent)` pairs.
+    fn assemble<HI, T>(
+        &self,
+        data: OntologyData<HI, T>,
+        pairs: Vec<(HI, HI)>,
+    ) -> Result<CsrOntology<HI, T>, OntoliusError>
+    where
+        HI: Idx,
+        T: MinimalTerm,
+    {
+        debug_assert!(
+            pairs.iter().all(|&(child, parent)| {
+                TermIdx::index(child) < data.terms().len()
+                    && TermIdx::index(parent) < data.terms().len()
+            }),
+            "The edges should point to the terms"
+        );
+        let mut ontology = match self.renumbering {
+            Renumbering::Keep => {
+                let terms = data.terms().to_vec().into_boxed_slice();
+                CsrOntology::assemble(terms, pairs, &data, self.layout, self.backend, None)
+            }
+            Renumbering::Bfs => {
+                let order = bfs_order(data.terms().len(), &pairs)?;
+
+                let mut index_mapping = vec![<HI as HierarchyIdx>::new(0); order.len()];
+                for (new, &old) in order.iter().enumerate() {
+                    index_mapping[old] = <HI as HierarchyIdx>::new(new);
+                }
+                let renumber = |idx: HI| -> HI { index_mapping[TermIdx::index(idx)] };
+
+                let terms: Box<[T]> = order.iter().map(|&old| data.terms()[old].clone()).collect();
+                let pairs = pairs
+                    .into_iter()
+                    .map(|(child, parent)| (renumber(child), renumber(parent)))
+                    .collect();
+                CsrOntology::assemble(
+                    terms,
+                    pairs,
+                    &data,
+                    self.layout,
+                    self.backend,
+                    Some(index_mapping.into_boxed_slice()),
+                )
+            }
+        }?;
+        if self.bloom_filter {
+            ontology.enable_bloom_filter();
+        }
+        #[cfg(feature = "paranoid")]
+        if let Err(e) = crate::ontology::invariants::check_invariants(&ontology) {
+            panic!("Broken ontology invariant: {e}");
+        }
+        Ok(ontology)
+    }
+}
+
+/// Get the indices of the terms in the breadth-first order from the root.
+///
+/// The children are visited in the order of their indices to make the order deterministic.
+/// The terms unreachable from the root are appended in the order of their indices.
+fn bfs_order<HI>(term_count: usize, pairs: &[(HI, HI)]) -> Result<Vec<usize>, OntoliusError>
+where
+    HI: TermIdx + HierarchyIdx + Hash,
+{
+    let root = TermIdx::index(find_root_idx(pairs)?);
+
+    let mut children = vec![vec![]; term_count];
+    for &(child, parent) in pairs {
+        children[TermIdx::index(parent)].push(TermIdx::index(child));
+    }
+    children.iter_mut().for_each(|c| c.sort_unstable());
+
+    let mut seen = vec![false; term_count];
+    let mut order = Vec::with_capacity(term_count);
+    let mut queue = VecDeque::from([root]);
+    seen[root] = true;
+    while let Some(node) = queue.pop_front() {
+        order.push(node);
+        for &child in &children[node] {
+            if !seen[child] {
+                seen[child] = true;
+                queue.push_back(child);
+            }
+        }
+    }
+    order.extend((0..term_count).filter(|&idx| !seen[idx]));
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::base::{Identified, TermId};
+    use crate::hierarchy::{GraphEdge, Relationship};
+    use crate::ontology::TermAware;
+
+    #[test]
+    fn test_bfs_renumbering() {
+        let terms: Vec<_> = ["HP:3", "HP:2", "HP:1"]
+            .into_iter()
+            .map(|curie| {
+                SimpleMinimalTerm::new(TermId::from_str(curie).unwrap(), curie, vec![], false)
+            })
+            .collect();
+        // HP:3 -> HP:2 -> HP:1
+        let edges: Vec<_> = [(0, 1), (1, 2)]
+            .into_iter()
+            .map(|(sub, obj)| GraphEdge::from((sub, Relationship::Child, obj)))
+            .collect();
+        let data: OntologyData<usize, _> = OntologyData::from((
+            terms.into_boxed_slice(),
+            edges.into_boxed_slice(),
+            HashMap::new(),
+        ));
+
+        let ontology = CsrOntologyBuilder::new()
+            .renumbering(Renumbering::Bfs)
+            .build(data)
+            .unwrap();
+
+        assert_eq!(ontology.index_mapping(), Some(&[2, 1, 0][..]));
+        let curies: Vec<_> = ontology
+            .iter_terms()
+            .map(|term| term.identifier().to_string())
+            .collect();
+        assert_eq!(curies, ["HP:1", "HP:2", "HP:3"]);
+        assert_eq!(
+            ontology.id_to_idx(&TermId::from_str("HP:3").unwrap()),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_bloom_filter() {
+        let terms: Vec<_> = ["HP:1", "HP:2"]
+            .into_iter()
+            .map(|curie| {
+                SimpleMinimalTerm::new(TermId::from_str(curie).unwrap(), curie, vec![], false)
+            })
+            .collect();
+        let edges = vec![GraphEdge::from((1, Relationship::Child, 0))];
+        let data: OntologyData<usize, _> = OntologyData::from((
+            terms.into_boxed_slice(),
+            edges.into_boxed_slice(),
+            HashMap::new(),
+        ));
+
+        let ontology = CsrOntologyBuilder::new()
+            .bloom_filter(true)
+            .build(data)
+            .unwrap();
+
+        let known = TermId::from_str("HP:2").unwrap();
+        assert!(ontology.might_contain(&known));
+        assert_eq!(ontology.id_to_idx(&known), Some(1));
+        let unknown: Vec<_> = (3..100)
+            .map(|i| TermId::from_str(&format!("HP:{i}")).unwrap())
+            .collect();
+        assert!(unknown.iter().all(|term_id| ontology.id_to_idx(term_id).is_none()));
+        assert!(unknown.iter().any(|term_id| !ontology.might_contain(term_id)));
+    }
+
+    #[test]
+    #[cfg(feature = "obographs")]
+    fn test_backends_agree() {
+        use curie_util::TrieCurieUtil;
+
+        use crate::hierarchy::{AncestorNodes, ChildNodes, OntologyHierarchy, ParentNodes};
+        use crate::io::obographs::ObographsParser;
+        use crate::io::OntologyLoaderBuilder;
+        use crate::ontology::HierarchyAware;
+
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .build();
+        let load = |layout: CsrLayout, backend: CsrBackend| -> CsrOntology<u32, _> {
+            let file = std::fs::File::open("resources/hp.small.json.gz").unwrap();
+            let mut read = std::io::BufReader::new(flate2::read::GzDecoder::new(file));
+            CsrOntologyBuilder::new()
+                .layout(layout)
+                .backend(backend)
+                .load_from_buf_read(&loader, &mut read)
+                .unwrap()
+        };
+
+        for layout in [CsrLayout::Unsorted, CsrLayout::Sorted, CsrLayout::Deduplicated] {
+            let hpo = load(layout, CsrBackend::GraphBuilder);
+            let (expected, actual) = (hpo.hierarchy(), load(layout, CsrBackend::Interleaved));
+            let actual = actual.hierarchy();
+
+            assert_eq!(expected.root(), actual.root());
+            for idx in 0..hpo.len() as u32 {
+                assert!(expected.parents_of(idx).eq(actual.parents_of(idx)));
+                assert!(expected.children_of(idx).eq(actual.children_of(idx)));
+                assert!(expected.ancestors_of(idx).eq(actual.ancestors_of(idx)));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "obographs")]
+    fn test_load_applies_loader_config() {
+        use curie_util::TrieCurieUtil;
+
+        use crate::io::obographs::ObographsParser;
+        use crate::io::OntologyLoaderBuilder;
+
+        let document = r#"{"graphs": [{
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All", "type": "CLASS"},
+                {"id": "http://purl.obolibrary.org/obo/HP_0000118", "lbl": "Phenotypic abnormality", "type": "CLASS"},
+                {"id": "http://purl.obolibrary.org/obo/UBERON_0002101", "lbl": "limb", "type": "CLASS"}
+            ],
+            "edges": [
+                {"sub": "http://purl.obolibrary.org/obo/HP_0000118", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000001"},
+                {"sub": "http://purl.obolibrary.org/obo/UBERON_0002101", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000001"}
+            ]
+        }]}"#;
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .keep_prefixes(["HP"])
+            .build();
+
+        let ontology: CsrOntology<u32, SimpleMinimalTerm> = CsrOntologyBuilder::new()
+            .load_from_buf_read(&loader, &mut document.as_bytes())
+            .unwrap();
+
+        let curies: Vec<_> = ontology
+            .iter_terms()
+            .map(|term| term.identifier().to_string())
+            .collect();
+        assert_eq!(curies, ["HP:0000001", "HP:0000118"]);
+    }
+
+    #[test]
+    fn test_bfs_order() {
+        // 3 -> 0, 1 -> 3, 4 -> 3, 2 -> 0; 5 is unreachable.
+        let pairs: Vec<(usize, usize)> = vec![(3, 0), (1, 3), (4, 3), (2, 0)];
+
+        assert_eq!(bfs_order(6, &pairs).unwrap(), [0, 2, 3, 1, 4, 5]);
+    }
+}
@@ -0,0 +1,285 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::base::term::MinimalTerm;
+use crate::error::OntoliusError;
+use crate::ontology::MetadataAware;
+
+use super::{CsrOntology, Idx};
+
+/// The bytes at the start of each cache file.
+const MAGIC: &[u8; 8] = b"ONTOLIUS";
+
+/// The version of the cache file format, bumped whenever the serialized form
+/// of [`CsrOntology`] or of the cache header changes.
+const FORMAT_VERSION: u32 = 1;
+
+/// The header of the cache file, following the magic bytes and the format version.
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+struct CacheHeader {
+    ontology_version: String,
+    index_type: String,
+    term_type: String,
+}
+
+impl CacheHeader {
+    fn new<HI, T>(ontology_version: &str) -> Self {
+        Self {
+            ontology_version: ontology_version.to_string(),
+            index_type: std::any::type_name::<HI>().to_string(),
+            term_type: std::any::type_name::<T>().to_string(),
+        }
+    }
+}
+
+impl<HI, T> CsrOntology<HI, T>
+where
+    HI: Idx + serde::Serialize + serde::de::DeserializeOwned,
+    T: MinimalTerm + serde::Serialize + serde::de::DeserializeOwned,
+{
+    /// Save the ontology into a binary cache file at `path`,
+    /// to skip parsing the ontology on the next start (see [`CsrOntology::load_cache`]).
+    ///
+    /// The cache is tied to the version of the cache format, to the ontology version,
+    /// and to the index and the term types.
+    pub fn save_cache<P>(&self, path: P) -> Result<(), OntoliusError>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::create(path.as_ref()).map_err(|e| {
+            OntoliusError::Other(format!(
+                "Cannot create the cache file {}: {e}",
+                path.as_ref().display()
+            ))
+        })?;
+        let mut write = BufWriter::new(file);
+        let header = CacheHeader::new::<HI, T>(self.version());
+
+        write
+            .write_all(MAGIC)
+            .and_then(|_| write.write_all(&FORMAT_VERSION.to_le_bytes()))
+            .map_err(write_error)?;
+        bincode::serialize_into(&mut write, &header).map_err(encode_error)?;
+        bincode::serialize_into(&mut write, self).map_err(encode_error)?;
+        write.flush().map_err(write_error)
+    }
+
+    /// Load the ontology from the cache file at `path`, written by [`CsrOntology::save_cache`].
+    ///
+    /// Returns `None` if the cache is stale and the ontology should be parsed again:
+    /// if there is no file at `path`, if the cache was written with another format version,
+    /// another index or term type, or if `ontology_version` is provided and the cached
+    /// ontology has another version.
+    /// Returns an error if the file cannot be read or decoded.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use curie_util::TrieCurieUtil;
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// use ontolius::ontology::csr::MinimalCsrOntology;
+    /// use ontolius::prelude::*;
+    ///
+    /// let cache = std::env::temp_dir().join(format!("hp.small.{}.bin", std::process::id()));
+    ///
+    /// let hpo = match MinimalCsrOntology::load_cache(&cache, None).unwrap() {
+    ///     Some(hpo) => hpo,
+    ///     None => {
+    /// #         let loader = OntologyLoaderBuilder::new()
+    /// #                        .parser(ObographsParser::new(TrieCurieUtil::default()))
+    /// #                        .build();
+    /// #         let hpo: MinimalCsrOntology = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                                             .expect("HPO should be loaded");
+    ///         hpo.save_cache(&cache).expect("The cache should be written");
+    ///         hpo
+    ///     }
+    /// };
+    ///
+    /// // The next start reads the cache.
+    /// let cached = MinimalCsrOntology::load_cache(&cache, Some(hpo.version())).unwrap();
+    /// assert!(cached.is_some_and(|cached| cached == hpo));
+    ///
+    /// // The cache of another release is stale.
+    /// let stale = MinimalCsrOntology::load_cache(&cache, Some("2024-04-26")).unwrap();
+    /// assert!(stale.is_none());
+    /// # std::fs::remove_file(&cache).unwrap();
+    /// ```
+    pub fn load_cache<P>(
+        path: P,
+        ontology_version: Option<&str>,
+    ) -> Result<Option<Self>, OntoliusError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(OntoliusError::Other(format!(
+                    "Cannot open the cache file {}: {e}",
+                    path.display()
+                )))
+            }
+        };
+        let mut read = BufReader::new(file);
+
+        // Check the magic bytes and the format version before decoding anything else,
+        // since an older header may not be decodable.
+        let mut prefix = [0; 12];
+        match read.read_exact(&mut prefix) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(read_error(e)),
+        }
+        if &prefix[..8] != MAGIC || prefix[8..] != FORMAT_VERSION.to_le_bytes() {
+            return Ok(None);
+        }
+
+        let header: CacheHeader = bincode::deserialize_from(&mut read).map_err(decode_error)?;
+        let expected = CacheHeader::new::<HI, T>(&header.ontology_version);
+        let version_matches =
+            ontology_version.is_none_or(|version| version == header.ontology_version);
+        if header != expected || !version_matches {
+            return Ok(None);
+        }
+
+        bincode::deserialize_from(&mut read)
+            .map(Some)
+            .map_err(decode_error)
+    }
+}
+
+fn write_error(e: std::io::Error) -> OntoliusError {
+    OntoliusError::Other(format!("Cannot write the cache file: {e}"))
+}
+
+fn read_error(e: std::io::Error) -> OntoliusError {
+    OntoliusError::Other(format!("Cannot read the cache file: {e}"))
+}
+
+fn encode_error(e: bincode::Error) -> OntoliusError {
+    OntoliusError::Other(format!("Cannot encode the ontology cache: {e}"))
+}
+
+fn decode_error(e: bincode::Error) -> OntoliusError {
+    OntoliusError::Other(format!("Cannot decode the ontology cache: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::base::term::simple::{SimpleMinimalTerm, SimpleTerm};
+    use crate::base::TermId;
+    use crate::hierarchy::{GraphEdge, Relationship};
+    use crate::io::OntologyData;
+    use crate::ontology::TermAware;
+
+    #[test]
+    fn test_cache_invalidation() {
+        let terms = vec![
+            SimpleMinimalTerm::new(TermId::from(("HP", "1")), "Root", vec![], false),
+            SimpleMinimalTerm::new(TermId::from(("HP", "2")), "Child", vec![], false),
+        ];
+        let edges = vec![GraphEdge::from((1, Relationship::Child, 0))];
+        let data: OntologyData<u32, _> = OntologyData::from((
+            terms.into_boxed_slice(),
+            edges.into_boxed_slice(),
+            HashMap::from([("version".to_string(), "2024-04-26".to_string())]),
+        ));
+        let ontology = CsrOntology::try_from(data).unwrap();
+        let path = std::env::temp_dir().join(format!("ontolius-cache-{}.bin", std::process::id()));
+
+        assert!(
+            CsrOntology::<u32, SimpleMinimalTerm>::load_cache(&path, None)
+                .unwrap()
+                .is_none()
+        );
+
+        ontology.save_cache(&path).unwrap();
+        let cached = CsrOntology::<u32, SimpleMinimalTerm>::load_cache(&path, None)
+            .unwrap()
+            .unwrap();
+        assert!(cached == ontology);
+        assert_eq!(cached.len(), 2);
+
+        // The version, the index type, or the term type differ.
+        assert!(
+            CsrOntology::<u32, SimpleMinimalTerm>::load_cache(&path, Some("2025-01-01"))
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            CsrOntology::<usize, SimpleMinimalTerm>::load_cache(&path, None)
+                .unwrap()
+                .is_none()
+        );
+        assert!(CsrOntology::<u32, SimpleTerm>::load_cache(&path, None)
+            .unwrap()
+            .is_none());
+
+        // Not a cache file.
+        std::fs::write(&path, b"{\"graphs\": []}").unwrap();
+        assert!(
+            CsrOntology::<u32, SimpleMinimalTerm>::load_cache(&path, None)
+                .unwrap()
+                .is_none()
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "obographs")]
+    fn test_cache_of_another_release() {
+        use curie_util::TrieCurieUtil;
+
+        use crate::io::obographs::ObographsParser;
+        use crate::io::OntologyDataParser;
+
+        fn load(version: &str) -> CsrOntology<u32, SimpleMinimalTerm> {
+            let document = format!(
+                r#"{{"graphs": [{{
+                    "meta": {{
+                        "basicPropertyValues": [
+                            {{"pred": "http://www.w3.org/2002/07/owl#versionInfo", "val": "{version}"}}
+                        ]
+                    }},
+                    "nodes": [
+                        {{"id": "http://purl.obolibrary.org/obo/HP_0000001", "lbl": "All"}},
+                        {{"id": "http://purl.obolibrary.org/obo/HP_0000118", "lbl": "Phenotypic abnormality"}}
+                    ],
+                    "edges": [
+                        {{"sub": "http://purl.obolibrary.org/obo/HP_0000118", "pred": "is_a", "obj": "http://purl.obolibrary.org/obo/HP_0000001"}}
+                    ]
+                }}]}}"#
+            );
+            let parser = ObographsParser::new(TrieCurieUtil::default());
+            let data = parser.load_from_buf_read(&mut document.as_bytes()).unwrap();
+            CsrOntology::try_from(data).unwrap()
+        }
+
+        let old = load("2023-10-09");
+        let new = load("2024-04-26");
+        assert_eq!(old.version(), "2023-10-09");
+        let path =
+            std::env::temp_dir().join(format!("ontolius-cache-release-{}.bin", std::process::id()));
+
+        old.save_cache(&path).unwrap();
+        assert!(
+            CsrOntology::<u32, SimpleMinimalTerm>::load_cache(&path, Some(old.version()))
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            CsrOntology::<u32, SimpleMinimalTerm>::load_cache(&path, Some(new.version()))
+                .unwrap()
+                .is_none()
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 
@@ -6,34 +7,52 @@ use crate::hierarchy::{
     AncestorNodes, ChildNodes, DescendantNodes, GraphEdge, HierarchyIdx, OntologyHierarchy,
     ParentNodes, Relationship,
 };
+use crate::ontology::TermIdx;
 
-use graph_builder::index::Idx as CsrIdx;
-use graph_builder::GraphBuilder;
-use graph_builder::{DirectedCsrGraph, DirectedNeighbors};
+use graph_builder::CsrLayout;
+
+use super::adjacency::{Adjacency, CsrBackend};
+use super::Idx;
 
-// TODO: here graph_builder is part of the public API through `I`.
 /// An ontology graph backed by a CSR adjacency matrix.
+#[derive(Clone)]
 pub struct CsrOntologyHierarchy<I>
 where
-    I: CsrIdx + HierarchyIdx + Hash,
+    I: Idx,
 {
     root_idx: I,
-    adjacency_matrix: DirectedCsrGraph<I>,
+    adjacency_matrix: Adjacency<I>,
 }
 
 impl<I> TryFrom<&[GraphEdge<I>]> for CsrOntologyHierarchy<I>
 where
-    I: CsrIdx + HierarchyIdx + Hash,
+    I: Idx,
 {
     type Error = OntoliusError;
     // TODO: we do not need an array, we need IntoIterator!
     fn try_from(graph_edges: &[GraphEdge<I>]) -> Result<Self, Self::Error> {
-        let root_idx = find_root_idx(graph_edges).copied()?;
+        Self::from_pairs(
+            make_edge_iterator(graph_edges).collect(),
+            CsrLayout::Sorted,
+            CsrBackend::default(),
+        )
+    }
+}
 
-        let adjacency_matrix = GraphBuilder::new()
-            .csr_layout(graph_builder::CsrLayout::Sorted)
-            .edges(make_edge_iterator(graph_edges))
-            .build();
+impl<I> CsrOntologyHierarchy<I>
+where
+    I: Idx,
+{
+    /// Build the hierarchy from the `(child, parent)` pairs
+    /// with the given `layout` and `backend` of the adjacency matrix.
+    pub(super) fn from_pairs(
+        pairs: Vec<(I, I)>,
+        layout: CsrLayout,
+        backend: CsrBackend,
+    ) -> Result<Self, OntoliusError> {
+        let root_idx = find_root_idx(&pairs)?;
+
+        let adjacency_matrix = Adjacency::new(pairs, layout, backend);
 
         Ok(CsrOntologyHierarchy {
             root_idx,
@@ -42,24 +61,130 @@ where
     }
 }
 
-fn find_root_idx<I>(graph_edges: &[GraphEdge<I>]) -> Result<&I, OntoliusError>
+impl<I> CsrOntologyHierarchy<I>
+where
+    I: Idx,
+{
+    /// Get the approximate number of bytes used by the adjacency matrix.
+    ///
+    /// The matrix stores the offsets and the targets of the outgoing and incoming edges.
+    pub fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.adjacency_matrix.memory_usage()
+    }
+
+    /// Test if `node` is a vertex of the adjacency matrix.
+    ///
+    /// The terms with no edges (e.g. the obsolete terms) may not be present in the matrix.
+    fn contains(&self, node: I) -> bool {
+        TermIdx::index(node) < self.adjacency_matrix.node_count()
+    }
+
+    fn in_neighbors(&self, node: I) -> std::slice::Iter<'_, I> {
+        if self.contains(node) {
+            self.adjacency_matrix.in_neighbors(node)
+        } else {
+            [].iter()
+        }
+    }
+
+    fn out_neighbors(&self, node: I) -> std::slice::Iter<'_, I> {
+        if self.contains(node) {
+            self.adjacency_matrix.out_neighbors(node)
+        } else {
+            [].iter()
+        }
+    }
+
+    /// Test if there is an `is_a` edge between `child` and `parent`.
+    ///
+    /// We scan the shorter of the two neighbor lists.
+    /// Usually, this means the parents of `child`, since a term has a few parents
+    /// but the top-level terms can have thousands of children.
+    fn has_edge(&self, child: I, parent: I) -> bool {
+        let parents = self.out_neighbors(child);
+        let children = self.in_neighbors(parent);
+        if parents.len() <= children.len() {
+            parents.as_slice().contains(&parent)
+        } else {
+            children.as_slice().contains(&child)
+        }
+    }
+}
+
+thread_local! {
+    /// The scratch space of [`CsrOntologyHierarchy::count_reachable`],
+    /// reused across the calls to avoid allocating in the hot path.
+    static SCRATCH: RefCell<Scratch> = RefCell::new(Scratch::default());
+}
+
+#[derive(Default)]
+struct Scratch {
+    /// A bitset of the nodes seen by the traversal.
+    seen: Vec<u64>,
+    /// The nodes seen by the traversal, in the order of the visit.
+    visited: Vec<usize>,
+}
+
+impl<I> CsrOntologyHierarchy<I>
+where
+    I: Idx,
+{
+    /// Count the nodes reachable from `node` by following the `neighbors`, excluding `node`.
+    fn count_reachable<'a, F>(&'a self, node: I, neighbors: F) -> usize
+    where
+        F: Fn(&'a Self, I) -> std::slice::Iter<'a, I>,
+    {
+        SCRATCH.with(|scratch| {
+            let mut scratch = scratch.borrow_mut();
+            let Scratch { seen, visited } = &mut *scratch;
+            let words = self.adjacency_matrix.node_count().div_ceil(64);
+            if seen.len() < words {
+                seen.resize(words, 0);
+            }
+
+            let mut mark = |idx: usize| {
+                let (word, bit) = (idx / 64, 1 << (idx % 64));
+                let unseen = seen[word] & bit == 0;
+                seen[word] |= bit;
+                unseen
+            };
+            visited.clear();
+            visited.extend(
+                neighbors(self, node)
+                    .map(|&neighbor| TermIdx::index(neighbor))
+                    .filter(|&idx| mark(idx)),
+            );
+            let mut cursor = 0;
+            while let Some(&idx) = visited.get(cursor) {
+                cursor += 1;
+                for neighbor in neighbors(self, <I as HierarchyIdx>::new(idx)) {
+                    let idx = TermIdx::index(*neighbor);
+                    if mark(idx) {
+                        visited.push(idx);
+                    }
+                }
+            }
+
+            // Only clear the words we touched, to keep the cost proportional to the count.
+            for &idx in visited.iter() {
+                seen[idx / 64] = 0;
+            }
+            visited.len()
+        })
+    }
+}
+
+/// Find the root among the `(child, parent)` pairs, i.e. the only parent that is not a child.
+pub(super) fn find_root_idx<I>(pairs: &[(I, I)]) -> Result<I, OntoliusError>
 where
     I: Hash + HierarchyIdx,
 {
     let mut root_candidate_set: HashSet<&I> = HashSet::new();
     let mut remove_mark_set: HashSet<&I> = HashSet::new();
 
-    for edge in graph_edges.iter() {
-        match edge.pred {
-            Relationship::Child => {
-                root_candidate_set.insert(&edge.obj);
-                remove_mark_set.insert(&edge.sub);
-            }
-            Relationship::Parent => {
-                root_candidate_set.insert(&edge.obj);
-                remove_mark_set.insert(&edge.sub);
-            }
-        }
+    for (child, parent) in pairs.iter() {
+        root_candidate_set.insert(parent);
+        remove_mark_set.insert(child);
     }
 
     let candidates: Vec<_> = root_candidate_set.difference(&remove_mark_set).collect();
@@ -68,14 +193,14 @@ where
         0 => Err(OntoliusError::OntologyAssemblyError(
             "No root candidate found!".into(),
         )),
-        1 => Ok(*candidates[0]),
+        1 => Ok(**candidates[0]),
         _ => Err(OntoliusError::OntologyAssemblyError(
             "More than one root candidate found".into(),
         )),
     }
 }
 
-fn make_edge_iterator<I>(graph_edges: &[GraphEdge<I>]) -> impl Iterator<Item = (I, I)> + '_
+pub(super) fn make_edge_iterator<I>(graph_edges: &[GraphEdge<I>]) -> impl Iterator<Item = (I, I)> + '_
 where
     I: HierarchyIdx,
 {
@@ -90,56 +215,84 @@ where
 
 impl<I> ChildNodes for CsrOntologyHierarchy<I>
 where
-    I: CsrIdx + HierarchyIdx + Hash,
+    I: Idx,
 {
     type I = I;
     type ChildIter<'a> = std::slice::Iter<'a, I> where I: 'a;
 
     fn children_of(&self, node: I) -> Self::ChildIter<'_> {
-        self.adjacency_matrix.in_neighbors(node)
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_call("children_of");
+        self.in_neighbors(node)
+    }
+
+    fn is_child_of(&self, sub: I, obj: I) -> bool {
+        self.has_edge(sub, obj)
+    }
+
+    fn child_count(&self, node: I) -> usize {
+        self.in_neighbors(node).len()
     }
 }
 
 impl<I> ParentNodes for CsrOntologyHierarchy<I>
 where
-    I: CsrIdx + HierarchyIdx + Hash,
+    I: Idx,
 {
     type I = I;
     type ParentIter<'a> = std::slice::Iter<'a, I> where I: 'a;
 
     fn parents_of(&self, node: I) -> Self::ParentIter<'_> {
-        self.adjacency_matrix.out_neighbors(node)
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_call("parents_of");
+        self.out_neighbors(node)
+    }
+
+    fn is_parent_of(&self, sub: I, obj: I) -> bool {
+        self.has_edge(obj, sub)
+    }
+
+    fn parent_count(&self, node: I) -> usize {
+        self.out_neighbors(node).len()
     }
 }
 
 impl<I> DescendantNodes for CsrOntologyHierarchy<I>
 where
-    I: CsrIdx + HierarchyIdx + Hash,
+    I: Idx,
 {
     type I = I;
     type DescendantIter<'a> = DescendantsIter<'a, I>;
 
     fn descendants_of(&self, node: I) -> Self::DescendantIter<'_> {
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_call("descendants_of");
         DescendantsIter {
             adjacency_matrix: &self.adjacency_matrix,
             seen: HashSet::new(),
-            queue: VecDeque::from_iter(self.adjacency_matrix.in_neighbors(node)),
+            queue: VecDeque::from_iter(self.in_neighbors(node)),
         }
     }
+
+    fn count_descendants(&self, node: I) -> usize {
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_call("count_descendants");
+        self.count_reachable(node, Self::in_neighbors)
+    }
 }
 
 pub struct DescendantsIter<'a, I>
 where
-    I: CsrIdx + HierarchyIdx + Hash,
+    I: Idx,
 {
-    adjacency_matrix: &'a DirectedCsrGraph<I>,
+    adjacency_matrix: &'a Adjacency<I>,
     seen: HashSet<&'a I>,
     queue: VecDeque<&'a I>,
 }
 
 impl<'a, I> Iterator for DescendantsIter<'a, I>
 where
-    I: CsrIdx + HierarchyIdx + Hash,
+    I: Idx,
 {
     type Item = &'a I;
 
@@ -157,7 +310,7 @@ where
 
 impl<I> AncestorNodes for CsrOntologyHierarchy<I>
 where
-    I: CsrIdx + HierarchyIdx + Hash,
+    I: Idx,
 {
     type I = I;
     type AncestorIter<'a> = AncestorIter<'a, I>
@@ -165,26 +318,34 @@ where
         Self: 'a;
 
     fn ancestors_of(&self, node: I) -> Self::AncestorIter<'_> {
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_call("ancestors_of");
         AncestorIter {
             adjacency_matrix: &self.adjacency_matrix,
             seen: HashSet::new(),
-            queue: VecDeque::from_iter(self.adjacency_matrix.out_neighbors(node)),
+            queue: VecDeque::from_iter(self.out_neighbors(node)),
         }
     }
+
+    fn count_ancestors(&self, node: I) -> usize {
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_call("count_ancestors");
+        self.count_reachable(node, Self::out_neighbors)
+    }
 }
 
 pub struct AncestorIter<'a, I>
 where
-    I: CsrIdx + HierarchyIdx + Hash,
+    I: Idx,
 {
-    adjacency_matrix: &'a DirectedCsrGraph<I>,
+    adjacency_matrix: &'a Adjacency<I>,
     seen: HashSet<&'a I>,
     queue: VecDeque<&'a I>,
 }
 
 impl<'a, I> Iterator for AncestorIter<'a, I>
 where
-    I: CsrIdx + HierarchyIdx + Hash,
+    I: Idx,
 {
     type Item = &'a I;
 
@@ -202,7 +363,7 @@ where
 
 impl<I> OntologyHierarchy for CsrOntologyHierarchy<I>
 where
-    I: CsrIdx + HierarchyIdx + Hash,
+    I: Idx,
 {
     type HI = I;
 
@@ -227,10 +388,7 @@ where
             }
         }
 
-        let adjacency_matrix = GraphBuilder::new()
-            .csr_layout(graph_builder::CsrLayout::Sorted)
-            .edges(edges)
-            .build();
+        let adjacency_matrix = Adjacency::new(edges, CsrLayout::Sorted, CsrBackend::default());
         let _hierarchy = CsrOntologyHierarchy {
             root_idx: subroot,
             adjacency_matrix,
@@ -260,73 +418,200 @@ mod test_hierarchy {
 
     #[test]
     fn test_children_of() {
-        let hierarchy = build_example_hierarchy();
-        let func = CsrOntologyHierarchy::children_of;
-
-        check_members!(hierarchy, func, 0, [1, 5, 9]);
-        check_members!(hierarchy, func, 1, [2, 3]);
-        check_members!(hierarchy, func, 2, [4]);
-        check_members!(hierarchy, func, 3, [4]);
-        check_members!(hierarchy, func, 4, [0; 0]);
-        check_members!(hierarchy, func, 5, [6, 7, 8]);
-        check_members!(hierarchy, func, 6, [0; 0]);
-        check_members!(hierarchy, func, 7, [0; 0]);
-        check_members!(hierarchy, func, 8, [0; 0]);
-        check_members!(hierarchy, func, 9, [0; 0]);
+        for hierarchy in build_example_hierarchies() {
+            let func = CsrOntologyHierarchy::children_of;
+
+            check_members!(hierarchy, func, 0, [1, 5, 9]);
+            check_members!(hierarchy, func, 1, [2, 3]);
+            check_members!(hierarchy, func, 2, [4]);
+            check_members!(hierarchy, func, 3, [4]);
+            check_members!(hierarchy, func, 4, [0; 0]);
+            check_members!(hierarchy, func, 5, [6, 7, 8]);
+            check_members!(hierarchy, func, 6, [0; 0]);
+            check_members!(hierarchy, func, 7, [0; 0]);
+            check_members!(hierarchy, func, 8, [0; 0]);
+            check_members!(hierarchy, func, 9, [0; 0]);
+        }
     }
 
     #[test]
     fn test_descendants_of() {
-        let hierarchy = build_example_hierarchy();
-        let func = CsrOntologyHierarchy::descendants_of;
-
-        check_members!(hierarchy, func, 0, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
-        check_members!(hierarchy, func, 1, [2, 3, 4]);
-        check_members!(hierarchy, func, 2, [4]);
-        check_members!(hierarchy, func, 3, [4]);
-        check_members!(hierarchy, func, 4, [0; 0]);
-        check_members!(hierarchy, func, 5, [6, 7, 8]);
-        check_members!(hierarchy, func, 6, [0; 0]);
-        check_members!(hierarchy, func, 7, [0; 0]);
-        check_members!(hierarchy, func, 8, [0; 0]);
-        check_members!(hierarchy, func, 9, [0; 0]);
+        for hierarchy in build_example_hierarchies() {
+            let func = CsrOntologyHierarchy::descendants_of;
+
+            check_members!(hierarchy, func, 0, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+            check_members!(hierarchy, func, 1, [2, 3, 4]);
+            check_members!(hierarchy, func, 2, [4]);
+            check_members!(hierarchy, func, 3, [4]);
+            check_members!(hierarchy, func, 4, [0; 0]);
+            check_members!(hierarchy, func, 5, [6, 7, 8]);
+            check_members!(hierarchy, func, 6, [0; 0]);
+            check_members!(hierarchy, func, 7, [0; 0]);
+            check_members!(hierarchy, func, 8, [0; 0]);
+            check_members!(hierarchy, func, 9, [0; 0]);
+        }
     }
 
     #[test]
     fn test_parents_of() {
-        let hierarchy = build_example_hierarchy();
-        let func = CsrOntologyHierarchy::parents_of;
-
-        check_members!(hierarchy, func, 0, [0; 0]);
-        check_members!(hierarchy, func, 1, [0]);
-        check_members!(hierarchy, func, 2, [1]);
-        check_members!(hierarchy, func, 3, [1]);
-        check_members!(hierarchy, func, 4, [2, 3]);
-        check_members!(hierarchy, func, 5, [0]);
-        check_members!(hierarchy, func, 6, [5]);
-        check_members!(hierarchy, func, 7, [5]);
-        check_members!(hierarchy, func, 8, [5]);
-        check_members!(hierarchy, func, 9, [0]);
+        for hierarchy in build_example_hierarchies() {
+            let func = CsrOntologyHierarchy::parents_of;
+
+            check_members!(hierarchy, func, 0, [0; 0]);
+            check_members!(hierarchy, func, 1, [0]);
+            check_members!(hierarchy, func, 2, [1]);
+            check_members!(hierarchy, func, 3, [1]);
+            check_members!(hierarchy, func, 4, [2, 3]);
+            check_members!(hierarchy, func, 5, [0]);
+            check_members!(hierarchy, func, 6, [5]);
+            check_members!(hierarchy, func, 7, [5]);
+            check_members!(hierarchy, func, 8, [5]);
+            check_members!(hierarchy, func, 9, [0]);
+        }
     }
 
     #[test]
     fn test_ancestors_of() {
-        let hierarchy = build_example_hierarchy();
-        let func = CsrOntologyHierarchy::ancestors_of;
-
-        check_members!(hierarchy, func, 0, [0; 0]);
-        check_members!(hierarchy, func, 1, [0]);
-        check_members!(hierarchy, func, 2, [0, 1]);
-        check_members!(hierarchy, func, 3, [0, 1]);
-        check_members!(hierarchy, func, 4, [0, 1, 2, 3]);
-        check_members!(hierarchy, func, 5, [0]);
-        check_members!(hierarchy, func, 6, [0, 5]);
-        check_members!(hierarchy, func, 7, [0, 5]);
-        check_members!(hierarchy, func, 8, [0, 5]);
-        check_members!(hierarchy, func, 9, [0]);
-    }
-
-    fn build_example_hierarchy() -> CsrOntologyHierarchy<u16> {
+        for hierarchy in build_example_hierarchies() {
+            let func = CsrOntologyHierarchy::ancestors_of;
+
+            check_members!(hierarchy, func, 0, [0; 0]);
+            check_members!(hierarchy, func, 1, [0]);
+            check_members!(hierarchy, func, 2, [0, 1]);
+            check_members!(hierarchy, func, 3, [0, 1]);
+            check_members!(hierarchy, func, 4, [0, 1, 2, 3]);
+            check_members!(hierarchy, func, 5, [0]);
+            check_members!(hierarchy, func, 6, [0, 5]);
+            check_members!(hierarchy, func, 7, [0, 5]);
+            check_members!(hierarchy, func, 8, [0, 5]);
+            check_members!(hierarchy, func, 9, [0]);
+        }
+    }
+
+    #[test]
+    fn test_count_descendants_and_ancestors() {
+        for hierarchy in build_example_hierarchies() {
+
+            for node in 0..10 {
+                assert_eq!(
+                    hierarchy.count_descendants(node),
+                    hierarchy.descendants_of(node).count()
+                );
+                assert_eq!(
+                    hierarchy.count_ancestors(node),
+                    hierarchy.ancestors_of(node).count()
+                );
+            }
+            assert_eq!(hierarchy.count_descendants(0), 9);
+            assert_eq!(hierarchy.count_ancestors(4), 4);
+            assert_eq!(hierarchy.count_ancestors(100), 0);
+        }
+    }
+
+    #[test]
+    fn test_is_child_of_and_is_parent_of() {
+        for hierarchy in build_example_hierarchies() {
+
+            assert!(hierarchy.is_child_of(4, 2));
+            assert!(hierarchy.is_child_of(4, 3));
+            assert!(hierarchy.is_child_of(9, 0));
+            assert!(!hierarchy.is_child_of(4, 1));
+            assert!(!hierarchy.is_child_of(0, 1));
+            assert!(!hierarchy.is_child_of(6, 0));
+
+            assert!(hierarchy.is_parent_of(5, 8));
+            assert!(hierarchy.is_parent_of(0, 1));
+            assert!(!hierarchy.is_parent_of(8, 5));
+            assert!(!hierarchy.is_parent_of(0, 4));
+        }
+    }
+
+    #[test]
+    fn test_child_count_and_parent_count() {
+        for hierarchy in build_example_hierarchies() {
+
+            assert_eq!(hierarchy.child_count(0), 3);
+            assert_eq!(hierarchy.child_count(1), 2);
+            assert_eq!(hierarchy.child_count(4), 0);
+            assert_eq!(hierarchy.parent_count(0), 0);
+            assert_eq!(hierarchy.parent_count(4), 2);
+            assert_eq!(hierarchy.parent_count(7), 1);
+
+            assert!(hierarchy.is_leaf(4));
+            assert!(!hierarchy.is_leaf(5));
+        }
+    }
+
+    #[test]
+    fn test_iter_neighbors() {
+        for hierarchy in build_example_hierarchies() {
+
+            let neighbors: Vec<_> = hierarchy.iter_neighbors(1).copied().collect();
+            assert_eq!(neighbors.len(), 3);
+            assert_eq!(neighbors[0], 0);
+            assert_eq!(HashSet::<u16>::from_iter(neighbors), HashSet::from([0, 2, 3]));
+
+            let tagged: HashSet<_> = hierarchy
+                .iter_tagged_neighbors(5)
+                .map(|(rel, &node)| (rel, node))
+                .collect();
+            assert_eq!(
+                tagged,
+                HashSet::from([
+                    (Relationship::Parent, 0),
+                    (Relationship::Child, 6),
+                    (Relationship::Child, 7),
+                    (Relationship::Child, 8),
+                ])
+            );
+        }
+    }
+
+    #[test]
+    fn test_generalize() {
+        for hierarchy in build_example_hierarchies() {
+
+            assert_eq!(hierarchy.generalize(4, 0), vec![4]);
+            assert_eq!(hierarchy.generalize(4, 1), vec![2, 3]);
+            assert_eq!(hierarchy.generalize(4, 2), vec![1]);
+            assert_eq!(hierarchy.generalize(4, 3), vec![0]);
+            assert_eq!(hierarchy.generalize(4, 10), vec![0]);
+            assert_eq!(hierarchy.generalize(7, 1), vec![5]);
+            assert_eq!(hierarchy.generalize(0, 1), vec![0]);
+        }
+    }
+
+    #[test]
+    fn test_neighborhood() {
+        for hierarchy in build_example_hierarchies() {
+
+            assert_eq!(hierarchy.neighborhood(2, 0, 0), vec![2]);
+            assert_eq!(hierarchy.neighborhood(2, 1, 1), vec![1, 2, 4]);
+            assert_eq!(hierarchy.neighborhood(2, 2, 1), vec![0, 1, 2, 4]);
+            assert_eq!(hierarchy.neighborhood(0, 0, 1), vec![0, 1, 5, 9]);
+            assert_eq!(hierarchy.neighborhood(0, 5, 2), vec![0, 1, 2, 3, 5, 6, 7, 8, 9]);
+            assert_eq!(hierarchy.neighborhood(5, 1, 1), vec![0, 5, 6, 7, 8]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_fuzz() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        for hierarchy in build_example_hierarchies() {
+            let mut rng = StdRng::seed_from_u64(42);
+
+            for _ in 0..20 {
+                let fuzzed = hierarchy.fuzz(4, &mut rng);
+                assert!([0, 1, 2, 3].contains(&fuzzed));
+            }
+            assert_eq!(hierarchy.fuzz(0, &mut rng), 0);
+        }
+    }
+
+    /// Build the example hierarchy with each backend, to check that the backends agree.
+    fn build_example_hierarchies() -> [CsrOntologyHierarchy<u16>; 2] {
         let root_idx = 0;
         // let nodes = vec![
         //     "HP:1", "HP:01", "HP:010", "HP:011", "HP:0110", "HP:02", "HP:020", "HP:021",
@@ -345,14 +630,12 @@ mod test_hierarchy {
             (9, 0),
         ];
 
-        let adjacency_matrix = GraphBuilder::new()
-            .csr_layout(CsrLayout::Sorted)
-            .edges(edges)
-            .build();
-        CsrOntologyHierarchy {
-            root_idx,
-            adjacency_matrix,
-        }
+        [CsrBackend::GraphBuilder, CsrBackend::Interleaved].map(|backend| {
+            CsrOntologyHierarchy {
+                root_idx,
+                adjacency_matrix: Adjacency::new(edges.clone(), CsrLayout::Sorted, backend),
+            }
+        })
     }
 }
 
@@ -363,7 +646,7 @@ mod create_csr_hierarchy {
 
     #[test]
     fn try_from_graph_edges() {
-        let edges = [
+        let edges: [GraphEdge<usize>; 3] = [
             GraphEdge::from((1, Relationship::Child, 0)),
             GraphEdge::from((2, Relationship::Child, 1)),
             GraphEdge::from((3, Relationship::Child, 1)),
@@ -0,0 +1,35 @@
+use std::hash::Hash;
+
+use crate::hierarchy::HierarchyIdx;
+use crate::ontology::TermIdx;
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+    impl Sealed for u64 {}
+    impl Sealed for usize {}
+}
+
+/// The index of the terms and of the graph nodes of [`super::CsrOntology`].
+///
+/// The trait is sealed, to keep the adjacency matrix backend an implementation detail,
+/// and it is implemented for the unsigned integers: `u8`, `u16`, `u32`, `u64`, and `usize`.
+/// Use it to write code generic over the index of [`super::CsrOntology`]:
+///
+/// ```
+/// use ontolius::ontology::csr::{CsrOntology, Idx};
+/// use ontolius::prelude::*;
+///
+/// fn term_count<I: Idx, T: MinimalTerm>(ontology: &CsrOntology<I, T>) -> usize {
+///     ontology.len()
+/// }
+/// ```
+pub trait Idx: TermIdx + HierarchyIdx + Hash + graph_builder::index::Idx + private::Sealed {}
+
+impl<T> Idx for T where
+    T: TermIdx + HierarchyIdx + Hash + graph_builder::index::Idx + private::Sealed
+{
+}
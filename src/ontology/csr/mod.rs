@@ -26,8 +26,28 @@
 //! 
 //! Check the [`crate::ontology::Ontology`] documentation for more info
 //! regarding the supported functionality.
+mod adjacency;
+mod bloom;
+mod builder;
+#[cfg(feature = "cache")]
+mod cache;
 mod hierarchy;
+mod idx;
 mod ontology;
 
+pub use adjacency::CsrBackend;
+pub use builder::{CsrOntologyBuilder, Renumbering};
+pub use graph_builder::CsrLayout;
 pub use hierarchy::CsrOntologyHierarchy;
-pub use ontology::CsrOntology;
+pub use idx::Idx;
+pub use ontology::{CsrOntology, MemoryUsage};
+
+use crate::base::term::simple::{SimpleMinimalTerm, SimpleTerm};
+
+/// [`CsrOntology`] with `u32` indices and [`SimpleMinimalTerm`]s,
+/// for the hierarchy queries that need only the term IDs and names.
+pub type MinimalCsrOntology = CsrOntology<u32, SimpleMinimalTerm>;
+
+/// [`CsrOntology`] with `u32` indices and [`SimpleTerm`]s,
+/// for the queries that need the definitions, synonyms, or cross-references of the terms.
+pub type FullCsrOntology = CsrOntology<u32, SimpleTerm>;
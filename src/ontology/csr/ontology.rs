@@ -1,67 +1,856 @@
 //! A module with an example implementation of [`Ontology`].
+use std::any::Any;
+use std::collections::HashMap;
 use std::hash::Hash;
-use std::{collections::HashMap, iter::once};
+use std::sync::Arc;
 
-use graph_builder::index::Idx as CsrIdx;
+use graph_builder::CsrLayout;
 
 use crate::base::{term::MinimalTerm, Identified, TermId};
 use crate::error::OntoliusError;
-use crate::hierarchy::HierarchyIdx;
-use crate::io::OntologyData;
-use crate::ontology::{HierarchyAware, MetadataAware, Ontology, TermAware, TermIdx};
+use crate::hierarchy::{HierarchyIdx, ParentNodes};
+use crate::io::{AltIdCollisionPolicy, OntologyData};
+use crate::ontology::attachments::Attachments;
+use crate::ontology::fingerprint::StableHasher;
+#[cfg(feature = "regex")]
+use crate::ontology::matching::{TermPattern, TrigramIndex};
+use crate::ontology::tags::TermTags;
+use crate::ontology::{
+    HierarchyAware, MetadataAware, Ontology, OutdatedId, OutdatedIdListener, RelationshipAware,
+    Relationships, TermAware, TermIdx,
+};
 
+use super::adjacency::CsrBackend;
+use super::bloom::BloomFilter;
+use super::builder::CsrOntologyBuilder;
 use super::hierarchy::CsrOntologyHierarchy;
+use super::Idx;
 
 /// An example implementation of [`Ontology`]
 /// backed by a ontology graph implemented
 /// with a CSR adjacency matrix.
+///
+/// Cloning the ontology makes a deep copy of the terms and of the graph.
+#[derive(Clone)]
 pub struct CsrOntology<HI, T>
 where
-    HI: TermIdx + HierarchyIdx + CsrIdx + Hash,
+    HI: Idx,
     T: MinimalTerm,
 {
     terms: Box<[T]>,
     term_id_to_idx: HashMap<TermId, HI>,
     hierarchy: CsrOntologyHierarchy<HI>,
     metadata: HashMap<String, String>,
+    relationships: Relationships,
+    index_mapping: Option<Box<[HI]>>,
+    content_digest: u64,
+    attachments: Attachments,
+    tags: TermTags,
+    bloom_filter: Option<BloomFilter>,
+    outdated_id_listener: Option<Arc<dyn OutdatedIdListener>>,
+    #[cfg(feature = "regex")]
+    trigram_index: std::sync::OnceLock<TrigramIndex>,
 }
 
 /// `CsrOntology` can be built from [`OntologyData`].
 impl<HI, T> TryFrom<OntologyData<HI, T>> for CsrOntology<HI, T>
 where
-    HI: TermIdx + HierarchyIdx + CsrIdx + Hash,
+    HI: Idx,
     T: MinimalTerm,
 {
     type Error = OntoliusError;
 
     fn try_from(value: OntologyData<HI, T>) -> Result<Self, Self::Error> {
-        // TODO: I am not sure this is the most efficient way to build the ontology.
-        let terms = value.terms().to_vec().into_boxed_slice();
-        let term_id_to_idx = terms
-            .iter()
+        CsrOntologyBuilder::new().build(value)
+    }
+}
+
+impl<HI, T> CsrOntology<HI, T>
+where
+    HI: Idx,
+    T: MinimalTerm,
+{
+    /// Get the mapping from the indices of the terms in the input data
+    /// to the indices in the ontology, if the terms were renumbered.
+    ///
+    /// The new index of the term with the input index `i` is at the position `i`.
+    /// Use the mapping to translate the artifacts that refer to the terms by the input index.
+    /// Returns `None` if the terms kept the order of the input data.
+    ///
+    /// See [`super::Renumbering`] for more info.
+    pub fn index_mapping(&self) -> Option<&[HI]> {
+        self.index_mapping.as_deref()
+    }
+
+    /// Get the digest of the ontology content, e.g. to invalidate the caches
+    /// of the artifacts computed from the ontology.
+    ///
+    /// The digest covers the IDs, names, alternate IDs, and the obsolete flags of the terms,
+    /// the `is_a` edges, the other relationships, and the metadata.
+    /// The other term data, such as the definitions, do not contribute to the digest.
+    ///
+    /// The digest depends on the term indices, since the cached artifacts, such as
+    /// the similarity matrices, usually refer to the terms by the index.
+    /// The ontologies with the same content but a different order of the terms
+    /// have different digests. The digest is stable across platforms and runs.
+    ///
+    /// The ontologies are hashed by the digest. The equality compares the terms,
+    /// the edges, and the metadata in full, since different ontologies can share a digest.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use curie_util::TrieCurieUtil;
+    /// use ontolius::io::obographs::ObographsParser;
+    /// use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    ///
+    /// let loader = OntologyLoaderBuilder::new()
+    ///                .parser(ObographsParser::new(TrieCurieUtil::default()))
+    ///                .build();
+    /// let path = "resources/hp.small.json.gz";
+    /// let hpo: CsrOntology<usize, _> = loader.load_from_path(path).expect("HPO should be loaded");
+    /// let other: CsrOntology<usize, _> = loader.load_from_path(path).expect("HPO should be loaded");
+    ///
+    /// assert_eq!(hpo.content_digest(), other.content_digest());
+    /// assert!(hpo == other);
+    /// ```
+    pub fn content_digest(&self) -> u64 {
+        self.content_digest
+    }
+
+    /// Attach the `value` to the ontology, e.g. a table with the information content of the terms,
+    /// to pass it along with the ontology through the application layers.
+    ///
+    /// The ontology holds at most one value of each type,
+    /// and the previous value of the same type is returned, if any.
+    /// The clones of the ontology share the attached values.
+    ///
+    /// The attached values contribute neither to [`CsrOntology::content_digest`]
+    /// nor to [`CsrOntology::memory_usage`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use curie_util::TrieCurieUtil;
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// # use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    ///
+    /// struct IcTable(Vec<f64>);
+    ///
+    /// # let loader = OntologyLoaderBuilder::new()
+    /// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+    /// #                .build();
+    /// # let mut hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                                        .expect("HPO should be loaded");
+    /// #
+    /// let table = IcTable(vec![0.; hpo.len()]);
+    /// hpo.attach::<IcTable>(table);
+    ///
+    /// let table = hpo.attachment::<IcTable>().expect("IC table should be attached");
+    /// assert_eq!(table.0.len(), 614);
+    /// ```
+    pub fn attach<A>(&mut self, value: A) -> Option<Arc<A>>
+    where
+        A: Any + Send + Sync,
+    {
+        self.attachments.insert(value)
+    }
+
+    /// Get the attached value of type `A`, if any.
+    pub fn attachment<A>(&self) -> Option<&A>
+    where
+        A: Any + Send + Sync,
+    {
+        self.attachments.get()
+    }
+
+    /// Remove the attached value of type `A` and return it, if any.
+    pub fn detach<A>(&mut self) -> Option<Arc<A>>
+    where
+        A: Any + Send + Sync,
+    {
+        self.attachments.remove()
+    }
+
+    /// Tag the term with the `term_id` (including an alternate ID), e.g. to mark
+    /// the terms curated by the application.
+    ///
+    /// The tags are stored as bitsets of the term indices, hence the tagged terms
+    /// can be queried quickly with [`CsrOntology::iter_terms_with_tag`].
+    /// Unlike the attached values, the tags are copied when the ontology is cloned.
+    /// The tags contribute neither to [`CsrOntology::content_digest`]
+    /// nor to [`CsrOntology::memory_usage`].
+    ///
+    /// Returns `true` if the term was tagged, or `false` if the term already had the tag
+    /// or if it is not in the ontology.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use curie_util::TrieCurieUtil;
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// # use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    ///
+    /// # let loader = OntologyLoaderBuilder::new()
+    /// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+    /// #                .build();
+    /// # let mut hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                                        .expect("HPO should be loaded");
+    /// #
+    /// let arachnodactyly = TermId::from(("HP", "0001166"));
+    /// assert!(hpo.tag(&arachnodactyly, "curated"));
+    ///
+    /// assert!(hpo.has_tag(&arachnodactyly, "curated"));
+    /// let names: Vec<_> = hpo.iter_terms_with_tag("curated").map(|term| term.name()).collect();
+    /// assert_eq!(names, ["Arachnodactyly"]);
+    /// ```
+    pub fn tag<ID>(&mut self, term_id: &ID, tag: &str) -> bool
+    where
+        ID: Identified,
+    {
+        match self.id_to_idx(term_id) {
+            Some(idx) => self.tags.insert(tag, TermIdx::index(idx)),
+            None => false,
+        }
+    }
+
+    /// Remove the `tag` of the term with the `term_id` (including an alternate ID).
+    ///
+    /// Returns `true` if the term had the tag.
+    pub fn untag<ID>(&mut self, term_id: &ID, tag: &str) -> bool
+    where
+        ID: Identified,
+    {
+        match self.id_to_idx(term_id) {
+            Some(idx) => self.tags.remove(tag, TermIdx::index(idx)),
+            None => false,
+        }
+    }
+
+    /// Test if the term with the `term_id` (including an alternate ID) has the `tag`.
+    pub fn has_tag<ID>(&self, term_id: &ID, tag: &str) -> bool
+    where
+        ID: Identified,
+    {
+        self.id_to_idx(term_id)
+            .is_some_and(|idx| self.tags.contains(tag, TermIdx::index(idx)))
+    }
+
+    /// Iterate over the terms with the `tag`, in the order of the term indices.
+    pub fn iter_terms_with_tag(&self, tag: &str) -> impl Iterator<Item = &T> {
+        self.tags.iter(tag).flat_map(|idx| self.terms.get(idx))
+    }
+
+    /// Iterate over the tags used in the ontology, in no particular order.
+    pub fn iter_tags(&self) -> impl Iterator<Item = &str> {
+        self.tags.names()
+    }
+
+    /// Get the approximate number of bytes used by the ontology, e.g. to budget memory.
+    ///
+    /// See [`MemoryUsage`] for the details of the estimate.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use curie_util::TrieCurieUtil;
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// # use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    ///
+    /// # let loader = OntologyLoaderBuilder::new()
+    /// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+    /// #                .build();
+    /// # let mut hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                                        .expect("HPO should be loaded");
+    /// #
+    /// let before = hpo.memory_usage();
+    /// hpo.shrink_to_fit();
+    /// let after = hpo.memory_usage();
+    ///
+    /// assert!(after.total() <= before.total());
+    /// assert_eq!(after.terms(), before.terms());
+    /// ```
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let terms = std::mem::size_of_val(&*self.terms)
+            + self
+                .terms
+                .iter()
+                .map(|term| {
+                    term.name().len()
+                        + term.iter_alt_term_ids().count() * std::mem::size_of::<TermId>()
+                })
+                .sum::<usize>();
+        // The hash map stores the entries and one control byte per bucket.
+        let term_index = self.term_id_to_idx.capacity() * (std::mem::size_of::<(TermId, HI)>() + 1)
+            + self
+                .bloom_filter
+                .as_ref()
+                .map_or(0, BloomFilter::memory_usage);
+        let metadata = self.metadata.capacity() * (std::mem::size_of::<(String, String)>() + 1)
+            + self
+                .metadata
+                .iter()
+                .map(|(key, value)| key.capacity() + value.capacity())
+                .sum::<usize>()
+            + self
+                .index_mapping
+                .as_deref()
+                .map_or(0, std::mem::size_of_val);
+        MemoryUsage {
+            terms,
+            term_index,
+            hierarchy: self.hierarchy.memory_usage(),
+            metadata,
+        }
+    }
+
+    /// Put a Bloom filter in front of the map from the term IDs to the term indices,
+    /// e.g. for the services that look up many IDs that are not in the ontology.
+    ///
+    /// The filter rejects most of the unknown IDs without the map lookup,
+    /// at the cost of about 10 bits per term ID (including the alternate IDs).
+    /// See also [`super::CsrOntologyBuilder::bloom_filter`].
+    pub fn enable_bloom_filter(&mut self) {
+        self.bloom_filter = Some(BloomFilter::new(self.term_id_to_idx.keys()));
+    }
+
+    /// Test if the ontology might contain the term with the `term_id` (including an alternate ID).
+    ///
+    /// Returns `false` only if the term is definitely not in the ontology.
+    /// With the Bloom filter (see [`CsrOntology::enable_bloom_filter`]), the check is cheap
+    /// but it can give a false positive. Without the filter, the check is exact.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use curie_util::TrieCurieUtil;
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// # use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    ///
+    /// # let loader = OntologyLoaderBuilder::new()
+    /// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+    /// #                .build();
+    /// # let mut hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                                        .expect("HPO should be loaded");
+    /// hpo.enable_bloom_filter();
+    ///
+    /// assert!(hpo.might_contain(&TermId::from(("HP", "0001166"))));
+    /// ```
+    pub fn might_contain(&self, term_id: &TermId) -> bool {
+        match &self.bloom_filter {
+            Some(filter) => filter.might_contain(term_id),
+            None => self.term_id_to_idx.contains_key(term_id),
+        }
+    }
+
+    /// Send the outdated IDs resolved by [`CsrOntology::resolve`] to the `listener`,
+    /// e.g. to tell the users that they used an alternate ID or an obsolete term.
+    ///
+    /// The listener receives an [`OutdatedId`] whenever [`CsrOntology::resolve`]
+    /// resolves an alternate ID or the ID of an obsolete term.
+    /// The [`TermAware`] lookups, used by the library internally, do not notify the listener.
+    /// The clones of the ontology share the listener.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// # use curie_util::TrieCurieUtil;
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// # use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::ontology::{OutdatedId, OutdatedIdReport};
+    /// use ontolius::prelude::*;
+    ///
+    /// # let loader = OntologyLoaderBuilder::new()
+    /// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+    /// #                .build();
+    /// # let mut hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                                        .expect("HPO should be loaded");
+    /// #
+    /// let report = Arc::new(OutdatedIdReport::default());
+    /// hpo.set_outdated_id_listener(report.clone());
+    ///
+    /// // `HP:0001505` is an alternate ID of Arachnodactyly.
+    /// let term = hpo.resolve(&TermId::from(("HP", "0001505")));
+    /// assert!(term.is_some());
+    /// assert_eq!(
+    ///     report.take(),
+    ///     [OutdatedId::Alternate {
+    ///         queried: TermId::from(("HP", "0001505")),
+    ///         primary: TermId::from(("HP", "0001166")),
+    ///     }]
+    /// );
+    /// ```
+    pub fn set_outdated_id_listener(&mut self, listener: Arc<dyn OutdatedIdListener>) {
+        self.outdated_id_listener = Some(listener);
+    }
+
+    /// Stop sending the outdated IDs to the listener.
+    pub fn clear_outdated_id_listener(&mut self) {
+        self.outdated_id_listener = None;
+    }
+
+    /// Get the term for a term ID supplied by a user, such as an alternate ID or the ID of an obsolete term.
+    ///
+    /// Unlike [`TermAware::id_to_term`], the method notifies the outdated ID listener
+    /// (see [`CsrOntology::set_outdated_id_listener`]) if the ID is outdated.
+    pub fn resolve<ID>(&self, id: &ID) -> Option<&T>
+    where
+        ID: Identified,
+    {
+        let term_id = id.identifier();
+        let term = self.id_to_term(term_id)?;
+        if let Some(listener) = &self.outdated_id_listener {
+            if term.identifier() != term_id {
+                listener.on_outdated_id(&OutdatedId::Alternate {
+                    queried: term_id.clone(),
+                    primary: term.identifier().clone(),
+                });
+            } else if term.is_obsolete() {
+                listener.on_outdated_id(&OutdatedId::Obsolete {
+                    term_id: term_id.clone(),
+                });
+            }
+        }
+        Some(term)
+    }
+
+    /// Release the unused capacity of the lookup tables.
+    pub fn shrink_to_fit(&mut self) {
+        self.term_id_to_idx.shrink_to_fit();
+        self.metadata.shrink_to_fit();
+    }
+
+    /// Get an iterator over the ontology terms in the order of their indices.
+    ///
+    /// Same as [`TermAware::iter_terms`], but available without importing the trait.
+    /// The ontology reference can be iterated as well:
+    ///
+    /// ```
+    /// # use curie_util::TrieCurieUtil;
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// # use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    ///
+    /// # let loader = OntologyLoaderBuilder::new()
+    /// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+    /// #                .build();
+    /// # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                                    .expect("HPO should be loaded");
+    /// #
+    /// let mut count = 0;
+    /// for term in &hpo {
+    ///     assert!(!term.name().is_empty());
+    ///     count += 1;
+    /// }
+    /// assert_eq!(count, hpo.iter().count());
+    /// ```
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.terms.iter()
+    }
+
+    /// Get an iterator over the consecutive chunks of the terms, with up to `chunk_size` terms each,
+    /// along with the index of the first term of the chunk.
+    ///
+    /// The chunks cover all terms in the order of their indices,
+    /// e.g. to process the terms in parallel.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use curie_util::TrieCurieUtil;
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// # use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    ///
+    /// # let loader = OntologyLoaderBuilder::new()
+    /// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+    /// #                .build();
+    /// # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                                    .expect("HPO should be loaded");
+    /// #
+    /// let chunks: Vec<_> = hpo.terms_chunked(100).collect();
+    /// assert_eq!(chunks.len(), 7);
+    ///
+    /// let (offset, terms) = chunks[6];
+    /// assert_eq!(offset, 600);
+    /// assert_eq!(terms.len(), 14);
+    /// assert_eq!(hpo.idx_to_term(offset).unwrap().identifier(), terms[0].identifier());
+    /// ```
+    pub fn terms_chunked(&self, chunk_size: usize) -> impl Iterator<Item = (usize, &[T])> {
+        self.terms
+            .chunks(chunk_size)
             .enumerate()
-            .flat_map(|(idx, term)| {
-                once((term.identifier().clone(), HierarchyIdx::new(idx))).chain(
-                    term.iter_alt_term_ids()
-                        .map(move |alt| (alt.clone(), HierarchyIdx::new(idx))),
-                )
-            })
-            .collect();
+            .map(move |(i, chunk)| (i * chunk_size, chunk))
+    }
+
+    /// Assemble the ontology from the `terms` and the `(child, parent)` pairs,
+    /// using the metadata and the settings of the `data`.
+    pub(super) fn assemble(
+        terms: Box<[T]>,
+        pairs: Vec<(HI, HI)>,
+        data: &OntologyData<HI, T>,
+        layout: CsrLayout,
+        backend: CsrBackend,
+        index_mapping: Option<Box<[HI]>>,
+    ) -> Result<Self, OntoliusError> {
+        // TODO: I am not sure this is the most efficient way to build the ontology.
+        let alt_ids = terms.iter().enumerate().flat_map(|(idx, term)| {
+            term.iter_alt_term_ids()
+                .map(move |alt| (alt.clone(), HierarchyIdx::new(idx)))
+        });
+        let mut term_id_to_idx: HashMap<_, _> = match data.alt_id_policy() {
+            AltIdCollisionPolicy::FirstWins => {
+                let mut term_id_to_idx = HashMap::new();
+                for (term_id, idx) in alt_ids {
+                    term_id_to_idx.entry(term_id).or_insert(idx);
+                }
+                term_id_to_idx
+            }
+            // The collisions fail the loading with `Error` policy,
+            // the data assembled directly is handled as with `LastWins`.
+            AltIdCollisionPolicy::LastWins | AltIdCollisionPolicy::Error => alt_ids.collect(),
+        };
+        // The alternate IDs never override the primary IDs.
+        term_id_to_idx.extend(
+            terms
+                .iter()
+                .enumerate()
+                .map(|(idx, term)| (term.identifier().clone(), HierarchyIdx::new(idx))),
+        );
 
-        let hierarchy = CsrOntologyHierarchy::try_from(value.edges())?;
-        let metadata = value.metadata().clone();
+        let hierarchy = CsrOntologyHierarchy::from_pairs(pairs, layout, backend)?;
+        let metadata = data.metadata().clone();
+        let relationships = data.relationships().clone();
+        let content_digest = content_digest(&terms, &hierarchy, &relationships, &metadata);
         Ok(Self {
             terms,
             term_id_to_idx,
             hierarchy,
             metadata,
+            relationships,
+            index_mapping,
+            content_digest,
+            attachments: Attachments::default(),
+            tags: TermTags::default(),
+            bloom_filter: None,
+            outdated_id_listener: None,
+            #[cfg(feature = "regex")]
+            trigram_index: std::sync::OnceLock::new(),
         })
     }
 }
 
+/// The approximate number of bytes used by the parts of [`CsrOntology`].
+///
+/// The estimate includes the inline size of the terms, along with the names and the alternate IDs,
+/// but not the other data allocated by the terms, such as the definitions or the synonyms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemoryUsage {
+    terms: usize,
+    term_index: usize,
+    hierarchy: usize,
+    metadata: usize,
+}
+
+impl MemoryUsage {
+    /// Get the number of bytes used by the terms.
+    pub fn terms(&self) -> usize {
+        self.terms
+    }
+
+    /// Get the number of bytes used by the map from the term IDs to the term indices.
+    pub fn term_index(&self) -> usize {
+        self.term_index
+    }
+
+    /// Get the number of bytes used by the adjacency matrix.
+    pub fn hierarchy(&self) -> usize {
+        self.hierarchy
+    }
+
+    /// Get the number of bytes used by the metadata and the other bookkeeping.
+    pub fn metadata(&self) -> usize {
+        self.metadata
+    }
+
+    /// Get the total number of bytes.
+    pub fn total(&self) -> usize {
+        self.terms + self.term_index + self.hierarchy + self.metadata
+    }
+}
+
+/// Compute the content digest of the ontology (see [`CsrOntology::content_digest`]).
+fn content_digest<HI, T>(
+    terms: &[T],
+    hierarchy: &CsrOntologyHierarchy<HI>,
+    relationships: &Relationships,
+    metadata: &HashMap<String, String>,
+) -> u64
+where
+    HI: Idx,
+    T: MinimalTerm,
+{
+    let mut hasher = StableHasher::new();
+
+    hasher.write_usize(terms.len());
+    for term in terms {
+        hasher.write_str(&term.identifier().to_string());
+        hasher.write_str(term.name());
+        hasher.write(&[u8::from(term.is_obsolete())]);
+        hasher.write_usize(term.iter_alt_term_ids().count());
+        term.iter_alt_term_ids()
+            .for_each(|alt| hasher.write_str(&alt.to_string()));
+    }
+
+    let mut parents = vec![];
+    for idx in 0..terms.len() {
+        parents.clear();
+        parents.extend(
+            hierarchy
+                .parents_of(<HI as HierarchyIdx>::new(idx))
+                .map(|&parent| TermIdx::index(parent)),
+        );
+        // The order of the parents depends on the CSR layout.
+        parents.sort_unstable();
+        hasher.write_usize(parents.len());
+        parents.iter().for_each(|&parent| hasher.write_usize(parent));
+    }
+
+    let mut relationships: Vec<_> = relationships
+        .iter()
+        .map(|(sub, pred, obj)| (sub.to_string(), pred.to_string(), obj.to_string()))
+        .collect();
+    relationships.sort_unstable();
+    hasher.write_usize(relationships.len());
+    for (sub, pred, obj) in relationships {
+        hasher.write_str(&sub);
+        hasher.write_str(&pred);
+        hasher.write_str(&obj);
+    }
+
+    let mut metadata: Vec<_> = metadata.iter().collect();
+    metadata.sort_unstable();
+    for (key, value) in metadata {
+        hasher.write_str(key);
+        hasher.write_str(value);
+    }
+
+    hasher.finish()
+}
+
+/// The ontologies are equal if they have the same terms in the same order, the same `is_a` edges,
+/// relationships, term ID lookup, and metadata.
+///
+/// The [`CsrOntology::content_digest`] only rules out the ontologies that differ.
+impl<HI, T> PartialEq for CsrOntology<HI, T>
+where
+    HI: Idx,
+    T: MinimalTerm + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.content_digest != other.content_digest || self.terms != other.terms {
+            return false;
+        }
+        // The order of the parents depends on the CSR layout.
+        let parents = |ontology: &Self, idx: usize| {
+            let mut parents: Vec<_> = ontology
+                .hierarchy
+                .parents_of(<HI as HierarchyIdx>::new(idx))
+                .copied()
+                .collect();
+            parents.sort_unstable();
+            parents
+        };
+        (0..self.terms.len()).all(|idx| parents(self, idx) == parents(other, idx))
+            && self.relationships == other.relationships
+            && self.term_id_to_idx == other.term_id_to_idx
+            && self.metadata == other.metadata
+    }
+}
+
+impl<HI, T> Eq for CsrOntology<HI, T>
+where
+    HI: Idx,
+    T: MinimalTerm + Eq,
+{
+}
+
+impl<HI, T> Hash for CsrOntology<HI, T>
+where
+    HI: Idx,
+    T: MinimalTerm,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.content_digest.hash(state);
+    }
+}
+
+/// The serialized form of [`CsrOntology`], with the `is_a` hierarchy as `(child, parent)` pairs.
+///
+/// The alternate IDs are resolved to the last term that claims the ID, as in
+/// [`AltIdCollisionPolicy::LastWins`], except for the IDs in `alt_id_overrides`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedCsrOntology<'a, HI, T>
+where
+    HI: Clone,
+    T: Clone,
+{
+    terms: std::borrow::Cow<'a, [T]>,
+    edges: Vec<(HI, HI)>,
+    alt_id_overrides: Vec<(TermId, HI)>,
+    metadata: std::borrow::Cow<'a, HashMap<String, String>>,
+    relationships: std::borrow::Cow<'a, Relationships>,
+    index_mapping: Option<std::borrow::Cow<'a, [HI]>>,
+}
+
+/// `CsrOntology` is serialized as the terms, the `is_a` edges, the metadata,
+/// the other relationships, and the index mapping.
+///
+/// The deserialized ontology has the same content digest and resolves the term IDs
+/// to the same terms, but it uses the sorted layout of the default backend,
+/// and it has no attachments, tags, Bloom filter, or outdated ID listener.
+///
+/// ## Examples
+///
+/// ```
+/// # use curie_util::TrieCurieUtil;
+/// # use ontolius::io::obographs::ObographsParser;
+/// use ontolius::ontology::csr::MinimalCsrOntology;
+/// use ontolius::prelude::*;
+///
+/// # let loader = OntologyLoaderBuilder::new()
+/// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+/// #                .build();
+/// # let hpo: MinimalCsrOntology = loader.load_from_path("resources/hp.small.json.gz")
+/// #                                     .expect("HPO should be loaded");
+/// #
+/// let json = serde_json::to_string(&hpo).expect("HPO should be serialized");
+/// let other: MinimalCsrOntology = serde_json::from_str(&json).expect("HPO should be deserialized");
+///
+/// assert!(hpo == other);
+/// ```
+#[cfg(feature = "serde")]
+impl<HI, T> serde::Serialize for CsrOntology<HI, T>
+where
+    HI: Idx + serde::Serialize,
+    T: MinimalTerm + serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let edges = (0..self.terms.len())
+            .map(<HI as HierarchyIdx>::new)
+            .flat_map(|child| {
+                self.hierarchy
+                    .parents_of(child)
+                    .map(move |&parent| (child, parent))
+            })
+            .collect();
+
+        // Replay the ID resolution of `CsrOntology::assemble` and keep the deviations.
+        let mut last_wins = HashMap::new();
+        for (idx, term) in self.terms.iter().enumerate() {
+            for alt_id in term.iter_alt_term_ids() {
+                last_wins.insert(alt_id, idx);
+            }
+        }
+        for (idx, term) in self.terms.iter().enumerate() {
+            last_wins.insert(term.identifier(), idx);
+        }
+        let alt_id_overrides = self
+            .term_id_to_idx
+            .iter()
+            .filter(|(term_id, &idx)| last_wins.get(term_id) != Some(&TermIdx::index(idx)))
+            .map(|(term_id, &idx)| (term_id.clone(), idx))
+            .collect();
+
+        SerializedCsrOntology {
+            terms: std::borrow::Cow::Borrowed(&self.terms[..]),
+            edges,
+            alt_id_overrides,
+            metadata: std::borrow::Cow::Borrowed(&self.metadata),
+            relationships: std::borrow::Cow::Borrowed(&self.relationships),
+            index_mapping: self
+                .index_mapping
+                .as_deref()
+                .map(std::borrow::Cow::Borrowed),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, HI, T> serde::Deserialize<'de> for CsrOntology<HI, T>
+where
+    HI: Idx + serde::Deserialize<'de>,
+    T: MinimalTerm + serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let serialized = SerializedCsrOntology::<HI, T>::deserialize(deserializer)?;
+        let data: OntologyData<HI, T> = OntologyData::from((
+            Box::new([]) as Box<[T]>,
+            Box::new([]) as Box<[_]>,
+            serialized.metadata.into_owned(),
+        ))
+        .with_relationships(serialized.relationships.into_owned());
+        let mut ontology = Self::assemble(
+            serialized.terms.into_owned().into_boxed_slice(),
+            serialized.edges,
+            &data,
+            CsrLayout::Sorted,
+            CsrBackend::default(),
+            serialized
+                .index_mapping
+                .map(|mapping| mapping.into_owned().into_boxed_slice()),
+        )
+        .map_err(serde::de::Error::custom)?;
+        ontology.term_id_to_idx.extend(serialized.alt_id_overrides);
+        Ok(ontology)
+    }
+}
+
+impl<'a, HI, T> IntoIterator for &'a CsrOntology<HI, T>
+where
+    HI: Idx,
+    T: MinimalTerm,
+{
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<HI, T> RelationshipAware for CsrOntology<HI, T>
+where
+    HI: Idx,
+    T: MinimalTerm,
+{
+    fn relationships(&self) -> &Relationships {
+        &self.relationships
+    }
+}
+
 impl<HI, T> HierarchyAware for CsrOntology<HI, T>
 where
-    HI: TermIdx + HierarchyIdx + CsrIdx + Hash,
+    HI: Idx,
     T: MinimalTerm,
 {
     type HI = HI;
@@ -74,7 +863,7 @@ where
 
 impl<HI, T> TermAware for CsrOntology<HI, T>
 where
-    HI: TermIdx + HierarchyIdx + CsrIdx + Hash,
+    HI: Idx,
     T: MinimalTerm,
 {
     type TI = HI;
@@ -88,6 +877,8 @@ where
     }
 
     fn idx_to_term(&self, idx: Self::TI) -> Option<&T> {
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_call("idx_to_term");
         self.terms.get(TermIdx::index(idx))
     }
 
@@ -95,17 +886,58 @@ where
     where
         ID: Identified,
     {
-        self.term_id_to_idx.get(id.identifier()).copied()
+        #[cfg(feature = "metrics")]
+        {
+            crate::metrics::record_call("id_to_idx");
+            crate::metrics::record_term_lookup(id.identifier().to_string());
+        }
+        let term_id = id.identifier();
+        if self
+            .bloom_filter
+            .as_ref()
+            .is_some_and(|filter| !filter.might_contain(term_id))
+        {
+            return None;
+        }
+        self.term_id_to_idx.get(term_id).copied()
     }
 
     fn len(&self) -> usize {
         self.terms.len()
     }
+
+    /// Pre-filter the terms with the trigram index, built on the first call.
+    #[cfg(feature = "regex")]
+    fn iter_terms_matching<'a>(
+        &'a self,
+        pattern: &str,
+    ) -> Result<impl Iterator<Item = &'a T>, OntoliusError>
+    where
+        T: crate::base::term::SynonymAware,
+    {
+        let pattern = TermPattern::new(pattern)?;
+        let index = self
+            .trigram_index
+            .get_or_init(|| TrigramIndex::new(self.terms.iter()));
+        let terms: Vec<_> = match index.candidates(&pattern) {
+            Some(candidates) => candidates
+                .into_iter()
+                .map(|i| &self.terms[i])
+                .filter(|term| pattern.is_match(*term))
+                .collect(),
+            None => self
+                .terms
+                .iter()
+                .filter(|term| pattern.is_match(*term))
+                .collect(),
+        };
+        Ok(terms.into_iter())
+    }
 }
 
 impl<HI, T> MetadataAware for CsrOntology<HI, T>
 where
-    HI: TermIdx + HierarchyIdx + CsrIdx + Hash,
+    HI: Idx,
     T: MinimalTerm,
 {
     fn version(&self) -> &str {
@@ -118,7 +950,7 @@ where
 
 impl<HI, T> Ontology for CsrOntology<HI, T>
 where
-    HI: TermIdx + HierarchyIdx + CsrIdx + Hash,
+    HI: Idx,
     T: MinimalTerm,
 {
     type Idx = HI;
@@ -131,6 +963,7 @@ mod test {
     use std::{collections::HashSet, str::FromStr};
 
     use super::*;
+    use crate::test_util::toy_ontology;
     use crate::{
         base::term::simple::SimpleMinimalTerm,
         ontology::{AllTermIdsIter, State, TermIdIter},
@@ -171,6 +1004,228 @@ mod test {
             .for_each(|&curie| assert!(term_ids.contains(curie)))
     }
 
+    #[test]
+    fn test_alt_id_policy() {
+        use crate::hierarchy::{GraphEdge, Relationship};
+
+        let terms = vec![
+            SimpleMinimalTerm::new(TermId::from_str("HP:1").unwrap(), "Root", vec![], false),
+            SimpleMinimalTerm::new(
+                TermId::from_str("HP:2").unwrap(),
+                "Second",
+                vec![TermId::from_str("HP:99").unwrap()],
+                false,
+            ),
+            SimpleMinimalTerm::new(
+                TermId::from_str("HP:3").unwrap(),
+                "Third",
+                vec![TermId::from_str("HP:99").unwrap()],
+                false,
+            ),
+        ];
+        let edges = vec![
+            GraphEdge::from((1, Relationship::Child, 0)),
+            GraphEdge::from((2, Relationship::Child, 0)),
+        ];
+        let data = || -> OntologyData<usize, _> {
+            OntologyData::from((
+                terms.clone().into_boxed_slice(),
+                edges.clone().into_boxed_slice(),
+                HashMap::new(),
+            ))
+        };
+        let alt_id = TermId::from_str("HP:99").unwrap();
+
+        let ontology =
+            CsrOntology::try_from(data().with_alt_id_policy(AltIdCollisionPolicy::FirstWins))
+                .unwrap();
+        assert_eq!(ontology.id_to_idx(&alt_id), Some(1));
+
+        let ontology = CsrOntology::try_from(data()).unwrap();
+        assert_eq!(ontology.id_to_idx(&alt_id), Some(2));
+    }
+
+    #[test]
+    fn test_only_resolve_notifies_outdated_id_listener() {
+        use crate::ontology::{OutdatedId, OutdatedIdReport};
+
+        let terms = vec![
+            SimpleMinimalTerm::new(TermId::from_str("HP:1").unwrap(), "Root", vec![], false),
+            SimpleMinimalTerm::new(
+                TermId::from_str("HP:2").unwrap(),
+                "Second",
+                vec![TermId::from_str("HP:99").unwrap()],
+                false,
+            ),
+            SimpleMinimalTerm::new(TermId::from_str("HP:3").unwrap(), "Obsolete", vec![], true),
+        ];
+        let mut ontology = toy_ontology(terms, &[(1, 0)]);
+        let report = Arc::new(OutdatedIdReport::default());
+        ontology.set_outdated_id_listener(report.clone());
+        let alt_id = TermId::from_str("HP:99").unwrap();
+        let obsolete_id = TermId::from_str("HP:3").unwrap();
+
+        assert_eq!(ontology.id_to_idx(&alt_id), Some(1));
+        assert!(ontology.id_to_term(&obsolete_id).is_some());
+        assert!(!ontology.has_tag(&alt_id, "curated"));
+        assert!(report.is_empty());
+
+        assert!(ontology.resolve(&alt_id).is_some());
+        assert!(ontology.resolve(&obsolete_id).is_some());
+        let primary_id = TermId::from_str("HP:2").unwrap();
+        assert!(ontology.resolve(&primary_id).is_some());
+        assert_eq!(
+            report.take(),
+            [
+                OutdatedId::Alternate {
+                    queried: alt_id,
+                    primary: primary_id,
+                },
+                OutdatedId::Obsolete {
+                    term_id: obsolete_id,
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        use crate::hierarchy::{ChildNodes, GraphEdge, Relationship};
+
+        let terms = vec![
+            SimpleMinimalTerm::new(TermId::from_str("HP:1").unwrap(), "Root", vec![], false),
+            SimpleMinimalTerm::new(
+                TermId::from_str("HP:2").unwrap(),
+                "Second",
+                vec![TermId::from_str("HP:99").unwrap()],
+                false,
+            ),
+            SimpleMinimalTerm::new(
+                TermId::from_str("HP:3").unwrap(),
+                "Third",
+                vec![TermId::from_str("HP:99").unwrap()],
+                true,
+            ),
+        ];
+        let edges = vec![
+            GraphEdge::from((1, Relationship::Child, 0)),
+            GraphEdge::from((0, Relationship::Parent, 2)),
+        ];
+        let data: OntologyData<usize, _> = OntologyData::from((
+            terms.into_boxed_slice(),
+            edges.into_boxed_slice(),
+            HashMap::from([("version".to_string(), "2024-04-26".to_string())]),
+        ))
+        .with_alt_id_policy(AltIdCollisionPolicy::FirstWins);
+
+        let json = serde_json::to_string(&data).unwrap();
+        let data: OntologyData<usize, SimpleMinimalTerm> = serde_json::from_str(&json).unwrap();
+        assert_eq!(data.terms()[2].name(), "Third");
+        assert_eq!(data.edges()[1].pred, Relationship::Parent);
+        assert_eq!(data.alt_id_policy(), AltIdCollisionPolicy::FirstWins);
+
+        let ontology = CsrOntology::try_from(data).unwrap();
+        let json = serde_json::to_string(&ontology).unwrap();
+        let other: CsrOntology<usize, SimpleMinimalTerm> = serde_json::from_str(&json).unwrap();
+
+        assert!(ontology == other);
+        assert_eq!(other.version(), "2024-04-26");
+        assert_eq!(
+            other.id_to_idx(&TermId::from_str("HP:99").unwrap()),
+            Some(1)
+        );
+        assert!(other.idx_to_term(2).unwrap().is_obsolete());
+        assert_eq!(
+            other.hierarchy().children_of(0).collect::<Vec<_>>(),
+            [&1, &2]
+        );
+    }
+
+    #[test]
+    fn test_terms_chunked() {
+        let terms: Vec<_> = (1..=5)
+            .map(|i| {
+                SimpleMinimalTerm::new(
+                    TermId::from(("HP", i.to_string().as_str())),
+                    "Term",
+                    vec![],
+                    false,
+                )
+            })
+            .collect();
+        let edges: Vec<_> = (1..5).map(|i| (i, 0)).collect();
+        let ontology = toy_ontology(terms, &edges);
+
+        let chunks: Vec<_> = ontology
+            .terms_chunked(2)
+            .map(|(offset, terms)| (offset, terms.len()))
+            .collect();
+        assert_eq!(chunks, [(0, 2), (2, 2), (4, 1)]);
+
+        let curies: Vec<_> = (&ontology)
+            .into_iter()
+            .map(|term| term.identifier().to_string())
+            .collect();
+        assert_eq!(curies, ["HP:1", "HP:2", "HP:3", "HP:4", "HP:5"]);
+        assert!(ontology.iter().eq(ontology.iter_terms()));
+    }
+
+    #[test]
+    fn test_content_digest() {
+        let build = |names: [&str; 3], edges: &[(usize, usize)]| {
+            let terms: Vec<_> = names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    let id = (i + 1).to_string();
+                    SimpleMinimalTerm::new(TermId::from(("HP", id.as_str())), *name, vec![], false)
+                })
+                .collect();
+            toy_ontology(terms, edges)
+        };
+
+        let ontology = build(["Root", "A", "B"], &[(1, 0), (2, 0)]);
+        let same = build(["Root", "A", "B"], &[(2, 0), (1, 0)]);
+        let renamed = build(["Root", "A", "C"], &[(1, 0), (2, 0)]);
+        let rewired = build(["Root", "A", "B"], &[(1, 0), (2, 1)]);
+
+        assert_eq!(ontology.content_digest(), same.content_digest());
+        assert!(ontology == same);
+        assert_ne!(ontology.content_digest(), renamed.content_digest());
+        assert_ne!(ontology.content_digest(), rewired.content_digest());
+        assert!(ontology != rewired);
+
+        let clone = ontology.clone();
+        assert!(clone == ontology);
+        assert_eq!(clone.len(), ontology.len());
+        assert_eq!(clone.memory_usage(), ontology.memory_usage());
+    }
+
+    #[test]
+    fn test_eq_compares_full_terms() {
+        use crate::base::term::simple::SimpleTerm;
+
+        let build = |definition: &str| {
+            let terms: Vec<_> = ["1", "2"]
+                .iter()
+                .map(|id| {
+                    SimpleTerm::new(TermId::from(("HP", *id)), "Term", vec![], false)
+                        .with_definition(definition)
+                })
+                .collect();
+            toy_ontology(terms, &[(1, 0)])
+        };
+
+        let ontology = build("A term.");
+        let redefined = build("Another term.");
+
+        // The definitions do not contribute to the digest, but to the equality.
+        assert_eq!(ontology.content_digest(), redefined.content_digest());
+        assert!(ontology != redefined);
+        assert!(ontology == build("A term."));
+    }
+
     fn get_terms() -> Vec<SimpleMinimalTerm> {
         vec![
             SimpleMinimalTerm::new(
@@ -0,0 +1,45 @@
+use ndarray::Array2;
+
+use crate::hierarchy::{AncestorNodes, HierarchyIdx};
+
+use super::{Ontology, TermIdx};
+
+pub(super) fn make_closure_matrix<O>(ontology: &O) -> Array2<bool>
+where
+    O: Ontology + ?Sized,
+{
+    let hierarchy = ontology.hierarchy();
+    let n_terms = ontology.len();
+    let mut matrix = Array2::from_elem((n_terms, n_terms), false);
+    for row in 0..n_terms {
+        matrix[[row, row]] = true;
+        for &ancestor in hierarchy.ancestors_of(<O::Idx as HierarchyIdx>::new(row)) {
+            matrix[[row, TermIdx::index(ancestor)]] = true;
+        }
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod test {
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::base::TermId;
+    use crate::ontology::Ontology;
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_dense_closure_matrix() {
+        let terms: Vec<_> = ["1", "2", "3", "4"]
+            .into_iter()
+            .map(|id| SimpleMinimalTerm::new(TermId::from(("HP", id)), id, vec![], false))
+            .collect();
+        let ontology = toy_ontology(terms, &[(1, 0), (2, 1), (3, 0)]);
+
+        let matrix = ontology.dense_closure_matrix();
+
+        assert_eq!(matrix.dim(), (4, 4));
+        assert_eq!(matrix.row(2).to_vec(), [true, true, true, false]);
+        assert_eq!(matrix.column(0).iter().filter(|&&x| x).count(), 4);
+        assert_eq!(matrix.iter().filter(|&&x| x).count(), 4 + 4);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::base::term::{MinimalTerm, SynonymAware};
+use crate::base::{Identified, TermId};
+use crate::hierarchy::{DescendantNodes, HierarchyIdx};
+
+use super::{Ontology, TermIdx};
+
+/// Split the `text` into the lowercase alphanumeric words, joined and surrounded by spaces,
+/// to find the phrases on the word boundaries with a substring search.
+fn normalize(text: &str) -> String {
+    let mut normalized = String::from(" ");
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        normalized.push_str(&word.to_lowercase());
+        normalized.push(' ');
+    }
+    normalized
+}
+
+pub(super) fn expand_query_terms<O>(ontology: &O, text: &str) -> Vec<TermId>
+where
+    O: Ontology + ?Sized,
+    O::T: SynonymAware,
+{
+    let text = normalize(text);
+    let hierarchy = ontology.hierarchy();
+
+    let mut selected = vec![false; ontology.len()];
+    for (idx, term) in ontology.iter_terms().enumerate() {
+        if selected[idx] || !term.is_current() {
+            continue;
+        }
+        let is_mentioned = std::iter::once(term.name())
+            .chain(term.iter_synonyms().map(|synonym| synonym.name()))
+            .map(normalize)
+            .any(|phrase| phrase.len() > 1 && text.contains(&phrase));
+        if is_mentioned {
+            selected[idx] = true;
+            for &descendant in hierarchy.descendants_of(<O::Idx as HierarchyIdx>::new(idx)) {
+                selected[TermIdx::index(descendant)] = true;
+            }
+        }
+    }
+
+    ontology
+        .iter_terms()
+        .zip(selected)
+        .filter(|(term, selected)| *selected && term.is_current())
+        .map(|(term, _)| term.identifier().clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleTerm;
+    use crate::base::term::{Synonym, SynonymCategory};
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_expand_query_terms() {
+        let term =
+            |id: &str, name: &str| SimpleTerm::new(TermId::from(("HP", id)), name, vec![], false);
+        let terms = vec![
+            term("1", "All"),
+            term("2", "Long fingers").with_synonyms(vec![Synonym::new(
+                "Elongated fingers",
+                SynonymCategory::Exact,
+                vec![],
+            )]),
+            term("3", "Arachnodactyly").with_synonyms(vec![Synonym::new(
+                "Spider fingers",
+                SynonymCategory::Exact,
+                vec![],
+            )]),
+            term("4", "Seizure"),
+            SimpleTerm::new(TermId::from(("HP", "5")), "Long", vec![], true),
+        ];
+        // `HP:3` is a child of `HP:2`.
+        let ontology = toy_ontology(terms, &[(1, 0), (2, 1), (3, 0)]);
+        let curies = |text: &str| -> Vec<String> {
+            ontology
+                .expand_query_terms(text)
+                .into_iter()
+                .map(|term_id| term_id.to_string())
+                .collect()
+        };
+
+        assert_eq!(curies("Patient with ELONGATED fingers."), ["HP:2", "HP:3"]);
+        assert_eq!(curies("spider fingers, seizure"), ["HP:3", "HP:4"]);
+        assert!(curies("long").is_empty());
+        assert!(curies("seizures").is_empty());
+    }
+}
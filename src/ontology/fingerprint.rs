@@ -0,0 +1,196 @@
+use crate::base::{term::MinimalTerm, Identified};
+use crate::hierarchy::{bfs_levels, ChildNodes, HierarchyIdx, OntologyHierarchy, ParentNodes};
+
+use super::Ontology;
+
+/// A summary of the ontology graph for detecting changes between ontology releases.
+///
+/// The fingerprint includes the counts of the terms, edges, roots, and leaves,
+/// the maximum depth of the hierarchy, and the hashes of the term IDs and of the edges.
+/// The values depend neither on the order of the terms in the input
+/// nor on the term indices, and the hashes are stable across platforms and runs.
+///
+/// Only the *current* terms contribute to the counts and hashes.
+/// The obsolete terms are not part of the hierarchy.
+///
+/// ## Examples
+///
+/// ```
+/// # use curie_util::TrieCurieUtil;
+/// # use ontolius::io::obographs::ObographsParser;
+/// # use ontolius::ontology::csr::CsrOntology;
+/// use ontolius::prelude::*;
+///
+/// # let loader = OntologyLoaderBuilder::new()
+/// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+/// #                .build();
+/// # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+/// #                                    .expect("HPO should be loaded");
+/// #
+/// let fingerprint = hpo.fingerprint();
+///
+/// assert_eq!(fingerprint.term_count(), 614);
+/// assert_eq!(fingerprint.edge_count(), 780);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fingerprint {
+    term_count: usize,
+    edge_count: usize,
+    root_count: usize,
+    leaf_count: usize,
+    max_depth: usize,
+    term_hash: u64,
+    edge_hash: u64,
+}
+
+impl Fingerprint {
+    /// Get the number of current terms.
+    pub fn term_count(&self) -> usize {
+        self.term_count
+    }
+
+    /// Get the number of `is_a` edges between the current terms.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Get the number of current terms with no parents.
+    pub fn root_count(&self) -> usize {
+        self.root_count
+    }
+
+    /// Get the number of current terms with no children.
+    pub fn leaf_count(&self) -> usize {
+        self.leaf_count
+    }
+
+    /// Get the length of the longest shortest path from the ontology root to a term.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Get the hash of the term IDs of the current terms.
+    pub fn term_hash(&self) -> u64 {
+        self.term_hash
+    }
+
+    /// Get the hash of the `is_a` edges between the current terms.
+    pub fn edge_hash(&self) -> u64 {
+        self.edge_hash
+    }
+}
+
+pub(super) fn compute_fingerprint<O>(ontology: &O) -> Fingerprint
+where
+    O: Ontology + ?Sized,
+{
+    let hierarchy = ontology.hierarchy();
+
+    let mut term_ids = Vec::new();
+    let mut edges = Vec::new();
+    let (mut root_count, mut leaf_count) = (0, 0);
+
+    for (idx, term) in ontology.iter_terms().enumerate() {
+        if term.is_obsolete() {
+            continue;
+        }
+        let idx = <O::Idx as HierarchyIdx>::new(idx);
+        let term_id = term.identifier().to_string();
+
+        let mut parent_count = 0;
+        for &parent in hierarchy.parents_of(idx) {
+            if let Some(parent_id) = ontology.idx_to_term_id(parent) {
+                edges.push(format!("{term_id} {parent_id}"));
+                parent_count += 1;
+            }
+        }
+        if parent_count == 0 {
+            root_count += 1;
+        }
+        if hierarchy.is_leaf(idx) {
+            leaf_count += 1;
+        }
+
+        term_ids.push(term_id);
+    }
+
+    let max_depth =
+        bfs_levels(*hierarchy.root(), None, |node| hierarchy.children_of(node)).len() - 1;
+
+    Fingerprint {
+        term_count: term_ids.len(),
+        edge_count: edges.len(),
+        root_count,
+        leaf_count,
+        max_depth,
+        term_hash: stable_hash(term_ids),
+        edge_hash: stable_hash(edges),
+    }
+}
+
+/// Compute FNV-1a hash of the sorted `values`.
+fn stable_hash(mut values: Vec<String>) -> u64 {
+    values.sort_unstable();
+    let mut hasher = StableHasher::new();
+    values.iter().for_each(|val| hasher.write_str(val));
+    hasher.finish()
+}
+
+/// FNV-1a hasher with the output stable across platforms and runs.
+///
+/// We cannot use [`std::hash::DefaultHasher`], because its output can change between Rust releases.
+pub(crate) struct StableHasher(u64);
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    pub(crate) fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Write the `value` followed by a separator,
+    /// to distinguish e.g. `["ab", "c"]` from `["a", "bc"]`.
+    pub(crate) fn write_str(&mut self, value: &str) {
+        self.write(value.as_bytes());
+        self.write(b"\n");
+    }
+
+    pub(crate) fn write_usize(&mut self, value: usize) {
+        self.write(&(value as u64).to_le_bytes());
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::stable_hash;
+
+    #[test]
+    fn test_stable_hash_ignores_order() {
+        let left = stable_hash(vec!["HP:1".into(), "HP:2".into()]);
+        let right = stable_hash(vec!["HP:2".into(), "HP:1".into()]);
+
+        assert_eq!(left, right);
+    }
+
+    #[test]
+    fn test_stable_hash_value() {
+        // The hash must not change between releases!
+        assert_eq!(stable_hash(vec![]), 0xcbf29ce484222325);
+        assert_eq!(stable_hash(vec!["HP:1".into()]), 0x6bf2175daaaca89a);
+        assert_ne!(
+            stable_hash(vec!["ab".into(), "c".into()]),
+            stable_hash(vec!["a".into(), "bc".into()])
+        );
+    }
+}
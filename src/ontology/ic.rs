@@ -0,0 +1,217 @@
+use crate::base::term::MinimalTerm;
+use crate::base::Identified;
+use crate::hierarchy::{DescendantNodes, HierarchyIdx, OntologyHierarchy};
+
+use super::{HierarchyAware, MetadataAware, Ontology, TermAware, TermIdx};
+
+/// An [`Ontology`] with the intrinsic information content (IC) of the terms
+/// computed when the wrapper is created.
+///
+/// The intrinsic IC of a term is `-ln(p)`, where `p` is the fraction of the hierarchy terms
+/// (the root and its descendants) that are the term or its descendants.
+/// Hence, the root has IC of `0` and the leaves have the highest IC.
+/// Unlike the IC computed from an annotation corpus, the intrinsic IC is available
+/// right after loading the ontology.
+///
+/// The wrapper implements [`Ontology`] by delegating to the base ontology.
+///
+/// ## Examples
+///
+/// ```
+/// # use curie_util::TrieCurieUtil;
+/// # use ontolius::io::obographs::ObographsParser;
+/// # use ontolius::ontology::csr::CsrOntology;
+/// use ontolius::prelude::*;
+///
+/// # let loader = OntologyLoaderBuilder::new()
+/// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+/// #                .build();
+/// # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+/// #                                    .expect("HPO should be loaded");
+/// #
+/// let hpo = hpo.with_intrinsic_ic();
+///
+/// let arachnodactyly = TermId::from(("HP", "0001166"));
+/// let abnormality_of_limbs = TermId::from(("HP", "0040064"));
+///
+/// assert_eq!(hpo.intrinsic_ic_of(hpo.root_term_id()), Some(0.));
+/// assert!(hpo.intrinsic_ic_of(&arachnodactyly) > hpo.intrinsic_ic_of(&abnormality_of_limbs));
+/// assert_eq!(hpo.specificity_of(&arachnodactyly), Some(1.));
+/// ```
+pub struct IcOntology<'b, O>
+where
+    O: Ontology,
+{
+    base: &'b O,
+    ics: Vec<Option<f64>>,
+    max_ic: f64,
+}
+
+impl<'b, O> IcOntology<'b, O>
+where
+    O: Ontology,
+{
+    /// Compute the intrinsic IC of the terms of the `base` ontology.
+    pub fn new(base: &'b O) -> Self {
+        let hierarchy = base.hierarchy();
+        let n_terms = (hierarchy.count_descendants(*hierarchy.root()) + 1) as f64;
+        let ics: Vec<_> = base
+            .iter_terms()
+            .enumerate()
+            .map(|(idx, term)| {
+                term.is_current().then(|| {
+                    let idx = <O::Idx as HierarchyIdx>::new(idx);
+                    let n_descendants = hierarchy.count_descendants(idx) as f64;
+                    // Clamp to avoid `-0.` for the root.
+                    (-((n_descendants + 1.) / n_terms).ln()).max(0.)
+                })
+            })
+            .collect();
+        let max_ic = ics.iter().flatten().copied().fold(0., f64::max);
+
+        Self { base, ics, max_ic }
+    }
+
+    /// Get the intrinsic IC of the term at `idx`.
+    ///
+    /// Returns `None` if the term is obsolete or not in the ontology.
+    pub fn intrinsic_ic(&self, idx: O::Idx) -> Option<f64> {
+        self.ics.get(TermIdx::index(idx)).copied().flatten()
+    }
+
+    /// Get the intrinsic IC of the term with the `term_id` (including an alternate ID).
+    ///
+    /// Returns `None` if the term is obsolete or not in the ontology.
+    pub fn intrinsic_ic_of<ID>(&self, term_id: &ID) -> Option<f64>
+    where
+        ID: Identified,
+    {
+        self.id_to_idx(term_id)
+            .and_then(|idx| self.intrinsic_ic(idx))
+    }
+
+    /// Get the highest intrinsic IC of a term in the ontology, i.e. the IC of a leaf.
+    pub fn max_ic(&self) -> f64 {
+        self.max_ic
+    }
+
+    /// Get the specificity of the term at `idx`, i.e. its intrinsic IC normalized to `[0, 1]`.
+    ///
+    /// Returns `None` if the term is obsolete or not in the ontology.
+    pub fn specificity(&self, idx: O::Idx) -> Option<f64> {
+        self.intrinsic_ic(idx).map(|ic| match self.max_ic {
+            0. => 0.,
+            max_ic => ic / max_ic,
+        })
+    }
+
+    /// Get the specificity of the term with the `term_id` (including an alternate ID).
+    ///
+    /// Returns `None` if the term is obsolete or not in the ontology.
+    pub fn specificity_of<ID>(&self, term_id: &ID) -> Option<f64>
+    where
+        ID: Identified,
+    {
+        self.id_to_idx(term_id)
+            .and_then(|idx| self.specificity(idx))
+    }
+
+    /// Get the wrapped ontology.
+    pub fn base(&self) -> &'b O {
+        self.base
+    }
+}
+
+impl<'b, O> TermAware for IcOntology<'b, O>
+where
+    O: Ontology,
+{
+    type TI = O::Idx;
+    type Term = O::T;
+    type TermIter<'a>
+        = O::TermIter<'a>
+    where
+        Self: 'a;
+
+    fn iter_terms(&self) -> Self::TermIter<'_> {
+        self.base.iter_terms()
+    }
+
+    fn idx_to_term(&self, idx: Self::TI) -> Option<&Self::Term> {
+        self.base.idx_to_term(idx)
+    }
+
+    fn id_to_idx<ID>(&self, id: &ID) -> Option<Self::TI>
+    where
+        ID: Identified,
+    {
+        self.base.id_to_idx(id)
+    }
+
+    fn len(&self) -> usize {
+        self.base.len()
+    }
+}
+
+impl<'b, O> HierarchyAware for IcOntology<'b, O>
+where
+    O: Ontology,
+{
+    type HI = O::Idx;
+    type Hierarchy = O::Hierarchy;
+
+    fn hierarchy(&self) -> &Self::Hierarchy {
+        self.base.hierarchy()
+    }
+}
+
+impl<'b, O> MetadataAware for IcOntology<'b, O>
+where
+    O: Ontology,
+{
+    fn version(&self) -> &str {
+        self.base.version()
+    }
+}
+
+impl<'b, O> Ontology for IcOntology<'b, O>
+where
+    O: Ontology,
+{
+    type Idx = O::Idx;
+    type T = O::T;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::base::TermId;
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_intrinsic_ic() {
+        let terms: Vec<_> = [("1", false), ("2", false), ("3", false), ("4", true)]
+            .into_iter()
+            .map(|(id, is_obsolete)| {
+                SimpleMinimalTerm::new(TermId::from(("HP", id)), id, vec![], is_obsolete)
+            })
+            .collect();
+        let ontology = toy_ontology(terms, &[(1, 0), (2, 1)]);
+
+        let ontology = ontology.with_intrinsic_ic();
+
+        assert_eq!(ontology.intrinsic_ic(0), Some(0.));
+        assert_eq!(ontology.intrinsic_ic(1), Some(-(2f64 / 3.).ln()));
+        assert_eq!(ontology.intrinsic_ic(2), Some(3f64.ln()));
+        assert_eq!(ontology.intrinsic_ic(3), None);
+        assert_eq!(ontology.intrinsic_ic(9), None);
+        assert_eq!(ontology.max_ic(), 3f64.ln());
+        assert_eq!(ontology.specificity(2), Some(1.));
+        assert_eq!(
+            ontology.specificity_of(&TermId::from(("HP", "1"))),
+            Some(0.)
+        );
+        assert_eq!(ontology.len(), 4);
+    }
+}
@@ -0,0 +1,113 @@
+//! Check the internal invariants of an ontology, e.g. to harden the loading
+//! against malformed input files with fuzzing.
+//!
+//! The checks are expensive, since they walk the transitive closure of the hierarchy.
+//! With the `paranoid` feature, [`CsrOntologyBuilder`](super::csr::CsrOntologyBuilder)
+//! checks every ontology it builds and panics if an invariant does not hold.
+use std::collections::HashSet;
+
+use crate::base::term::AltTermIdAware;
+use crate::base::Identified;
+use crate::error::OntoliusError;
+use crate::hierarchy::{
+    AncestorNodes, ChildNodes, DescendantNodes, HierarchyIdx, OntologyHierarchy, ParentNodes,
+};
+
+use super::{Ontology, TermIdx};
+
+/// Check the invariants of the `ontology`:
+///
+/// * all term indices, including the indices of the term IDs and the alternate IDs,
+///   point to a term,
+/// * the root has no parents,
+/// * a node is a parent of its children and a child of its parents,
+/// * a node is a descendant of its ancestors and an ancestor of its descendants.
+///
+/// Returns an error describing the first broken invariant.
+pub fn check_invariants<O>(ontology: &O) -> Result<(), OntoliusError>
+where
+    O: Ontology,
+{
+    let len = ontology.len();
+    let hierarchy = ontology.hierarchy();
+    let in_bounds = |idx: O::Idx| TermIdx::index(idx) < len;
+    let broken = |msg: String| Err(OntoliusError::OntologyAssemblyError(msg));
+
+    let root = *hierarchy.root();
+    if !in_bounds(root) {
+        return broken(format!("Root index {} out of bounds", TermIdx::index(root)));
+    }
+    if hierarchy.parents_of(root).next().is_some() {
+        return broken("Root has parents".to_string());
+    }
+
+    let mut up = HashSet::new();
+    let mut down = HashSet::new();
+    for i in 0..len {
+        let idx = <O::Idx as HierarchyIdx>::new(i);
+        let Some(term) = ontology.idx_to_term(idx) else {
+            return broken(format!("No term at index {i}"));
+        };
+        for term_id in std::iter::once(term.identifier()).chain(term.iter_alt_term_ids()) {
+            match ontology.id_to_idx(term_id) {
+                Some(found) if in_bounds(found) => {}
+                _ => return broken(format!("{term_id} does not point to a term")),
+            }
+        }
+
+        for &parent in hierarchy.parents_of(idx) {
+            if !in_bounds(parent) || !hierarchy.children_of(parent).any(|&child| child == idx) {
+                return broken(format!(
+                    "{} is not a child of its parent",
+                    term.identifier()
+                ));
+            }
+        }
+        for &child in hierarchy.children_of(idx) {
+            if !in_bounds(child) || !hierarchy.parents_of(child).any(|&parent| parent == idx) {
+                return broken(format!(
+                    "{} is not a parent of its child",
+                    term.identifier()
+                ));
+            }
+        }
+
+        up.extend(
+            hierarchy
+                .ancestors_of(idx)
+                .map(|&anc| (i, TermIdx::index(anc))),
+        );
+        down.extend(
+            hierarchy
+                .descendants_of(idx)
+                .map(|&desc| (TermIdx::index(desc), i)),
+        );
+    }
+    if let Some(&(desc, anc)) = up.symmetric_difference(&down).next() {
+        return broken(format!(
+            "Ancestors and descendants disagree on the terms at {desc} and {anc}"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::base::{term::simple::SimpleMinimalTerm, TermId};
+    use crate::test_util::toy_ontology;
+
+    use super::*;
+
+    #[test]
+    fn test_check_invariants() {
+        let terms: Vec<_> = (1..=4)
+            .map(|i| {
+                let id = i.to_string();
+                SimpleMinimalTerm::new(TermId::from(("HP", id.as_str())), "Term", vec![], false)
+            })
+            .collect();
+        let ontology = toy_ontology(terms, &[(1, 0), (2, 0), (3, 1), (3, 2)]);
+
+        assert!(check_invariants(&ontology).is_ok());
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use regex_syntax::hir::literal::{ExtractKind, Extractor};
+use regex_syntax::hir::Hir;
+
+use crate::base::term::{MinimalTerm, SynonymAware};
+use crate::error::OntoliusError;
+
+/// A regular expression for matching the term names and synonyms,
+/// along with its syntax tree for pre-filtering the terms.
+pub(crate) struct TermPattern {
+    regex: Regex,
+    hir: Hir,
+}
+
+impl TermPattern {
+    pub(crate) fn new(pattern: &str) -> Result<Self, OntoliusError> {
+        let invalid = |e: &dyn std::fmt::Display| {
+            OntoliusError::Other(format!("Invalid regex `{pattern}`: {e}"))
+        };
+        Ok(Self {
+            regex: Regex::new(pattern).map_err(|e| invalid(&e))?,
+            hir: regex_syntax::parse(pattern).map_err(|e| invalid(&e))?,
+        })
+    }
+
+    /// Test if the name or a synonym of the `term` matches the pattern.
+    pub(crate) fn is_match<T>(&self, term: &T) -> bool
+    where
+        T: MinimalTerm + SynonymAware,
+    {
+        texts(term).any(|text| self.regex.is_match(text))
+    }
+}
+
+/// Get the name and the synonyms of the `term`.
+fn texts<T>(term: &T) -> impl Iterator<Item = &str>
+where
+    T: MinimalTerm + SynonymAware,
+{
+    std::iter::once(term.name()).chain(term.iter_synonyms().map(|synonym| synonym.name()))
+}
+
+/// An index of the byte trigrams of the term names and synonyms.
+#[derive(Clone, Default)]
+pub(crate) struct TrigramIndex {
+    /// The sorted positions of the terms with the trigram.
+    postings: HashMap<[u8; 3], Vec<usize>>,
+}
+
+impl TrigramIndex {
+    pub(crate) fn new<'a, I, T>(terms: I) -> Self
+    where
+        I: IntoIterator<Item = &'a T>,
+        T: MinimalTerm + SynonymAware + 'a,
+    {
+        let mut postings: HashMap<[u8; 3], Vec<usize>> = HashMap::new();
+        for (i, term) in terms.into_iter().enumerate() {
+            for text in texts(term) {
+                for window in text.as_bytes().windows(3) {
+                    let positions = postings
+                        .entry([window[0], window[1], window[2]])
+                        .or_default();
+                    if positions.last() != Some(&i) {
+                        positions.push(i);
+                    }
+                }
+            }
+        }
+        Self { postings }
+    }
+
+    /// Get the sorted positions of the terms that may match the `pattern`.
+    ///
+    /// Each match starts with one of the prefix literals of the pattern,
+    /// hence a matching term has all trigrams of at least one literal.
+    /// Returns `None` if the pattern has no finite set of literals of 3 or more bytes,
+    /// and all terms must be checked.
+    pub(crate) fn candidates(&self, pattern: &TermPattern) -> Option<Vec<usize>> {
+        let literals = Extractor::new()
+            .kind(ExtractKind::Prefix)
+            .extract(&pattern.hir);
+        let literals = literals.literals()?;
+        if literals.iter().any(|literal| literal.as_bytes().len() < 3) {
+            return None;
+        }
+
+        let mut candidates = vec![];
+        for literal in literals {
+            let mut positions: Option<Vec<usize>> = None;
+            for window in literal.as_bytes().windows(3) {
+                let postings = self
+                    .postings
+                    .get(&[window[0], window[1], window[2]])
+                    .map_or(&[][..], Vec::as_slice);
+                positions = Some(match positions {
+                    None => postings.to_vec(),
+                    Some(positions) => positions
+                        .into_iter()
+                        .filter(|position| postings.binary_search(position).is_ok())
+                        .collect(),
+                });
+            }
+            candidates.extend(positions.unwrap_or_default());
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+        Some(candidates)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleTerm;
+    use crate::base::term::{Synonym, SynonymCategory};
+    use crate::base::TermId;
+
+    #[test]
+    fn test_candidates() {
+        let terms = vec![
+            SimpleTerm::new(TermId::from(("HP", "1")), "Carcinoma", vec![], false),
+            SimpleTerm::new(TermId::from(("HP", "2")), "Seizure", vec![], false).with_synonyms(
+                vec![Synonym::new(
+                    "Epileptic seizure",
+                    SynonymCategory::Exact,
+                    vec![],
+                )],
+            ),
+            SimpleTerm::new(TermId::from(("HP", "3")), "Renal carcinoma", vec![], false),
+        ];
+        let index = TrigramIndex::new(&terms);
+        let candidates = |pattern: &str| index.candidates(&TermPattern::new(pattern).unwrap());
+
+        assert_eq!(candidates("carcinoma"), Some(vec![2]));
+        assert_eq!(candidates("(?i)carcinoma"), Some(vec![0, 2]));
+        assert_eq!(candidates("^Epilep"), Some(vec![1]));
+        assert_eq!(candidates("(Seiz|Carc)"), Some(vec![0, 1]));
+        assert_eq!(candidates("xyz"), Some(vec![]));
+        assert_eq!(candidates("a.c"), None);
+        assert_eq!(candidates(".*"), None);
+        assert!(TermPattern::new("(").is_err());
+    }
+}
@@ -1,8 +1,44 @@
 //! A module with the ontology parts.
+mod attachments;
 pub mod csr;
+mod closure;
+mod components;
+#[cfg(feature = "ndarray")]
+mod dense;
+mod expand;
+pub(crate) mod fingerprint;
+mod ic;
+#[cfg(feature = "paranoid")]
+pub mod invariants;
+#[cfg(feature = "regex")]
+mod matching;
+pub mod overlay;
+mod outdated;
+mod path;
+mod query;
+mod relationships;
+mod releases;
+mod subgraph;
+mod tags;
+mod traversal;
+mod weights;
 
-use crate::base::{term::MinimalTerm, Identified, TermId};
-use crate::hierarchy::{HierarchyIdx, OntologyHierarchy};
+pub use fingerprint::Fingerprint;
+pub use ic::IcOntology;
+pub use outdated::{OutdatedId, OutdatedIdListener, OutdatedIdReport};
+pub use path::{PathStep, RelationPath};
+pub use query::Query;
+pub use relationships::Relationships;
+pub use releases::ReleaseSet;
+pub use subgraph::Subgraph;
+pub use traversal::{RelationshipTraversals, RelationshipType};
+pub use weights::EdgeWeights;
+
+use std::collections::HashSet;
+
+use crate::base::term::{MinimalTerm, SubsetAware, SynonymAware};
+use crate::base::{Identified, TermId};
+use crate::hierarchy::{DescendantNodes, HierarchyIdx, OntologyHierarchy, ParentNodes};
 
 /// The implementors can be used to index the [`super::TermAware`].
 pub trait TermIdx: Copy {
@@ -98,6 +134,11 @@ pub trait TermAware {
         self.iter_terms().count()
     }
 
+    /// Test if the container includes no terms.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Iterate over term IDs of the *current* terms.
     fn iter_term_ids(&self) -> TermIdIter<'_, Self::Term> {
         TermIdIter {
@@ -112,6 +153,63 @@ pub trait TermAware {
             terms: Box::new(self.iter_terms()),
         }
     }
+
+    /// Iterate over the terms with a synonym contributed by the curator with the `orcid` ID
+    /// (e.g. `ORCID:0000-0001-5208-3432`), e.g. for curation credit reports.
+    fn iter_terms_contributed_by<'a>(
+        &'a self,
+        orcid: &'a TermId,
+    ) -> impl Iterator<Item = &'a Self::Term>
+    where
+        Self::Term: SynonymAware,
+    {
+        self.iter_terms()
+            .filter(move |term| term.is_contributed_by(orcid))
+    }
+
+    /// Iterate over the terms with a name or a synonym matching the regular expression `pattern`,
+    /// e.g. for auditing all terms that mention `carcinoma`.
+    ///
+    /// The pattern follows the syntax of the [`regex`] crate,
+    /// and it is matched anywhere in the text unless anchored.
+    /// Returns an error if the pattern is invalid.
+    ///
+    /// [`csr::CsrOntology`] pre-filters the terms with an index of the trigrams
+    /// of the names and synonyms, built on the first call,
+    /// if the pattern requires literals of 3 or more characters.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use curie_util::TrieCurieUtil;
+    /// # use ontolius::base::term::simple::SimpleTerm;
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// # use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    ///
+    /// # let loader = OntologyLoaderBuilder::new()
+    /// #                .parser(ObographsParser::with_term_type(TrieCurieUtil::default()))
+    /// #                .build();
+    /// # let hpo: CsrOntology<usize, SimpleTerm> = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                                             .expect("HPO should be loaded");
+    /// #
+    /// let names: Vec<_> = hpo.iter_terms_matching("(?i)arachnodactyl")
+    ///                        .expect("The pattern should be valid")
+    ///                        .map(|term| term.name())
+    ///                        .collect();
+    /// assert_eq!(names, ["Arachnodactyly"]);
+    /// ```
+    #[cfg(feature = "regex")]
+    fn iter_terms_matching<'a>(
+        &'a self,
+        pattern: &str,
+    ) -> Result<impl Iterator<Item = &'a Self::Term>, crate::error::OntoliusError>
+    where
+        Self::Term: SynonymAware,
+    {
+        let pattern = matching::TermPattern::new(pattern)?;
+        Ok(self.iter_terms().filter(move |term| pattern.is_match(*term)))
+    }
 }
 
 /// Iterator over the *primary* term ids of [`TermAware`].
@@ -237,4 +335,284 @@ pub trait Ontology:
     {
         self.root_term().identifier()
     }
+
+    /// Compute the [`Fingerprint`] of the ontology graph,
+    /// e.g. to detect unexpected changes between ontology releases.
+    fn fingerprint(&self) -> Fingerprint {
+        fingerprint::compute_fingerprint(self)
+    }
+
+    /// Iterate over all `is_a` edges as pairs of the child and parent term IDs,
+    /// e.g. to export the hierarchy into a database or a data frame.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// # use curie_util::TrieCurieUtil;
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// # use ontolius::ontology::csr::CsrOntology;
+    /// use ontolius::prelude::*;
+    ///
+    /// # let loader = OntologyLoaderBuilder::new()
+    /// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+    /// #                .build();
+    /// # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                                    .expect("HPO should be loaded");
+    /// #
+    /// let arachnodactyly = TermId::from_str("HP:0001166").unwrap();
+    /// let parents: Vec<_> = hpo.iter_child_parent_pairs()
+    ///                          .filter(|&(child, _)| child == &arachnodactyly)
+    ///                          .map(|(_, parent)| parent.to_string())
+    ///                          .collect();
+    ///
+    /// assert_eq!(hpo.iter_child_parent_pairs().count(), 780);
+    /// assert_eq!(parents.len(), 2);
+    /// ```
+    fn iter_child_parent_pairs(&self) -> impl Iterator<Item = (&TermId, &TermId)> {
+        let hierarchy = self.hierarchy();
+        self.iter_terms()
+            .enumerate()
+            .flat_map(move |(idx, term)| {
+                let idx = <Self::Idx as HierarchyIdx>::new(idx);
+                hierarchy
+                    .parents_of(idx)
+                    .filter_map(move |&parent| self.idx_to_term_id(parent))
+                    .map(move |parent| (term.identifier(), parent))
+            })
+    }
+
+    /// Get the current terms with no parents and no children.
+    ///
+    /// The orphan terms are unreachable from the root and do not show up in the hierarchy traversals,
+    /// which usually indicates a malformed ontology.
+    fn orphan_terms(&self) -> Vec<&Self::T> {
+        components::find_orphan_terms(self)
+    }
+
+    /// Get the connected components of the ontology graph, ignoring the edge direction.
+    ///
+    /// Each component includes the sorted indices of the current terms, and the components are
+    /// ordered by their smallest index. A well-formed ontology has a single component,
+    /// and each orphan term (see [`Ontology::orphan_terms`]) forms a component of its own.
+    fn connected_components(&self) -> Vec<Vec<Self::Idx>> {
+        components::find_connected_components(self)
+    }
+
+    /// Iterate over the IDs of the ancestors of the `query` term in the breadth-first order,
+    /// without ascending above the `stop` term.
+    ///
+    /// The `stop` term (e.g. Phenotypic abnormality) is included if it is an ancestor
+    /// of the `query`, but its ancestors (e.g. the administrative upper-level terms) are not,
+    /// unless they are reachable by a path that avoids the `stop` term.
+    /// The `query` term itself is not included.
+    /// Returns no IDs if the `query` term is the `stop` term or if it is not in the ontology.
+    fn iter_ancestor_ids_until(&self, query: &TermId, stop: &TermId) -> impl Iterator<Item = &TermId> {
+        closure::find_ancestors_until(self, query, stop).into_iter()
+    }
+
+    /// Iterate over the IDs of the ancestors of the `query` term in the breadth-first order,
+    /// skipping the `excluded` terms and their descendants,
+    /// e.g. to get the ancestors except those under Clinical modifier.
+    ///
+    /// The skipped terms are not traversed, hence their ancestors are included only
+    /// if they are reachable by another path. The `query` term itself is not included,
+    /// and it can be a descendant of an excluded term, e.g. a term under both
+    /// Phenotypic abnormality and Clinical modifier.
+    /// Returns no IDs if the `query` term is not in the ontology.
+    fn iter_ancestor_ids_excluding(
+        &self,
+        query: &TermId,
+        excluded: &HashSet<TermId>,
+    ) -> impl Iterator<Item = &TermId> {
+        closure::find_ancestors_excluding(self, query, excluded).into_iter()
+    }
+
+    /// Iterate over the IDs of the descendants of the `query` term in the breadth-first order,
+    /// skipping the `excluded` terms and their descendants.
+    ///
+    /// The `query` term itself is not included.
+    /// Returns no IDs if the `query` term is excluded or if it is not in the ontology.
+    fn iter_descendant_ids_excluding(
+        &self,
+        query: &TermId,
+        excluded: &HashSet<TermId>,
+    ) -> impl Iterator<Item = &TermId> {
+        closure::find_descendants_excluding(self, query, excluded).into_iter()
+    }
+
+    /// Get the subgraph induced by the `terms`, e.g. to render a compact view
+    /// of the phenotypic profile of a patient.
+    ///
+    /// The subgraph links each of the terms to its closest ancestors among the terms.
+    /// If `include_lcas` is `true`, the lowest common ancestors of each pair of the terms
+    /// are added to the subgraph too, to show how the terms are related.
+    /// The term IDs not in the ontology are ignored.
+    fn induced_subgraph(&self, terms: &HashSet<TermId>, include_lcas: bool) -> Subgraph<'_> {
+        subgraph::induced_subgraph(self, terms, include_lcas)
+    }
+
+    /// Get the union of the paths from the `terms` to the root, e.g. to render the context
+    /// of the terms like the term view of the HPO browser.
+    ///
+    /// The subgraph includes the terms, all their ancestors, and all `is_a` edges among them.
+    /// The term IDs not in the ontology are ignored.
+    fn spanning_subgraph(&self, terms: &HashSet<TermId>) -> Subgraph<'_> {
+        subgraph::spanning_subgraph(self, terms)
+    }
+
+    /// Get a reproducible sample of about `n_terms` terms around the `seeds`,
+    /// e.g. to create a small ontology for tests.
+    ///
+    /// The sample includes the seeds and their ancestors, hence it can exceed `n_terms`,
+    /// and the descendants of the seeds, added breadth-first until the sample has `n_terms` terms.
+    /// The sample is connected, and the subgraph includes all `is_a` edges among the sampled terms.
+    /// The seed IDs not in the ontology are ignored.
+    ///
+    /// Use [`crate::io::obographs::write_subgraph`] to write the sample as an Obographs document.
+    fn sample_subgraph(&self, seeds: &[TermId], n_terms: usize) -> Subgraph<'_> {
+        subgraph::sample_subgraph(self, seeds, n_terms)
+    }
+
+    /// Get the dense matrix of the reflexive `is_a` closure, e.g. for vectorized algorithms
+    /// or teaching examples with small ontologies.
+    ///
+    /// The entry at `[i, j]` is `true` if the term at index `j` is the term at index `i`
+    /// or one of its ancestors. The matrix takes `len()²` bytes, hence it is not suitable
+    /// for large ontologies.
+    #[cfg(feature = "ndarray")]
+    fn dense_closure_matrix(&self) -> ndarray::Array2<bool> {
+        dense::make_closure_matrix(self)
+    }
+
+    /// Get the terms mentioned in the free `text` by their names or synonyms,
+    /// along with all their descendants, e.g. to expand a search query
+    /// for ontology-aware document retrieval.
+    ///
+    /// A term is mentioned if its name or a synonym appears in the text as a whole phrase,
+    /// ignoring the case and the punctuation. The term IDs are in the order of the term indices,
+    /// and the obsolete terms are not included.
+    fn expand_query_terms(&self, text: &str) -> Vec<TermId>
+    where
+        Self::T: SynonymAware,
+    {
+        expand::expand_query_terms(self, text)
+    }
+
+    /// Iterate over the terms in the `subset` (slim), such as `goslim_generic`,
+    /// in the order of [`TermAware::iter_terms`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use curie_util::TrieCurieUtil;
+    /// # use ontolius::io::obographs::ObographsParser;
+    /// use ontolius::prelude::*;
+    ///
+    /// # let loader = OntologyLoaderBuilder::new()
+    /// #                .parser(ObographsParser::with_term_type(TrieCurieUtil::default()))
+    /// #                .build();
+    /// # let hpo: FullCsrOntology = loader.load_from_path("resources/hp.small.json.gz")
+    /// #                              .expect("HPO should be loaded");
+    /// #
+    /// let core = hpo.iter_terms_in_subset("hposlim_core").count();
+    /// assert_eq!(core, 123);
+    /// assert_eq!(hpo.iter_terms_in_subset("goslim_generic").count(), 0);
+    /// ```
+    fn iter_terms_in_subset<'a>(&'a self, subset: &'a str) -> impl Iterator<Item = &'a Self::T>
+    where
+        Self::T: SubsetAware,
+    {
+        self.iter_terms().filter(move |term| term.in_subset(subset))
+    }
+
+    /// Wrap the ontology into [`IcOntology`] with the intrinsic information content
+    /// of the terms, computed from the descendant counts.
+    fn with_intrinsic_ic(&self) -> IcOntology<'_, Self>
+    where
+        Self: Sized,
+    {
+        IcOntology::new(self)
+    }
+
+    /// Start a builder-style [`Query`] to select the ontology terms.
+    fn query(&self) -> Query<'_, Self>
+    where
+        Self: Sized,
+    {
+        Query::new(self)
+    }
+}
+
+/// The implementors know about the [`Relationships`] other than `is_a`.
+pub trait RelationshipAware: Ontology {
+    /// Get the relationships.
+    fn relationships(&self) -> &Relationships;
+
+    /// Get the primary IDs of the terms related by the `pred`icate to the `obj`ect term,
+    /// in the order of the term indices.
+    ///
+    /// The relationships are propagated along the `is_a` hierarchy: the terms related
+    /// to a descendant of the `obj`ect are related to the `obj`ect as well,
+    /// and the descendants of the related terms inherit the relationship.
+    /// Returns no terms if the `obj`ect is not in the ontology.
+    fn terms_related_to(&self, pred: &TermId, obj: &TermId) -> Vec<&TermId> {
+        let Some(obj) = self.id_to_idx(obj) else {
+            return vec![];
+        };
+        let hierarchy = self.hierarchy();
+        let mut related = vec![false; self.len()];
+        let targets = std::iter::once(obj).chain(hierarchy.descendants_of(obj).copied());
+        for target in targets.flat_map(|target| self.idx_to_term_id(target)) {
+            for sub in self.relationships().subjects_of(target, pred) {
+                if let Some(sub) = self.id_to_idx(sub) {
+                    for node in std::iter::once(sub).chain(hierarchy.descendants_of(sub).copied()) {
+                        related[TermIdx::index(node)] = true;
+                    }
+                }
+            }
+        }
+
+        related
+            .iter()
+            .enumerate()
+            .filter(|(_, &is_related)| is_related)
+            .flat_map(|(idx, _)| self.idx_to_term_id(<Self::Idx as HierarchyIdx>::new(idx)))
+            .collect()
+    }
+
+    /// Iterate over the chemicals with the `role` or with any of its subroles,
+    /// e.g. in CHEBI, where the roles are linked by `has_role` (`RO:0000087`).
+    ///
+    /// See [`RelationshipAware::terms_related_to`] for the propagation of the roles.
+    fn iter_chemicals_with_role(&self, role: &TermId) -> impl Iterator<Item = &TermId> {
+        self.terms_related_to(&TermId::from(("RO", "0000087")), role)
+            .into_iter()
+    }
+
+    /// Get the ancestors of the term with the `term_id` along the `is_a` edges
+    /// and the relationships with the `predicates`, in the breadth-first order.
+    ///
+    /// The traversal follows any chain of the edges, assuming the `predicates` are transitive
+    /// and compose with `is_a`: for instance, a part of a subclass of an entity
+    /// is a part of the entity. The term itself is not included.
+    /// Returns no terms if the term is not in the ontology.
+    fn ancestors_over(&self, term_id: &TermId, predicates: &[TermId]) -> Vec<&TermId> {
+        let via: Vec<_> = std::iter::once(RelationshipType::IsA)
+            .chain(predicates.iter().cloned().map(RelationshipType::Predicate))
+            .collect();
+        traversal::traverse(self, term_id, &via, traversal::Direction::Up)
+    }
+
+    /// Iterate over the ancestors of the term with the `term_id` along `is_a` and `part_of`
+    /// (`BFO:0000050`), e.g. in UBERON or in the cellular component branch of GO.
+    ///
+    /// Unlike the subsumption traversal (see [`crate::hierarchy::AncestorNodes`]),
+    /// the ancestors include the entities the term is a part of,
+    /// and the entities their superclasses are a part of.
+    /// See [`RelationshipAware::ancestors_over`] for more info.
+    fn iter_partonomy_ancestors(&self, term_id: &TermId) -> impl Iterator<Item = &TermId> {
+        self.ancestors_over(term_id, &[TermId::from(("BFO", "0000050"))])
+            .into_iter()
+    }
 }
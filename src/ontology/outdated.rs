@@ -0,0 +1,80 @@
+use std::sync::Mutex;
+
+use crate::base::TermId;
+
+/// An outdated term ID resolved by a term lookup, such as [`super::csr::CsrOntology::resolve`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OutdatedId {
+    /// The ID is an alternate ID of the term with the `primary` ID, e.g. after a merge.
+    Alternate { queried: TermId, primary: TermId },
+    /// The ID is the primary ID of an obsolete term.
+    Obsolete { term_id: TermId },
+}
+
+impl OutdatedId {
+    /// Get the term ID used in the lookup.
+    pub fn queried(&self) -> &TermId {
+        match self {
+            OutdatedId::Alternate { queried, .. } => queried,
+            OutdatedId::Obsolete { term_id } => term_id,
+        }
+    }
+}
+
+/// The receiver of the [`OutdatedId`]s resolved by an ontology,
+/// e.g. to tell the users that they used an outdated term ID.
+///
+/// The listener is implemented for the closures and for [`OutdatedIdReport`].
+pub trait OutdatedIdListener: Send + Sync {
+    /// Receive the outdated ID.
+    fn on_outdated_id(&self, outdated: &OutdatedId);
+}
+
+impl<F> OutdatedIdListener for F
+where
+    F: Fn(&OutdatedId) + Send + Sync,
+{
+    fn on_outdated_id(&self, outdated: &OutdatedId) {
+        self(outdated)
+    }
+}
+
+/// An [`OutdatedIdListener`] that accumulates the outdated IDs until they are taken.
+#[derive(Debug, Default)]
+pub struct OutdatedIdReport {
+    outdated: Mutex<Vec<OutdatedId>>,
+}
+
+impl OutdatedIdReport {
+    /// Take the outdated IDs received so far, in the order of the lookups, and clear the report.
+    pub fn take(&self) -> Vec<OutdatedId> {
+        std::mem::take(
+            &mut *self
+                .outdated
+                .lock()
+                .expect("Report lock should not be poisoned"),
+        )
+    }
+
+    /// Get the number of the outdated IDs received so far.
+    pub fn len(&self) -> usize {
+        self.outdated
+            .lock()
+            .expect("Report lock should not be poisoned")
+            .len()
+    }
+
+    /// Test if no outdated IDs were received.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl OutdatedIdListener for OutdatedIdReport {
+    fn on_outdated_id(&self, outdated: &OutdatedId) {
+        self.outdated
+            .lock()
+            .expect("Report lock should not be poisoned")
+            .push(outdated.clone());
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::hierarchy::{
+    AncestorNodes, ChildNodes, DescendantNodes, OntologyHierarchy, ParentNodes,
+};
+
+/// A hierarchy with local edits layered over an immutable base hierarchy.
+///
+/// The neighbors of a node are copied from the base on the first edit of the node.
+/// The unedited nodes are answered by the base hierarchy.
+pub struct OverlayHierarchy<'b, H>
+where
+    H: OntologyHierarchy,
+{
+    base: &'b H,
+    root: H::HI,
+    parents: BTreeMap<H::HI, Vec<H::HI>>,
+    children: BTreeMap<H::HI, Vec<H::HI>>,
+    /// The nodes of a subhierarchy, or `None` if all nodes are included.
+    scope: Option<BTreeSet<H::HI>>,
+}
+
+impl<'b, H> OverlayHierarchy<'b, H>
+where
+    H: OntologyHierarchy,
+{
+    pub(super) fn new(base: &'b H) -> Self {
+        Self {
+            base,
+            root: *base.root(),
+            parents: BTreeMap::new(),
+            children: BTreeMap::new(),
+            scope: None,
+        }
+    }
+
+    /// Test if the `node` is out of the scope of the subhierarchy.
+    fn is_out_of_scope(&self, node: H::HI) -> bool {
+        self.scope
+            .as_ref()
+            .is_some_and(|scope| !scope.contains(&node))
+    }
+
+    /// Register a node that is not present in the base hierarchy.
+    pub(super) fn add_node(&mut self, node: H::HI) {
+        self.parents.insert(node, vec![]);
+        self.children.insert(node, vec![]);
+    }
+
+    pub(super) fn add_edge(&mut self, child: H::HI, parent: H::HI) {
+        let parents = self.parents_mut(child);
+        if !parents.contains(&parent) {
+            parents.push(parent);
+            self.children_mut(parent).push(child);
+        }
+    }
+
+    pub(super) fn remove_edge(&mut self, child: H::HI, parent: H::HI) -> bool {
+        let parents = self.parents_mut(child);
+        let len = parents.len();
+        parents.retain(|&p| p != parent);
+        if parents.len() == len {
+            return false;
+        }
+        self.children_mut(parent).retain(|&c| c != child);
+        true
+    }
+
+    /// Remove all edges of the `node`.
+    pub(super) fn detach(&mut self, node: H::HI) {
+        let parents: Vec<_> = self.parents_of(node).copied().collect();
+        for parent in parents {
+            self.remove_edge(node, parent);
+        }
+        let children: Vec<_> = self.children_of(node).copied().collect();
+        for child in children {
+            self.remove_edge(child, node);
+        }
+    }
+
+    fn parents_mut(&mut self, node: H::HI) -> &mut Vec<H::HI> {
+        let base = self.base;
+        self.parents
+            .entry(node)
+            .or_insert_with(|| base.parents_of(node).copied().collect())
+    }
+
+    fn children_mut(&mut self, node: H::HI) -> &mut Vec<H::HI> {
+        let base = self.base;
+        self.children
+            .entry(node)
+            .or_insert_with(|| base.children_of(node).copied().collect())
+    }
+}
+
+/// An iterator over the neighbors of a node in the base or in the overlay.
+pub enum NeighborIter<'a, I, B> {
+    Base(B),
+    Overlay(std::slice::Iter<'a, I>),
+}
+
+impl<'a, I, B> Iterator for NeighborIter<'a, I, B>
+where
+    B: Iterator<Item = &'a I>,
+{
+    type Item = &'a I;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            NeighborIter::Base(iter) => iter.next(),
+            NeighborIter::Overlay(iter) => iter.next(),
+        }
+    }
+}
+
+impl<'b, H> ChildNodes for OverlayHierarchy<'b, H>
+where
+    H: OntologyHierarchy,
+{
+    type I = H::HI;
+    type ChildIter<'a>
+        = NeighborIter<'a, H::HI, <H as ChildNodes>::ChildIter<'a>>
+    where
+        Self: 'a;
+
+    fn children_of(&self, node: H::HI) -> Self::ChildIter<'_> {
+        match self.children.get(&node) {
+            Some(children) => NeighborIter::Overlay(children.iter()),
+            None if self.is_out_of_scope(node) => NeighborIter::Overlay([].iter()),
+            None => NeighborIter::Base(self.base.children_of(node)),
+        }
+    }
+}
+
+impl<'b, H> ParentNodes for OverlayHierarchy<'b, H>
+where
+    H: OntologyHierarchy,
+{
+    type I = H::HI;
+    type ParentIter<'a>
+        = NeighborIter<'a, H::HI, <H as ParentNodes>::ParentIter<'a>>
+    where
+        Self: 'a;
+
+    fn parents_of(&self, node: H::HI) -> Self::ParentIter<'_> {
+        match self.parents.get(&node) {
+            Some(parents) => NeighborIter::Overlay(parents.iter()),
+            None if self.is_out_of_scope(node) => NeighborIter::Overlay([].iter()),
+            None => NeighborIter::Base(self.base.parents_of(node)),
+        }
+    }
+}
+
+impl<'b, H> DescendantNodes for OverlayHierarchy<'b, H>
+where
+    H: OntologyHierarchy,
+{
+    type I = H::HI;
+    type DescendantIter<'a>
+        = TraversalIter<'a, 'b, H>
+    where
+        Self: 'a;
+
+    fn descendants_of(&self, node: H::HI) -> Self::DescendantIter<'_> {
+        TraversalIter {
+            hierarchy: self,
+            upward: false,
+            seen: BTreeSet::new(),
+            queue: self.children_of(node).collect(),
+        }
+    }
+}
+
+impl<'b, H> AncestorNodes for OverlayHierarchy<'b, H>
+where
+    H: OntologyHierarchy,
+{
+    type I = H::HI;
+    type AncestorIter<'a>
+        = TraversalIter<'a, 'b, H>
+    where
+        Self: 'a;
+
+    fn ancestors_of(&self, node: H::HI) -> Self::AncestorIter<'_> {
+        TraversalIter {
+            hierarchy: self,
+            upward: true,
+            seen: BTreeSet::new(),
+            queue: self.parents_of(node).collect(),
+        }
+    }
+}
+
+/// Breadth-first traversal of the ancestors or descendants in [`OverlayHierarchy`].
+pub struct TraversalIter<'a, 'b, H>
+where
+    H: OntologyHierarchy,
+{
+    hierarchy: &'a OverlayHierarchy<'b, H>,
+    upward: bool,
+    seen: BTreeSet<H::HI>,
+    queue: VecDeque<&'a H::HI>,
+}
+
+impl<'a, 'b, H> Iterator for TraversalIter<'a, 'b, H>
+where
+    H: OntologyHierarchy,
+{
+    type Item = &'a H::HI;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(i) = self.queue.pop_front() {
+            if self.seen.insert(*i) {
+                if self.upward {
+                    self.queue.extend(self.hierarchy.parents_of(*i));
+                } else {
+                    self.queue.extend(self.hierarchy.children_of(*i));
+                }
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
+impl<'b, H> OntologyHierarchy for OverlayHierarchy<'b, H>
+where
+    H: OntologyHierarchy,
+{
+    type HI = H::HI;
+
+    fn root(&self) -> &H::HI {
+        &self.root
+    }
+
+    /// Get the hierarchy of the `subroot_idx` and its descendants, including the local edits.
+    ///
+    /// The nodes keep their indices, and the nodes out of the subhierarchy have no neighbors.
+    fn subhierarchy(&self, subroot_idx: H::HI) -> Self {
+        let nodes: BTreeSet<_> = std::iter::once(subroot_idx)
+            .chain(self.descendants_of(subroot_idx).copied())
+            .collect();
+        let mut parents = BTreeMap::new();
+        let mut children = BTreeMap::new();
+        for &node in &nodes {
+            let node_parents = if node == subroot_idx {
+                vec![]
+            } else {
+                self.parents_of(node)
+                    .copied()
+                    .filter(|parent| nodes.contains(parent))
+                    .collect()
+            };
+            parents.insert(node, node_parents);
+            children.insert(node, self.children_of(node).copied().collect());
+        }
+
+        Self {
+            base: self.base,
+            root: subroot_idx,
+            parents,
+            children,
+            scope: Some(nodes),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Module with `Ontology` that layers local edits over an immutable base ontology.
+//!
+//! The overlay is handy for prototyping curation changes,
+//! such as adding new terms or moving terms in the hierarchy,
+//! without rebuilding the base ontology.
+//!
+//! Check the [`OverlayOntology`] documentation for an example.
+mod hierarchy;
+mod ontology;
+
+pub use hierarchy::{NeighborIter, OverlayHierarchy, TraversalIter};
+pub use ontology::{OverlayOntology, OverlayTermIter};
@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+
+use crate::base::term::{AltTermIdAware, MinimalTerm};
+use crate::base::{Identified, TermId};
+use crate::error::OntoliusError;
+use crate::hierarchy::HierarchyIdx;
+use crate::ontology::{HierarchyAware, MetadataAware, Ontology, TermAware, TermIdx};
+
+use super::hierarchy::OverlayHierarchy;
+
+/// An [`Ontology`] with in-memory edits layered over an immutable base ontology.
+///
+/// The overlay supports adding terms and `is_a` edges, removing edges,
+/// and retiring terms. The base ontology is never modified
+/// and the edits are copy-on-write, hence creating an overlay is cheap
+/// even for large ontologies.
+///
+/// The added terms get the indices following the base terms.
+///
+/// ## Examples
+///
+/// ```
+/// # use curie_util::TrieCurieUtil;
+/// use ontolius::base::term::simple::SimpleMinimalTerm;
+/// # use ontolius::io::obographs::ObographsParser;
+/// # use ontolius::ontology::csr::CsrOntology;
+/// use ontolius::ontology::overlay::OverlayOntology;
+/// use ontolius::prelude::*;
+///
+/// # let loader = OntologyLoaderBuilder::new()
+/// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+/// #                .build();
+/// # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+/// #                                    .expect("HPO should be loaded");
+/// #
+/// let arachnodactyly = TermId::from(("HP", "0001166"));
+/// let proposed = SimpleMinimalTerm::new(
+///     TermId::from(("HP", "9999999")), "Proposed arachnodactyly subtype", vec![], false,
+/// );
+///
+/// let mut overlay = OverlayOntology::new(&hpo);
+/// let idx = overlay.add_term(proposed, &[arachnodactyly.clone()])
+///                  .expect("The term should be added");
+///
+/// let parent_idx = overlay.id_to_idx(&arachnodactyly).expect("Arachnodactyly should be present");
+/// assert!(overlay.hierarchy().is_parent_of(parent_idx, idx));
+/// assert_eq!(overlay.len(), hpo.len() + 1);
+/// ```
+pub struct OverlayOntology<'b, O>
+where
+    O: Ontology,
+{
+    base: &'b O,
+    added_terms: Vec<O::T>,
+    replaced_terms: HashMap<usize, O::T>,
+    added_ids: HashMap<TermId, O::Idx>,
+    hierarchy: OverlayHierarchy<'b, O::Hierarchy>,
+}
+
+impl<'b, O> OverlayOntology<'b, O>
+where
+    O: Ontology,
+{
+    /// Create an overlay with no edits over the `base` ontology.
+    pub fn new(base: &'b O) -> Self {
+        Self {
+            base,
+            added_terms: vec![],
+            replaced_terms: HashMap::new(),
+            added_ids: HashMap::new(),
+            hierarchy: OverlayHierarchy::new(base.hierarchy()),
+        }
+    }
+
+    /// Add a new `term` as a child of the terms with the `parents` IDs.
+    ///
+    /// Returns the index of the added term or an error if the term ID (or one of its alternative IDs)
+    /// is already used or if a parent is not in the ontology.
+    pub fn add_term(&mut self, term: O::T, parents: &[TermId]) -> Result<O::Idx, OntoliusError> {
+        for id in std::iter::once(term.identifier()).chain(term.iter_alt_term_ids()) {
+            if self.id_to_idx(id).is_some() {
+                return Err(OntoliusError::Other(format!(
+                    "Term ID {id} is already used"
+                )));
+            }
+        }
+        let parents = parents
+            .iter()
+            .map(|parent| self.resolve(parent))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let idx = <O::Idx as HierarchyIdx>::new(self.len());
+        for id in std::iter::once(term.identifier()).chain(term.iter_alt_term_ids()) {
+            self.added_ids.insert(id.clone(), idx);
+        }
+        self.added_terms.push(term);
+
+        self.hierarchy.add_node(idx);
+        for parent in parents {
+            self.hierarchy.add_edge(idx, parent);
+        }
+
+        Ok(idx)
+    }
+
+    /// Add an `is_a` edge between the `child` and the `parent`.
+    pub fn add_edge(&mut self, child: &TermId, parent: &TermId) -> Result<(), OntoliusError> {
+        let child = self.resolve(child)?;
+        let parent = self.resolve(parent)?;
+        self.hierarchy.add_edge(child, parent);
+        Ok(())
+    }
+
+    /// Remove the `is_a` edge between the `child` and the `parent`.
+    ///
+    /// Returns `true` if the edge was present.
+    pub fn remove_edge(&mut self, child: &TermId, parent: &TermId) -> Result<bool, OntoliusError> {
+        let child = self.resolve(child)?;
+        let parent = self.resolve(parent)?;
+        Ok(self.hierarchy.remove_edge(child, parent))
+    }
+
+    /// Replace a term with its `retired` (obsolete) version and remove all its edges.
+    ///
+    /// The children of the retired term are *not* re-attached to another term.
+    ///
+    /// Returns an error if the term is not in the ontology or if `retired` is not obsolete.
+    pub fn retire(&mut self, retired: O::T) -> Result<(), OntoliusError> {
+        if retired.is_current() {
+            return Err(OntoliusError::Other(format!(
+                "Term {} must be obsolete to be retired",
+                retired.identifier()
+            )));
+        }
+        let idx = self.resolve(retired.identifier())?;
+
+        self.hierarchy.detach(idx);
+        let index = TermIdx::index(idx);
+        match index.checked_sub(self.base.len()) {
+            Some(added) => self.added_terms[added] = retired,
+            None => {
+                self.replaced_terms.insert(index, retired);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn resolve(&self, id: &TermId) -> Result<O::Idx, OntoliusError> {
+        self.id_to_idx(id)
+            .ok_or_else(|| OntoliusError::Other(format!("Term {id} is not in the ontology")))
+    }
+}
+
+/// An iterator over the terms of [`OverlayOntology`].
+pub struct OverlayTermIter<'a, O>
+where
+    O: Ontology + 'a,
+{
+    base: O::TermIter<'a>,
+    idx: usize,
+    replaced: &'a HashMap<usize, O::T>,
+    added: std::slice::Iter<'a, O::T>,
+}
+
+impl<'a, O> Iterator for OverlayTermIter<'a, O>
+where
+    O: Ontology + 'a,
+{
+    type Item = &'a O::T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.base.next() {
+            Some(term) => {
+                let idx = self.idx;
+                self.idx += 1;
+                Some(self.replaced.get(&idx).unwrap_or(term))
+            }
+            None => self.added.next(),
+        }
+    }
+}
+
+impl<'b, O> TermAware for OverlayOntology<'b, O>
+where
+    O: Ontology,
+{
+    type TI = O::Idx;
+    type Term = O::T;
+    type TermIter<'a>
+        = OverlayTermIter<'a, O>
+    where
+        Self: 'a;
+
+    fn iter_terms(&self) -> Self::TermIter<'_> {
+        OverlayTermIter {
+            base: self.base.iter_terms(),
+            idx: 0,
+            replaced: &self.replaced_terms,
+            added: self.added_terms.iter(),
+        }
+    }
+
+    fn idx_to_term(&self, idx: Self::TI) -> Option<&Self::Term> {
+        let index = TermIdx::index(idx);
+        match index.checked_sub(self.base.len()) {
+            Some(added) => self.added_terms.get(added),
+            None => self
+                .replaced_terms
+                .get(&index)
+                .or_else(|| self.base.idx_to_term(idx)),
+        }
+    }
+
+    fn id_to_idx<ID>(&self, id: &ID) -> Option<Self::TI>
+    where
+        ID: Identified,
+    {
+        self.added_ids
+            .get(id.identifier())
+            .copied()
+            .or_else(|| self.base.id_to_idx(id))
+    }
+
+    fn len(&self) -> usize {
+        self.base.len() + self.added_terms.len()
+    }
+}
+
+impl<'b, O> HierarchyAware for OverlayOntology<'b, O>
+where
+    O: Ontology,
+{
+    type HI = O::Idx;
+    type Hierarchy = OverlayHierarchy<'b, O::Hierarchy>;
+
+    fn hierarchy(&self) -> &Self::Hierarchy {
+        &self.hierarchy
+    }
+}
+
+impl<'b, O> MetadataAware for OverlayOntology<'b, O>
+where
+    O: Ontology,
+{
+    fn version(&self) -> &str {
+        self.base.version()
+    }
+}
+
+impl<'b, O> Ontology for OverlayOntology<'b, O>
+where
+    O: Ontology,
+{
+    type Idx = O::Idx;
+    type T = O::T;
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use curie_util::TrieCurieUtil;
+
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::hierarchy::{
+        AncestorNodes, ChildNodes, DescendantNodes, OntologyHierarchy, ParentNodes,
+    };
+    use crate::io::{obographs::ObographsParser, OntologyLoaderBuilder};
+    use crate::ontology::csr::CsrOntology;
+
+    fn load_hpo() -> CsrOntology<usize, SimpleMinimalTerm> {
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .build();
+        loader
+            .load_from_path("resources/hp.small.json.gz")
+            .expect("HPO should be loaded")
+    }
+
+    fn term_id(curie: &str) -> TermId {
+        TermId::from_str(curie).unwrap()
+    }
+
+    #[test]
+    fn test_add_term() {
+        let hpo = load_hpo();
+        let mut overlay = OverlayOntology::new(&hpo);
+        let arachnodactyly = term_id("HP:0001166");
+        let proposed = SimpleMinimalTerm::new(term_id("HP:9999999"), "Proposed", vec![], false);
+
+        let idx = overlay
+            .add_term(proposed, std::slice::from_ref(&arachnodactyly))
+            .unwrap();
+
+        let parent = overlay.id_to_idx(&arachnodactyly).unwrap();
+        let hierarchy = overlay.hierarchy();
+        assert_eq!(hierarchy.parents_of(idx).collect::<Vec<_>>(), [&parent]);
+        assert!(hierarchy.children_of(parent).any(|&child| child == idx));
+        assert!(hierarchy
+            .descendants_of(*hierarchy.root())
+            .any(|&d| d == idx));
+        assert!(hierarchy.ancestors_of(idx).any(|&a| a == *hierarchy.root()));
+        assert_eq!(overlay.iter_terms().count(), hpo.len() + 1);
+        assert_eq!(overlay.idx_to_term(idx).unwrap().name(), "Proposed");
+
+        // The base ontology is not modified.
+        assert!(hpo.id_to_idx(&term_id("HP:9999999")).is_none());
+        assert_eq!(hpo.hierarchy().children_of(parent).count(), 0);
+    }
+
+    #[test]
+    fn test_subhierarchy() {
+        let hpo = load_hpo();
+        let mut overlay = OverlayOntology::new(&hpo);
+        let arachnodactyly = term_id("HP:0001166");
+        let proposed = SimpleMinimalTerm::new(term_id("HP:9999999"), "Proposed", vec![], false);
+        let idx = overlay
+            .add_term(proposed, std::slice::from_ref(&arachnodactyly))
+            .unwrap();
+        let subroot = overlay.id_to_idx(&arachnodactyly).unwrap();
+
+        let subhierarchy = overlay.hierarchy().subhierarchy(subroot);
+
+        assert_eq!(subhierarchy.root(), &subroot);
+        assert_eq!(subhierarchy.parents_of(subroot).count(), 0);
+        assert_eq!(
+            subhierarchy.descendants_of(subroot).collect::<Vec<_>>(),
+            [&idx]
+        );
+        assert_eq!(
+            subhierarchy.ancestors_of(idx).collect::<Vec<_>>(),
+            [&subroot]
+        );
+        let root = *overlay.hierarchy().root();
+        assert_eq!(subhierarchy.children_of(root).count(), 0);
+    }
+
+    #[test]
+    fn test_add_term_with_used_id_fails() {
+        let hpo = load_hpo();
+        let mut overlay = OverlayOntology::new(&hpo);
+        let duplicate = SimpleMinimalTerm::new(term_id("HP:0001166"), "Duplicate", vec![], false);
+
+        assert!(overlay.add_term(duplicate, &[]).is_err());
+    }
+
+    #[test]
+    fn test_remove_edge() {
+        let hpo = load_hpo();
+        let mut overlay = OverlayOntology::new(&hpo);
+        let arachnodactyly = term_id("HP:0001166");
+        let child = overlay.id_to_idx(&arachnodactyly).unwrap();
+        let parents: Vec<_> = overlay.hierarchy().parents_of(child).copied().collect();
+        let parent_id = overlay.idx_to_term_id(parents[0]).unwrap().clone();
+
+        assert!(overlay.remove_edge(&arachnodactyly, &parent_id).unwrap());
+        assert!(!overlay.remove_edge(&arachnodactyly, &parent_id).unwrap());
+
+        let hierarchy = overlay.hierarchy();
+        assert_eq!(hierarchy.parents_of(child).count(), parents.len() - 1);
+        assert!(!hierarchy.children_of(parents[0]).any(|&c| c == child));
+        assert_eq!(hpo.hierarchy().parents_of(child).count(), parents.len());
+    }
+
+    #[test]
+    fn test_retire() {
+        let hpo = load_hpo();
+        let mut overlay = OverlayOntology::new(&hpo);
+        let arachnodactyly = term_id("HP:0001166");
+        let idx = overlay.id_to_idx(&arachnodactyly).unwrap();
+        let retired =
+            SimpleMinimalTerm::new(arachnodactyly.clone(), "Arachnodactyly", vec![], true);
+
+        overlay.retire(retired).unwrap();
+
+        assert!(overlay.idx_to_term(idx).unwrap().is_obsolete());
+        assert!(overlay
+            .iter_terms()
+            .any(|t| t.identifier() == &arachnodactyly && t.is_obsolete()));
+        assert_eq!(overlay.hierarchy().parents_of(idx).count(), 0);
+        assert!(hpo.idx_to_term(idx).unwrap().is_current());
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeSet;
+
+use crate::base::TermId;
+use crate::hierarchy::{AncestorNodes, DescendantNodes};
+
+use super::traversal::{related, Direction};
+use super::RelationshipAware;
+
+/// A step of a [`RelationPath`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathStep {
+    /// Zero or more `is_a` edges.
+    IsA,
+    /// Exactly one relationship with the predicate, e.g. `part_of` (`BFO:0000050`).
+    Relation(TermId),
+    /// One or more relationships with the predicate.
+    Transitive(TermId),
+}
+
+/// A composed relation, such as `is_a` followed by `part_of` and `is_a` again,
+/// evaluated over the `is_a` hierarchy and the other [`super::Relationships`] of an ontology.
+///
+/// The path is built from the [`PathStep`]s, and the terms are related by the path
+/// if there is a chain of the edges that matches the steps, in the order of the steps.
+/// For instance, the path `is_a*, part_of+, is_a*` relates the left ventricle
+/// to the heart as well as to the organ, and the heart to the cardiovascular system.
+///
+/// The paths answer the questions such as "which anatomical entities are a part of the heart",
+/// e.g. to select the phenotypes that affect any part of the heart
+/// using [`crate::anatomy::AnatomicalProjection`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RelationPath {
+    steps: Vec<PathStep>,
+}
+
+impl RelationPath {
+    /// Create an empty path that relates each term to itself.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add zero or more `is_a` edges to the path.
+    #[must_use]
+    pub fn is_a(self) -> Self {
+        self.step(PathStep::IsA)
+    }
+
+    /// Add exactly one relationship with the `pred`icate to the path.
+    #[must_use]
+    pub fn relation(self, pred: TermId) -> Self {
+        self.step(PathStep::Relation(pred))
+    }
+
+    /// Add one or more relationships with the `pred`icate to the path.
+    #[must_use]
+    pub fn transitive(self, pred: TermId) -> Self {
+        self.step(PathStep::Transitive(pred))
+    }
+
+    /// Add the `step` to the path.
+    #[must_use]
+    pub fn step(mut self, step: PathStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Get the steps of the path.
+    pub fn steps(&self) -> &[PathStep] {
+        &self.steps
+    }
+
+    /// Get the primary IDs of the terms that the term with the `sub`ject ID
+    /// is related to by the path, in the order of the term indices.
+    ///
+    /// Returns no terms if the term is not in the `ontology`.
+    pub fn objects_of<'o, O>(&self, ontology: &'o O, sub: &TermId) -> Vec<&'o TermId>
+    where
+        O: RelationshipAware,
+    {
+        let Some(sub) = ontology.id_to_idx(sub) else {
+            return vec![];
+        };
+        let mut nodes = BTreeSet::from([sub]);
+        for step in &self.steps {
+            nodes = match step {
+                PathStep::IsA => {
+                    let hierarchy = ontology.hierarchy();
+                    let mut next = nodes.clone();
+                    for &node in &nodes {
+                        next.extend(hierarchy.ancestors_of(node).copied());
+                    }
+                    next
+                }
+                PathStep::Relation(pred) => hop(ontology, &nodes, pred, Direction::Up),
+                PathStep::Transitive(pred) => closure(ontology, nodes, pred, Direction::Up),
+            };
+        }
+        to_term_ids(ontology, nodes)
+    }
+
+    /// Get the primary IDs of the terms related to the term with the `obj`ect ID by the path,
+    /// in the order of the term indices.
+    ///
+    /// Returns no terms if the term is not in the `ontology`.
+    pub fn subjects_of<'o, O>(&self, ontology: &'o O, obj: &TermId) -> Vec<&'o TermId>
+    where
+        O: RelationshipAware,
+    {
+        let Some(obj) = ontology.id_to_idx(obj) else {
+            return vec![];
+        };
+        let mut nodes = BTreeSet::from([obj]);
+        for step in self.steps.iter().rev() {
+            nodes = match step {
+                PathStep::IsA => {
+                    let hierarchy = ontology.hierarchy();
+                    let mut next = nodes.clone();
+                    for &node in &nodes {
+                        next.extend(hierarchy.descendants_of(node).copied());
+                    }
+                    next
+                }
+                PathStep::Relation(pred) => hop(ontology, &nodes, pred, Direction::Down),
+                PathStep::Transitive(pred) => closure(ontology, nodes, pred, Direction::Down),
+            };
+        }
+        to_term_ids(ontology, nodes)
+    }
+}
+
+/// Follow one relationship with the `pred`icate from each of the `nodes`.
+fn hop<O>(
+    ontology: &O,
+    nodes: &BTreeSet<O::Idx>,
+    pred: &TermId,
+    direction: Direction,
+) -> BTreeSet<O::Idx>
+where
+    O: RelationshipAware,
+{
+    nodes
+        .iter()
+        .flat_map(|&node| related(ontology, node, pred, direction))
+        .collect()
+}
+
+/// Follow one or more relationships with the `pred`icate from each of the `nodes`.
+fn closure<O>(
+    ontology: &O,
+    nodes: BTreeSet<O::Idx>,
+    pred: &TermId,
+    direction: Direction,
+) -> BTreeSet<O::Idx>
+where
+    O: RelationshipAware,
+{
+    let mut reached = BTreeSet::new();
+    let mut frontier = nodes;
+    while !frontier.is_empty() {
+        frontier = hop(ontology, &frontier, pred, direction)
+            .into_iter()
+            .filter(|&node| reached.insert(node))
+            .collect();
+    }
+    reached
+}
+
+fn to_term_ids<O>(ontology: &O, nodes: BTreeSet<O::Idx>) -> Vec<&TermId>
+where
+    O: RelationshipAware,
+{
+    nodes
+        .into_iter()
+        .flat_map(|node| ontology.idx_to_term_id(node))
+        .collect()
+}
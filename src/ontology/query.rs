@@ -0,0 +1,187 @@
+use crate::base::{term::MinimalTerm, Identified};
+use crate::hierarchy::{AncestorNodes, DescendantNodes};
+
+use super::{Ontology, TermIdx};
+
+/// A part of the [`Query`] that selects a group of terms.
+enum Selection<I> {
+    Term(I),
+    Descendants(I),
+    Ancestors(I),
+    // The term ID is not in the ontology.
+    Missing,
+}
+
+/// A builder-style query for selecting the ontology terms.
+///
+/// The query selects the terms to include (e.g. the descendants of a term),
+/// the terms to exclude, and the CURIE prefixes of the terms.
+/// The query considers all *current* terms of the ontology,
+/// if no selection of terms to include is provided.
+///
+/// The traversals follow the semantics of [`crate::hierarchy::OntologyHierarchy`],
+/// and, for instance, the descendants of a term do *not* include the term itself.
+/// Use [`Query::term`] or [`Query::exclude`] to select a single term.
+///
+/// The term IDs that are not present in the ontology select no terms.
+///
+/// Use [`Ontology::query`] to create a query.
+///
+/// ## Examples
+///
+/// ```
+/// # use curie_util::TrieCurieUtil;
+/// # use ontolius::io::obographs::ObographsParser;
+/// # use ontolius::ontology::csr::CsrOntology;
+/// use ontolius::prelude::*;
+///
+/// # let loader = OntologyLoaderBuilder::new()
+/// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+/// #                .build();
+/// # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+/// #                                    .expect("HPO should be loaded");
+/// #
+/// let abnormal_finger_morphology = TermId::from(("HP", "0001167"));
+/// let abnormal_finger_phalanx_morphology = TermId::from(("HP", "0005918"));
+///
+/// let terms = hpo.query()
+///                 .descendants_of(&abnormal_finger_morphology)
+///                 .exclude(&abnormal_finger_phalanx_morphology)
+///                 .exclude_descendants_of(&abnormal_finger_phalanx_morphology)
+///                 .with_prefix("HP")
+///                 .collect();
+///
+/// assert_eq!(terms.len(), 10);
+/// assert!(terms.iter().any(|term| term.name() == "Arachnodactyly"));
+/// ```
+pub struct Query<'o, O>
+where
+    O: Ontology,
+{
+    ontology: &'o O,
+    include: Vec<Selection<O::Idx>>,
+    exclude: Vec<Selection<O::Idx>>,
+    prefixes: Vec<String>,
+}
+
+impl<'o, O> Query<'o, O>
+where
+    O: Ontology,
+{
+    pub(super) fn new(ontology: &'o O) -> Self {
+        Self {
+            ontology,
+            include: vec![],
+            exclude: vec![],
+            prefixes: vec![],
+        }
+    }
+
+    fn select<ID, F>(&self, id: &ID, f: F) -> Selection<O::Idx>
+    where
+        ID: Identified,
+        F: FnOnce(O::Idx) -> Selection<O::Idx>,
+    {
+        self.ontology.id_to_idx(id).map_or(Selection::Missing, f)
+    }
+
+    /// Include the term with `id`.
+    #[must_use]
+    pub fn term<ID: Identified>(mut self, id: &ID) -> Self {
+        self.include.push(self.select(id, Selection::Term));
+        self
+    }
+
+    /// Include the descendants of the term with `id`.
+    #[must_use]
+    pub fn descendants_of<ID: Identified>(mut self, id: &ID) -> Self {
+        self.include.push(self.select(id, Selection::Descendants));
+        self
+    }
+
+    /// Include the ancestors of the term with `id`.
+    #[must_use]
+    pub fn ancestors_of<ID: Identified>(mut self, id: &ID) -> Self {
+        self.include.push(self.select(id, Selection::Ancestors));
+        self
+    }
+
+    /// Exclude the term with `id`.
+    #[must_use]
+    pub fn exclude<ID: Identified>(mut self, id: &ID) -> Self {
+        self.exclude.push(self.select(id, Selection::Term));
+        self
+    }
+
+    /// Exclude the descendants of the term with `id`.
+    #[must_use]
+    pub fn exclude_descendants_of<ID: Identified>(mut self, id: &ID) -> Self {
+        self.exclude.push(self.select(id, Selection::Descendants));
+        self
+    }
+
+    /// Exclude the ancestors of the term with `id`.
+    #[must_use]
+    pub fn exclude_ancestors_of<ID: Identified>(mut self, id: &ID) -> Self {
+        self.exclude.push(self.select(id, Selection::Ancestors));
+        self
+    }
+
+    /// Keep only the terms with the CURIE `prefix` (e.g. `HP`).
+    ///
+    /// The terms are kept if they match any of the prefixes, if called multiple times.
+    #[must_use]
+    pub fn with_prefix<T: ToString>(mut self, prefix: T) -> Self {
+        self.prefixes.push(prefix.to_string());
+        self
+    }
+
+    /// Run the query and collect the selected terms in the order of their indices.
+    pub fn collect(self) -> Vec<&'o O::T> {
+        let ontology = self.ontology;
+        let mut selected = vec![self.include.is_empty(); ontology.len()];
+
+        for selection in &self.include {
+            self.mark(selection, &mut selected, true);
+        }
+        for selection in &self.exclude {
+            self.mark(selection, &mut selected, false);
+        }
+
+        ontology
+            .iter_terms()
+            .zip(selected)
+            .filter(|(term, is_selected)| {
+                *is_selected
+                    && term.is_current()
+                    && (self.prefixes.is_empty()
+                        || self
+                            .prefixes
+                            .iter()
+                            .any(|prefix| term.identifier().prefix() == prefix))
+            })
+            .map(|(term, _)| term)
+            .collect()
+    }
+
+    /// Run the query and count the selected terms.
+    pub fn count(self) -> usize {
+        self.collect().len()
+    }
+
+    fn mark(&self, selection: &Selection<O::Idx>, selected: &mut [bool], value: bool) {
+        let hierarchy = self.ontology.hierarchy();
+        let mut set = |idx: O::Idx| {
+            if let Some(flag) = selected.get_mut(TermIdx::index(idx)) {
+                *flag = value;
+            }
+        };
+
+        match selection {
+            Selection::Term(idx) => set(*idx),
+            Selection::Descendants(idx) => hierarchy.descendants_of(*idx).for_each(|&i| set(i)),
+            Selection::Ancestors(idx) => hierarchy.ancestors_of(*idx).for_each(|&i| set(i)),
+            Selection::Missing => {}
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+
+use crate::base::TermId;
+
+/// The relationships between the ontology terms other than `is_a`,
+/// such as `part_of` (`BFO:0000050`) or `has_role` (`RO:0000087`).
+///
+/// The relationships are indexed by the predicate and refer to the terms by their primary IDs.
+///
+/// ## Examples
+///
+/// ```
+/// use ontolius::base::TermId;
+/// use ontolius::ontology::Relationships;
+///
+/// let has_role = TermId::from(("RO", "0000087"));
+/// let aspirin = TermId::from(("CHEBI", "15365"));
+/// let nsaid = TermId::from(("CHEBI", "35475"));
+///
+/// let relationships: Relationships = [(aspirin.clone(), has_role.clone(), nsaid.clone())]
+///     .into_iter()
+///     .collect();
+///
+/// assert_eq!(relationships.objects_of(&aspirin, &has_role), [nsaid.clone()]);
+/// assert_eq!(relationships.subjects_of(&nsaid, &has_role), [aspirin]);
+/// assert_eq!(relationships.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Relationships {
+    by_predicate: HashMap<TermId, Adjacency>,
+    len: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Adjacency {
+    objects: HashMap<TermId, Vec<TermId>>,
+    subjects: HashMap<TermId, Vec<TermId>>,
+}
+
+impl Relationships {
+    /// Add the relationship of the `sub`ject to the `obj`ect by the `pred`icate.
+    ///
+    /// Returns `false` if the relationship was already present.
+    pub fn insert(&mut self, sub: TermId, pred: TermId, obj: TermId) -> bool {
+        let adjacency = self.by_predicate.entry(pred).or_default();
+        let objects = adjacency.objects.entry(sub.clone()).or_default();
+        if objects.contains(&obj) {
+            return false;
+        }
+        objects.push(obj.clone());
+        adjacency.subjects.entry(obj).or_default().push(sub);
+        self.len += 1;
+        true
+    }
+
+    /// Get the objects of the `sub`ject's relationships by the `pred`icate.
+    pub fn objects_of(&self, sub: &TermId, pred: &TermId) -> &[TermId] {
+        self.by_predicate
+            .get(pred)
+            .and_then(|adjacency| adjacency.objects.get(sub))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Get the subjects of the relationships to the `obj`ect by the `pred`icate.
+    pub fn subjects_of(&self, obj: &TermId, pred: &TermId) -> &[TermId] {
+        self.by_predicate
+            .get(pred)
+            .and_then(|adjacency| adjacency.subjects.get(obj))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Iterate over the predicates of the relationships.
+    pub fn predicates(&self) -> impl Iterator<Item = &TermId> {
+        self.by_predicate.keys()
+    }
+
+    /// Iterate over all relationships as `(subject, predicate, object)` triples, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&TermId, &TermId, &TermId)> {
+        self.by_predicate.iter().flat_map(|(pred, adjacency)| {
+            adjacency
+                .objects
+                .iter()
+                .flat_map(move |(sub, objects)| objects.iter().map(move |obj| (sub, pred, obj)))
+        })
+    }
+
+    /// Get the number of the relationships.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Test if there are no relationships.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl FromIterator<(TermId, TermId, TermId)> for Relationships {
+    fn from_iter<I: IntoIterator<Item = (TermId, TermId, TermId)>>(iter: I) -> Self {
+        let mut relationships = Relationships::default();
+        for (sub, pred, obj) in iter {
+            relationships.insert(sub, pred, obj);
+        }
+        relationships
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::hierarchy::{GraphEdge, Relationship};
+    use crate::io::OntologyData;
+    use crate::ontology::csr::CsrOntology;
+    use crate::ontology::{
+        RelationPath, RelationshipAware, RelationshipTraversals, RelationshipType,
+    };
+
+    /// A small anatomy ontology, where the left ventricle (`UBERON:4`) is a part of the heart
+    /// (`UBERON:2`), and the heart is a part of the cardiovascular system (`UBERON:5`).
+    fn anatomy() -> CsrOntology<usize, SimpleMinimalTerm> {
+        let terms: Vec<_> = [
+            "anatomical entity",
+            "organ",
+            "heart",
+            "cardiac chamber",
+            "left ventricle",
+            "cardiovascular system",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| SimpleMinimalTerm::new(uberon(&i.to_string()), name, vec![], false))
+        .collect();
+        let edges: Vec<_> = [(1, 0), (2, 1), (3, 0), (4, 3), (5, 0)]
+            .into_iter()
+            .map(|(sub, obj)| GraphEdge::from((sub, Relationship::Child, obj)))
+            .collect();
+        let part_of = TermId::from(("BFO", "0000050"));
+        let relationships: Relationships = [
+            (uberon("4"), part_of.clone(), uberon("2")),
+            (uberon("2"), part_of.clone(), uberon("5")),
+        ]
+        .into_iter()
+        .collect();
+        let data: OntologyData<usize, _> = OntologyData::from((
+            terms.into_boxed_slice(),
+            edges.into_boxed_slice(),
+            HashMap::new(),
+        ))
+        .with_relationships(relationships);
+        CsrOntology::try_from(data).unwrap()
+    }
+
+    fn uberon(id: &str) -> TermId {
+        TermId::from(("UBERON", id))
+    }
+
+    #[test]
+    fn test_partonomy_ancestors() {
+        let ontology = anatomy();
+
+        let ancestors: Vec<_> = ontology
+            .iter_partonomy_ancestors(&uberon("4"))
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            ancestors,
+            ["UBERON:3", "UBERON:2", "UBERON:0", "UBERON:1", "UBERON:5"]
+        );
+
+        let ancestors = ontology.ancestors_over(&uberon("4"), &[]);
+        assert_eq!(ancestors, [&uberon("3"), &uberon("0")]);
+        let part_of = TermId::from(("BFO", "0000050"));
+        assert!(ontology.ancestors_over(&uberon("9"), &[part_of]).is_empty());
+    }
+
+    #[test]
+    fn test_traversals_via() {
+        let ontology = anatomy();
+        let to_strings = |term_ids: Vec<&TermId>| -> Vec<_> {
+            term_ids.into_iter().map(ToString::to_string).collect()
+        };
+
+        let via = [RelationshipType::IsA, RelationshipType::part_of()];
+        assert_eq!(
+            to_strings(ontology.iter_ancestor_ids_via(&uberon("4"), &via).collect()),
+            ["UBERON:3", "UBERON:2", "UBERON:0", "UBERON:1", "UBERON:5"]
+        );
+        assert_eq!(
+            to_strings(
+                ontology
+                    .iter_descendant_ids_via(&uberon("5"), &via)
+                    .collect()
+            ),
+            ["UBERON:2", "UBERON:4"]
+        );
+
+        let part_of = [RelationshipType::part_of()];
+        assert_eq!(
+            to_strings(
+                ontology
+                    .iter_ancestor_ids_via(&uberon("4"), &part_of)
+                    .collect()
+            ),
+            ["UBERON:2", "UBERON:5"]
+        );
+        let is_a = [RelationshipType::IsA];
+        assert_eq!(
+            to_strings(
+                ontology
+                    .iter_ancestor_ids_via(&uberon("4"), &is_a)
+                    .collect()
+            ),
+            ["UBERON:3", "UBERON:0"]
+        );
+        assert_eq!(
+            to_strings(
+                ontology
+                    .iter_descendant_ids_via(&uberon("1"), &is_a)
+                    .collect()
+            ),
+            ["UBERON:2"]
+        );
+        assert_eq!(
+            ontology.iter_ancestor_ids_via(&uberon("9"), &via).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_relation_path() {
+        let ontology = anatomy();
+        let part_of = TermId::from(("BFO", "0000050"));
+        let path = RelationPath::new()
+            .is_a()
+            .transitive(part_of.clone())
+            .is_a();
+
+        let to_strings = |term_ids: Vec<&TermId>| -> Vec<_> {
+            term_ids.into_iter().map(ToString::to_string).collect()
+        };
+        assert_eq!(
+            to_strings(path.objects_of(&ontology, &uberon("4"))),
+            ["UBERON:0", "UBERON:1", "UBERON:2", "UBERON:5"]
+        );
+        assert_eq!(
+            to_strings(path.subjects_of(&ontology, &uberon("1"))),
+            ["UBERON:4"]
+        );
+        assert_eq!(
+            to_strings(path.subjects_of(&ontology, &uberon("5"))),
+            ["UBERON:2", "UBERON:4"]
+        );
+
+        let path = RelationPath::new().relation(part_of);
+        assert_eq!(
+            to_strings(path.objects_of(&ontology, &uberon("4"))),
+            ["UBERON:2"]
+        );
+        assert!(RelationPath::new()
+            .is_a()
+            .subjects_of(&ontology, &uberon("9"))
+            .is_empty());
+    }
+}
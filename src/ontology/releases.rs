@@ -0,0 +1,218 @@
+use crate::base::term::MinimalTerm;
+use crate::base::Identified;
+use crate::error::OntoliusError;
+
+use super::{Ontology, TermAware};
+
+/// Several releases of the same ontology, in the chronological order.
+///
+/// The set answers the questions about the history of a term,
+/// such as the release where the term appeared or got obsoleted,
+/// and routes the queries to a release by its version.
+///
+/// The releases are labeled by the versions given by the caller,
+/// because the ontology files do not always include the version.
+///
+/// ## Examples
+///
+/// ```
+/// # use curie_util::TrieCurieUtil;
+/// # use ontolius::io::obographs::ObographsParser;
+/// # use ontolius::ontology::csr::CsrOntology;
+/// use ontolius::ontology::ReleaseSet;
+/// use ontolius::prelude::*;
+///
+/// # let loader = OntologyLoaderBuilder::new()
+/// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+/// #                .build();
+/// # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+/// #                                    .expect("HPO should be loaded");
+/// #
+/// let mut releases = ReleaseSet::default();
+/// releases.push("2023-04-05", hpo).expect("The version should be new");
+///
+/// let arachnodactyly = TermId::from(("HP", "0001166"));
+/// assert_eq!(releases.appeared_in(&arachnodactyly), Some("2023-04-05"));
+/// assert_eq!(releases.obsoleted_in(&arachnodactyly), None);
+/// assert!(releases.get("2023-04-05").is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReleaseSet<O> {
+    releases: Vec<(String, O)>,
+}
+
+impl<O> Default for ReleaseSet<O> {
+    fn default() -> Self {
+        Self { releases: vec![] }
+    }
+}
+
+impl<O> ReleaseSet<O>
+where
+    O: Ontology,
+{
+    /// Add the `ontology` of the `version` that is newer than the releases in the set.
+    ///
+    /// Returns an error if the set already includes a release of the `version`.
+    pub fn push<V>(&mut self, version: V, ontology: O) -> Result<(), OntoliusError>
+    where
+        V: ToString,
+    {
+        let version = version.to_string();
+        if self.get(&version).is_some() {
+            return Err(OntoliusError::Other(format!(
+                "Release {version} is already in the set"
+            )));
+        }
+        self.releases.push((version, ontology));
+        Ok(())
+    }
+
+    /// Get the release of the `version`.
+    pub fn get(&self, version: &str) -> Option<&O> {
+        self.releases
+            .iter()
+            .find(|(v, _)| v == version)
+            .map(|(_, ontology)| ontology)
+    }
+
+    /// Get the version and the ontology of the newest release.
+    pub fn latest(&self) -> Option<(&str, &O)> {
+        self.releases
+            .last()
+            .map(|(version, ontology)| (version.as_str(), ontology))
+    }
+
+    /// Iterate over the versions and the ontologies of the releases, from the oldest.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &O)> {
+        self.releases
+            .iter()
+            .map(|(version, ontology)| (version.as_str(), ontology))
+    }
+
+    /// Get the number of releases.
+    pub fn len(&self) -> usize {
+        self.releases.len()
+    }
+
+    /// Test if the set has no releases.
+    pub fn is_empty(&self) -> bool {
+        self.releases.is_empty()
+    }
+
+    /// Get the version of the first release where `term_id` is the primary ID of a term.
+    ///
+    /// Returns `None` if no release has the term.
+    pub fn appeared_in<ID>(&self, term_id: &ID) -> Option<&str>
+    where
+        ID: Identified,
+    {
+        self.iter()
+            .find(|(_, ontology)| is_primary(*ontology, term_id))
+            .map(|(version, _)| version)
+    }
+
+    /// Get the version of the first release, after the term appeared,
+    /// where `term_id` no longer identifies a current term.
+    ///
+    /// The term is obsolete in the release, is an alternate ID of another term
+    /// (e.g. after a merge), or is missing altogether.
+    /// Returns `None` if the term never appeared or is current in the latest release.
+    pub fn obsoleted_in<ID>(&self, term_id: &ID) -> Option<&str>
+    where
+        ID: Identified,
+    {
+        self.iter()
+            .skip_while(|(_, ontology)| !is_primary(*ontology, term_id))
+            .find(|(_, ontology)| {
+                !ontology.id_to_term(term_id).is_some_and(|term| {
+                    term.is_current() && term.identifier() == term_id.identifier()
+                })
+            })
+            .map(|(version, _)| version)
+    }
+}
+
+/// Test if the `term_id` is the primary ID of a term of the `ontology`.
+fn is_primary<O, ID>(ontology: &O, term_id: &ID) -> bool
+where
+    O: TermAware,
+    ID: Identified,
+{
+    ontology
+        .id_to_term(term_id)
+        .is_some_and(|term| term.identifier() == term_id.identifier())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::base::TermId;
+    use crate::ontology::csr::CsrOntology;
+    use crate::test_util::toy_ontology;
+
+    fn release(terms: &[(&str, &[&str], bool)]) -> CsrOntology<usize, SimpleMinimalTerm> {
+        let terms: Vec<_> = terms
+            .iter()
+            .map(|&(id, alt_ids, is_obsolete)| {
+                SimpleMinimalTerm::new(
+                    TermId::from(("HP", id)),
+                    id,
+                    alt_ids
+                        .iter()
+                        .map(|&alt| TermId::from(("HP", alt)))
+                        .collect(),
+                    is_obsolete,
+                )
+            })
+            .collect();
+        let edges: Vec<_> = (1..terms.len()).map(|sub| (sub, 0)).collect();
+        toy_ontology(terms, &edges)
+    }
+
+    #[test]
+    fn test_release_set() {
+        let mut releases = ReleaseSet::default();
+        releases
+            .push(
+                "v1",
+                release(&[("1", &[], false), ("2", &[], false), ("3", &[], false)]),
+            )
+            .unwrap();
+        // `HP:4` is new, `HP:3` got merged into `HP:2`.
+        releases
+            .push(
+                "v2",
+                release(&[("1", &[], false), ("2", &["3"], false), ("4", &[], false)]),
+            )
+            .unwrap();
+        // `HP:4` got obsoleted.
+        releases
+            .push(
+                "v3",
+                release(&[("1", &[], false), ("2", &["3"], false), ("4", &[], true)]),
+            )
+            .unwrap();
+        assert!(releases
+            .push("v3", release(&[("1", &[], false), ("2", &[], false)]))
+            .is_err());
+
+        let hp = |id: &str| TermId::from(("HP", id));
+        assert_eq!(releases.len(), 3);
+        assert_eq!(releases.appeared_in(&hp("2")), Some("v1"));
+        assert_eq!(releases.obsoleted_in(&hp("2")), None);
+        assert_eq!(releases.appeared_in(&hp("3")), Some("v1"));
+        assert_eq!(releases.obsoleted_in(&hp("3")), Some("v2"));
+        assert_eq!(releases.appeared_in(&hp("4")), Some("v2"));
+        assert_eq!(releases.obsoleted_in(&hp("4")), Some("v3"));
+        assert_eq!(releases.appeared_in(&hp("9")), None);
+        assert_eq!(releases.obsoleted_in(&hp("9")), None);
+
+        assert_eq!(releases.get("v2").map(|o| o.len()), Some(3));
+        assert!(releases.get("v9").is_none());
+        assert_eq!(releases.latest().map(|(version, _)| version), Some("v3"));
+        let versions: Vec<_> = releases.iter().map(|(version, _)| version).collect();
+        assert_eq!(versions, ["v1", "v2", "v3"]);
+    }
+}
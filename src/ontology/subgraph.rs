@@ -0,0 +1,301 @@
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+use crate::base::TermId;
+use crate::hierarchy::{AncestorNodes, ChildNodes, ParentNodes};
+
+use super::Ontology;
+
+/// A small part of the ontology graph, e.g. for rendering the context of a set of terms.
+///
+/// The subgraph includes the primary IDs of the terms and the `(child, parent)` edges,
+/// both in the order of the term indices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subgraph<'o> {
+    terms: Vec<&'o TermId>,
+    edges: Vec<(&'o TermId, &'o TermId)>,
+}
+
+impl<'o> Subgraph<'o> {
+    /// Get the terms of the subgraph.
+    pub fn terms(&self) -> &[&'o TermId] {
+        &self.terms
+    }
+
+    /// Get the `(child, parent)` edges of the subgraph.
+    pub fn edges(&self) -> &[(&'o TermId, &'o TermId)] {
+        &self.edges
+    }
+}
+
+pub(super) fn induced_subgraph<'o, O>(
+    ontology: &'o O,
+    terms: &HashSet<TermId>,
+    include_lcas: bool,
+) -> Subgraph<'o>
+where
+    O: Ontology + ?Sized,
+{
+    let hierarchy = ontology.hierarchy();
+    let mut nodes: BTreeSet<_> = terms
+        .iter()
+        .flat_map(|term_id| ontology.id_to_idx(term_id))
+        .collect();
+
+    if include_lcas {
+        let selected: Vec<_> = nodes.iter().copied().collect();
+        let ancestors: Vec<BTreeSet<_>> = selected
+            .iter()
+            .map(|&node| with_ancestors(ontology, node))
+            .collect();
+        for (i, a) in ancestors.iter().enumerate() {
+            for b in &ancestors[i + 1..] {
+                let common: Vec<_> = a.intersection(b).copied().collect();
+                // The lowest common ancestors are not ancestors of another common ancestor.
+                nodes.extend(common.iter().copied().filter(|&candidate| {
+                    !common
+                        .iter()
+                        .any(|&other| hierarchy.is_ancestor_of(candidate, other))
+                }));
+            }
+        }
+    }
+
+    Subgraph {
+        terms: to_term_ids(ontology, nodes.iter().copied()),
+        edges: transitive_reduction(ontology, &nodes),
+    }
+}
+
+pub(super) fn spanning_subgraph<'o, O>(ontology: &'o O, terms: &HashSet<TermId>) -> Subgraph<'o>
+where
+    O: Ontology + ?Sized,
+{
+    let hierarchy = ontology.hierarchy();
+    let mut nodes = BTreeSet::new();
+    for node in terms.iter().flat_map(|term_id| ontology.id_to_idx(term_id)) {
+        nodes.insert(node);
+        nodes.extend(hierarchy.ancestors_of(node).copied());
+    }
+
+    Subgraph {
+        terms: to_term_ids(ontology, nodes.iter().copied()),
+        edges: edges_within(ontology, &nodes),
+    }
+}
+
+pub(super) fn sample_subgraph<'o, O>(
+    ontology: &'o O,
+    seeds: &[TermId],
+    n_terms: usize,
+) -> Subgraph<'o>
+where
+    O: Ontology + ?Sized,
+{
+    let hierarchy = ontology.hierarchy();
+    let mut nodes = BTreeSet::new();
+    let mut queue = VecDeque::new();
+    for seed in seeds.iter().flat_map(|term_id| ontology.id_to_idx(term_id)) {
+        nodes.insert(seed);
+        nodes.extend(hierarchy.ancestors_of(seed).copied());
+        queue.push_back(seed);
+    }
+
+    // Add the descendants of the seeds breadth-first, in the order of the indices,
+    // so that each added term is connected to its parent.
+    while let Some(node) = queue.pop_front() {
+        let mut children: Vec<_> = hierarchy.children_of(node).copied().collect();
+        children.sort_unstable();
+        for child in children {
+            if nodes.len() >= n_terms {
+                break;
+            }
+            if nodes.insert(child) {
+                queue.push_back(child);
+            }
+        }
+    }
+
+    Subgraph {
+        terms: to_term_ids(ontology, nodes.iter().copied()),
+        edges: edges_within(ontology, &nodes),
+    }
+}
+
+/// Get the `is_a` edges between the `nodes`.
+fn edges_within<'o, O>(ontology: &'o O, nodes: &BTreeSet<O::Idx>) -> Vec<(&'o TermId, &'o TermId)>
+where
+    O: Ontology + ?Sized,
+{
+    let hierarchy = ontology.hierarchy();
+    let mut edges = vec![];
+    for &node in nodes {
+        let mut parents: Vec<_> = hierarchy
+            .parents_of(node)
+            .copied()
+            .filter(|parent| nodes.contains(parent))
+            .collect();
+        parents.sort_unstable();
+        edges.extend(
+            parents
+                .into_iter()
+                .flat_map(|parent| to_edge(ontology, node, parent)),
+        );
+    }
+    edges
+}
+
+/// Link each of the `nodes` to its closest ancestors among the `nodes`.
+fn transitive_reduction<'o, O>(
+    ontology: &'o O,
+    nodes: &BTreeSet<O::Idx>,
+) -> Vec<(&'o TermId, &'o TermId)>
+where
+    O: Ontology + ?Sized,
+{
+    let hierarchy = ontology.hierarchy();
+    let mut edges = vec![];
+    for &node in nodes {
+        let ancestors: Vec<_> = nodes
+            .iter()
+            .copied()
+            .filter(|&other| hierarchy.is_ancestor_of(other, node))
+            .collect();
+        let closest = ancestors.iter().copied().filter(|&ancestor| {
+            !ancestors
+                .iter()
+                .any(|&other| hierarchy.is_ancestor_of(ancestor, other))
+        });
+        edges.extend(closest.flat_map(|ancestor| to_edge(ontology, node, ancestor)));
+    }
+    edges
+}
+
+fn with_ancestors<O>(ontology: &O, node: O::Idx) -> BTreeSet<O::Idx>
+where
+    O: Ontology + ?Sized,
+{
+    std::iter::once(node)
+        .chain(ontology.hierarchy().ancestors_of(node).copied())
+        .collect()
+}
+
+fn to_edge<O>(ontology: &O, child: O::Idx, parent: O::Idx) -> Option<(&TermId, &TermId)>
+where
+    O: Ontology + ?Sized,
+{
+    Some((
+        ontology.idx_to_term_id(child)?,
+        ontology.idx_to_term_id(parent)?,
+    ))
+}
+
+fn to_term_ids<O, I>(ontology: &O, nodes: I) -> Vec<&TermId>
+where
+    O: Ontology + ?Sized,
+    I: Iterator<Item = O::Idx>,
+{
+    nodes
+        .flat_map(|node| ontology.idx_to_term_id(node))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::base::TermId;
+    use crate::ontology::csr::CsrOntology;
+    use crate::ontology::Ontology;
+    use crate::test_util::toy_ontology;
+
+    fn build_ontology() -> CsrOntology<usize, SimpleMinimalTerm> {
+        let terms: Vec<_> = ["HP:1", "HP:2", "HP:3", "HP:4", "HP:5", "HP:6"]
+            .into_iter()
+            .map(|curie| {
+                SimpleMinimalTerm::new(TermId::from_str(curie).unwrap(), curie, vec![], false)
+            })
+            .collect();
+        toy_ontology(terms, &[(1, 0), (2, 1), (3, 1), (4, 0), (5, 2)])
+    }
+
+    fn term_ids(curies: &[&str]) -> HashSet<TermId> {
+        curies
+            .iter()
+            .map(|curie| TermId::from_str(curie).unwrap())
+            .collect()
+    }
+
+    fn to_strings(edges: &[(&TermId, &TermId)]) -> Vec<(String, String)> {
+        edges
+            .iter()
+            .map(|(child, parent)| (child.to_string(), parent.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_induced_subgraph() {
+        let ontology = build_ontology();
+        let terms = term_ids(&["HP:3", "HP:4", "HP:6", "HP:9"]);
+
+        let subgraph = ontology.induced_subgraph(&terms, false);
+        assert_eq!(subgraph.terms().len(), 3);
+        assert_eq!(
+            to_strings(subgraph.edges()),
+            [("HP:6".to_string(), "HP:3".to_string())]
+        );
+
+        let subgraph = ontology.induced_subgraph(&terms, true);
+        let ids: Vec<_> = subgraph.terms().iter().map(ToString::to_string).collect();
+        assert_eq!(ids, ["HP:2", "HP:3", "HP:4", "HP:6"]);
+        assert_eq!(
+            to_strings(subgraph.edges()),
+            [
+                ("HP:3".to_string(), "HP:2".to_string()),
+                ("HP:4".to_string(), "HP:2".to_string()),
+                ("HP:6".to_string(), "HP:3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spanning_subgraph() {
+        let ontology = build_ontology();
+        let terms = term_ids(&["HP:4", "HP:6", "HP:9"]);
+
+        let subgraph = ontology.spanning_subgraph(&terms);
+        let ids: Vec<_> = subgraph.terms().iter().map(ToString::to_string).collect();
+        assert_eq!(ids, ["HP:1", "HP:2", "HP:3", "HP:4", "HP:6"]);
+        assert_eq!(
+            to_strings(subgraph.edges()),
+            [
+                ("HP:2".to_string(), "HP:1".to_string()),
+                ("HP:3".to_string(), "HP:2".to_string()),
+                ("HP:4".to_string(), "HP:2".to_string()),
+                ("HP:6".to_string(), "HP:3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sample_subgraph() {
+        let ontology = build_ontology();
+        let seeds = [TermId::from_str("HP:2").unwrap()];
+
+        let subgraph = ontology.sample_subgraph(&seeds, 4);
+        let ids: Vec<_> = subgraph.terms().iter().map(ToString::to_string).collect();
+        assert_eq!(ids, ["HP:1", "HP:2", "HP:3", "HP:4"]);
+        assert_eq!(
+            to_strings(subgraph.edges()),
+            [
+                ("HP:2".to_string(), "HP:1".to_string()),
+                ("HP:3".to_string(), "HP:2".to_string()),
+                ("HP:4".to_string(), "HP:2".to_string()),
+            ]
+        );
+
+        assert_eq!(ontology.sample_subgraph(&seeds, 0).terms().len(), 2);
+        assert_eq!(ontology.sample_subgraph(&seeds, 10).terms().len(), 5);
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+/// The user-defined tags of the ontology terms, with a bitset of the term indices per tag.
+#[derive(Clone, Default)]
+pub(crate) struct TermTags {
+    tags: HashMap<String, Vec<u64>>,
+}
+
+impl TermTags {
+    /// Tag the term at `idx` and return `true` if the term did not have the tag.
+    pub(crate) fn insert(&mut self, tag: &str, idx: usize) -> bool {
+        let bits = match self.tags.get_mut(tag) {
+            Some(bits) => bits,
+            None => self.tags.entry(tag.to_string()).or_default(),
+        };
+        let (word, bit) = (idx / 64, 1 << (idx % 64));
+        if bits.len() <= word {
+            bits.resize(word + 1, 0);
+        }
+        let untagged = bits[word] & bit == 0;
+        bits[word] |= bit;
+        untagged
+    }
+
+    /// Remove the tag of the term at `idx` and return `true` if the term had the tag.
+    pub(crate) fn remove(&mut self, tag: &str, idx: usize) -> bool {
+        let Some(bits) = self.tags.get_mut(tag) else {
+            return false;
+        };
+        let (word, bit) = (idx / 64, 1 << (idx % 64));
+        match bits.get_mut(word) {
+            Some(value) if *value & bit != 0 => {
+                *value &= !bit;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn contains(&self, tag: &str, idx: usize) -> bool {
+        self.tags
+            .get(tag)
+            .and_then(|bits| bits.get(idx / 64))
+            .is_some_and(|value| value & (1 << (idx % 64)) != 0)
+    }
+
+    /// Iterate over the indices of the terms with the `tag`, in the ascending order.
+    pub(crate) fn iter(&self, tag: &str) -> impl Iterator<Item = usize> + '_ {
+        self.tags
+            .get(tag)
+            .into_iter()
+            .flat_map(|bits| bits.iter().enumerate())
+            .flat_map(|(word, &value)| {
+                (0..64)
+                    .filter(move |bit| value & (1 << bit) != 0)
+                    .map(move |bit| word * 64 + bit)
+            })
+    }
+
+    /// Iterate over the tags, including the tags with no terms, in no particular order.
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        self.tags.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tags() {
+        let mut tags = TermTags::default();
+        assert!(tags.insert("curated", 3));
+        assert!(tags.insert("curated", 130));
+        assert!(!tags.insert("curated", 3));
+        assert!(tags.insert("reviewed", 0));
+
+        assert!(tags.contains("curated", 130));
+        assert!(!tags.contains("curated", 0));
+        assert!(!tags.contains("unknown", 3));
+        assert_eq!(tags.iter("curated").collect::<Vec<_>>(), [3, 130]);
+
+        assert!(tags.remove("curated", 3));
+        assert!(!tags.remove("curated", 3));
+        assert!(!tags.remove("unknown", 3));
+        assert_eq!(tags.iter("curated").collect::<Vec<_>>(), [130]);
+        assert_eq!(tags.iter("unknown").count(), 0);
+
+        let mut names: Vec<_> = tags.names().collect();
+        names.sort_unstable();
+        assert_eq!(names, ["curated", "reviewed"]);
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::VecDeque;
+
+use crate::base::TermId;
+use crate::hierarchy::{ChildNodes, ParentNodes};
+
+use super::{RelationshipAware, TermIdx};
+
+/// A type of the edges followed by the [`RelationshipTraversals`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RelationshipType {
+    /// The `is_a` edges of the hierarchy.
+    IsA,
+    /// The relationships with the predicate, e.g. `part_of` (`BFO:0000050`).
+    Predicate(TermId),
+}
+
+impl RelationshipType {
+    /// Get the `part_of` (`BFO:0000050`) relationships.
+    pub fn part_of() -> Self {
+        RelationshipType::Predicate(TermId::from(("BFO", "0000050")))
+    }
+}
+
+/// The traversals of the ontology graph along the selected types of the edges,
+/// e.g. along `is_a` and `part_of` by the GO convention, or along `is_a` only.
+///
+/// The trait is implemented for all [`RelationshipAware`] ontologies.
+///
+/// ## Examples
+///
+/// ```
+/// # use curie_util::TrieCurieUtil;
+/// # use ontolius::io::obographs::ObographsParser;
+/// use ontolius::ontology::{RelationshipTraversals, RelationshipType};
+/// use ontolius::prelude::*;
+///
+/// # let loader = OntologyLoaderBuilder::new()
+/// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+/// #                .build();
+/// # let hpo: MinimalCsrOntology = loader.load_from_path("resources/hp.small.json.gz")
+/// #                                 .expect("HPO should be loaded");
+/// #
+/// // Along `is_a`, the traversal visits the same ancestors as `AncestorNodes`.
+/// let arachnodactyly = TermId::from(("HP", "0001166"));
+/// let idx = hpo.id_to_idx(&arachnodactyly).unwrap();
+/// assert_eq!(
+///     hpo.iter_ancestor_ids_via(&arachnodactyly, &[RelationshipType::IsA]).count(),
+///     hpo.hierarchy().ancestors_of(idx).count(),
+/// );
+///
+/// // HPO has no `part_of` relationships.
+/// let part_of = [RelationshipType::part_of()];
+/// assert_eq!(hpo.iter_ancestor_ids_via(&arachnodactyly, &part_of).count(), 0);
+/// ```
+pub trait RelationshipTraversals: RelationshipAware {
+    /// Iterate over the terms reachable from the term with the `query` ID
+    /// along the edges of the relationship types `via`, from the subject to the object,
+    /// in the breadth-first order.
+    ///
+    /// The query term is not included.
+    /// Returns no terms if the term is not in the ontology.
+    fn iter_ancestor_ids_via(
+        &self,
+        query: &TermId,
+        via: &[RelationshipType],
+    ) -> impl Iterator<Item = &TermId> {
+        traverse(self, query, via, Direction::Up).into_iter()
+    }
+
+    /// Iterate over the terms that reach the term with the `query` ID
+    /// along the edges of the relationship types `via`, from the object to the subject,
+    /// in the breadth-first order.
+    ///
+    /// The query term is not included.
+    /// Returns no terms if the term is not in the ontology.
+    fn iter_descendant_ids_via(
+        &self,
+        query: &TermId,
+        via: &[RelationshipType],
+    ) -> impl Iterator<Item = &TermId> {
+        traverse(self, query, via, Direction::Down).into_iter()
+    }
+}
+
+impl<O: RelationshipAware> RelationshipTraversals for O {}
+
+/// The direction of a traversal along the edges.
+#[derive(Clone, Copy)]
+pub(super) enum Direction {
+    /// From the subject to the object, e.g. from a child to its parents.
+    Up,
+    /// From the object to the subject.
+    Down,
+}
+
+/// Iterate over the terms related to the term at `node` by the relationships
+/// with the `pred`icate, in the `direction`.
+pub(super) fn related<'o, O>(
+    ontology: &'o O,
+    node: O::Idx,
+    pred: &TermId,
+    direction: Direction,
+) -> impl Iterator<Item = O::Idx> + 'o
+where
+    O: RelationshipAware + ?Sized,
+{
+    let relationships = ontology.relationships();
+    let related = ontology
+        .idx_to_term_id(node)
+        .map(|term_id| match direction {
+            Direction::Up => relationships.objects_of(term_id, pred),
+            Direction::Down => relationships.subjects_of(term_id, pred),
+        })
+        .unwrap_or_default();
+    related
+        .iter()
+        .flat_map(move |term_id| ontology.id_to_idx(term_id))
+}
+
+/// Get the terms reachable from the term with the `query` ID along the edges
+/// of the relationship types `via` in the `direction`, in the breadth-first order.
+pub(super) fn traverse<'o, O>(
+    ontology: &'o O,
+    query: &TermId,
+    via: &[RelationshipType],
+    direction: Direction,
+) -> Vec<&'o TermId>
+where
+    O: RelationshipAware + ?Sized,
+{
+    let Some(idx) = ontology.id_to_idx(query) else {
+        return vec![];
+    };
+    let hierarchy = ontology.hierarchy();
+    let is_a = via.contains(&RelationshipType::IsA);
+    let predicates: Vec<_> = via
+        .iter()
+        .filter_map(|kind| match kind {
+            RelationshipType::IsA => None,
+            RelationshipType::Predicate(pred) => Some(pred),
+        })
+        .collect();
+
+    let mut seen = vec![false; ontology.len()];
+    seen[TermIdx::index(idx)] = true;
+    let mut queue = VecDeque::from([idx]);
+    let mut visited = vec![];
+    while let Some(current) = queue.pop_front() {
+        let mut next = vec![];
+        if is_a {
+            match direction {
+                Direction::Up => next.extend(hierarchy.parents_of(current).copied()),
+                Direction::Down => next.extend(hierarchy.children_of(current).copied()),
+            }
+        }
+        for pred in &predicates {
+            next.extend(related(ontology, current, pred, direction));
+        }
+        for node in next {
+            if !std::mem::replace(&mut seen[TermIdx::index(node)], true) {
+                visited.extend(ontology.idx_to_term_id(node));
+                queue.push_back(node);
+            }
+        }
+    }
+    visited
+}
@@ -0,0 +1,305 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::base::TermId;
+use crate::error::OntoliusError;
+use crate::hierarchy::ParentNodes;
+
+use super::{Ontology, TermIdx};
+
+/// The weights of the `is_a` edges, such as the evidence-based confidence
+/// of the inferred relationships, kept alongside an ontology.
+///
+/// The weights are in the `[0, 1]` range and the edges with no weight have the weight of `1`.
+/// The weight of a path is the product of the weights of its edges, and the weight
+/// of the subsumption of a term by its ancestor is the weight of the best path (max-product).
+/// Hence, the weights support the probabilistic queries over an ontology
+/// without changing the ontology itself.
+///
+/// The edges are identified by the indices of the child and the parent,
+/// hence the weights are bound to the ontology they were inserted for.
+///
+/// ## Examples
+///
+/// ```
+/// # use curie_util::TrieCurieUtil;
+/// # use ontolius::io::obographs::ObographsParser;
+/// # use ontolius::ontology::csr::CsrOntology;
+/// use ontolius::ontology::EdgeWeights;
+/// use ontolius::prelude::*;
+///
+/// # let loader = OntologyLoaderBuilder::new()
+/// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+/// #                .build();
+/// # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+/// #                                    .expect("HPO should be loaded");
+/// #
+/// let arachnodactyly = TermId::from(("HP", "0001166"));
+/// let long_fingers = TermId::from(("HP", "0100807"));
+///
+/// let mut weights = EdgeWeights::default();
+/// weights.insert(&hpo, &arachnodactyly, &long_fingers, 0.8)
+///        .expect("The weight should be valid");
+///
+/// assert_eq!(weights.subsumption_weight(&hpo, &arachnodactyly, &long_fingers), 0.8);
+/// assert_eq!(weights.subsumption_weight(&hpo, &arachnodactyly, hpo.root_term_id()), 1.);
+/// assert_eq!(weights.subsumption_weight(&hpo, &long_fingers, &arachnodactyly), 0.);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EdgeWeights {
+    weights: HashMap<(usize, usize), f64>,
+}
+
+impl EdgeWeights {
+    /// Set the `weight` of the `is_a` edge between the `child` and the `parent`.
+    ///
+    /// Returns the previous weight of the edge, or an error if the weight is not in `[0, 1]`
+    /// or if any of the terms is not in the `ontology`.
+    pub fn insert<O>(
+        &mut self,
+        ontology: &O,
+        child: &TermId,
+        parent: &TermId,
+        weight: f64,
+    ) -> Result<Option<f64>, OntoliusError>
+    where
+        O: Ontology,
+    {
+        // The range check rejects NaN as well.
+        if !(0. ..=1.).contains(&weight) {
+            return Err(OntoliusError::Other(format!(
+                "Weight {weight} of the edge between {child} and {parent} is not in [0, 1]"
+            )));
+        }
+        let idx = |term_id: &TermId| {
+            ontology
+                .id_to_idx(term_id)
+                .map(TermIdx::index)
+                .ok_or_else(|| {
+                    OntoliusError::Other(format!("Term {term_id} is not in the ontology"))
+                })
+        };
+        Ok(self.weights.insert((idx(child)?, idx(parent)?), weight))
+    }
+
+    /// Get the weight of the `is_a` edge between the `child` and the `parent`.
+    ///
+    /// Returns `1` if the edge has no weight,
+    /// even if the edge or any of the terms is not in the `ontology`.
+    pub fn weight<O>(&self, ontology: &O, child: &TermId, parent: &TermId) -> f64
+    where
+        O: Ontology,
+    {
+        match (ontology.id_to_idx(child), ontology.id_to_idx(parent)) {
+            (Some(child), Some(parent)) => self.weight_of(child, parent),
+            _ => 1.,
+        }
+    }
+
+    fn weight_of<I: TermIdx>(&self, child: I, parent: I) -> f64 {
+        self.weights
+            .get(&(TermIdx::index(child), TermIdx::index(parent)))
+            .copied()
+            .unwrap_or(1.)
+    }
+
+    /// Get the number of the weighted edges.
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Test if no edges have a weight.
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Get the ancestors of the term with the `term_id` along with the max-product weight
+    /// of the paths from the term, in the order of the decreasing weight.
+    ///
+    /// The ancestors reachable only by the paths with the weight of `0` are not included.
+    /// Returns no ancestors if the term is not in the `ontology`.
+    pub fn ancestor_weights<'o, O>(
+        &self,
+        ontology: &'o O,
+        term_id: &TermId,
+    ) -> Vec<(&'o TermId, f64)>
+    where
+        O: Ontology,
+    {
+        let Some(idx) = ontology.id_to_idx(term_id) else {
+            return vec![];
+        };
+        let hierarchy = ontology.hierarchy();
+
+        // A variant of Dijkstra's algorithm, where the path weight decreases along the path.
+        let mut best = vec![0.; ontology.len()];
+        let mut done = vec![false; ontology.len()];
+        best[TermIdx::index(idx)] = 1.;
+        let mut heap = BinaryHeap::from([Candidate { weight: 1., idx }]);
+        let mut ancestors = vec![];
+        while let Some(Candidate {
+            weight,
+            idx: current,
+        }) = heap.pop()
+        {
+            if std::mem::replace(&mut done[TermIdx::index(current)], true) {
+                continue;
+            }
+            let Some(current_id) = ontology.idx_to_term_id(current) else {
+                continue;
+            };
+            if current != idx {
+                ancestors.push((current_id, weight));
+            }
+            for &parent in hierarchy.parents_of(current) {
+                let candidate = weight * self.weight_of(current, parent);
+                let best = &mut best[TermIdx::index(parent)];
+                if candidate > *best {
+                    *best = candidate;
+                    heap.push(Candidate {
+                        weight: candidate,
+                        idx: parent,
+                    });
+                }
+            }
+        }
+        ancestors
+    }
+
+    /// Get the max-product weight of the paths from the `sub`-term to the `obj`-term.
+    ///
+    /// Returns `1` if the terms are the same and `0` if the `obj`-term is not an ancestor
+    /// of the `sub`-term or if any of the terms is not in the `ontology`.
+    pub fn subsumption_weight<O>(&self, ontology: &O, sub: &TermId, obj: &TermId) -> f64
+    where
+        O: Ontology,
+    {
+        let (Some(sub), Some(obj)) = (ontology.primary_term_id(sub), ontology.primary_term_id(obj))
+        else {
+            return 0.;
+        };
+        if sub == obj {
+            return 1.;
+        }
+        self.ancestor_weights(ontology, sub)
+            .into_iter()
+            .find(|(ancestor, _)| *ancestor == obj)
+            .map_or(0., |(_, weight)| weight)
+    }
+}
+
+impl<I> FromIterator<(I, I, f64)> for EdgeWeights
+where
+    I: TermIdx,
+{
+    /// Collect the `(child, parent, weight)` triples of the term indices,
+    /// clamping the weights to `[0, 1]` and skipping the NaN weights.
+    fn from_iter<T: IntoIterator<Item = (I, I, f64)>>(iter: T) -> Self {
+        Self {
+            weights: iter
+                .into_iter()
+                .filter(|(_, _, weight)| !weight.is_nan())
+                .map(|(child, parent, weight)| {
+                    let edge = (TermIdx::index(child), TermIdx::index(parent));
+                    (edge, weight.clamp(0., 1.))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A node with the weight of the best path found so far, ordered by the weight.
+struct Candidate<I> {
+    weight: f64,
+    idx: I,
+}
+
+impl<I> PartialEq for Candidate<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<I> Eq for Candidate<I> {}
+
+impl<I> PartialOrd for Candidate<I> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<I> Ord for Candidate<I> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.weight.total_cmp(&other.weight)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_ancestor_weights() {
+        let terms: Vec<_> = ["1", "2", "3", "4"]
+            .into_iter()
+            .map(|id| SimpleMinimalTerm::new(TermId::from(("HP", id)), id, vec![], false))
+            .collect();
+        // `HP:4` has two paths to the root, through `HP:2` and through `HP:3`.
+        let ontology = toy_ontology(terms, &[(1, 0), (2, 0), (3, 1), (3, 2)]);
+        let hp = |id: &str| TermId::from(("HP", id));
+
+        let weights: EdgeWeights = [
+            (3, 1, 0.5),
+            (3, 2, 0.9),
+            (2, 0, 0.2),
+            (1, 0, 1.5),
+            (2, 1, f64::NAN),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(weights.len(), 4);
+
+        let ancestors: Vec<_> = weights
+            .ancestor_weights(&ontology, &hp("4"))
+            .into_iter()
+            .map(|(term_id, weight)| (term_id.to_string(), weight))
+            .collect();
+        assert_eq!(
+            ancestors,
+            [
+                ("HP:3".to_string(), 0.9),
+                ("HP:2".to_string(), 0.5),
+                ("HP:1".to_string(), 0.5),
+            ]
+        );
+        assert_eq!(
+            weights.subsumption_weight(&ontology, &hp("4"), &hp("4")),
+            1.
+        );
+        assert_eq!(
+            weights.subsumption_weight(&ontology, &hp("2"), &hp("3")),
+            0.
+        );
+        assert!(weights.ancestor_weights(&ontology, &hp("9")).is_empty());
+
+        let mut weights = EdgeWeights::default();
+        assert!(weights.insert(&ontology, &hp("2"), &hp("1"), 1.1).is_err());
+        assert!(weights
+            .insert(&ontology, &hp("2"), &hp("1"), f64::NAN)
+            .is_err());
+        assert!(weights.insert(&ontology, &hp("9"), &hp("1"), 0.3).is_err());
+        assert_eq!(
+            weights.insert(&ontology, &hp("2"), &hp("1"), 0.3).unwrap(),
+            None
+        );
+        assert_eq!(
+            weights.insert(&ontology, &hp("2"), &hp("1"), 0.4).unwrap(),
+            Some(0.3)
+        );
+        assert_eq!(weights.weight(&ontology, &hp("2"), &hp("1")), 0.4);
+        assert_eq!(weights.weight(&ontology, &hp("3"), &hp("1")), 1.);
+        assert_eq!(weights.len(), 1);
+    }
+}
@@ -1,6 +1,31 @@
 //! The list of recommended imports for using the library.
+//!
+//! The prelude includes the term and the ontology traits, the loader builder,
+//! and the [`CsrOntology`] along with its aliases, hence the examples need a single import
+//! in addition to the parser of the ontology format.
+//!
+//! ## Examples
+//!
+//! ```
+//! use curie_util::TrieCurieUtil;
+//! use ontolius::io::obographs::ObographsParser;
+//! use ontolius::prelude::*;
+//!
+//! let loader = OntologyLoaderBuilder::new()
+//!                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//!                .build();
+//! let hpo: MinimalCsrOntology = loader.load_from_path("resources/hp.small.json.gz")
+//!                                 .expect("HPO should be loaded");
+//!
+//! let arachnodactyly = TermId::from(("HP", "0001166"));
+//! let idx = hpo.id_to_idx(&arachnodactyly).expect("Arachnodactyly should be present");
+//! assert_eq!(hpo.hierarchy().parents_of(idx).count(), 2);
+//! assert!(!hpo.version().is_empty());
+//! ```
 pub use crate::base::term::AltTermIdAware;
 pub use crate::base::term::MinimalTerm;
+pub use crate::base::term::SubsetAware;
+pub use crate::base::term::SynonymAware;
 pub use crate::base::term::Term;
 pub use crate::base::Identified;
 pub use crate::base::TermId;
@@ -18,7 +43,11 @@ pub use crate::io::OntologyData;
 pub use crate::io::OntologyLoader;
 pub use crate::io::OntologyLoaderBuilder;
 
+pub use crate::ontology::csr::{CsrOntology, FullCsrOntology, MinimalCsrOntology};
 pub use crate::ontology::HierarchyAware;
+pub use crate::ontology::MetadataAware;
 pub use crate::ontology::Ontology;
+pub use crate::ontology::RelationshipAware;
+pub use crate::ontology::RelationshipTraversals;
 pub use crate::ontology::TermAware;
 pub use crate::ontology::TermIdx;
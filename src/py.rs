@@ -0,0 +1,228 @@
+//! The `ontolius` Python module.
+//!
+//! The module is organized into submodules:
+//!
+//! * `ontolius.term` with `TermId` (see [`PyTermId`]),
+//! * `ontolius.ontology` with `MinimalOntology` (see [`PyMinimalOntology`]).
+//!
+//! Build the Python package with `maturin build`, see `pyproject.toml`.
+//! Maturin builds the `cdylib` on its own, hence the crate ships only the `rlib` to Rust users.
+use curie_util::TrieCurieUtil;
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyDict};
+
+use crate::base::py::PyTermId;
+use crate::base::term::simple::SimpleMinimalTerm;
+use crate::io::obographs::ObographsParser;
+use crate::ontology::csr::CsrOntology;
+use crate::ontology::MetadataAware;
+use crate::prelude::*;
+
+/// A Python wrapper around [`CsrOntology`] with [`SimpleMinimalTerm`]s.
+///
+/// In Python, the class is denoted as `MinimalOntology` and we can load an ontology
+/// from an Obographs JSON file by running `MinimalOntology.load(path)`.
+/// The terms are referred to by `TermId`s or by CURIE `str`s.
+#[pyclass(name = "MinimalOntology", module = "ontolius.ontology", frozen)]
+pub struct PyMinimalOntology(CsrOntology<usize, SimpleMinimalTerm>);
+
+impl PyMinimalOntology {
+    fn idx(&self, term_id: &PyTermId) -> PyResult<usize> {
+        self.0
+            .id_to_idx(&**term_id)
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown term ID {}", **term_id)))
+    }
+
+    fn to_term_ids<'a, I>(&self, indices: I) -> Vec<PyTermId>
+    where
+        I: Iterator<Item = &'a usize>,
+    {
+        indices
+            .flat_map(|&idx| self.0.idx_to_term_id(idx))
+            .cloned()
+            .map(PyTermId::from)
+            .collect()
+    }
+}
+
+#[pymethods]
+impl PyMinimalOntology {
+    /// Load the ontology from an Obographs JSON file at `path`.
+    ///
+    /// The gzipped file is uncompressed on the fly, as long as the `path` ends with `.gz`.
+    #[staticmethod]
+    fn load(path: &str) -> PyResult<Self> {
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .build();
+        loader
+            .load_from_path(path)
+            .map(PyMinimalOntology)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Get the ontology version.
+    fn version(&self) -> &str {
+        self.0.version()
+    }
+
+    /// Get the term ID of the ontology root.
+    fn root_term_id(&self) -> PyTermId {
+        PyTermId::from(self.0.root_term_id().clone())
+    }
+
+    /// Get the name of the term with the `term_id`, or `None` if the term is unknown.
+    fn term_name(&self, term_id: PyTermId) -> Option<&str> {
+        self.0.id_to_term(&*term_id).map(|term| term.name())
+    }
+
+    /// Get the primary ID of the term with the `term_id`, or `None` if the term is unknown.
+    fn primary_term_id(&self, term_id: PyTermId) -> Option<PyTermId> {
+        self.0.primary_term_id(&*term_id).cloned().map(PyTermId::from)
+    }
+
+    fn parents_of(&self, term_id: PyTermId) -> PyResult<Vec<PyTermId>> {
+        let idx = self.idx(&term_id)?;
+        Ok(self.to_term_ids(self.0.hierarchy().parents_of(idx)))
+    }
+
+    fn children_of(&self, term_id: PyTermId) -> PyResult<Vec<PyTermId>> {
+        let idx = self.idx(&term_id)?;
+        Ok(self.to_term_ids(self.0.hierarchy().children_of(idx)))
+    }
+
+    fn ancestors_of(&self, term_id: PyTermId) -> PyResult<Vec<PyTermId>> {
+        let idx = self.idx(&term_id)?;
+        Ok(self.to_term_ids(self.0.hierarchy().ancestors_of(idx)))
+    }
+
+    fn descendants_of(&self, term_id: PyTermId) -> PyResult<Vec<PyTermId>> {
+        let idx = self.idx(&term_id)?;
+        Ok(self.to_term_ids(self.0.hierarchy().descendants_of(idx)))
+    }
+
+    /// Get the terms as a list of dicts with `term_id`, `name`, `is_obsolete`,
+    /// and `alt_term_ids` keys, e.g. to create `pandas.DataFrame`.
+    ///
+    /// The term IDs are CURIE `str`s.
+    fn term_records<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.0
+            .iter()
+            .map(|term| {
+                let record = PyDict::new_bound(py);
+                record.set_item("term_id", term.identifier().to_string())?;
+                record.set_item("name", term.name())?;
+                record.set_item("is_obsolete", term.is_obsolete())?;
+                let alt_term_ids: Vec<_> = term
+                    .iter_alt_term_ids()
+                    .map(ToString::to_string)
+                    .collect();
+                record.set_item("alt_term_ids", alt_term_ids)?;
+                Ok(record)
+            })
+            .collect()
+    }
+
+    /// Get the transitive closure of the hierarchy as a list of dicts
+    /// with `term_id` and `ancestor_id` keys, e.g. to create `pandas.DataFrame`.
+    ///
+    /// Each current term is paired with each of its ancestors, and with itself if `include_self` is set.
+    /// The term IDs are CURIE `str`s.
+    #[pyo3(signature = (include_self = false))]
+    fn closure_records<'py>(
+        &self,
+        py: Python<'py>,
+        include_self: bool,
+    ) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let hierarchy = self.0.hierarchy();
+        let mut records = vec![];
+        for (idx, term) in self.0.iter().enumerate() {
+            if term.is_obsolete() {
+                continue;
+            }
+            let term_id = term.identifier().to_string();
+            let ancestors = hierarchy
+                .ancestors_of(idx)
+                .copied()
+                .chain(include_self.then_some(idx));
+            for ancestor in ancestors {
+                if let Some(ancestor_id) = self.0.idx_to_term_id(ancestor) {
+                    let record = PyDict::new_bound(py);
+                    record.set_item("term_id", &term_id)?;
+                    record.set_item("ancestor_id", ancestor_id.to_string())?;
+                    records.push(record);
+                }
+            }
+        }
+        Ok(records)
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    fn __contains__(&self, term_id: PyTermId) -> bool {
+        self.0.id_to_idx(&*term_id).is_some()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MinimalOntology(root={}, terms={})",
+            self.0.root_term_id(),
+            self.0.len()
+        )
+    }
+}
+
+/// The `ontolius` Python module.
+#[pymodule]
+fn ontolius(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let term = PyModule::new_bound(m.py(), "term")?;
+    term.add_class::<PyTermId>()?;
+    add_submodule(m, &term)?;
+
+    let ontology = PyModule::new_bound(m.py(), "ontology")?;
+    ontology.add_class::<PyMinimalOntology>()?;
+    add_submodule(m, &ontology)?;
+
+    Ok(())
+}
+
+/// Add the `submodule` to the `parent` module.
+///
+/// We register the submodule in `sys.modules` as well,
+/// to support the imports such as `from ontolius.term import TermId`.
+fn add_submodule(parent: &Bound<'_, PyModule>, submodule: &Bound<'_, PyModule>) -> PyResult<()> {
+    parent.add_submodule(submodule)?;
+    let name = format!("{}.{}", parent.name()?, submodule.name()?);
+    parent
+        .py()
+        .import_bound("sys")?
+        .getattr("modules")?
+        .set_item(name, submodule)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::TermId;
+
+    use super::*;
+
+    fn hp(id: &str) -> PyTermId {
+        PyTermId::from(TermId::from(("HP", id)))
+    }
+
+    #[test]
+    fn test_load_contains_ancestors() {
+        let ontology = PyMinimalOntology::load("resources/hp.small.json.gz").unwrap();
+        assert!(ontology.__len__() > 0);
+
+        assert!(ontology.__contains__(hp("0001166")));
+        assert!(!ontology.__contains__(hp("9999999")));
+
+        let ancestors = ontology.ancestors_of(hp("0001166")).unwrap();
+        assert!(ancestors.contains(&ontology.root_term_id()));
+        assert!(ancestors.contains(&hp("0001238")));
+
+        assert!(ontology.ancestors_of(hp("9999999")).is_err());
+    }
+}
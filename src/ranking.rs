@@ -0,0 +1,231 @@
+//! Rank the terms (or other entities with a [`TermId`], such as the diseases) by a score,
+//! e.g. the outputs of the similarity searches or of the disease matchers.
+//!
+//! The terms are ranked from the highest to the lowest score, and the terms with the same
+//! score are ordered by the CURIE (see [`LexOrdered`]). Hence, the ranking is deterministic,
+//! regardless of the order of the input.
+//!
+//! ## Examples
+//!
+//! ```
+//! use ontolius::prelude::*;
+//! use ontolius::ranking::{rank_all, top_k};
+//!
+//! let scores = vec![
+//!     (TermId::from(("HP", "0000003")), 0.5),
+//!     (TermId::from(("HP", "0000002")), 0.9),
+//!     (TermId::from(("HP", "0000001")), 0.5),
+//! ];
+//!
+//! let ranked = rank_all(scores.clone());
+//! let curies: Vec<_> = ranked.iter().map(|r| r.term_id().to_string()).collect();
+//! assert_eq!(curies, ["HP:0000002", "HP:0000001", "HP:0000003"]);
+//! assert_eq!(ranked[2].rank(), 3);
+//!
+//! // Select the best terms without sorting all of them.
+//! let best = top_k(scores, 2);
+//! assert_eq!(&best[..], &ranked[..2]);
+//! ```
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::base::{LexOrdered, TermId};
+
+/// A score that can be ranked.
+pub trait Score: Copy {
+    /// Compare the scores, with the greater score being better.
+    fn cmp_score(&self, other: &Self) -> Ordering;
+}
+
+/// The `NaN` scores are worse than any other score.
+impl Score for f64 {
+    fn cmp_score(&self, other: &Self) -> Ordering {
+        match (self.is_nan(), other.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => self.total_cmp(other),
+        }
+    }
+}
+
+/// The `NaN` scores are worse than any other score.
+impl Score for f32 {
+    fn cmp_score(&self, other: &Self) -> Ordering {
+        match (self.is_nan(), other.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => self.total_cmp(other),
+        }
+    }
+}
+
+macro_rules! impl_score_for_ord {
+    ($($t:ty),*) => {
+        $(
+            impl Score for $t {
+                fn cmp_score(&self, other: &Self) -> Ordering {
+                    self.cmp(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_score_for_ord!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Compare the scored terms in the ranking order: the better score first,
+/// and the lexicographically smaller CURIE first among the equal scores.
+pub fn cmp_ranked<S>(left: (&TermId, &S), right: (&TermId, &S)) -> Ordering
+where
+    S: Score,
+{
+    right
+        .1
+        .cmp_score(left.1)
+        .then_with(|| LexOrdered(left.0).cmp(&LexOrdered(right.0)))
+}
+
+/// A term with a score and its 1-based rank.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ranked<S> {
+    rank: usize,
+    term_id: TermId,
+    score: S,
+}
+
+impl<S> Ranked<S> {
+    /// Get the 1-based rank. The ranks are unique, since the ties are broken by the CURIE.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Get the ID of the ranked term.
+    pub fn term_id(&self) -> &TermId {
+        &self.term_id
+    }
+
+    /// Get the score.
+    pub fn score(&self) -> &S {
+        &self.score
+    }
+}
+
+/// Rank all `scored` terms.
+pub fn rank_all<S, I>(scored: I) -> Vec<Ranked<S>>
+where
+    S: Score,
+    I: IntoIterator<Item = (TermId, S)>,
+{
+    let mut scored: Vec<_> = scored.into_iter().collect();
+    scored.sort_by(|left, right| cmp_ranked((&left.0, &left.1), (&right.0, &right.1)));
+    into_ranked(scored)
+}
+
+/// Get the `k` best of the `scored` terms, in the ranking order.
+///
+/// Keeps at most `k` terms in memory, hence it is cheaper than [`rank_all`]
+/// when `k` is much smaller than the number of the terms.
+pub fn top_k<S, I>(scored: I, k: usize) -> Vec<Ranked<S>>
+where
+    S: Score,
+    I: IntoIterator<Item = (TermId, S)>,
+{
+    if k == 0 {
+        return vec![];
+    }
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for (term_id, score) in scored {
+        heap.push(HeapEntry { term_id, score });
+        if heap.len() > k {
+            // Drop the worst term.
+            heap.pop();
+        }
+    }
+    let best = heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|entry| (entry.term_id, entry.score))
+        .collect();
+    into_ranked(best)
+}
+
+fn into_ranked<S>(sorted: Vec<(TermId, S)>) -> Vec<Ranked<S>> {
+    sorted
+        .into_iter()
+        .enumerate()
+        .map(|(i, (term_id, score))| Ranked {
+            rank: i + 1,
+            term_id,
+            score,
+        })
+        .collect()
+}
+
+/// An entry of the top-k heap, ordered so that the worst term is the greatest.
+struct HeapEntry<S> {
+    term_id: TermId,
+    score: S,
+}
+
+impl<S: Score> PartialEq for HeapEntry<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl<S: Score> Eq for HeapEntry<S> {}
+
+impl<S: Score> PartialOrd for HeapEntry<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: Score> Ord for HeapEntry<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_ranked((&self.term_id, &self.score), (&other.term_id, &other.score))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_top_k_agrees_with_rank_all() {
+        let scores: Vec<_> = [3., f64::NAN, 1., 3., 2., f64::INFINITY, 2.]
+            .into_iter()
+            .enumerate()
+            .map(|(i, score)| (TermId::from(("HP", (10 - i).to_string().as_str())), score))
+            .collect();
+
+        let ranked = rank_all(scores.clone());
+        let curies: Vec<_> = ranked.iter().map(|r| r.term_id().to_string()).collect();
+        assert_eq!(
+            curies,
+            ["HP:5", "HP:10", "HP:7", "HP:4", "HP:6", "HP:8", "HP:9"]
+        );
+        assert!(ranked[6].score().is_nan());
+
+        for k in 0..=8 {
+            let best = top_k(scores.clone(), k);
+            assert_eq!(best.len(), k.min(scores.len()));
+            for (top, all) in best.iter().zip(&ranked) {
+                assert_eq!(top.rank(), all.rank());
+                assert_eq!(top.term_id(), all.term_id());
+            }
+        }
+
+        let counts = top_k(
+            [
+                (TermId::from(("HP", "1")), 4u32),
+                (TermId::from(("HP", "2")), 7u32),
+            ],
+            1,
+        );
+        assert_eq!(counts[0].term_id(), &TermId::from(("HP", "2")));
+    }
+}
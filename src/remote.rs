@@ -0,0 +1,372 @@
+//! Query ontology terms from the [OLS4](https://www.ebi.ac.uk/ols4) REST API
+//! without downloading the full ontology.
+//!
+//! [`OlsClient`] gets the terms and their hierarchy neighbors on demand
+//! and caches the responses in memory. Therefore, each term or hierarchy query
+//! is sent to the server at most once during the lifetime of the client.
+//!
+//! Unlike [`crate::ontology::Ontology`], the client returns owned values,
+//! because the terms are not known upfront. Therefore, the client does not implement
+//! the ontology traits, such as [`crate::ontology::TermAware`]
+//! or [`crate::ontology::HierarchyAware`], which lend the terms and the hierarchy.
+//!
+//! ## Examples
+//!
+//! ```no_run
+//! use ontolius::base::TermId;
+//! use ontolius::prelude::*;
+//! use ontolius::remote::OlsClient;
+//!
+//! let client = OlsClient::new("hp");
+//!
+//! let seizure = TermId::from(("HP", "0001250"));
+//! let term = client.term(&seizure)
+//!                  .expect("OLS4 should be reachable")
+//!                  .expect("Seizure should be in HPO");
+//! assert_eq!(term.name(), "Seizure");
+//!
+//! let parents = client.parents(&seizure).expect("OLS4 should be reachable");
+//! assert!(!parents.is_empty());
+//! ```
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use serde::Deserialize;
+
+use crate::base::term::simple::SimpleMinimalTerm;
+use crate::base::TermId;
+use crate::error::OntoliusError;
+
+/// The base URL of the public OLS4 API.
+pub const OLS4_API_URL: &str = "https://www.ebi.ac.uk/ols4/api";
+
+/// The number of terms requested in a single page of the hierarchy responses.
+const PAGE_SIZE: usize = 500;
+
+/// The hierarchy neighbors supported by OLS4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Walk {
+    Parents,
+    Children,
+    Ancestors,
+    Descendants,
+}
+
+impl Walk {
+    fn path(&self) -> &'static str {
+        match self {
+            Walk::Parents => "parents",
+            Walk::Children => "children",
+            Walk::Ancestors => "ancestors",
+            Walk::Descendants => "descendants",
+        }
+    }
+}
+
+/// A caching client of the OLS4 REST API for a single ontology.
+pub struct OlsClient {
+    base_url: String,
+    ontology: String,
+    agent: ureq::Agent,
+    terms: Mutex<HashMap<TermId, Option<SimpleMinimalTerm>>>,
+    walks: Mutex<HashMap<(TermId, Walk), Vec<TermId>>>,
+}
+
+impl OlsClient {
+    /// Create a client for the `ontology` (e.g. `hp`) of the public OLS4 instance.
+    pub fn new<T: ToString>(ontology: T) -> Self {
+        OlsClient::with_base_url(OLS4_API_URL, ontology)
+    }
+
+    /// Create a client for the `ontology` of an OLS4 instance
+    /// with the API at `base_url` (e.g. `https://www.ebi.ac.uk/ols4/api`).
+    pub fn with_base_url<U: ToString, T: ToString>(base_url: U, ontology: T) -> Self {
+        OlsClient {
+            base_url: base_url.to_string().trim_end_matches('/').to_string(),
+            ontology: ontology.to_string().to_lowercase(),
+            agent: ureq::Agent::new(),
+            terms: Mutex::new(HashMap::new()),
+            walks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the term for the primary term `id`
+    /// or `None` if the term is not in the ontology.
+    pub fn term(&self, id: &TermId) -> Result<Option<SimpleMinimalTerm>, OntoliusError> {
+        if let Some(term) = lock(&self.terms).get(id) {
+            return Ok(term.clone());
+        }
+
+        let term = match self.get(&self.term_url(id, None))? {
+            Some(body) => Some(parse_term(&body, id)?),
+            None => None,
+        };
+        lock(&self.terms).insert(id.clone(), term.clone());
+
+        Ok(term)
+    }
+
+    /// Get the IDs of the parents of the term with `id`.
+    pub fn parents(&self, id: &TermId) -> Result<Vec<TermId>, OntoliusError> {
+        self.walk(id, Walk::Parents)
+    }
+
+    /// Get the IDs of the children of the term with `id`.
+    pub fn children(&self, id: &TermId) -> Result<Vec<TermId>, OntoliusError> {
+        self.walk(id, Walk::Children)
+    }
+
+    /// Get the IDs of the ancestors of the term with `id`.
+    ///
+    /// The term itself is not included.
+    pub fn ancestors(&self, id: &TermId) -> Result<Vec<TermId>, OntoliusError> {
+        self.walk(id, Walk::Ancestors)
+    }
+
+    /// Get the IDs of the descendants of the term with `id`.
+    ///
+    /// The term itself is not included.
+    pub fn descendants(&self, id: &TermId) -> Result<Vec<TermId>, OntoliusError> {
+        self.walk(id, Walk::Descendants)
+    }
+
+    fn walk(&self, id: &TermId, walk: Walk) -> Result<Vec<TermId>, OntoliusError> {
+        let key = (id.clone(), walk);
+        if let Some(ids) = lock(&self.walks).get(&key) {
+            return Ok(ids.clone());
+        }
+
+        let mut ids = Vec::new();
+        let mut page = 0;
+        loop {
+            let url = self.term_url(id, Some(walk));
+            let url = format!("{url}?size={PAGE_SIZE}&page={page}");
+            let Some(body) = self.get(&url)? else {
+                // Unknown term has no neighbors.
+                break;
+            };
+            let (terms, total_pages) = parse_term_page(&body)?;
+            // The terms without a CURIE, such as `owl:Thing`, are skipped.
+            ids.extend(terms.iter().filter_map(OlsTerm::term_id));
+
+            page += 1;
+            if page >= total_pages {
+                break;
+            }
+        }
+        lock(&self.walks).insert(key, ids.clone());
+
+        Ok(ids)
+    }
+
+    fn term_url(&self, id: &TermId, walk: Option<Walk>) -> String {
+        // OLS4 expects the term IRI to be URL-encoded twice.
+        let iri = encode(&encode(&obo_iri(id)));
+        let url = format!(
+            "{}/ontologies/{}/terms/{}",
+            self.base_url, self.ontology, iri
+        );
+        match walk {
+            Some(walk) => format!("{url}/{}", walk.path()),
+            None => url,
+        }
+    }
+
+    /// Get the response body or `None` if the resource does not exist.
+    fn get(&self, url: &str) -> Result<Option<String>, OntoliusError> {
+        match self.agent.get(url).call() {
+            Ok(response) => response
+                .into_string()
+                .map(Some)
+                .map_err(|e| OntoliusError::Other(e.to_string())),
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(e) => Err(OntoliusError::Other(e.to_string())),
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    // The cache stays consistent even if another thread panicked.
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[derive(Deserialize)]
+struct OlsTerm {
+    obo_id: Option<String>,
+    short_form: Option<String>,
+    label: Option<String>,
+    #[serde(default)]
+    is_obsolete: bool,
+}
+
+impl OlsTerm {
+    /// Get the term ID from the `obo_id` or from the `short_form` (e.g. `HP_0001250`),
+    /// or `None` if the term has no ID in the CURIE form.
+    fn term_id(&self) -> Option<TermId> {
+        let from_obo_id = self
+            .obo_id
+            .as_deref()
+            .and_then(|curie| TermId::from_str(curie).ok());
+        from_obo_id.or_else(|| {
+            let (prefix, local) = self.short_form.as_deref()?.split_once('_')?;
+            TermId::from_str(&format!("{prefix}:{local}")).ok()
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct OlsTermPage {
+    #[serde(rename = "_embedded")]
+    embedded: Option<OlsTerms>,
+    page: Option<OlsPageInfo>,
+}
+
+#[derive(Deserialize)]
+struct OlsTerms {
+    terms: Vec<OlsTerm>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OlsPageInfo {
+    total_pages: usize,
+}
+
+/// Parse the term requested by the `id`.
+///
+/// The requested `id` is used if the response has no term ID in the CURIE form.
+fn parse_term(body: &str, id: &TermId) -> Result<SimpleMinimalTerm, OntoliusError> {
+    let term: OlsTerm =
+        serde_json::from_str(body).map_err(|e| OntoliusError::Other(e.to_string()))?;
+    let term_id = term.term_id().unwrap_or_else(|| id.clone());
+
+    Ok(SimpleMinimalTerm::new(
+        term_id,
+        term.label.unwrap_or_default(),
+        vec![],
+        term.is_obsolete,
+    ))
+}
+
+fn parse_term_page(body: &str) -> Result<(Vec<OlsTerm>, usize), OntoliusError> {
+    let page: OlsTermPage =
+        serde_json::from_str(body).map_err(|e| OntoliusError::Other(e.to_string()))?;
+    let terms = page.embedded.map(|e| e.terms).unwrap_or_default();
+    let total_pages = page.page.map_or(1, |p| p.total_pages);
+
+    Ok((terms, total_pages))
+}
+
+fn obo_iri(id: &TermId) -> String {
+    let curie = id.to_string();
+    let (prefix, local) = curie.split_once(':').unwrap_or((&curie, ""));
+    format!("http://purl.obolibrary.org/obo/{prefix}_{local}")
+}
+
+/// Percent-encode all but the unreserved characters of RFC 3986.
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(char::from(byte))
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::{term::MinimalTerm, Identified};
+
+    #[test]
+    fn test_term_url() {
+        let client = OlsClient::with_base_url("http://localhost:8080/api/", "HP");
+        let seizure = TermId::from(("HP", "0001250"));
+
+        assert_eq!(
+            client.term_url(&seizure, Some(Walk::Parents)),
+            "http://localhost:8080/api/ontologies/hp/terms/\
+            http%253A%252F%252Fpurl.obolibrary.org%252Fobo%252FHP_0001250/parents"
+        );
+    }
+
+    #[test]
+    fn test_parse_term() {
+        let body = r#"{
+            "iri": "http://purl.obolibrary.org/obo/HP_0001250",
+            "label": "Seizure",
+            "obo_id": "HP:0001250",
+            "is_obsolete": false
+        }"#;
+
+        let seizure = TermId::from(("HP", "0001250"));
+        let term = parse_term(body, &seizure).expect("Term should be parsed");
+
+        assert_eq!(term.identifier(), &seizure);
+        assert_eq!(term.name(), "Seizure");
+        assert!(term.is_current());
+
+        let body = r#"{"label": "Seizure", "obo_id": null}"#;
+        let term = parse_term(body, &seizure).expect("Term should be parsed");
+
+        assert_eq!(term.identifier(), &seizure);
+    }
+
+    #[test]
+    fn test_parse_term_page() {
+        let body = r#"{
+            "_embedded": {
+                "terms": [
+                    {"label": "Abnormal nervous system physiology", "obo_id": "HP:0012638"}
+                ]
+            },
+            "page": {"size": 500, "totalElements": 1, "totalPages": 1, "number": 0}
+        }"#;
+
+        let (terms, total_pages) = parse_term_page(body).expect("Page should be parsed");
+
+        assert_eq!(total_pages, 1);
+        assert_eq!(terms.len(), 1);
+        assert_eq!(terms[0].obo_id.as_deref(), Some("HP:0012638"));
+    }
+
+    #[test]
+    fn test_page_term_ids() {
+        let body = r#"{
+            "_embedded": {
+                "terms": [
+                    {"label": "Phenotypic abnormality", "obo_id": "HP:0000118", "short_form": "HP_0000118"},
+                    {"label": "All", "obo_id": null, "short_form": "HP_0000001"},
+                    {"label": "Thing", "obo_id": null, "short_form": "Thing"}
+                ]
+            },
+            "page": {"size": 500, "totalElements": 3, "totalPages": 1, "number": 0}
+        }"#;
+
+        let (terms, _) = parse_term_page(body).expect("Page should be parsed");
+        let term_ids: Vec<_> = terms.iter().filter_map(OlsTerm::term_id).collect();
+
+        assert_eq!(
+            term_ids,
+            [
+                TermId::from(("HP", "0000118")),
+                TermId::from(("HP", "0000001"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_term_page() {
+        let body = r#"{"page": {"size": 500, "totalElements": 0, "totalPages": 0, "number": 0}}"#;
+
+        let (terms, total_pages) = parse_term_page(body).expect("Page should be parsed");
+
+        assert!(terms.is_empty());
+        assert_eq!(total_pages, 0);
+    }
+}
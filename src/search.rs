@@ -0,0 +1,515 @@
+//! Find ontology terms by name, e.g. for autocomplete.
+//!
+//! [`Searcher`] matches a text query against the names of the current terms
+//! (and their synonyms, see [`Searcher::with_synonyms`])
+//! and ranks the matches with a [`RankingPolicy`].
+//! By default, the matches are ranked by the string score alone.
+//! Use [`WeightedRanking`] to prefer the specific terms (with high information content)
+//! or to keep only the terms of selected branches of the ontology.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//! use ontolius::search::{Searcher, WeightedRanking};
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                    .expect("HPO should be loaded");
+//! #
+//! let searcher = Searcher::new(&hpo);
+//!
+//! // Rank by the string score.
+//! let hits = searcher.search("arachnodactyly", 10);
+//! assert_eq!(hits[0].term().name(), "Arachnodactyly");
+//!
+//! // Keep only the terms of the Phenotypic abnormality branch.
+//! let policy = WeightedRanking::default()
+//!                 .within(TermId::from(("HP", "0000118")));
+//! let hits = searcher.search_with("finger", 10, &policy);
+//! assert!(!hits.is_empty());
+//! ```
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::base::term::{MinimalTerm, Synonym, SynonymAware};
+use crate::base::TermId;
+use crate::hierarchy::{AncestorNodes, ChildNodes, HierarchyIdx, OntologyHierarchy};
+use crate::ontology::{HierarchyAware, IcOntology, Ontology, TermAware, TermIdx};
+
+/// A term matching the search query.
+pub struct Hit<'o, O>
+where
+    O: Ontology,
+{
+    idx: O::Idx,
+    term: &'o O::T,
+    score: f64,
+}
+
+impl<'o, O> Hit<'o, O>
+where
+    O: Ontology,
+{
+    /// Get the index of the matching term.
+    pub fn idx(&self) -> O::Idx {
+        self.idx
+    }
+
+    /// Get the matching term.
+    pub fn term(&self) -> &'o O::T {
+        self.term
+    }
+
+    /// Get the score assigned by the [`RankingPolicy`]. The greater, the better.
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+/// Policy for ranking the terms that match the search query.
+pub trait RankingPolicy<O: Ontology> {
+    /// Compute the score of the term at `idx` that matched the query with `string_score`.
+    ///
+    /// The string score is in `(0, 1]`, where `1` is an exact match.
+    /// The `ontology` provides the intrinsic IC of the terms, computed once by [`Searcher::new`].
+    /// Return `None` to exclude the term from the results.
+    fn rank(&self, ontology: &IcOntology<'_, O>, idx: O::Idx, string_score: f64) -> Option<f64>;
+}
+
+/// Rank the matches by the string score alone.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StringRanking;
+
+impl<O: Ontology> RankingPolicy<O> for StringRanking {
+    fn rank(&self, _: &IcOntology<'_, O>, _: O::Idx, string_score: f64) -> Option<f64> {
+        Some(string_score)
+    }
+}
+
+/// Rank the matches by a weighted sum of the string score and of the term IC,
+/// optionally keeping only the terms of selected branches.
+///
+/// The information content (IC) is the term specificity, the intrinsic IC normalized to `[0, 1]`,
+/// where `1` corresponds to a leaf term (see [`IcOntology::specificity`]).
+#[derive(Debug, Clone)]
+pub struct WeightedRanking {
+    string_weight: f64,
+    ic_weight: f64,
+    branches: Vec<TermId>,
+}
+
+impl Default for WeightedRanking {
+    /// Rank by the string score and by the IC with the weights `0.8` and `0.2`, respectively.
+    fn default() -> Self {
+        Self {
+            string_weight: 0.8,
+            ic_weight: 0.2,
+            branches: vec![],
+        }
+    }
+}
+
+impl WeightedRanking {
+    /// Set the weight of the string score.
+    #[must_use]
+    pub fn string_weight(mut self, weight: f64) -> Self {
+        self.string_weight = weight;
+        self
+    }
+
+    /// Set the weight of the term IC.
+    #[must_use]
+    pub fn ic_weight(mut self, weight: f64) -> Self {
+        self.ic_weight = weight;
+        self
+    }
+
+    /// Keep only the term with `branch` ID and its descendants.
+    ///
+    /// The terms are kept if they belong to any of the branches, if called multiple times.
+    #[must_use]
+    pub fn within(mut self, branch: TermId) -> Self {
+        self.branches.push(branch);
+        self
+    }
+}
+
+impl<O: Ontology> RankingPolicy<O> for WeightedRanking {
+    fn rank(&self, ontology: &IcOntology<'_, O>, idx: O::Idx, string_score: f64) -> Option<f64> {
+        if !self.branches.is_empty() {
+            let hierarchy = ontology.hierarchy();
+            let in_branch = self
+                .branches
+                .iter()
+                .filter_map(|branch| ontology.id_to_idx(branch))
+                .any(|branch| branch == idx || hierarchy.is_descendant_of(idx, branch));
+            if !in_branch {
+                return None;
+            }
+        }
+
+        let ic = ontology.specificity(idx).unwrap_or(0.);
+        Some(self.string_weight * string_score + self.ic_weight * ic)
+    }
+}
+
+/// A label shared by several current terms.
+pub struct DuplicateLabel<'o, O>
+where
+    O: Ontology,
+{
+    label: String,
+    terms: Vec<&'o O::T>,
+}
+
+impl<'o, O> DuplicateLabel<'o, O>
+where
+    O: Ontology,
+{
+    /// Get the lowercase label.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the terms with the label in the order of their indices.
+    pub fn terms(&self) -> &[&'o O::T] {
+        &self.terms
+    }
+}
+
+/// A term that may correspond to an ambiguous label,
+/// with the context for choosing among the candidates.
+pub struct Candidate<'o, O>
+where
+    O: Ontology,
+{
+    idx: O::Idx,
+    term: &'o O::T,
+    branches: Vec<&'o O::T>,
+    ic: f64,
+}
+
+impl<'o, O> Candidate<'o, O>
+where
+    O: Ontology,
+{
+    /// Get the index of the candidate term.
+    pub fn idx(&self) -> O::Idx {
+        self.idx
+    }
+
+    /// Get the candidate term.
+    pub fn term(&self) -> &'o O::T {
+        self.term
+    }
+
+    /// Get the top-level branches (the children of the ontology root)
+    /// that include the candidate term.
+    pub fn branches(&self) -> &[&'o O::T] {
+        &self.branches
+    }
+
+    /// Get the specificity of the candidate term, i.e. its intrinsic IC normalized to `[0, 1]`.
+    pub fn ic(&self) -> f64 {
+        self.ic
+    }
+}
+
+/// Searches the names, and optionally the synonyms, of the current ontology terms.
+pub struct Searcher<'o, O>
+where
+    O: Ontology,
+{
+    ontology: &'o O,
+    ic: IcOntology<'o, O>,
+    // The lowercase names (and synonyms) of the current terms, in the order of the term indices.
+    names: Vec<(O::Idx, String)>,
+}
+
+impl<'o, O> Searcher<'o, O>
+where
+    O: Ontology,
+{
+    /// Create a searcher for the `ontology`.
+    ///
+    /// The intrinsic IC of the terms is computed here, once, for ranking the matches.
+    pub fn new(ontology: &'o O) -> Self {
+        let names = ontology
+            .iter_terms()
+            .enumerate()
+            .filter(|(_, term)| term.is_current())
+            .map(|(idx, term)| (HierarchyIdx::new(idx), term.name().to_lowercase()))
+            .collect();
+
+        Self::from_names(ontology, names)
+    }
+
+    /// Create a searcher that matches the names and the synonyms of the `ontology` terms.
+    ///
+    /// A term matched by several labels is reported once, with the best string score.
+    pub fn with_synonyms(ontology: &'o O) -> Self
+    where
+        O::T: SynonymAware,
+    {
+        let names = ontology
+            .iter_terms()
+            .enumerate()
+            .filter(|(_, term)| term.is_current())
+            .flat_map(|(idx, term)| {
+                let mut labels: Vec<_> = std::iter::once(term.name())
+                    .chain(term.iter_synonyms().map(Synonym::name))
+                    .map(str::to_lowercase)
+                    .collect();
+                labels.sort_unstable();
+                labels.dedup();
+                labels
+                    .into_iter()
+                    .map(move |label| (HierarchyIdx::new(idx), label))
+            })
+            .collect();
+
+        Self::from_names(ontology, names)
+    }
+
+    fn from_names(ontology: &'o O, names: Vec<(O::Idx, String)>) -> Self {
+        Self {
+            ontology,
+            ic: IcOntology::new(ontology),
+            names,
+        }
+    }
+
+    /// Get at most `limit` best matches of the `query` ranked by the string score.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<Hit<'o, O>> {
+        self.search_with(query, limit, &StringRanking)
+    }
+
+    /// Get at most `limit` best matches of the `query` ranked by the `policy`.
+    ///
+    /// The matches with the same score are sorted by the term index.
+    pub fn search_with<P>(&self, query: &str, limit: usize, policy: &P) -> Vec<Hit<'o, O>>
+    where
+        P: RankingPolicy<O> + ?Sized,
+    {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return vec![];
+        }
+
+        // The best string score of each term.
+        let mut string_scores: BTreeMap<O::Idx, f64> = BTreeMap::new();
+        for (idx, name) in &self.names {
+            if let Some(score) = string_score(&query, name) {
+                let best = string_scores.entry(*idx).or_insert(score);
+                *best = best.max(score);
+            }
+        }
+
+        let mut hits: Vec<_> = string_scores
+            .into_iter()
+            .filter_map(|(idx, string_score)| {
+                let score = policy.rank(&self.ic, idx, string_score)?;
+                let term = self.ontology.idx_to_term(idx)?;
+                Some(Hit { idx, term, score })
+            })
+            .collect();
+
+        hits.sort_by(|left, right| {
+            right
+                .score
+                .partial_cmp(&left.score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| TermIdx::index(left.idx).cmp(&TermIdx::index(right.idx)))
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    /// List the labels shared by several current terms, sorted by the label.
+    ///
+    /// The labels are compared case-insensitively.
+    /// The labels include the synonyms if the searcher was created by [`Searcher::with_synonyms`].
+    pub fn duplicate_labels(&self) -> Vec<DuplicateLabel<'o, O>> {
+        let mut groups: BTreeMap<&str, Vec<O::Idx>> = BTreeMap::new();
+        for (idx, name) in &self.names {
+            groups.entry(name.as_str()).or_default().push(*idx);
+        }
+
+        groups
+            .into_iter()
+            .filter(|(_, indices)| indices.len() > 1)
+            .map(|(label, indices)| DuplicateLabel {
+                label: label.to_string(),
+                terms: indices
+                    .into_iter()
+                    .filter_map(|idx| self.ontology.idx_to_term(idx))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Get all current terms with the `label`, with the context for disambiguation.
+    ///
+    /// The label is compared case-insensitively, to the names and to the indexed synonyms.
+    /// The candidates are sorted by decreasing IC, the most specific term first.
+    pub fn resolve(&self, label: &str) -> Vec<Candidate<'o, O>> {
+        let label = label.trim().to_lowercase();
+        let hierarchy = self.ontology.hierarchy();
+        let top_level: Vec<_> = hierarchy.children_of(*hierarchy.root()).copied().collect();
+
+        let mut candidates: Vec<_> = self
+            .names
+            .iter()
+            .filter(|(_, name)| *name == label)
+            .filter_map(|&(idx, _)| {
+                let branches = top_level
+                    .iter()
+                    .filter(|&&branch| branch == idx || hierarchy.is_descendant_of(idx, branch))
+                    .filter_map(|&branch| self.ontology.idx_to_term(branch))
+                    .collect();
+                Some(Candidate {
+                    idx,
+                    term: self.ontology.idx_to_term(idx)?,
+                    branches,
+                    ic: self.ic.specificity(idx).unwrap_or(0.),
+                })
+            })
+            .collect();
+
+        candidates.sort_by(|left, right| right.ic.partial_cmp(&left.ic).unwrap_or(Ordering::Equal));
+        candidates
+    }
+}
+
+/// Score the match of the lowercase `query` and `name`.
+///
+/// The exact matches score `1`, followed by the prefix matches and the other substring matches.
+/// The shorter names score higher within the groups.
+fn string_score(query: &str, name: &str) -> Option<f64> {
+    let coverage = query.len() as f64 / name.len() as f64;
+    if name == query {
+        Some(1.)
+    } else if name.starts_with(query) {
+        Some(0.5 + 0.4 * coverage)
+    } else if name.contains(query) {
+        Some(0.1 + 0.4 * coverage)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::base::term::simple::{SimpleMinimalTerm, SimpleTerm};
+    use crate::base::term::SynonymCategory;
+    use crate::base::Identified;
+    use crate::ontology::csr::CsrOntology;
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_string_score() {
+        let exact = string_score("seizure", "seizure").unwrap();
+        let prefix = string_score("seizure", "seizure cluster").unwrap();
+        let infix = string_score("seizure", "focal seizure").unwrap();
+
+        assert_eq!(exact, 1.);
+        assert!(exact > prefix);
+        assert!(prefix > infix);
+        assert!(string_score("seizure", "tremor").is_none());
+    }
+
+    #[test]
+    fn test_duplicate_labels() {
+        let ontology = build_ontology();
+        let searcher = Searcher::new(&ontology);
+
+        let duplicates = searcher.duplicate_labels();
+
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].label(), "severe");
+        let ids: Vec<_> = duplicates[0]
+            .terms()
+            .iter()
+            .map(|term| term.identifier().to_string())
+            .collect();
+        assert_eq!(ids, ["HP:4", "HP:5"]);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let ontology = build_ontology();
+        let searcher = Searcher::new(&ontology);
+
+        let candidates = searcher.resolve("Severe");
+
+        assert_eq!(candidates.len(), 2);
+        let branches: Vec<_> = candidates
+            .iter()
+            .map(|candidate| candidate.branches()[0].name())
+            .collect();
+        assert!(branches.contains(&"Phenotypic abnormality"));
+        assert!(branches.contains(&"Clinical modifier"));
+        let ic = ontology.with_intrinsic_ic();
+        assert!(candidates
+            .iter()
+            .all(|candidate| Some(candidate.ic()) == ic.specificity(candidate.idx())));
+        assert!(searcher.resolve("Mild").is_empty());
+    }
+
+    #[test]
+    fn test_search_synonyms() {
+        let terms = vec![
+            SimpleTerm::new(TermId::from(("HP", "1")), "All", vec![], false),
+            SimpleTerm::new(TermId::from(("HP", "2")), "Long fingers", vec![], false),
+            SimpleTerm::new(TermId::from(("HP", "3")), "Arachnodactyly", vec![], false)
+                .with_synonyms(vec![
+                    Synonym::new("Spider fingers", SynonymCategory::Exact, vec![]),
+                    Synonym::new("arachnodactyly", SynonymCategory::Exact, vec![]),
+                ]),
+        ];
+        let ontology = toy_ontology(terms, &[(1, 0), (2, 1)]);
+
+        let searcher = Searcher::with_synonyms(&ontology);
+        let names = |query| -> Vec<_> {
+            searcher
+                .search(query, 10)
+                .iter()
+                .map(|hit| hit.term().name())
+                .collect()
+        };
+        assert_eq!(names("spider"), ["Arachnodactyly"]);
+        assert_eq!(names("arachno"), ["Arachnodactyly"]);
+        assert_eq!(names("fingers"), ["Long fingers", "Arachnodactyly"]);
+        let candidates = searcher.resolve("Spider fingers");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].term().name(), "Arachnodactyly");
+        assert!(searcher.duplicate_labels().is_empty());
+
+        assert!(Searcher::new(&ontology).search("spider", 10).is_empty());
+    }
+
+    fn build_ontology() -> CsrOntology<usize, SimpleMinimalTerm> {
+        let terms: Vec<_> = [
+            ("HP:1", "All"),
+            ("HP:2", "Phenotypic abnormality"),
+            ("HP:3", "Clinical modifier"),
+            ("HP:4", "Severe"),
+            ("HP:5", "severe"),
+        ]
+        .into_iter()
+        .map(|(curie, name)| {
+            SimpleMinimalTerm::new(TermId::from_str(curie).unwrap(), name, vec![], false)
+        })
+        .collect();
+        toy_ontology(terms, &[(1, 0), (2, 0), (3, 1), (4, 2)])
+    }
+}
@@ -0,0 +1,447 @@
+//! Request/response data transfer objects (DTOs) and handlers
+//! for putting an ontology behind a web API.
+//!
+//! The handlers are independent of the web framework. They take a request DTO,
+//! query an [`Ontology`], and return a response DTO or a [`ServiceError`].
+//! The DTOs can be (de)serialized with `serde`, e.g. into JSON.
+//!
+//! The hierarchy handlers return the terms in pages.
+//! The cursor of the next page is included in the response.
+//! See [`crate::hierarchy::Paginate`] for more details.
+//!
+//! The search handler takes a [`Searcher`] and the similarity handler takes an [`IcOntology`],
+//! to build the search index and to compute the information content once, when the service starts.
+//!
+//! Check out the `axum_service` example for serving an ontology with `axum`:
+//!
+//! ```shell
+//! cargo run --example axum_service --features service
+//! ```
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//! use ontolius::service::{lookup_term, TermRequest};
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                    .expect("HPO should be loaded");
+//! #
+//! let request = TermRequest { id: "HP:0001166".into() };
+//! let response = lookup_term(&hpo, &request).expect("Arachnodactyly should be present");
+//!
+//! assert_eq!(response.term.name, "Arachnodactyly");
+//! ```
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::base::{term::MinimalTerm, TermId};
+use crate::hierarchy::{AncestorNodes, DescendantNodes, HierarchyIdx, Page, Paginate};
+use crate::ontology::{HierarchyAware, IcOntology, Ontology, TermAware, TermIdx};
+use crate::search::Searcher;
+
+/// The default number of terms in a page.
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+
+/// The errors returned by the service handlers.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ServiceError {
+    /// Returned when the term ID is not a valid CURIE.
+    /// Corresponds to `400 Bad Request`.
+    #[error("Invalid CURIE {0}")]
+    InvalidCurie(String),
+
+    /// Returned when the term ID is not in the ontology.
+    /// Corresponds to `404 Not Found`.
+    #[error("Unknown term {0}")]
+    UnknownTerm(String),
+
+    /// Returned when the page cursor is not an index of an ontology term.
+    /// Corresponds to `400 Bad Request`.
+    #[error("Invalid cursor {0}")]
+    InvalidCursor(usize),
+
+    /// Returned when the page limit is `0`.
+    /// Corresponds to `400 Bad Request`.
+    #[error("The page limit must be positive")]
+    InvalidLimit,
+}
+
+/// An ontology term.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TermDto {
+    /// The primary term ID CURIE (e.g. `HP:0001250`).
+    pub id: String,
+    /// The term name (e.g. `Seizure`).
+    pub name: String,
+    /// The CURIEs of the alternative term IDs.
+    pub alt_ids: Vec<String>,
+    /// `true` if the term is obsolete.
+    pub is_obsolete: bool,
+}
+
+impl<T: MinimalTerm> From<&T> for TermDto {
+    fn from(term: &T) -> Self {
+        Self {
+            id: term.identifier().to_string(),
+            name: term.name().to_string(),
+            alt_ids: term.iter_alt_term_ids().map(ToString::to_string).collect(),
+            is_obsolete: term.is_obsolete(),
+        }
+    }
+}
+
+/// Request for a single term.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TermRequest {
+    /// The term ID CURIE. Both primary and alternative term IDs are supported.
+    pub id: String,
+}
+
+/// Response with a single term.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TermResponse {
+    pub term: TermDto,
+}
+
+/// Request for a page of the terms related to a term, such as its ancestors.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HierarchyRequest {
+    /// The term ID CURIE. Both primary and alternative term IDs are supported.
+    pub id: String,
+    /// The cursor from the previous page or `None` for the first page.
+    #[serde(default)]
+    pub cursor: Option<usize>,
+    /// The maximum number of terms in a page. Must be positive.
+    #[serde(default = "default_page_limit")]
+    pub limit: usize,
+}
+
+fn default_page_limit() -> usize {
+    DEFAULT_PAGE_LIMIT
+}
+
+/// Response with a page of terms.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TermPageResponse {
+    pub terms: Vec<TermDto>,
+    /// The cursor for getting the next page or `None` for the last page.
+    pub next_cursor: Option<usize>,
+}
+
+/// Request for the terms whose names match a query.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SearchRequest {
+    /// The query, e.g. `arachno`.
+    pub query: String,
+    /// The maximum number of hits.
+    #[serde(default = "default_page_limit")]
+    pub limit: usize,
+}
+
+/// A term matching the search query.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SearchHitDto {
+    pub term: TermDto,
+    /// The score of the match. The greater, the better.
+    pub score: f64,
+}
+
+/// Response with the search hits, the best hit first.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SearchResponse {
+    pub hits: Vec<SearchHitDto>,
+}
+
+/// Request for the semantic similarity of two terms.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SimilarityRequest {
+    /// The term ID CURIE of the first term.
+    pub left: String,
+    /// The term ID CURIE of the second term.
+    pub right: String,
+}
+
+/// Response with the semantic similarity of two terms.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SimilarityResponse {
+    /// The most informative common ancestor (MICA) of the terms,
+    /// or `None` if the terms have no common ancestor.
+    pub mica: Option<TermDto>,
+    /// The Resnik similarity, i.e. the intrinsic IC of the MICA.
+    pub resnik: f64,
+}
+
+/// Response with an error.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+impl From<&ServiceError> for ErrorResponse {
+    fn from(value: &ServiceError) -> Self {
+        Self {
+            error: value.to_string(),
+        }
+    }
+}
+
+/// Get a term by its primary or alternative ID.
+pub fn lookup_term<O>(ontology: &O, request: &TermRequest) -> Result<TermResponse, ServiceError>
+where
+    O: Ontology,
+{
+    let idx = resolve_idx(ontology, &request.id)?;
+    let term = ontology
+        .idx_to_term(idx)
+        .ok_or_else(|| ServiceError::UnknownTerm(request.id.clone()))?;
+
+    Ok(TermResponse {
+        term: TermDto::from(term),
+    })
+}
+
+/// Get the current terms whose names match the query, the best match first.
+pub fn search<O>(searcher: &Searcher<'_, O>, request: &SearchRequest) -> SearchResponse
+where
+    O: Ontology,
+{
+    SearchResponse {
+        hits: searcher
+            .search(&request.query, request.limit)
+            .into_iter()
+            .map(|hit| SearchHitDto {
+                term: TermDto::from(hit.term()),
+                score: hit.score(),
+            })
+            .collect(),
+    }
+}
+
+/// Get the Resnik similarity of two terms.
+///
+/// The similarity is the intrinsic IC of the most informative common ancestor (MICA) of the terms,
+/// where a term is considered to be its own ancestor.
+/// See [`IcOntology`] for the definition of the intrinsic IC.
+pub fn similarity<O>(
+    ontology: &IcOntology<'_, O>,
+    request: &SimilarityRequest,
+) -> Result<SimilarityResponse, ServiceError>
+where
+    O: Ontology,
+{
+    let left = resolve_idx(ontology, &request.left)?;
+    let right = resolve_idx(ontology, &request.right)?;
+
+    let hierarchy = ontology.hierarchy();
+    let left_ancestors: BTreeSet<_> = hierarchy
+        .ancestors_of(left)
+        .copied()
+        .chain([left])
+        .collect();
+    let mica = hierarchy
+        .ancestors_of(right)
+        .copied()
+        .chain([right])
+        .filter(|idx| left_ancestors.contains(idx))
+        .filter_map(|idx| ontology.intrinsic_ic(idx).map(|ic| (idx, ic)))
+        .max_by(|(_, left), (_, right)| left.total_cmp(right));
+
+    Ok(match mica {
+        Some((idx, ic)) => SimilarityResponse {
+            mica: ontology.idx_to_term(idx).map(TermDto::from),
+            resnik: ic,
+        },
+        None => SimilarityResponse {
+            mica: None,
+            resnik: 0.,
+        },
+    })
+}
+
+/// Get a page of the ancestors of a term.
+pub fn ancestors<O>(
+    ontology: &O,
+    request: &HierarchyRequest,
+) -> Result<TermPageResponse, ServiceError>
+where
+    O: Ontology,
+{
+    let idx = resolve_idx(ontology, &request.id)?;
+    let page = ontology
+        .hierarchy()
+        .ancestors_of(idx)
+        .page(resolve_cursor(ontology, request)?, resolve_limit(request)?);
+
+    Ok(to_page_response(ontology, page))
+}
+
+/// Get a page of the descendants of a term.
+pub fn descendants<O>(
+    ontology: &O,
+    request: &HierarchyRequest,
+) -> Result<TermPageResponse, ServiceError>
+where
+    O: Ontology,
+{
+    let idx = resolve_idx(ontology, &request.id)?;
+    let page = ontology
+        .hierarchy()
+        .descendants_of(idx)
+        .page(resolve_cursor(ontology, request)?, resolve_limit(request)?);
+
+    Ok(to_page_response(ontology, page))
+}
+
+fn resolve_idx<O>(ontology: &O, curie: &str) -> Result<O::Idx, ServiceError>
+where
+    O: Ontology,
+{
+    let term_id =
+        TermId::from_str(curie).map_err(|_| ServiceError::InvalidCurie(curie.to_string()))?;
+    ontology
+        .id_to_idx(&term_id)
+        .ok_or_else(|| ServiceError::UnknownTerm(curie.to_string()))
+}
+
+fn resolve_cursor<O>(
+    ontology: &O,
+    request: &HierarchyRequest,
+) -> Result<Option<O::Idx>, ServiceError>
+where
+    O: Ontology,
+{
+    // A cursor is the index of a term, hence it fits into the index type of the ontology.
+    request
+        .cursor
+        .map(|cursor| {
+            if cursor < ontology.len() {
+                Ok(HierarchyIdx::new(cursor))
+            } else {
+                Err(ServiceError::InvalidCursor(cursor))
+            }
+        })
+        .transpose()
+}
+
+fn resolve_limit(request: &HierarchyRequest) -> Result<usize, ServiceError> {
+    match request.limit {
+        0 => Err(ServiceError::InvalidLimit),
+        limit => Ok(limit),
+    }
+}
+
+fn to_page_response<O>(ontology: &O, page: Page<O::Idx>) -> TermPageResponse
+where
+    O: Ontology,
+{
+    TermPageResponse {
+        terms: page
+            .items()
+            .iter()
+            .flat_map(|&idx| ontology.idx_to_term(idx))
+            .map(TermDto::from)
+            .collect(),
+        next_cursor: page.next_cursor().map(TermIdx::index),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curie_util::TrieCurieUtil;
+
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::io::obographs::ObographsParser;
+    use crate::io::OntologyLoaderBuilder;
+    use crate::ontology::csr::CsrOntology;
+
+    fn load_hpo() -> CsrOntology<u16, SimpleMinimalTerm> {
+        OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .build()
+            .load_from_path("resources/hp.small.json.gz")
+            .expect("HPO should be loaded")
+    }
+
+    #[test]
+    fn test_invalid_cursor() {
+        let hpo = load_hpo();
+        let request = HierarchyRequest {
+            id: "HP:0001166".into(),
+            cursor: Some(usize::MAX),
+            limit: DEFAULT_PAGE_LIMIT,
+        };
+
+        assert_eq!(
+            ancestors(&hpo, &request),
+            Err(ServiceError::InvalidCursor(usize::MAX))
+        );
+        assert_eq!(
+            descendants(&hpo, &request),
+            Err(ServiceError::InvalidCursor(usize::MAX))
+        );
+    }
+
+    #[test]
+    fn test_zero_limit() {
+        let hpo = load_hpo();
+        let request = HierarchyRequest {
+            id: "HP:0001166".into(),
+            cursor: None,
+            limit: 0,
+        };
+
+        assert_eq!(ancestors(&hpo, &request), Err(ServiceError::InvalidLimit));
+    }
+
+    #[test]
+    fn test_search() {
+        let hpo = load_hpo();
+        let searcher = Searcher::new(&hpo);
+        let request = SearchRequest {
+            query: "Arachnodactyly".into(),
+            limit: 5,
+        };
+
+        let response = search(&searcher, &request);
+
+        assert_eq!(response.hits[0].term.id, "HP:0001166");
+        assert!(response.hits.len() <= 5);
+    }
+
+    #[test]
+    fn test_similarity() {
+        let hpo = load_hpo();
+        let ic = hpo.with_intrinsic_ic();
+        let request = |left: &str, right: &str| SimilarityRequest {
+            left: left.into(),
+            right: right.into(),
+        };
+
+        // Arachnodactyly is a Long fingers.
+        let response = similarity(&ic, &request("HP:0001166", "HP:0100807")).unwrap();
+        assert_eq!(response.mica.unwrap().id, "HP:0100807");
+        assert_eq!(
+            Some(response.resnik),
+            ic.intrinsic_ic_of(&TermId::from(("HP", "0100807")))
+        );
+
+        let response = similarity(&ic, &request("HP:0001166", "HP:0001166")).unwrap();
+        assert_eq!(response.mica.unwrap().id, "HP:0001166");
+
+        assert_eq!(
+            similarity(&ic, &request("HP:0001166", "HP:9999999")),
+            Err(ServiceError::UnknownTerm("HP:9999999".into()))
+        );
+    }
+}
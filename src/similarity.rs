@@ -0,0 +1,371 @@
+//! Compute the all-vs-all similarity matrices, e.g. of the diseases or of the genes,
+//! in parallel with `rayon`.
+//!
+//! The computation over a large corpus can take days, hence [`SimilarityMatrixBuilder`]
+//! can save the computed rows into a checkpoint file, and resume from the file
+//! after an interruption instead of starting over.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! use ontolius::annotation::hpoa::{read_hpoa, DiseaseProfile, DiseaseProfileBuilder};
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//! use ontolius::similarity::SimilarityMatrixBuilder;
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                    .expect("HPO should be loaded");
+//! let file = std::fs::File::open("resources/phenotype.real-shortlist.hpoa").unwrap();
+//! let records = read_hpoa(std::io::BufReader::new(file)).expect("Annotations should be readable");
+//! let profiles = DiseaseProfileBuilder::new().build(&records, &hpo);
+//!
+//! // The Jaccard index of the annotated phenotypes.
+//! let jaccard = |left: &DiseaseProfile, right: &DiseaseProfile| {
+//!     let shared = left.iter().filter(|(hpo_id, _)| right.frequency(hpo_id).is_some()).count();
+//!     shared as f64 / (left.len() + right.len() - shared) as f64
+//! };
+//!
+//! let checkpoint = std::env::temp_dir().join(format!("diseases.{}.sim", std::process::id()));
+//! let matrix = SimilarityMatrixBuilder::new(&profiles)
+//!                 .checkpoint(&checkpoint)
+//!                 .compute(jaccard)
+//!                 .expect("The similarities should be computed");
+//!
+//! assert_eq!(matrix.len(), profiles.len());
+//! assert_eq!(matrix.get(0, 0), 1.);
+//! assert_eq!(matrix.get(0, 1), matrix.get(1, 0));
+//!
+//! // The next run reads the similarities from the checkpoint.
+//! let resumed = SimilarityMatrixBuilder::new(&profiles)
+//!                 .checkpoint(&checkpoint)
+//!                 .compute(|_, _| unreachable!("All rows are in the checkpoint"))
+//!                 .unwrap();
+//! assert_eq!(resumed, matrix);
+//! # std::fs::remove_file(&checkpoint).unwrap();
+//! ```
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::base::{Identified, TermId};
+use crate::error::OntoliusError;
+use crate::ontology::fingerprint::StableHasher;
+
+/// The bytes at the start of each checkpoint file.
+const MAGIC: &[u8; 8] = b"ONTOLSIM";
+
+/// The version of the checkpoint file format.
+const FORMAT_VERSION: u32 = 1;
+
+/// The length of the checkpoint header: the magic bytes, the format version,
+/// the number of the items, and the hash of the item IDs.
+const HEADER_LEN: usize = 8 + 4 + 8 + 8;
+
+/// A symmetric matrix of the similarities between all pairs of the items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimilarityMatrix {
+    ids: Vec<TermId>,
+    /// The rows of the upper triangle, including the diagonal.
+    values: Vec<f64>,
+}
+
+impl SimilarityMatrix {
+    /// Get the IDs of the items, in the order of the rows and the columns.
+    pub fn ids(&self) -> &[TermId] {
+        &self.ids
+    }
+
+    /// Get the number of the items.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Test if the matrix has no items.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Get the similarity between the `i`th and the `j`th item.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if an index is out of bounds.
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        let n = self.len();
+        assert!(i < n && j < n, "The index must be less than {n}");
+        let (i, j) = if i <= j { (i, j) } else { (j, i) };
+        self.values[row_offset(n, i) + j - i]
+    }
+}
+
+/// The offset of the `row` in the upper triangle of `n` items.
+fn row_offset(n: usize, row: usize) -> usize {
+    row * n - row * (row.saturating_sub(1)) / 2
+}
+
+/// Builder of the [`SimilarityMatrix`] of the items.
+///
+/// The rows of the matrix are computed in parallel. With a checkpoint file, the rows
+/// are saved in batches, `256` rows by default, and the rows found in the file are not computed again.
+pub struct SimilarityMatrixBuilder<'a, T> {
+    items: &'a [T],
+    checkpoint: Option<PathBuf>,
+    rows_per_checkpoint: usize,
+}
+
+impl<'a, T> SimilarityMatrixBuilder<'a, T>
+where
+    T: Identified + Sync,
+{
+    pub fn new(items: &'a [T]) -> Self {
+        Self {
+            items,
+            checkpoint: None,
+            rows_per_checkpoint: 256,
+        }
+    }
+
+    /// Save the computed rows into the checkpoint file at `path`,
+    /// and resume from the file if it exists.
+    ///
+    /// The file is tied to the IDs of the items, and it is kept when the computation finishes.
+    #[must_use]
+    pub fn checkpoint<P>(mut self, path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        self.checkpoint = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set the number of the rows computed between saving the checkpoints.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `rows` is `0`.
+    #[must_use]
+    pub fn rows_per_checkpoint(mut self, rows: usize) -> Self {
+        assert!(rows > 0, "The number of rows must be positive");
+        self.rows_per_checkpoint = rows;
+        self
+    }
+
+    /// Compute the `similarity` of all pairs of the items.
+    ///
+    /// The `similarity` must be symmetric, since it is computed once for each pair.
+    ///
+    /// Returns an error if the checkpoint file cannot be read or written,
+    /// or if it was written for other items.
+    pub fn compute<F>(&self, similarity: F) -> Result<SimilarityMatrix, OntoliusError>
+    where
+        F: Fn(&T, &T) -> f64 + Sync,
+    {
+        let ids: Vec<TermId> = self
+            .items
+            .iter()
+            .map(|item| item.identifier().clone())
+            .collect();
+        let n = ids.len();
+        let mut values = vec![0.; row_offset(n, n)];
+        let mut done = vec![false; n];
+
+        let mut writer = match &self.checkpoint {
+            Some(path) => Some(open_checkpoint(path, &ids, &mut values, &mut done)?),
+            None => None,
+        };
+
+        let remaining: Vec<_> = (0..n).filter(|&row| !done[row]).collect();
+        for batch in remaining.chunks(self.rows_per_checkpoint) {
+            let rows: Vec<(usize, Vec<f64>)> = batch
+                .par_iter()
+                .map(|&row| {
+                    let values = self.items[row..]
+                        .iter()
+                        .map(|other| similarity(&self.items[row], other))
+                        .collect();
+                    (row, values)
+                })
+                .collect();
+
+            if let Some(writer) = writer.as_mut() {
+                for (row, row_values) in rows.iter() {
+                    writer
+                        .write_all(&(*row as u64).to_le_bytes())
+                        .and_then(|_| {
+                            row_values
+                                .iter()
+                                .try_for_each(|value| writer.write_all(&value.to_le_bytes()))
+                        })
+                        .map_err(write_error)?;
+                }
+                writer.flush().map_err(write_error)?;
+                writer.get_ref().sync_data().map_err(write_error)?;
+            }
+
+            for (row, row_values) in rows {
+                let offset = row_offset(n, row);
+                values[offset..offset + row_values.len()].copy_from_slice(&row_values);
+            }
+        }
+
+        Ok(SimilarityMatrix { ids, values })
+    }
+}
+
+/// Read the rows of the checkpoint file at `path` into `values`, and mark them as `done`.
+///
+/// Creates the file if it does not exist, and drops an incomplete row at the end of the file,
+/// e.g. if the previous run was interrupted while saving the checkpoint.
+/// Returns the writer to append the new rows to.
+fn open_checkpoint(
+    path: &Path,
+    ids: &[TermId],
+    values: &mut [f64],
+    done: &mut [bool],
+) -> Result<BufWriter<File>, OntoliusError> {
+    let n = ids.len();
+    let mut hasher = StableHasher::new();
+    ids.iter().for_each(|id| hasher.write_str(&id.to_string()));
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    header.extend_from_slice(MAGIC);
+    header.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    header.extend_from_slice(&(n as u64).to_le_bytes());
+    header.extend_from_slice(&hasher.finish().to_le_bytes());
+
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(|e| {
+            OntoliusError::Other(format!(
+                "Cannot open the checkpoint file {}: {e}",
+                path.display()
+            ))
+        })?;
+    let file_len = file.metadata().map_err(read_error)?.len();
+
+    let mut valid_len = HEADER_LEN as u64;
+    if file_len == 0 {
+        let mut write = &file;
+        write.write_all(&header).map_err(write_error)?;
+    } else {
+        let mut read = BufReader::new(&file);
+        let mut existing = [0; HEADER_LEN];
+        read.read_exact(&mut existing).map_err(read_error)?;
+        if existing[..] != header[..] {
+            return Err(OntoliusError::Other(format!(
+                "The checkpoint file {} was written for other items",
+                path.display()
+            )));
+        }
+
+        let mut index = [0; 8];
+        loop {
+            match read.read_exact(&mut index) {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(read_error(e)),
+            }
+            let row = u64::from_le_bytes(index) as usize;
+            if row >= n {
+                return Err(OntoliusError::Other(format!(
+                    "The checkpoint file {} has an invalid row {row}",
+                    path.display()
+                )));
+            }
+            let mut bytes = vec![0; (n - row) * 8];
+            match read.read_exact(&mut bytes) {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(read_error(e)),
+            }
+            let offset = row_offset(n, row);
+            for (value, chunk) in values[offset..].iter_mut().zip(bytes.chunks_exact(8)) {
+                *value =
+                    f64::from_le_bytes(chunk.try_into().expect("The chunk should have 8 bytes"));
+            }
+            done[row] = true;
+            valid_len += 8 + bytes.len() as u64;
+        }
+    }
+
+    file.set_len(valid_len).map_err(write_error)?;
+    let mut writer = BufWriter::new(file);
+    std::io::Seek::seek(&mut writer, std::io::SeekFrom::End(0)).map_err(write_error)?;
+    Ok(writer)
+}
+
+fn write_error(e: std::io::Error) -> OntoliusError {
+    OntoliusError::Other(format!("Cannot write the checkpoint file: {e}"))
+}
+
+fn read_error(e: std::io::Error) -> OntoliusError {
+    OntoliusError::Other(format!("Cannot read the checkpoint file: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let terms: Vec<_> = (1..=5)
+            .map(|i| {
+                let id = i.to_string();
+                SimpleMinimalTerm::new(TermId::from(("HP", id.as_str())), "Term", vec![], false)
+            })
+            .collect();
+        let value =
+            |term: &SimpleMinimalTerm| term.identifier().to_string()[3..].parse::<f64>().unwrap();
+        let calls = AtomicUsize::new(0);
+        let similarity = |left: &SimpleMinimalTerm, right: &SimpleMinimalTerm| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            value(left) * value(right)
+        };
+        let path = std::env::temp_dir().join(format!("ontolius-sim-{}.bin", std::process::id()));
+
+        let matrix = SimilarityMatrixBuilder::new(&terms)
+            .checkpoint(&path)
+            .rows_per_checkpoint(2)
+            .compute(similarity)
+            .unwrap();
+        assert_eq!(calls.load(Ordering::Relaxed), 15);
+        assert_eq!(matrix.get(1, 3), 8.);
+        assert_eq!(matrix.get(3, 1), 8.);
+        assert_eq!(matrix.get(4, 4), 25.);
+
+        // Drop the last row and a part of the row before, as if the run was interrupted.
+        let len = std::fs::metadata(&path).unwrap().len();
+        let file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len(len - 8 - 8 - 4).unwrap();
+        drop(file);
+
+        calls.store(0, Ordering::Relaxed);
+        let resumed = SimilarityMatrixBuilder::new(&terms)
+            .checkpoint(&path)
+            .compute(similarity)
+            .unwrap();
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert_eq!(resumed, matrix);
+
+        // The checkpoint of other items is rejected.
+        assert!(SimilarityMatrixBuilder::new(&terms[1..])
+            .checkpoint(&path)
+            .compute(similarity)
+            .is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
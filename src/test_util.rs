@@ -0,0 +1,25 @@
+//! Fixtures shared by the unit tests.
+use std::collections::HashMap;
+
+use crate::base::term::MinimalTerm;
+use crate::hierarchy::{GraphEdge, Relationship};
+use crate::io::OntologyData;
+use crate::ontology::csr::CsrOntology;
+
+/// Build a [`CsrOntology`] from the `terms` and the `is_a` edges,
+/// each given as a `(child, parent)` pair of term indices.
+pub(crate) fn toy_ontology<T>(terms: Vec<T>, edges: &[(usize, usize)]) -> CsrOntology<usize, T>
+where
+    T: MinimalTerm,
+{
+    let edges: Vec<_> = edges
+        .iter()
+        .map(|&(child, parent)| GraphEdge::from((child, Relationship::Child, parent)))
+        .collect();
+    let data = OntologyData::from((
+        terms.into_boxed_slice(),
+        edges.into_boxed_slice(),
+        HashMap::new(),
+    ));
+    CsrOntology::try_from(data).expect("Toy ontology should be built")
+}
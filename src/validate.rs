@@ -0,0 +1,301 @@
+//! Validate the CURIEs of a CSV column against an ontology.
+//!
+//! The validation reports the CURIEs that are malformed, that have an unexpected prefix,
+//! that are unknown to the ontology, or that refer to obsolete terms.
+//! If the table includes a column with the term labels,
+//! the labels that differ from the term names are reported as well.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use curie_util::TrieCurieUtil;
+//! # use ontolius::io::obographs::ObographsParser;
+//! # use ontolius::ontology::csr::CsrOntology;
+//! use ontolius::prelude::*;
+//! use ontolius::validate::{CurieColumnValidator, Issue};
+//!
+//! # let loader = OntologyLoaderBuilder::new()
+//! #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+//! #                .build();
+//! # let hpo: CsrOntology<usize, _> = loader.load_from_path("resources/hp.small.json.gz")
+//! #                                    .expect("HPO should be loaded");
+//! #
+//! let table = "\
+//! patient,phenotype,label
+//! A,HP:0001166,Arachnodactyly
+//! B,HP:0001166,Long fingers
+//! C,MONDO:0007947,Marfan syndrome
+//! D,HP:9999999,Unknown
+//! ";
+//!
+//! let findings = CurieColumnValidator::new("phenotype")
+//!                  .label_column("label")
+//!                  .validate(table.as_bytes(), &hpo)
+//!                  .expect("Table should be readable");
+//!
+//! assert_eq!(findings.len(), 3);
+//! assert_eq!(findings[0].row(), 2);
+//! assert!(matches!(findings[0].issue(), Issue::LabelMismatch { .. }));
+//! assert_eq!(findings[1].issue(), &Issue::WrongPrefix);
+//! assert_eq!(findings[2].issue(), &Issue::Unknown);
+//! ```
+use std::fmt::Display;
+use std::io::Read;
+use std::str::FromStr;
+
+use crate::base::{term::MinimalTerm, Identified, TermId};
+use crate::error::OntoliusError;
+use crate::ontology::Ontology;
+
+/// An issue of a CURIE found by the validation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Issue {
+    /// The value is not a CURIE.
+    Malformed,
+    /// The CURIE prefix is not among the expected prefixes.
+    WrongPrefix,
+    /// The ontology has no term with the CURIE.
+    Unknown,
+    /// The CURIE refers to an obsolete term, or it is an alternate ID
+    /// of the term with the `primary` ID.
+    Obsolete { primary: Option<TermId> },
+    /// The supplied `label` differs from the `expected` name of the term.
+    LabelMismatch { label: String, expected: String },
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::Malformed => write!(f, "malformed CURIE"),
+            Issue::WrongPrefix => write!(f, "wrong prefix"),
+            Issue::Unknown => write!(f, "unknown term"),
+            Issue::Obsolete {
+                primary: Some(primary),
+            } => {
+                write!(f, "obsolete term, replaced by {primary}")
+            }
+            Issue::Obsolete { primary: None } => write!(f, "obsolete term"),
+            Issue::LabelMismatch { label, expected } => {
+                write!(f, "label mismatch: {label:?} != {expected:?}")
+            }
+        }
+    }
+}
+
+/// An issue of the CURIE at a row of the table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Finding {
+    row: usize,
+    curie: String,
+    issue: Issue,
+}
+
+impl Finding {
+    /// Get the 1-based index of the data row, excluding the header.
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    /// Get the value of the CURIE column.
+    pub fn curie(&self) -> &str {
+        &self.curie
+    }
+
+    /// Get the issue.
+    pub fn issue(&self) -> &Issue {
+        &self.issue
+    }
+}
+
+/// Validate the CURIEs of a CSV column against an ontology.
+///
+/// By default, the table is comma-separated, the CURIEs are expected to have
+/// the prefix of the ontology root (e.g. `HP`), and the labels are not checked.
+/// The empty values are skipped.
+pub struct CurieColumnValidator {
+    column: String,
+    label_column: Option<String>,
+    prefixes: Option<Vec<String>>,
+    delimiter: u8,
+}
+
+impl CurieColumnValidator {
+    /// Create a validator of the CURIE `column`.
+    pub fn new(column: impl ToString) -> Self {
+        Self {
+            column: column.to_string(),
+            label_column: None,
+            prefixes: None,
+            delimiter: b',',
+        }
+    }
+
+    /// Check the labels of the `label_column` against the names of the terms.
+    #[must_use]
+    pub fn label_column(mut self, label_column: impl ToString) -> Self {
+        self.label_column = Some(label_column.to_string());
+        self
+    }
+
+    /// Set the expected CURIE `prefixes` (e.g. `HP`).
+    #[must_use]
+    pub fn prefixes<I, S>(mut self, prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        self.prefixes = Some(prefixes.into_iter().map(|p| p.to_string()).collect());
+        self
+    }
+
+    /// Set the field delimiter, e.g. `b'\t'` for TSV.
+    #[must_use]
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Read the table from the `reader` and validate the CURIEs against the `ontology`.
+    ///
+    /// Returns the findings in the order of the rows, or an error
+    /// if the table cannot be read or if it lacks a configured column.
+    pub fn validate<R, O>(&self, reader: R, ontology: &O) -> Result<Vec<Finding>, OntoliusError>
+    where
+        R: Read,
+        O: Ontology,
+    {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .from_reader(reader);
+        let headers = reader.headers().map_err(csv_error)?;
+        let curie_idx = find_column(headers, &self.column)?;
+        let label_idx = match &self.label_column {
+            Some(label_column) => Some(find_column(headers, label_column)?),
+            None => None,
+        };
+        let root_prefix = [ontology.root_term_id().prefix().to_string()];
+        let prefixes = self.prefixes.as_deref().unwrap_or(&root_prefix);
+
+        let mut findings = vec![];
+        for (row, record) in reader.records().enumerate() {
+            let record = record.map_err(csv_error)?;
+            let curie = record.get(curie_idx).unwrap_or_default().trim();
+            if curie.is_empty() {
+                continue;
+            }
+            let label = label_idx.and_then(|idx| record.get(idx));
+            if let Some(issue) = check(ontology, prefixes, curie, label) {
+                findings.push(Finding {
+                    row: row + 1,
+                    curie: curie.to_string(),
+                    issue,
+                });
+            }
+        }
+
+        Ok(findings)
+    }
+}
+
+/// Validate the CURIEs of the CSV `column` against the `ontology`
+/// with the default [`CurieColumnValidator`].
+pub fn validate_curie_column<R, O>(
+    reader: R,
+    column: &str,
+    ontology: &O,
+) -> Result<Vec<Finding>, OntoliusError>
+where
+    R: Read,
+    O: Ontology,
+{
+    CurieColumnValidator::new(column).validate(reader, ontology)
+}
+
+/// Check the `curie` and the optional `label`, and return the first issue found.
+fn check<O>(ontology: &O, prefixes: &[String], curie: &str, label: Option<&str>) -> Option<Issue>
+where
+    O: Ontology,
+{
+    let term_id = match TermId::from_str(curie) {
+        Ok(term_id) => term_id,
+        Err(_) => return Some(Issue::Malformed),
+    };
+    if !prefixes.iter().any(|prefix| prefix == term_id.prefix()) {
+        return Some(Issue::WrongPrefix);
+    }
+    let term = match ontology.id_to_term(&term_id) {
+        Some(term) => term,
+        None => return Some(Issue::Unknown),
+    };
+    if term.is_obsolete() {
+        return Some(Issue::Obsolete { primary: None });
+    }
+    if term.identifier() != &term_id {
+        return Some(Issue::Obsolete {
+            primary: Some(term.identifier().clone()),
+        });
+    }
+    match label.map(str::trim) {
+        Some(label) if !label.is_empty() && label != term.name() => Some(Issue::LabelMismatch {
+            label: label.to_string(),
+            expected: term.name().to_string(),
+        }),
+        _ => None,
+    }
+}
+
+fn find_column(headers: &csv::StringRecord, column: &str) -> Result<usize, OntoliusError> {
+    headers
+        .iter()
+        .position(|header| header == column)
+        .ok_or_else(|| OntoliusError::Other(format!("Column {column} not found")))
+}
+
+fn csv_error(e: csv::Error) -> OntoliusError {
+    OntoliusError::Other(format!("Cannot read the table: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::test_util::toy_ontology;
+
+    #[test]
+    fn test_validate() {
+        let terms = vec![
+            SimpleMinimalTerm::new(TermId::from(("HP", "1")), "Root", vec![], false),
+            SimpleMinimalTerm::new(
+                TermId::from(("HP", "2")),
+                "Child",
+                vec![TermId::from(("HP", "3"))],
+                false,
+            ),
+        ];
+        let ontology = toy_ontology(terms, &[(1, 0)]);
+
+        let table = "curie\tlabel\nHP:2\tChild\nHP:3\t\nHP\t\n\t\nHP:2\tKid\nMONDO:1\t\n";
+        let findings = CurieColumnValidator::new("curie")
+            .label_column("label")
+            .delimiter(b'\t')
+            .validate(table.as_bytes(), &ontology)
+            .unwrap();
+
+        let issues: Vec<_> = findings
+            .iter()
+            .map(|finding| (finding.row(), finding.issue().to_string()))
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                (2, "obsolete term, replaced by HP:2".to_string()),
+                (3, "malformed CURIE".to_string()),
+                (5, "label mismatch: \"Kid\" != \"Child\"".to_string()),
+                (6, "wrong prefix".to_string()),
+            ]
+        );
+
+        let result = validate_curie_column("id\nHP:1\n".as_bytes(), "curie", &ontology);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Render parts of the ontology graph as GraphViz DOT documents.
+//!
+//! The nodes are labelled with the term name and the CURIE, and the edges point
+//! from the child to the parent, with the root at the top of the rendered graph:
+//!
+//! ```text
+//! digraph ontology {
+//!   rankdir=BT;
+//!   node [shape=box];
+//!   "HP:0100807" [label="Long fingers\nHP:0100807"];
+//!   "HP:0001166" [label="Arachnodactyly\nHP:0001166"];
+//!   "HP:0001166" -> "HP:0100807";
+//! }
+//! ```
+//!
+//! Render the document with `dot -Tsvg branch.dot -o branch.svg`.
+use std::collections::BTreeSet;
+use std::io::Write;
+
+use crate::base::term::MinimalTerm;
+use crate::base::{Identified, TermId};
+use crate::error::OntoliusError;
+use crate::hierarchy::{ChildNodes, DescendantNodes};
+use crate::ontology::{Ontology, Subgraph};
+
+/// Write the term with the `term_id` and all its descendants as a DOT document,
+/// with all `is_a` edges among them.
+///
+/// Returns an error if the term is not in the `ontology`.
+///
+/// ## Examples
+///
+/// ```
+/// use std::str::FromStr;
+/// # use curie_util::TrieCurieUtil;
+/// # use ontolius::io::obographs::ObographsParser;
+/// # use ontolius::ontology::csr::MinimalCsrOntology;
+/// use ontolius::prelude::*;
+/// use ontolius::viz::write_descendants_dot;
+///
+/// # let loader = OntologyLoaderBuilder::new()
+/// #                .parser(ObographsParser::new(TrieCurieUtil::default()))
+/// #                .build();
+/// # let hpo: MinimalCsrOntology = loader.load_from_path("resources/hp.small.json.gz")
+/// #                                     .expect("HPO should be loaded");
+/// #
+/// // Render `Long fingers` and its descendants.
+/// let long_fingers = TermId::from_str("HP:0100807").unwrap();
+/// let mut buffer = vec![];
+/// write_descendants_dot(&hpo, &long_fingers, &mut buffer).expect("DOT should be written");
+///
+/// let dot = String::from_utf8(buffer).unwrap();
+/// assert!(dot.contains(r#""HP:0001166" [label="Arachnodactyly\nHP:0001166"];"#));
+/// assert!(dot.contains(r#""HP:0001166" -> "HP:0100807";"#));
+/// ```
+pub fn write_descendants_dot<O, W>(
+    ontology: &O,
+    term_id: &TermId,
+    write: W,
+) -> Result<(), OntoliusError>
+where
+    O: Ontology,
+    W: Write,
+{
+    let idx = ontology
+        .id_to_idx(term_id)
+        .ok_or_else(|| OntoliusError::Other(format!("{term_id} is not in the ontology")))?;
+    let hierarchy = ontology.hierarchy();
+    let nodes: BTreeSet<_> = std::iter::once(idx)
+        .chain(hierarchy.descendants_of(idx).copied())
+        .collect();
+
+    let terms: Vec<_> = nodes
+        .iter()
+        .flat_map(|&node| ontology.idx_to_term_id(node))
+        .collect();
+    let edges: Vec<_> = nodes
+        .iter()
+        .flat_map(|&parent| {
+            hierarchy
+                .children_of(parent)
+                .map(move |&child| (child, parent))
+        })
+        .flat_map(|(child, parent)| {
+            Some((
+                ontology.idx_to_term_id(child)?,
+                ontology.idx_to_term_id(parent)?,
+            ))
+        })
+        .collect();
+    write_dot(ontology, &terms, &edges, write)
+}
+
+/// Write the `subgraph` of the `ontology` as a DOT document,
+/// e.g. the subgraph induced by a set of terms (see [`Ontology::induced_subgraph`]).
+pub fn write_subgraph_dot<O, W>(
+    ontology: &O,
+    subgraph: &Subgraph<'_>,
+    write: W,
+) -> Result<(), OntoliusError>
+where
+    O: Ontology,
+    W: Write,
+{
+    write_dot(ontology, subgraph.terms(), subgraph.edges(), write)
+}
+
+fn write_dot<O, W>(
+    ontology: &O,
+    terms: &[&TermId],
+    edges: &[(&TermId, &TermId)],
+    mut write: W,
+) -> Result<(), OntoliusError>
+where
+    O: Ontology,
+    W: Write,
+{
+    let mut dot = String::from("digraph ontology {\n  rankdir=BT;\n  node [shape=box];\n");
+    for &term_id in terms {
+        let label = match ontology.id_to_term(term_id) {
+            Some(term) => format!("{}\\n{}", escape(term.name()), term.identifier()),
+            None => term_id.to_string(),
+        };
+        dot.push_str(&format!("  \"{term_id}\" [label=\"{label}\"];\n"));
+    }
+    for (child, parent) in edges {
+        dot.push_str(&format!("  \"{child}\" -> \"{parent}\";\n"));
+    }
+    dot.push_str("}\n");
+
+    write
+        .write_all(dot.as_bytes())
+        .map_err(|e| OntoliusError::Other(format!("Cannot write the DOT document: {e}")))
+}
+
+/// Escape the characters with a special meaning in the quoted DOT strings.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::base::term::simple::SimpleMinimalTerm;
+    use crate::ontology::csr::CsrOntology;
+    use crate::test_util::toy_ontology;
+
+    fn build_ontology() -> CsrOntology<usize, SimpleMinimalTerm> {
+        let term = |id: &str, name: &str| {
+            SimpleMinimalTerm::new(TermId::from(("HP", id)), name, vec![], false)
+        };
+        let terms = vec![
+            term("1", "All"),
+            term("2", "Long fingers"),
+            term("3", "\"Spider\" fingers"),
+            term("4", "Seizure"),
+        ];
+        toy_ontology(terms, &[(1, 0), (2, 1), (3, 0)])
+    }
+
+    #[test]
+    fn test_write_descendants_dot() {
+        let ontology = build_ontology();
+
+        let mut buffer = vec![];
+        write_descendants_dot(&ontology, &TermId::from(("HP", "2")), &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            r#"digraph ontology {
+  rankdir=BT;
+  node [shape=box];
+  "HP:2" [label="Long fingers\nHP:2"];
+  "HP:3" [label="\"Spider\" fingers\nHP:3"];
+  "HP:3" -> "HP:2";
+}
+"#
+        );
+        assert!(write_descendants_dot(&ontology, &TermId::from(("HP", "5")), vec![]).is_err());
+    }
+
+    #[test]
+    fn test_write_subgraph_dot() {
+        let ontology = build_ontology();
+        let terms = HashSet::from([TermId::from(("HP", "3")), TermId::from(("HP", "4"))]);
+        let subgraph = ontology.induced_subgraph(&terms, true);
+
+        let mut buffer = vec![];
+        write_subgraph_dot(&ontology, &subgraph, &mut buffer).unwrap();
+        let dot = String::from_utf8(buffer).unwrap();
+        assert!(dot.contains(r#""HP:1" [label="All\nHP:1"];"#));
+        assert!(dot.contains(r#""HP:3" -> "HP:1";"#));
+        assert!(dot.contains(r#""HP:4" -> "HP:1";"#));
+    }
+}
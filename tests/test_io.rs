@@ -2,7 +2,7 @@
 mod tests {
 
     use curie_util::TrieCurieUtil;
-    use ontolius::io::{obographs::ObographsParser, OntologyLoaderBuilder};
+    use ontolius::io::{obographs::ObographsParser, AltIdCollisionPolicy, OntologyLoaderBuilder};
     use ontolius::ontology::csr::CsrOntology;
 
     #[test]
@@ -18,4 +18,19 @@ mod tests {
         assert!(ontology.is_ok());
         // TODO: more tests?
     }
+
+    #[test]
+    fn test_csr_ontology_loader_with_report() {
+        let path = "resources/hp.small.json.gz";
+
+        let loader = OntologyLoaderBuilder::new()
+            .parser(ObographsParser::new(TrieCurieUtil::default()))
+            .alt_id_collision_policy(AltIdCollisionPolicy::Error)
+            .build();
+
+        let result: Result<(CsrOntology<usize, _>, _), _> = loader.load_with_report_from_path(path);
+
+        let (_, report) = result.expect("HPO has no alt ID collisions");
+        assert!(report.is_clean());
+    }
 }